@@ -320,6 +320,25 @@ impl ChunkOrder {
                 .expect("did not overflow, so cannot be zero"),
         )
     }
+
+    /// Get an order strictly between `a` and `b`, which may be given in
+    /// either order.
+    ///
+    /// Returns the midpoint of the two orders, or `None` if there is no
+    /// integer between them, in which case the surrounding chunks must be
+    /// re-numbered.
+    pub fn between(a: Self, b: Self) -> Option<Self> {
+        let (lo, hi) = if a <= b {
+            (a.get(), b.get())
+        } else {
+            (b.get(), a.get())
+        };
+
+        if hi - lo < 2 {
+            return None;
+        }
+        Self::new(lo + (hi - lo) / 2)
+    }
 }
 
 impl std::fmt::Display for ChunkOrder {
@@ -332,6 +351,31 @@ impl std::fmt::Display for ChunkOrder {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_chunk_order_between() {
+        let order = |o| ChunkOrder::new(o).unwrap();
+
+        // no gap
+        assert_eq!(ChunkOrder::between(order(5), order(5)), None);
+        assert_eq!(ChunkOrder::between(order(5), order(6)), None);
+        assert_eq!(ChunkOrder::between(order(6), order(5)), None);
+
+        // gap
+        assert_eq!(ChunkOrder::between(order(5), order(7)), Some(order(6)));
+        assert_eq!(ChunkOrder::between(order(10), order(2)), Some(order(6)));
+        assert_eq!(ChunkOrder::between(order(2), order(5)), Some(order(3)));
+
+        // does not overflow
+        assert_eq!(
+            ChunkOrder::between(ChunkOrder::MIN, ChunkOrder::MAX),
+            Some(order(u32::MAX / 2 + 1))
+        );
+        assert_eq!(
+            ChunkOrder::between(ChunkOrder::MAX, order(u32::MAX - 2)),
+            Some(order(u32::MAX - 1))
+        );
+    }
+
     #[test]
     fn test_chunk_id_new() {
         // `ChunkId::new()` create new random ID
@@ -18,6 +18,9 @@ pub enum Error {
 
     #[snafu(display("No shards defined"))]
     NoShardsDefined,
+
+    #[snafu(display("Invalid partition key '{}': {}", key, reason))]
+    InvalidPartitionKey { key: String, reason: String },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -211,6 +214,150 @@ pub struct PartitionTemplate {
     pub parts: Vec<TemplatePart>,
 }
 
+impl PartitionTemplate {
+    /// Splits a partition key generated by this template back into the values
+    /// of its parts.
+    ///
+    /// Parts are joined by hyphens, which may also appear within a part. Time
+    /// parts are assumed to contain as many hyphens as their format and
+    /// other parts none, except for the last part, which takes the rest of the
+    /// key.
+    pub fn parse_key<'a>(&'a self, key: &'a str) -> Result<Vec<PartitionKeyPart<'a>>> {
+        let invalid = |reason: String| Error::InvalidPartitionKey {
+            key: key.to_string(),
+            reason,
+        };
+
+        let separators: Vec<_> = key.match_indices('-').map(|(idx, _)| idx).collect();
+        let mut start = 0;
+        let mut consumed_separators = 0;
+        let mut parsed = Vec::with_capacity(self.parts.len());
+
+        for (idx, part) in self.parts.iter().enumerate() {
+            let hyphens = match part {
+                TemplatePart::TimeFormat(format) => format.matches('-').count(),
+                _ => 0,
+            };
+
+            let value = if idx + 1 == self.parts.len() {
+                if separators.len() - consumed_separators < hyphens {
+                    return Err(invalid("too few parts".to_string()));
+                }
+                &key[start..]
+            } else {
+                let end = *separators
+                    .get(consumed_separators + hyphens)
+                    .ok_or_else(|| invalid("too few parts".to_string()))?;
+                let value = &key[start..end];
+                start = end + 1;
+                consumed_separators += hyphens + 1;
+                value
+            };
+
+            parsed.push(match part {
+                TemplatePart::Table => PartitionKeyPart::Table(value),
+                TemplatePart::Column(column) if value.is_empty() => PartitionKeyPart::Column {
+                    column,
+                    value: None,
+                },
+                TemplatePart::Column(column) => {
+                    let value = value
+                        .strip_prefix(column.as_str())
+                        .and_then(|v| v.strip_prefix('_'))
+                        .ok_or_else(|| {
+                            invalid(format!("expected a value for column '{}'", column))
+                        })?;
+                    PartitionKeyPart::Column {
+                        column,
+                        value: Some(value),
+                    }
+                }
+                TemplatePart::TimeFormat(format) => {
+                    if !matches_time_format(format, value) {
+                        return Err(invalid(format!(
+                            "'{}' does not match time format '{}'",
+                            value, format
+                        )));
+                    }
+                    PartitionKeyPart::Time(value)
+                }
+                TemplatePart::RegexCapture(_) | TemplatePart::StrftimeColumn(_) => {
+                    return Err(invalid("unsupported template part".to_string()))
+                }
+            });
+        }
+
+        if self.parts.is_empty() && !key.is_empty() {
+            return Err(invalid("the template has no parts".to_string()));
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// The value of a single [`TemplatePart`] parsed from a partition key
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum PartitionKeyPart<'a> {
+    /// The name of the table
+    Table(&'a str),
+    /// The value of the named column, `None` if it was null
+    Column {
+        column: &'a str,
+        value: Option<&'a str>,
+    },
+    /// The formatted time
+    Time(&'a str),
+}
+
+/// Checks that `value` could have been produced by the `strftime` `format`.
+///
+/// Only the widths of numeric specifiers and literal characters are checked;
+/// other specifiers match anything up to the next literal.
+fn matches_time_format(format: &str, value: &str) -> bool {
+    let mut format = format.chars().peekable();
+    let mut value = value.chars().peekable();
+
+    while let Some(c) = format.next() {
+        let specifier = match c {
+            '%' => format.next(),
+            literal => {
+                if value.next() != Some(literal) {
+                    return false;
+                }
+                continue;
+            }
+        };
+
+        let digits = match specifier {
+            Some('Y') => 4,
+            Some('m' | 'd' | 'H' | 'M' | 'S' | 'y') => 2,
+            Some('j') => 3,
+            Some('%') => {
+                if value.next() != Some('%') {
+                    return false;
+                }
+                continue;
+            }
+            _ => {
+                // skip until the next literal in the format
+                let next_literal = format.peek().copied();
+                while value.peek().is_some() && value.peek().copied() != next_literal {
+                    value.next();
+                }
+                continue;
+            }
+        };
+
+        for _ in 0..digits {
+            if !value.next().map_or(false, |c| c.is_ascii_digit()) {
+                return false;
+            }
+        }
+    }
+
+    value.next().is_none()
+}
+
 /// `TemplatePart` specifies what part of a row should be used to compute this
 /// part of a partition key.
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -258,6 +405,81 @@ pub struct StrftimeColumn {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_partition_key() {
+        let template = PartitionTemplate {
+            parts: vec![
+                TemplatePart::TimeFormat("%Y-%m-%d".to_string()),
+                TemplatePart::Column("region".to_string()),
+            ],
+        };
+
+        assert_eq!(
+            template.parse_key("2021-03-14-region_us-west").unwrap(),
+            vec![
+                PartitionKeyPart::Time("2021-03-14"),
+                PartitionKeyPart::Column {
+                    column: "region",
+                    value: Some("us-west")
+                },
+            ]
+        );
+
+        // a null column value is rendered as an empty string
+        assert_eq!(
+            template.parse_key("2021-03-14-").unwrap(),
+            vec![
+                PartitionKeyPart::Time("2021-03-14"),
+                PartitionKeyPart::Column {
+                    column: "region",
+                    value: None
+                },
+            ]
+        );
+
+        let template = PartitionTemplate {
+            parts: vec![
+                TemplatePart::Table,
+                TemplatePart::TimeFormat("%Y-%m-%d %H:00".to_string()),
+            ],
+        };
+        assert_eq!(
+            template.parse_key("cpu-2021-03-14 12:00").unwrap(),
+            vec![
+                PartitionKeyPart::Table("cpu"),
+                PartitionKeyPart::Time("2021-03-14 12:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_malformed_partition_key() {
+        let template = PartitionTemplate {
+            parts: vec![
+                TemplatePart::TimeFormat("%Y-%m-%d".to_string()),
+                TemplatePart::Column("region".to_string()),
+            ],
+        };
+
+        let err = template.parse_key("2021-03").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid partition key '2021-03': too few parts"
+        );
+
+        let err = template.parse_key("2021-xx-14-region_west").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid partition key '2021-xx-14-region_west': '2021-xx-14' does not match time format '%Y-%m-%d'"
+        );
+
+        let err = template.parse_key("2021-03-14-host_a").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid partition key '2021-03-14-host_a': expected a value for column 'region'"
+        );
+    }
+
     #[test]
     fn test_max_active_compactions_cpu_fraction() {
         let n = MaxActiveCompactions::new(1.0);
@@ -20,6 +20,7 @@ pub mod job;
 pub mod names;
 pub mod non_empty;
 pub mod partition_metadata;
+pub mod precision;
 pub mod router;
 pub mod sequence;
 pub mod server_id;
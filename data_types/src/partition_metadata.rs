@@ -11,7 +11,7 @@ use std::{
 };
 
 /// Address of the chunk within the catalog
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct PartitionAddr {
     /// Database name
     pub db_name: Arc<str>,
@@ -0,0 +1,108 @@
+//! The precision of line protocol timestamps.
+use snafu::Snafu;
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display(
+        "invalid precision '{}', expected one of 'ns', 'us', 'ms' or 's'",
+        value
+    ))]
+    InvalidPrecision { value: String },
+}
+
+/// The precision of the timestamps in a line protocol write, as given by the
+/// `precision` parameter of the `/api/v2/write` API.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Precision {
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+}
+
+impl Precision {
+    /// The number of nanoseconds in one unit of this precision, i.e. the
+    /// factor converting a timestamp of this precision to nanoseconds.
+    pub fn multiplier_nanos(&self) -> i64 {
+        match self {
+            Self::Nanoseconds => 1,
+            Self::Microseconds => 1_000,
+            Self::Milliseconds => 1_000_000,
+            Self::Seconds => 1_000_000_000,
+        }
+    }
+
+    /// The value of the `precision` parameter for this precision.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Nanoseconds => "ns",
+            Self::Microseconds => "us",
+            Self::Milliseconds => "ms",
+            Self::Seconds => "s",
+        }
+    }
+}
+
+impl Default for Precision {
+    fn default() -> Self {
+        Self::Nanoseconds
+    }
+}
+
+impl fmt::Display for Precision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Precision {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "ns" => Ok(Self::Nanoseconds),
+            "us" => Ok(Self::Microseconds),
+            "ms" => Ok(Self::Milliseconds),
+            "s" => Ok(Self::Seconds),
+            _ => InvalidPrecisionSnafu { value }.fail(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let cases = [
+            ("ns", Precision::Nanoseconds, 1),
+            ("us", Precision::Microseconds, 1_000),
+            ("ms", Precision::Milliseconds, 1_000_000),
+            ("s", Precision::Seconds, 1_000_000_000),
+        ];
+
+        for (value, precision, multiplier) in cases {
+            assert_eq!(value.parse::<Precision>().unwrap(), precision);
+            assert_eq!(precision.multiplier_nanos(), multiplier);
+            assert_eq!(precision.to_string(), value);
+        }
+
+        assert_eq!(Precision::default(), Precision::Nanoseconds);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for value in ["", "n", "NS", "h", "sec"] {
+            let err = value.parse::<Precision>().unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "invalid precision '{}', expected one of 'ns', 'us', 'ms' or 's'",
+                    value
+                )
+            );
+        }
+    }
+}
@@ -120,10 +120,10 @@ impl QueryCatalogAccess {
         catalog: Arc<Catalog>,
         jobs: Arc<JobRegistry>,
         time_provider: Arc<dyn TimeProvider>,
-        metric_registry: &metric::Registry,
+        metric_registry: Arc<metric::Registry>,
     ) -> Self {
         let db_name = Arc::from(db_name.into());
-        let access_metrics = AccessMetrics::new(metric_registry, Arc::clone(&db_name));
+        let access_metrics = AccessMetrics::new(&metric_registry, Arc::clone(&db_name));
         let chunk_access = Arc::new(ChunkAccess::new(Arc::clone(&catalog), access_metrics));
         let query_log = Arc::new(QueryLog::new(QUERY_LOG_SIZE, time_provider));
 
@@ -132,6 +132,7 @@ impl QueryCatalogAccess {
             Arc::clone(&catalog),
             jobs,
             Arc::clone(&query_log),
+            metric_registry,
         ));
         let user_tables = Arc::new(DbSchemaProvider::new(
             Arc::clone(&catalog),
@@ -1,9 +1,11 @@
 //! This module contains the implementation of the InfluxDB IOx Metadata catalog
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
+use std::time::Duration;
 
 use data_types::chunk_metadata::ChunkId;
 use data_types::chunk_metadata::ChunkOrder;
+use data_types::chunk_metadata::{ChunkAddr, ChunkLifecycleAction, ChunkStorage};
 use hashbrown::{HashMap, HashSet};
 
 use data_types::chunk_metadata::ChunkSummary;
@@ -15,15 +17,19 @@ use tracker::{
 };
 
 use self::chunk::CatalogChunk;
+use self::events::{CatalogEvent, CatalogEvents};
 use self::metrics::CatalogMetrics;
 use self::partition::Partition;
+use self::snapshot::CatalogSnapshot;
 use self::table::Table;
 use data_types::write_summary::WriteSummary;
-use time::TimeProvider;
+use time::{Time, TimeProvider};
 
 pub mod chunk;
+pub mod events;
 mod metrics;
 pub mod partition;
+pub mod snapshot;
 pub mod table;
 
 #[derive(Debug, Snafu)]
@@ -45,6 +51,18 @@ pub enum Error {
         partition: String,
         table: String,
     },
+
+    #[snafu(display(
+        "cannot merge partition '{}' of table '{}': {}",
+        partition,
+        table,
+        reason
+    ))]
+    CannotMergePartitions {
+        table: String,
+        partition: String,
+        reason: String,
+    },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -95,6 +113,9 @@ pub struct Catalog {
 
     metrics: Arc<CatalogMetrics>,
 
+    /// Publishes chunk changes to subscribers
+    events: CatalogEvents,
+
     time_provider: Arc<dyn TimeProvider>,
 }
 
@@ -119,6 +140,7 @@ impl Catalog {
             db_name,
             tables: Default::default(),
             metrics,
+            events: CatalogEvents::new(),
             time_provider,
         }
     }
@@ -154,6 +176,7 @@ impl Catalog {
                         Arc::clone(&self.db_name),
                         Arc::clone(&table_name),
                         self.metrics.new_table_metrics(table_name.as_ref()),
+                        self.events.clone(),
                         Arc::clone(&self.time_provider),
                     );
 
@@ -224,6 +247,112 @@ impl Catalog {
         Arc::clone(table.get_or_create_partition(partition_key))
     }
 
+    /// Moves all chunks of the partitions `keys` of `table_name` into the
+    /// partition `into`, creating it if necessary.
+    ///
+    /// Moved chunks receive fresh orders in the target partition, preserving
+    /// their relative order, so that chunk orders remain distinct. Nothing is
+    /// moved if a source partition is missing, is the target or has unpersisted
+    /// writes, if any chunk is persisted or has a lifecycle action in progress,
+    /// if a chunk ID is used by more than one of the involved partitions, or if
+    /// the target would end up with more than one open chunk.
+    pub fn merge_partitions(&self, table_name: &str, keys: &[&str], into: &str) -> Result<()> {
+        let keys: BTreeSet<&str> = keys.iter().copied().collect();
+        if keys.contains(into) {
+            return CannotMergePartitionsSnafu {
+                table: table_name,
+                partition: into,
+                reason: "target is also a source",
+            }
+            .fail();
+        }
+
+        // The partitions are locked after releasing the tables lock. A missing
+        // target is only created once the merge has been validated, after which
+        // the validation is repeated as the partitions were unlocked meanwhile.
+        let mut created_target = false;
+        loop {
+            let (sources, target) = {
+                let table = self.table(table_name)?;
+                let sources = keys
+                    .iter()
+                    .map(|key| {
+                        table
+                            .partition(key)
+                            .cloned()
+                            .context(PartitionNotFoundSnafu {
+                                partition: *key,
+                                table: table_name,
+                            })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                (sources, table.partition(into).cloned())
+            };
+
+            // Lock the partitions in key order, as `snapshot` does, so that
+            // concurrent merges and snapshots cannot deadlock.
+            let mut to_lock: Vec<_> = keys.iter().copied().zip(&sources).collect();
+            to_lock.extend(target.as_ref().map(|target| (into, target)));
+            to_lock.sort_unstable_by_key(|(key, _)| *key);
+
+            let mut target_guard = None;
+            let mut source_guards = Vec::with_capacity(sources.len());
+            for (key, partition) in to_lock {
+                let guard = partition.write();
+                if key == into {
+                    target_guard = Some(guard);
+                } else {
+                    source_guards.push(guard);
+                }
+            }
+
+            let validated = validate_merge(
+                table_name,
+                into,
+                target_guard.as_deref(),
+                source_guards.iter().map(|partition| &**partition),
+            );
+            if let Err(e) = validated {
+                // Remove a target created above unless it has been used since,
+                // releasing the partitions before taking the tables lock.
+                std::mem::drop(source_guards);
+                std::mem::drop(target_guard);
+                std::mem::drop(target);
+                if created_target {
+                    if let Some(table) = self.tables.write().get_mut(table_name) {
+                        table.remove_unused_partition(into);
+                    }
+                }
+                return Err(e);
+            }
+
+            let target = match target_guard.as_mut() {
+                Some(target) => target,
+                None => {
+                    std::mem::drop(source_guards);
+                    self.get_or_create_partition(table_name, into);
+                    created_target = true;
+                    continue;
+                }
+            };
+
+            // any open chunk goes last so that it keeps the highest order
+            let (open, mut chunks): (Vec<_>, Vec<_>) = source_guards
+                .iter_mut()
+                .flat_map(|partition| partition.take_chunks())
+                .partition(|chunk| chunk.read().stage().is_open());
+            chunks.extend(open);
+
+            for chunk in chunks {
+                target
+                    .adopt_chunk(chunk)
+                    .expect("chunk IDs were checked and adopted chunks get fresh orders");
+            }
+
+            return Ok(());
+        }
+    }
+
     /// Returns a list of summaries for each partition.
     pub fn partition_summaries(&self) -> Vec<PartitionSummary> {
         self.tables
@@ -278,6 +407,19 @@ impl Catalog {
         })
     }
 
+    /// Returns the address and lifecycle action of every chunk that currently
+    /// has a lifecycle action in progress, in an arbitrary order
+    pub fn active_lifecycle_actions(&self) -> Vec<(ChunkAddr, ChunkLifecycleAction)> {
+        self.filtered_chunks(TableNameFilter::AllTables, None, |chunk| {
+            chunk
+                .lifecycle_action()
+                .map(|tracker| (chunk.addr().clone(), *tracker.metadata()))
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
     /// Returns all chunks within the catalog in an arbitrary order
     pub fn chunks(&self) -> Vec<Arc<RwLock<CatalogChunk>>> {
         let mut chunks = Vec::new();
@@ -292,6 +434,88 @@ impl Catalog {
         chunks
     }
 
+    /// Returns the number of chunks in the catalog in each storage state.
+    /// States without any chunks are omitted.
+    pub fn storage_state_counts(&self) -> BTreeMap<ChunkStorage, usize> {
+        self.filtered_chunks(TableNameFilter::AllTables, None, |chunk| chunk.storage().1)
+            .into_iter()
+            .fold(BTreeMap::new(), |mut counts, storage| {
+                *counts.entry(storage).or_default() += 1;
+                counts
+            })
+    }
+
+    /// Returns the addresses of the chunks whose newest data is older than
+    /// `now - retention`, and so can be dropped.
+    ///
+    /// Chunks with any data within the retention period, or without time
+    /// statistics, are never returned.
+    pub fn chunks_past_retention(&self, retention: Duration, now: Time) -> Vec<ChunkAddr> {
+        let horizon = match now.checked_sub(retention) {
+            Some(horizon) => horizon,
+            None => return vec![],
+        };
+
+        self.filtered_chunks(TableNameFilter::AllTables, None, |chunk| {
+            chunk
+                .max_timestamp()
+                .filter(|max_timestamp| *max_timestamp < horizon)
+                .map(|_| chunk.addr().clone())
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Returns all chunks in the catalog grouped by the partition they belong
+    /// to. Partitions without chunks are omitted.
+    ///
+    /// Within each partition chunks are ordered by chunk order and then ID.
+    pub fn chunks_by_partition(&self) -> HashMap<PartitionAddr, Vec<Arc<RwLock<CatalogChunk>>>> {
+        let tables = self.tables.read();
+
+        tables
+            .values()
+            .flat_map(|table| table.partitions())
+            .filter_map(|partition| {
+                let partition = partition.read();
+                let chunks = partition.chunks().cloned().collect::<Vec<_>>();
+                (!chunks.is_empty()).then(|| (partition.addr().clone(), chunks))
+            })
+            .collect()
+    }
+
+    /// Captures the metadata of every chunk in the catalog as a
+    /// [`CatalogSnapshot`].
+    ///
+    /// All partitions are locked while the snapshot is taken so that it
+    /// reflects a single point in time, even across partitions.
+    pub fn snapshot(&self) -> CatalogSnapshot {
+        let tables = self.tables.read();
+
+        // Lock the partitions in key order, see `merge_partitions`
+        let mut partitions = tables
+            .iter()
+            .flat_map(|(table_name, table)| {
+                table
+                    .keyed_partitions()
+                    .map(move |(key, partition)| ((table_name, key), partition))
+            })
+            .collect::<Vec<_>>();
+        partitions.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        let partitions = partitions
+            .into_iter()
+            .map(|(_, partition)| partition.read())
+            .collect::<Vec<_>>();
+
+        CatalogSnapshot::new(partitions.iter().flat_map(|partition| {
+            partition.chunks().map(|chunk| {
+                let chunk = chunk.read();
+                (chunk.addr().clone(), chunk.summary())
+            })
+        }))
+    }
+
     /// Calls `map` with every chunk and returns a collection of the results
     ///
     /// If `partition_key` is Some(partition_key) only returns chunks
@@ -340,15 +564,154 @@ impl Catalog {
         self.tables.read().keys().map(ToString::to_string).collect()
     }
 
+    /// Return the sorted names of the tables in the catalog that match
+    /// `pattern`.
+    ///
+    /// Patterns are matched against the whole table name. A `*` matches any
+    /// sequence of characters, including none, and a `?` matches any single
+    /// character; all other characters match themselves. For example `cpu*`
+    /// matches all tables whose names start with `cpu`, while `cpu` only
+    /// matches the table `cpu` itself.
+    pub fn tables_matching(&self, pattern: &str) -> Vec<String> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let mut names: Vec<_> = self
+            .tables
+            .read()
+            .keys()
+            .filter(|name| glob_matches(&pattern, name))
+            .map(ToString::to_string)
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
     pub fn metrics(&self) -> &CatalogMetrics {
         &self.metrics
     }
+
+    /// Subscribe to [`CatalogEvent`]s for chunks being created, moving between
+    /// storage locations or being dropped.
+    ///
+    /// Events are only generated while there are subscribers.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<CatalogEvent> {
+        self.events.subscribe()
+    }
+}
+
+/// Returns true if `pattern` matches the whole of `name`, where `*` matches
+/// any sequence of characters and `?` any single character
+fn glob_matches(pattern: &[char], name: &str) -> bool {
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+
+    // the position of the last `*` seen in the pattern, and of the character
+    // in `name` it is currently matched up to
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // let the last `*` consume one more character
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    n = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Checks that the chunks of `sources` can be moved into `target`, see
+/// [`Catalog::merge_partitions`].
+fn validate_merge<'a>(
+    table_name: &str,
+    into: &str,
+    target: Option<&Partition>,
+    sources: impl Iterator<Item = &'a Partition>,
+) -> Result<()> {
+    let merge_error = |partition: &str, reason: String| {
+        CannotMergePartitionsSnafu {
+            table: table_name,
+            partition,
+            reason,
+        }
+        .build()
+    };
+
+    let mut open_chunks = target.map_or(0, |partition| partition.open_chunk().is_some() as usize);
+    let mut chunk_ids: HashSet<ChunkId> = target
+        .iter()
+        .flat_map(|partition| partition.keyed_chunks().map(|(id, _, _)| id))
+        .collect();
+    for partition in sources {
+        let key = partition.key();
+        if partition
+            .persistence_windows()
+            .map_or(false, |windows| !windows.is_empty())
+        {
+            return Err(merge_error(
+                key,
+                "partition has unpersisted writes".to_string(),
+            ));
+        }
+
+        for (id, _, chunk) in partition.keyed_chunks() {
+            if !chunk_ids.insert(id) {
+                return Err(merge_error(
+                    key,
+                    format!("chunk ID {} is used by another merged partition", id),
+                ));
+            }
+
+            let chunk = chunk.read();
+            if let Some(action) = chunk.lifecycle_action() {
+                return Err(merge_error(
+                    key,
+                    format!(
+                        "chunk {} has in-progress lifecycle action: {}",
+                        chunk.id(),
+                        action.metadata()
+                    ),
+                ));
+            }
+            if chunk.is_persisted() {
+                return Err(merge_error(
+                    key,
+                    format!("chunk {} is persisted", chunk.id()),
+                ));
+            }
+            if chunk.stage().is_open() {
+                open_chunks += 1;
+            }
+        }
+    }
+    if open_chunks > 1 {
+        return Err(merge_error(
+            into,
+            "merge would result in more than one open chunk".to_string(),
+        ));
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use data_types::chunk_metadata::ChunkAddr;
+    use data_types::chunk_metadata::ChunkStorage;
     use mutable_buffer::test_helpers::write_lp_to_new_chunk;
+    use tracker::TaskRegistration;
 
     use super::*;
 
@@ -361,6 +724,360 @@ mod tests {
         chunk.addr().clone()
     }
 
+    #[test]
+    fn chunk_events() {
+        let catalog = Catalog::test();
+        let p1 = catalog.get_or_create_partition("t1", "p1");
+
+        // nothing is published before anyone subscribes
+        let addr1 = create_open_chunk(&p1);
+
+        let mut events = catalog.subscribe();
+        let addr2 = create_open_chunk(&p1);
+        catalog
+            .chunk("t1", "p1", addr2.chunk_id)
+            .unwrap()
+            .0
+            .write()
+            .freeze()
+            .unwrap();
+        p1.write().drop_chunk(addr1.chunk_id).unwrap();
+        p1.write().force_drop_chunk(addr2.chunk_id).unwrap();
+
+        let expected = vec![
+            CatalogEvent::ChunkCreated {
+                addr: addr2.clone(),
+                storage: ChunkStorage::OpenMutableBuffer,
+            },
+            CatalogEvent::ChunkTransitioned {
+                addr: addr2.clone(),
+                from: ChunkStorage::OpenMutableBuffer,
+                to: ChunkStorage::ClosedMutableBuffer,
+            },
+            CatalogEvent::ChunkDropped { addr: addr1 },
+            CatalogEvent::ChunkDropped { addr: addr2 },
+        ];
+        for expected in expected {
+            assert_eq!(events.try_recv().unwrap(), expected);
+        }
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn chunks_by_partition() {
+        let catalog = Catalog::test();
+        let p1 = catalog.get_or_create_partition("t1", "p1");
+        let p2 = catalog.get_or_create_partition("t1", "p2");
+        let p3 = catalog.get_or_create_partition("t2", "p1");
+
+        let addr1 = create_open_chunk(&p1);
+        let addr2 = create_open_chunk(&p1);
+        let addr3 = create_open_chunk(&p3);
+
+        // p2 has no chunks
+        let by_partition = catalog.chunks_by_partition();
+        assert_eq!(by_partition.len(), 2);
+        assert!(!by_partition.contains_key(p2.read().addr()));
+
+        let chunk_addrs = |addr: &PartitionAddr| {
+            by_partition[addr]
+                .iter()
+                .map(|chunk| chunk.read().addr().clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(chunk_addrs(p1.read().addr()), vec![addr1, addr2]);
+        assert_eq!(chunk_addrs(p3.read().addr()), vec![addr3]);
+    }
+
+    #[test]
+    fn storage_state_counts() {
+        let catalog = Catalog::test();
+        assert!(catalog.storage_state_counts().is_empty());
+
+        let p1 = catalog.get_or_create_partition("t1", "p1");
+        let p2 = catalog.get_or_create_partition("t2", "p2");
+
+        let addr1 = create_open_chunk(&p1);
+        create_open_chunk(&p1);
+        create_open_chunk(&p2);
+
+        catalog
+            .chunk("t1", "p1", addr1.chunk_id)
+            .unwrap()
+            .0
+            .write()
+            .freeze()
+            .unwrap();
+
+        let counts = catalog.storage_state_counts();
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            vec![
+                (ChunkStorage::OpenMutableBuffer, 2),
+                (ChunkStorage::ClosedMutableBuffer, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn chunks_past_retention() {
+        let catalog = Catalog::test();
+        let p1 = catalog.get_or_create_partition("t1", "p1");
+
+        let create_chunk = |lp: &str| {
+            let mut partition = p1.write();
+            let chunk = partition.create_open_chunk(write_lp_to_new_chunk(lp));
+            let chunk = chunk.read();
+            chunk.addr().clone()
+        };
+
+        let old = create_chunk("t1 bar=1 10\nt1 bar=2 20");
+        // partially within the retention period
+        create_chunk("t1 bar=1 10\nt1 bar=2 9500");
+        create_chunk("t1 bar=1 9800");
+
+        let now = Time::from_timestamp_nanos(10_000);
+        let retention = Duration::from_nanos(1_000);
+        assert_eq!(catalog.chunks_past_retention(retention, now), vec![old]);
+
+        // the horizon is exclusive
+        let retention = Duration::from_nanos(9_980);
+        assert!(catalog.chunks_past_retention(retention, now).is_empty());
+
+        // a long retention period keeps everything
+        let retention = Duration::from_secs(1);
+        assert!(catalog.chunks_past_retention(retention, now).is_empty());
+    }
+
+    #[test]
+    fn tables_matching() {
+        let catalog = Catalog::test();
+        for table in ["cpu", "cpu_load", "cpu2", "disk", "mem"] {
+            catalog.get_or_create_partition(table, "p1");
+        }
+
+        assert_eq!(
+            catalog.tables_matching("cpu*"),
+            vec!["cpu", "cpu2", "cpu_load"]
+        );
+        assert_eq!(catalog.tables_matching("cpu"), vec!["cpu"]);
+        assert_eq!(catalog.tables_matching("cpu?"), vec!["cpu2"]);
+        assert_eq!(catalog.tables_matching("*_*d"), vec!["cpu_load"]);
+        assert_eq!(
+            catalog.tables_matching("*"),
+            vec!["cpu", "cpu2", "cpu_load", "disk", "mem"]
+        );
+        assert!(catalog.tables_matching("net*").is_empty());
+        assert!(catalog.tables_matching("cp").is_empty());
+    }
+
+    #[test]
+    fn merge_partitions() {
+        let catalog = Catalog::test();
+        let p1 = catalog.get_or_create_partition("t1", "p1");
+        let p2 = catalog.get_or_create_partition("t1", "p2");
+
+        let freeze = |addr: &ChunkAddr| {
+            let (chunk, _) = catalog
+                .chunk("t1", &addr.partition_key, addr.chunk_id)
+                .unwrap();
+            chunk.write().freeze().unwrap();
+        };
+
+        let addr1 = create_open_chunk(&p1);
+        freeze(&addr1);
+        let addr2 = create_open_chunk(&p1);
+        let addr3 = create_open_chunk(&p2);
+        freeze(&addr3);
+
+        // both open chunks would end up in the target
+        let addr4 = create_open_chunk(&p2);
+        let err = catalog
+            .merge_partitions("t1", &["p1", "p2"], "p3")
+            .unwrap_err();
+        assert!(matches!(err, Error::CannotMergePartitions { .. }));
+        assert_eq!(p2.read().chunks().count(), 2);
+        assert!(catalog.partition("t1", "p3").is_err());
+        freeze(&addr4);
+
+        let err = catalog
+            .merge_partitions("t1", &["p1", "p2"], "p1")
+            .unwrap_err();
+        assert!(matches!(err, Error::CannotMergePartitions { .. }));
+
+        catalog.merge_partitions("t1", &["p1", "p2"], "p3").unwrap();
+
+        assert_eq!(p1.read().chunks().count(), 0);
+        assert_eq!(p2.read().chunks().count(), 0);
+
+        let p3 = catalog.partition("t1", "p3").unwrap();
+        let p3 = p3.read();
+        p3.validate_chunk_orders().unwrap();
+
+        let chunks: Vec<_> = p3
+            .keyed_chunks()
+            .map(|(id, order, chunk)| {
+                let chunk = chunk.read();
+                assert_eq!(chunk.key(), "p3");
+                assert_eq!(chunk.order(), order);
+                id
+            })
+            .collect();
+        // the open chunk is moved last
+        let expected: Vec<_> = [addr1, addr3, addr4, addr2]
+            .iter()
+            .map(|addr| addr.chunk_id)
+            .collect();
+        assert_eq!(chunks, expected);
+
+        let orders: BTreeSet<_> = p3.keyed_chunks().map(|(_, order, _)| order).collect();
+        assert_eq!(orders.len(), 4);
+    }
+
+    #[test]
+    fn merge_partitions_duplicate_chunk_id() {
+        let catalog = Catalog::test();
+        let p1 = catalog.get_or_create_partition("t1", "p1");
+        let p2 = catalog.get_or_create_partition("t1", "p2");
+
+        let create_chunk = |partition: &Arc<RwLock<Partition>>, id: u128| {
+            let mut partition = partition.write();
+            let chunk = partition
+                .create_open_chunk_with_specified_id_order(
+                    write_lp_to_new_chunk("t1 bar=1 10"),
+                    ChunkId::new_test(id),
+                    ChunkOrder::new(1).unwrap(),
+                    partition::ChunkOrderCheck::Unique,
+                )
+                .unwrap();
+            chunk.write().freeze().unwrap();
+        };
+        create_chunk(&p1, 1);
+        create_chunk(&p2, 1);
+
+        let err = catalog
+            .merge_partitions("t1", &["p1", "p2"], "p3")
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::CannotMergePartitions { .. }),
+            "{}",
+            err
+        );
+        assert_eq!(p1.read().chunks().count(), 1);
+        assert_eq!(p2.read().chunks().count(), 1);
+        assert!(catalog.partition("t1", "p3").is_err());
+
+        // a collision with the target is rejected as well
+        let err = catalog.merge_partitions("t1", &["p1"], "p2").unwrap_err();
+        assert!(
+            matches!(err, Error::CannotMergePartitions { .. }),
+            "{}",
+            err
+        );
+        assert_eq!(p1.read().chunks().count(), 1);
+        assert_eq!(p2.read().chunks().count(), 1);
+    }
+
+    #[test]
+    fn merge_partitions_concurrently() {
+        let catalog = Arc::new(Catalog::test());
+        for key in ["p1", "p2", "p3"] {
+            catalog.get_or_create_partition("t1", key);
+        }
+
+        // merges in opposite directions and snapshots lock the same partitions
+        let handles: Vec<_> = [(["p1", "p2"], "p3"), (["p3", "p2"], "p1")]
+            .into_iter()
+            .map(|(keys, into)| {
+                let catalog = Arc::clone(&catalog);
+                std::thread::spawn(move || {
+                    for _ in 0..1_000 {
+                        catalog.merge_partitions("t1", &keys, into).unwrap();
+                        catalog.snapshot();
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn remove_unused_partition() {
+        let catalog = Catalog::test();
+        let p1 = catalog.get_or_create_partition("t1", "p1");
+        catalog.get_or_create_partition("t1", "p2");
+        create_open_chunk(&catalog.get_or_create_partition("t1", "p3"));
+
+        let mut tables = catalog.tables.write();
+        let table = tables.get_mut("t1").unwrap();
+        for key in ["p1", "p2", "p3"] {
+            table.remove_unused_partition(key);
+        }
+        std::mem::drop(tables);
+
+        // p1 is still referenced and p3 has a chunk
+        assert!(Arc::ptr_eq(&catalog.partition("t1", "p1").unwrap(), &p1));
+        assert!(catalog.partition("t1", "p2").is_err());
+        assert!(catalog.partition("t1", "p3").is_ok());
+    }
+
+    #[test]
+    fn snapshot() {
+        let catalog = Catalog::test();
+        let p1 = catalog.get_or_create_partition("t1", "p1");
+        let p2 = catalog.get_or_create_partition("t2", "p2");
+
+        let addr1 = create_open_chunk(&p1);
+        let addr2 = create_open_chunk(&p1);
+        let addr3 = create_open_chunk(&p2);
+
+        let snapshot = catalog.snapshot();
+        assert_eq!(snapshot.len(), 3);
+
+        p1.write().drop_chunk(addr1.chunk_id).unwrap();
+        catalog
+            .chunk("t1", "p1", addr2.chunk_id)
+            .unwrap()
+            .0
+            .write()
+            .freeze()
+            .unwrap();
+
+        // the snapshot still sees the dropped chunk and the old storage
+        assert_eq!(snapshot.len(), 3);
+        let summary = snapshot.chunk_by_addr(&addr1).unwrap();
+        assert_eq!(summary.id, addr1.chunk_id);
+        assert_eq!(
+            snapshot.chunk_by_addr(&addr2).unwrap().storage,
+            ChunkStorage::OpenMutableBuffer
+        );
+        assert!(snapshot.chunk_by_addr(&addr3).is_some());
+
+        let ids = snapshot
+            .chunk_summaries()
+            .map(|summary| summary.id)
+            .collect::<Vec<_>>();
+        let mut expected = vec![addr1.clone(), addr2.clone(), addr3];
+        expected.sort();
+        let expected = expected
+            .into_iter()
+            .map(|addr| addr.chunk_id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, expected);
+
+        // a new snapshot reflects the changes
+        let snapshot = catalog.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot.chunk_by_addr(&addr1).is_none());
+        assert_eq!(
+            snapshot.chunk_by_addr(&addr2).unwrap().storage,
+            ChunkStorage::ClosedMutableBuffer
+        );
+    }
+
     #[test]
     fn partition_get() {
         let catalog = Catalog::test();
@@ -566,6 +1283,31 @@ mod tests {
         assert_eq!(d.len(), 1);
     }
 
+    #[test]
+    fn active_lifecycle_actions() {
+        let catalog = Catalog::test();
+
+        let p1 = catalog.get_or_create_partition("table1", "p1");
+        let p2 = catalog.get_or_create_partition("table2", "p1");
+        let addr1 = create_open_chunk(&p1);
+        create_open_chunk(&p1);
+        create_open_chunk(&p2);
+
+        assert!(catalog.active_lifecycle_actions().is_empty());
+
+        let registration = TaskRegistration::new(Arc::clone(&catalog.time_provider));
+        {
+            let p1 = p1.read();
+            let (chunk, _order) = p1.chunk(addr1.chunk_id).unwrap();
+            chunk.write().set_compacting(&registration).unwrap();
+        }
+
+        assert_eq!(
+            catalog.active_lifecycle_actions(),
+            vec![(addr1, ChunkLifecycleAction::Compacting)]
+        );
+    }
+
     fn make_set(s: impl Into<String>) -> BTreeSet<String> {
         std::iter::once(s.into()).collect()
     }
@@ -1,21 +1,23 @@
-use crate::catalog::metrics::StorageRecorder;
+use crate::catalog::events::{CatalogEvent, CatalogEvents};
+use crate::catalog::metrics::{LifecycleActionCounter, LifecycleActionGuard, StorageRecorder};
 use data_types::{
     chunk_metadata::{
         ChunkAddr, ChunkColumnSummary, ChunkId, ChunkLifecycleAction, ChunkOrder, ChunkStorage,
         ChunkSummary, DetailedChunkSummary,
     },
     delete_predicate::DeletePredicate,
-    partition_metadata::TableSummary,
+    partition_metadata::{Statistics, TableSummary},
 };
 use internal_types::access::AccessRecorder;
+use metric::{Attributes, RecorderCollection, U64Counter};
 use mutable_buffer::{snapshot::ChunkSnapshot as MBChunkSnapshot, MBChunk};
 use observability_deps::tracing::debug;
 use parking_lot::Mutex;
 use parquet_file::chunk::ParquetChunk;
 use read_buffer::RBChunk;
-use schema::Schema;
+use schema::{Schema, TIME_COLUMN_NAME};
 use snafu::Snafu;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use time::{Time, TimeProvider};
 use tracker::{TaskRegistration, TaskTracker};
 
@@ -169,6 +171,35 @@ impl ChunkStage {
         }
     }
 
+    /// Returns a human-readable summary of this stage including where the
+    /// data is stored and how many rows it holds, e.g.
+    /// `"Frozen (read buffer, 10 rows)"`.
+    ///
+    /// Unlike [`name`](Self::name) this is intended for logs and diagnostics.
+    pub fn describe(&self) -> String {
+        let (storage, rows) = match self {
+            Self::Open { mb_chunk } => ("mutable buffer", mb_chunk.rows()),
+            Self::Frozen { representation, .. } => match representation {
+                ChunkStageFrozenRepr::MutableBufferSnapshot(snapshot) => {
+                    ("mutable buffer snapshot", snapshot.rows())
+                }
+                ChunkStageFrozenRepr::ReadBuffer(rb_chunk) => {
+                    ("read buffer", rb_chunk.rows() as usize)
+                }
+            },
+            Self::Persisted {
+                parquet,
+                read_buffer,
+                ..
+            } => match read_buffer {
+                Some(_) => ("read buffer and object store", parquet.rows()),
+                None => ("object store", parquet.rows()),
+            },
+        };
+
+        format!("{} ({}, {} rows)", self.name(), storage, rows)
+    }
+
     pub fn is_open(&self) -> bool {
         matches!(self, ChunkStage::Open { .. })
     }
@@ -178,6 +209,22 @@ impl ChunkStage {
     }
 }
 
+/// The thresholds at which an open chunk should be closed.
+///
+/// A chunk should be closed as soon as any of the configured thresholds is
+/// reached, and thresholds that are `None` never trigger.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ClosePolicy {
+    /// The maximum time since the first write to the chunk
+    pub max_age: Option<Duration>,
+
+    /// The maximum number of rows in the chunk
+    pub max_rows: Option<usize>,
+
+    /// The maximum in-memory size of the chunk in bytes
+    pub max_bytes: Option<usize>,
+}
+
 /// The catalog representation of a Chunk in IOx. Note that a chunk
 /// may exist in several physical locations at any given time (e.g. in
 /// mutable buffer and in read buffer)
@@ -211,6 +258,10 @@ pub struct CatalogChunk {
     /// See compact_object_store_chunks for the detail.
     to_be_created_chunk_id: Option<ChunkId>,
 
+    /// Counts this chunk as loading into the read buffer for as long as its
+    /// lifecycle action is `LoadingReadBuffer`
+    loading_read_buffer: Option<LifecycleActionGuard>,
+
     /// The metrics for this chunk
     ///
     /// Wrapped in a mutex to allow updating metrics without exclusive access to CatalogChunk
@@ -234,6 +285,9 @@ pub struct CatalogChunk {
 
     /// Order of this chunk relative to other overlapping chunks.
     order: ChunkOrder,
+
+    /// Publishes changes to this chunk to catalog subscribers
+    events: CatalogEvents,
 }
 
 impl std::fmt::Display for CatalogChunk {
@@ -264,6 +318,15 @@ pub struct ChunkMetrics {
 
     /// Catalog memory metrics
     pub(super) memory_metrics: StorageRecorder,
+
+    /// Number of storage transitions, keyed by `from` and `to` storage
+    pub(super) storage_transitions: RecorderCollection<U64Counter>,
+
+    /// Attributes added to each storage transition observation
+    pub(super) transition_attributes: Attributes,
+
+    /// Number of chunks being loaded into the read buffer
+    pub(super) loading_read_buffer: LifecycleActionCounter,
 }
 
 impl ChunkMetrics {
@@ -276,8 +339,19 @@ impl ChunkMetrics {
             chunk_storage: StorageRecorder::new_unregistered(),
             row_count: StorageRecorder::new_unregistered(),
             memory_metrics: StorageRecorder::new_unregistered(),
+            storage_transitions: RecorderCollection::new_unregistered(),
+            transition_attributes: Attributes::from(&[]),
+            loading_read_buffer: Default::default(),
         }
     }
+
+    /// Records a chunk moving from one storage location to another
+    fn record_storage_transition(&mut self, from: ChunkStorage, to: ChunkStorage) {
+        let mut attributes = self.transition_attributes.clone();
+        attributes.insert("from", from.as_str());
+        attributes.insert("to", to.as_str());
+        self.storage_transitions.recorder(attributes).inc(1);
+    }
 }
 
 impl CatalogChunk {
@@ -288,6 +362,7 @@ impl CatalogChunk {
         addr: ChunkAddr,
         chunk: mutable_buffer::MBChunk,
         metrics: ChunkMetrics,
+        events: CatalogEvents,
         order: ChunkOrder,
         time_provider: Arc<dyn TimeProvider>,
     ) -> Self {
@@ -302,14 +377,17 @@ impl CatalogChunk {
             stage,
             lifecycle_action: None,
             to_be_created_chunk_id: None,
+            loading_read_buffer: None,
             metrics: Mutex::new(metrics),
             access_recorder: AccessRecorder::new(Arc::clone(&time_provider)),
             time_provider,
             time_of_first_write: now,
             time_of_last_write: now,
             order,
+            events,
         };
         chunk.update_metrics();
+        chunk.publish_created();
         chunk
     }
 
@@ -324,6 +402,7 @@ impl CatalogChunk {
         time_of_last_write: Time,
         schema: Arc<Schema>,
         metrics: ChunkMetrics,
+        events: CatalogEvents,
         delete_predicates: Vec<Arc<DeletePredicate>>,
         order: ChunkOrder,
         time_provider: Arc<dyn TimeProvider>,
@@ -342,14 +421,17 @@ impl CatalogChunk {
             stage,
             lifecycle_action: None,
             to_be_created_chunk_id: None,
+            loading_read_buffer: None,
             metrics: Mutex::new(metrics),
             access_recorder: AccessRecorder::new(Arc::clone(&time_provider)),
             time_provider,
             time_of_first_write,
             time_of_last_write,
             order,
+            events,
         };
         chunk.update_metrics();
+        chunk.publish_created();
         chunk
     }
 
@@ -362,6 +444,7 @@ impl CatalogChunk {
         time_of_first_write: Time,
         time_of_last_write: Time,
         metrics: ChunkMetrics,
+        events: CatalogEvents,
         delete_predicates: Vec<Arc<DeletePredicate>>,
         order: ChunkOrder,
         time_provider: Arc<dyn TimeProvider>,
@@ -386,14 +469,17 @@ impl CatalogChunk {
             stage,
             lifecycle_action: None,
             to_be_created_chunk_id: None,
+            loading_read_buffer: None,
             metrics: Mutex::new(metrics),
             access_recorder: AccessRecorder::new(Arc::clone(&time_provider)),
             time_provider,
             time_of_first_write,
             time_of_last_write,
             order,
+            events,
         };
         chunk.update_metrics();
+        chunk.publish_created();
         chunk
     }
 
@@ -453,10 +539,66 @@ impl CatalogChunk {
         self.time_of_last_write
     }
 
+    /// Returns true if this chunk is open and has reached any of the
+    /// thresholds of `policy` at time `now`
+    pub fn should_close(&self, policy: &ClosePolicy, now: Time) -> bool {
+        if !self.stage.is_open() {
+            return false;
+        }
+
+        let age = now
+            .checked_duration_since(self.time_of_first_write)
+            .unwrap_or_default();
+
+        matches!(policy.max_age, Some(max_age) if age >= max_age)
+            || matches!(policy.max_rows, Some(max_rows) if self.storage().0 >= max_rows)
+            || matches!(policy.max_bytes, Some(max_bytes) if self.memory_bytes() >= max_bytes)
+    }
+
+    /// Returns the newest timestamp of the data in this chunk, according to
+    /// the statistics of its time column, if known
+    pub fn max_timestamp(&self) -> Option<Time> {
+        let table_summary = self.table_summary();
+        match &table_summary.column(TIME_COLUMN_NAME)?.stats {
+            Statistics::I64(stats) => stats.max.map(Time::from_timestamp_nanos),
+            _ => None,
+        }
+    }
+
     pub fn order(&self) -> ChunkOrder {
         self.order
     }
 
+    /// Re-homes this chunk at `addr` with the given `order`, when it is moved
+    /// into another partition of the same table
+    pub(super) fn move_to(&mut self, addr: ChunkAddr, order: ChunkOrder) {
+        assert_eq!(addr.table_name, self.addr.table_name);
+        self.addr = addr;
+        self.order = order;
+    }
+
+    /// Records a storage transition metric, and publishes a catalog event, if
+    /// this chunk is no longer in the `from` storage location
+    fn record_storage_transition(&self, from: ChunkStorage) {
+        let (_, to) = self.storage();
+        if from != to {
+            self.metrics.lock().record_storage_transition(from, to);
+            self.events.publish(|| CatalogEvent::ChunkTransitioned {
+                addr: self.addr.clone(),
+                from,
+                to,
+            });
+        }
+    }
+
+    /// Publishes a catalog event for the creation of this chunk
+    fn publish_created(&self) {
+        self.events.publish(|| CatalogEvent::ChunkCreated {
+            addr: self.addr.clone(),
+            storage: self.storage().1,
+        });
+    }
+
     /// Updates `self.metrics` to match the contents of `self.stage`
     pub fn update_metrics(&self) {
         let mut metrics = self.metrics.lock();
@@ -725,6 +867,7 @@ impl CatalogChunk {
                     meta: Arc::new(metadata),
                 };
                 self.update_metrics();
+                self.record_storage_transition(ChunkStorage::OpenMutableBuffer);
 
                 Ok(())
             }
@@ -841,6 +984,7 @@ impl CatalogChunk {
                             read_buffer: Some(db),
                         };
                         self.update_metrics();
+                        self.record_storage_transition(ChunkStorage::ReadBuffer);
                         Ok(())
                     }
                 }
@@ -885,6 +1029,7 @@ impl CatalogChunk {
         match &mut self.stage {
             ChunkStage::Persisted { read_buffer, .. } => {
                 *read_buffer = Some(loaded);
+                self.record_storage_transition(ChunkStorage::ObjectStoreOnly);
                 Ok(())
             }
             _ => {
@@ -899,6 +1044,7 @@ impl CatalogChunk {
             ChunkStage::Persisted { read_buffer, .. } => {
                 if let Some(rub_chunk) = read_buffer.take() {
                     self.update_metrics();
+                    self.record_storage_transition(ChunkStorage::ReadBufferAndObjectStore);
 
                     Ok(rub_chunk)
                 } else {
@@ -943,6 +1089,9 @@ impl CatalogChunk {
             });
         }
         self.lifecycle_action = Some(registration.clone().into_tracker(lifecycle_action));
+        if lifecycle_action == ChunkLifecycleAction::LoadingReadBuffer {
+            self.loading_read_buffer = Some(self.metrics.lock().loading_read_buffer.start());
+        }
         if lifecycle_action == ChunkLifecycleAction::CompactingObjectStore {
             assert!(
                 to_be_created_chunk_id.is_some(),
@@ -971,6 +1120,7 @@ impl CatalogChunk {
         }
         self.lifecycle_action = None;
         self.to_be_created_chunk_id = None;
+        self.loading_read_buffer = None;
         Ok(())
     }
 
@@ -987,6 +1137,7 @@ impl CatalogChunk {
             }
             self.lifecycle_action = None;
             self.to_be_created_chunk_id = None;
+            self.loading_read_buffer = None;
 
             // Some lifecycle actions (e.g. Drop) modify the memory metrics so that the catalog accounts chunks w/
             // actions correctly. When clearing out that action, we need to restore the pre-action state. The easiest
@@ -1033,6 +1184,76 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_stage_describe() {
+        let mut chunk = make_open_chunk();
+        assert_eq!(chunk.stage().describe(), "Open (mutable buffer, 1 rows)");
+
+        chunk.freeze().unwrap();
+        assert_eq!(
+            chunk.stage().describe(),
+            "Frozen (mutable buffer snapshot, 1 rows)"
+        );
+
+        let mut chunk = make_persisted_chunk().await;
+        let rows = match chunk.stage() {
+            ChunkStage::Persisted { parquet, .. } => parquet.rows(),
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            chunk.stage().describe(),
+            format!("Persisted (object store, {} rows)", rows)
+        );
+
+        let registration = TaskRegistration::new(Arc::clone(&chunk.time_provider));
+        chunk.set_loading_to_read_buffer(&registration).unwrap();
+        chunk.set_loaded_to_read_buffer(make_rb_chunk()).unwrap();
+        assert_eq!(
+            chunk.stage().describe(),
+            format!("Persisted (read buffer and object store, {} rows)", rows)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_close() {
+        let chunk = make_open_chunk();
+        let created = chunk.time_of_first_write();
+        let (rows, _) = chunk.storage();
+        let bytes = chunk.memory_bytes();
+
+        let age_policy = ClosePolicy {
+            max_age: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        assert!(!chunk.should_close(&age_policy, created + Duration::from_secs(59)));
+        assert!(chunk.should_close(&age_policy, created + Duration::from_secs(60)));
+
+        let rows_policy = ClosePolicy {
+            max_rows: Some(rows),
+            ..Default::default()
+        };
+        assert!(chunk.should_close(&rows_policy, created));
+
+        let bytes_policy = ClosePolicy {
+            max_bytes: Some(bytes),
+            ..Default::default()
+        };
+        assert!(chunk.should_close(&bytes_policy, created));
+
+        // no threshold reached
+        let policy = ClosePolicy {
+            max_age: Some(Duration::from_secs(60)),
+            max_rows: Some(rows + 1),
+            max_bytes: Some(bytes + 1),
+        };
+        assert!(!chunk.should_close(&policy, created + Duration::from_secs(1)));
+        assert!(!chunk.should_close(&ClosePolicy::default(), created + Duration::from_secs(3600)));
+
+        // only open chunks are closed
+        let chunk = make_persisted_chunk().await;
+        assert!(!chunk.should_close(&rows_policy, created));
+    }
+
     #[tokio::test]
     async fn set_compacting_freezes_chunk() {
         let mut chunk = make_open_chunk();
@@ -1043,6 +1264,63 @@ mod tests {
         assert!(matches!(chunk.stage, ChunkStage::Frozen { .. }));
     }
 
+    #[tokio::test]
+    async fn test_storage_transition_metrics() {
+        fn transitions(chunk: &CatalogChunk, from: ChunkStorage, to: ChunkStorage) -> u64 {
+            let mut metrics = chunk.metrics.lock();
+            metrics
+                .storage_transitions
+                .recorder(&[("from", from.as_str()), ("to", to.as_str())])
+                .fetch()
+        }
+
+        let mut chunk = make_open_chunk();
+        chunk.freeze().unwrap();
+        // freezing a frozen chunk is not a transition
+        chunk.freeze().unwrap();
+        assert_eq!(
+            transitions(
+                &chunk,
+                ChunkStorage::OpenMutableBuffer,
+                ChunkStorage::ClosedMutableBuffer
+            ),
+            1
+        );
+
+        let mut chunk = make_persisted_chunk().await;
+        for _ in 0..2 {
+            let registration = TaskRegistration::new(Arc::clone(&chunk.time_provider));
+            chunk.set_loading_to_read_buffer(&registration).unwrap();
+            chunk.set_loaded_to_read_buffer(make_rb_chunk()).unwrap();
+            chunk.set_unloaded_from_read_buffer().unwrap();
+        }
+
+        assert_eq!(
+            transitions(
+                &chunk,
+                ChunkStorage::ObjectStoreOnly,
+                ChunkStorage::ReadBufferAndObjectStore
+            ),
+            2
+        );
+        assert_eq!(
+            transitions(
+                &chunk,
+                ChunkStorage::ReadBufferAndObjectStore,
+                ChunkStorage::ObjectStoreOnly
+            ),
+            2
+        );
+        assert_eq!(
+            transitions(
+                &chunk,
+                ChunkStorage::OpenMutableBuffer,
+                ChunkStorage::ClosedMutableBuffer
+            ),
+            0
+        );
+    }
+
     #[tokio::test]
     async fn test_drop() {
         let mut chunk = make_open_chunk();
@@ -1160,6 +1438,35 @@ mod tests {
         chunk.clear_lifecycle_action().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_loading_read_buffer_count() {
+        let mut chunk = make_persisted_chunk().await;
+        let counter = chunk.metrics.lock().loading_read_buffer.clone();
+        let registration = TaskRegistration::new(Arc::clone(&chunk.time_provider));
+
+        chunk.set_loading_to_read_buffer(&registration).unwrap();
+        assert_eq!(counter.get(), 1);
+
+        registration.into_tracker(1).cancel();
+        chunk.clear_lifecycle_action().unwrap();
+        assert_eq!(counter.get(), 0);
+
+        // other actions are not counted
+        let registration = TaskRegistration::new(Arc::clone(&chunk.time_provider));
+        chunk.set_dropping(&registration).unwrap();
+        assert_eq!(counter.get(), 0);
+
+        registration.into_tracker(1).cancel();
+        chunk.clear_lifecycle_action().unwrap();
+
+        // dropping a loading chunk stops counting it
+        let registration = TaskRegistration::new(Arc::clone(&chunk.time_provider));
+        chunk.set_loading_to_read_buffer(&registration).unwrap();
+        assert_eq!(counter.get(), 1);
+        drop(chunk);
+        assert_eq!(counter.get(), 0);
+    }
+
     #[test]
     fn test_add_delete_predicate_open_chunk() {
         let mut chunk = make_open_chunk();
@@ -1214,6 +1521,17 @@ mod tests {
         write_lp_to_new_chunk(&format!("{} bar=1 10", table_name))
     }
 
+    fn make_rb_chunk() -> Arc<RBChunk> {
+        let batch = make_mb_chunk("table1")
+            .to_arrow(schema::selection::Selection::All)
+            .unwrap();
+        Arc::new(RBChunk::new(
+            "table1",
+            batch,
+            read_buffer::ChunkMetrics::new_unregistered(),
+        ))
+    }
+
     fn chunk_addr() -> ChunkAddr {
         ChunkAddr {
             db_name: Arc::from("db"),
@@ -1231,6 +1549,7 @@ mod tests {
             addr,
             mb_chunk,
             ChunkMetrics::new_unregistered(),
+            CatalogEvents::new(),
             ChunkOrder::new(5).unwrap(),
             Arc::new(time::SystemProvider::new()),
         )
@@ -1249,6 +1568,7 @@ mod tests {
             now,
             now,
             ChunkMetrics::new_unregistered(),
+            CatalogEvents::new(),
             vec![],
             ChunkOrder::new(6).unwrap(),
             Arc::new(time::SystemProvider::new()),
@@ -0,0 +1,64 @@
+//! Notifications about changes to the chunks of a [`Catalog`](super::Catalog)
+use data_types::chunk_metadata::{ChunkAddr, ChunkStorage};
+use tokio::sync::broadcast;
+
+/// The number of events buffered for each subscriber; subscribers that fall
+/// further behind miss events and are told so with
+/// [`RecvError::Lagged`](broadcast::error::RecvError::Lagged)
+const EVENT_BUFFER_SIZE: usize = 1024;
+
+/// A change to a chunk in the catalog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogEvent {
+    /// A chunk was added to the catalog
+    ChunkCreated {
+        addr: ChunkAddr,
+        storage: ChunkStorage,
+    },
+
+    /// A chunk moved from one storage location to another
+    ChunkTransitioned {
+        addr: ChunkAddr,
+        from: ChunkStorage,
+        to: ChunkStorage,
+    },
+
+    /// A chunk was removed from the catalog
+    ChunkDropped { addr: ChunkAddr },
+}
+
+/// Broadcasts [`CatalogEvent`]s to subscribers.
+///
+/// Events are only constructed and sent while there is at least one
+/// subscriber.
+#[derive(Debug, Clone)]
+pub struct CatalogEvents {
+    sender: broadcast::Sender<CatalogEvent>,
+}
+
+impl CatalogEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER_SIZE);
+        Self { sender }
+    }
+
+    /// Returns a receiver of all events published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<CatalogEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publishes the event returned by `event` if anyone is subscribed
+    pub(super) fn publish(&self, event: impl FnOnce() -> CatalogEvent) {
+        if self.sender.receiver_count() > 0 {
+            // sending only fails if all receivers have been dropped since
+            // checking, in which case nobody is interested in the event
+            let _ = self.sender.send(event());
+        }
+    }
+}
+
+impl Default for CatalogEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
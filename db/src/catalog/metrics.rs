@@ -2,9 +2,15 @@ use crate::catalog::chunk::ChunkMetrics;
 use data_types::write_summary::TimestampSummary;
 use metric::{
     Attributes, CumulativeGauge, CumulativeRecorder, DurationHistogram, DurationHistogramOptions,
-    Metric, MetricObserver,
+    Metric, MetricObserver, RecorderCollection, U64Counter,
+};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
-use std::{sync::Arc, time::Duration};
 use tracker::{LockMetrics, RwLock};
 
 const TIMESTAMP_METRICS_ENABLE_ENV: &str = "INFLUXDB_IOX_ROW_TIMESTAMP_METRICS";
@@ -34,6 +40,9 @@ pub struct CatalogMetrics {
 
     /// Catalog memory metrics
     memory_metrics: StorageGauge,
+
+    /// Number of chunks being loaded into the read buffer
+    loading_read_buffer: LifecycleActionCounter,
 }
 
 impl CatalogMetrics {
@@ -71,6 +80,7 @@ impl CatalogMetrics {
             partition_lock_metrics,
             chunk_lock_metrics,
             memory_metrics,
+            loading_read_buffer: Default::default(),
         }
     }
 
@@ -79,6 +89,11 @@ impl CatalogMetrics {
         &self.memory_metrics
     }
 
+    /// Returns the number of chunks being loaded into the read buffer
+    pub fn loading_read_buffer_chunks(&self) -> usize {
+        self.loading_read_buffer.get()
+    }
+
     pub(super) fn new_table_metrics(self: &Arc<Self>, table_name: &str) -> TableMetrics {
         let base_attributes = metric::Attributes::from([
             ("db_name", self.db_name.to_string().into()),
@@ -95,17 +110,24 @@ impl CatalogMetrics {
             "The number of rows loaded in each chunk storage location",
         );
 
+        let storage_transitions = self.metric_registry.register_metric(
+            "catalog_chunk_storage_transitions",
+            "The number of times chunks moved between storage locations",
+        );
+
         let timestamp_histogram = report_timestamp_metrics(table_name).then(|| {
             TimestampHistogram::new(self.metric_registry.as_ref(), base_attributes.clone())
         });
 
         let chunk_storage = StorageGauge::new(&storage_gauge, base_attributes.clone());
-        let row_count = StorageGauge::new(&row_gauge, base_attributes);
+        let row_count = StorageGauge::new(&row_gauge, base_attributes.clone());
 
         TableMetrics {
             catalog_metrics: Arc::clone(self),
+            base_attributes,
             chunk_storage,
             row_count,
+            storage_transitions,
             timestamp_histogram,
         }
     }
@@ -116,12 +138,18 @@ impl CatalogMetrics {
 pub struct TableMetrics {
     catalog_metrics: Arc<CatalogMetrics>,
 
+    /// Attributes identifying this table
+    base_attributes: Attributes,
+
     /// Chunk storage metrics
     chunk_storage: StorageGauge,
 
     /// Chunk row count metrics
     row_count: StorageGauge,
 
+    /// Chunk storage transition counts
+    storage_transitions: Metric<U64Counter>,
+
     /// Track ingested timestamps
     timestamp_histogram: Option<TimestampHistogram>,
 }
@@ -168,10 +196,45 @@ impl PartitionMetrics {
             chunk_storage: self.table_metrics.chunk_storage.recorder(),
             row_count: self.table_metrics.row_count.recorder(),
             memory_metrics: self.table_metrics.catalog_metrics.memory_metrics.recorder(),
+            storage_transitions: RecorderCollection::new(
+                self.table_metrics.storage_transitions.clone(),
+            ),
+            transition_attributes: self.table_metrics.base_attributes.clone(),
+            loading_read_buffer: self
+                .table_metrics
+                .catalog_metrics
+                .loading_read_buffer
+                .clone(),
         }
     }
 }
 
+/// Counts the chunks with a given lifecycle action in progress
+#[derive(Debug, Clone, Default)]
+pub struct LifecycleActionCounter(Arc<AtomicUsize>);
+
+impl LifecycleActionCounter {
+    pub(super) fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Counts a chunk until the returned guard is dropped
+    pub(super) fn start(&self) -> LifecycleActionGuard {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        LifecycleActionGuard(Arc::clone(&self.0))
+    }
+}
+
+/// Removes a chunk from its [`LifecycleActionCounter`] when dropped
+#[derive(Debug)]
+pub struct LifecycleActionGuard(Arc<AtomicUsize>);
+
+impl Drop for LifecycleActionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 /// Created from a `Metric<CumulativeGauge>` and extracts a `CumulativeRecorder` for each chunk storage
 ///
 /// This can then be used within each `CatalogChunk` to record its observations for
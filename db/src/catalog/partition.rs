@@ -1,6 +1,7 @@
 //! The catalog representation of a Partition
 
 use super::chunk::{CatalogChunk, Error as ChunkError};
+use crate::catalog::events::{CatalogEvent, CatalogEvents};
 use crate::catalog::metrics::PartitionMetrics;
 use data_types::{
     chunk_metadata::{ChunkAddr, ChunkId, ChunkLifecycleAction, ChunkOrder, ChunkSummary},
@@ -17,6 +18,7 @@ use snafu::{OptionExt, Snafu};
 use std::{
     collections::{BTreeMap, BTreeSet},
     fmt::Display,
+    ops::Bound,
     ops::RangeInclusive,
     sync::Arc,
 };
@@ -41,12 +43,44 @@ pub enum Error {
     #[snafu(display("creating new mutable buffer chunk failed: {}", source))]
     CreateOpenChunk { source: ChunkError },
 
+    #[snafu(display("chunk {} already exists", chunk))]
+    DuplicateChunkId { chunk: ChunkAddr },
+
     #[snafu(display("checking chunk contiguous fails"))]
     ContiguousCheck {},
+
+    #[snafu(display(
+        "chunks {} and {} in partition {} share chunk order {}",
+        first,
+        second,
+        partition,
+        order
+    ))]
+    DuplicateChunkOrder {
+        partition: PartitionAddr,
+        order: ChunkOrder,
+        first: ChunkId,
+        second: ChunkId,
+    },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Whether a chunk added to a partition may share its `ChunkOrder` with
+/// chunks already in the partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkOrderCheck {
+    /// Adding the chunk fails if another chunk has the same order.
+    Unique,
+
+    /// The order may be shared. Persistence splits its input chunks into a
+    /// persisted chunk and a remainder that keep the order of the inputs, as
+    /// their rows are disjoint in time, so such chunks may also be loaded
+    /// from a preserved catalog. Compacting either of them produces a chunk
+    /// that keeps the shared order.
+    AllowShared,
+}
+
 /// Provides ordered iteration of a collection of chunks
 #[derive(Debug, Default)]
 struct ChunkCollection {
@@ -105,6 +139,22 @@ impl ChunkCollection {
         }
     }
 
+    /// Returns the ID of a chunk other than `id` with the given order, if any
+    fn other_chunk_with_order(&self, id: ChunkId, order: ChunkOrder) -> Option<ChunkId> {
+        let before = self.chunks.range(..(order, id)).next_back();
+        let after = self
+            .chunks
+            .range((Bound::Excluded((order, id)), Bound::Unbounded))
+            .next();
+
+        before
+            .into_iter()
+            .chain(after)
+            .map(|((other_order, other_id), _)| (*other_order, *other_id))
+            .find(|(other_order, _)| *other_order == order)
+            .map(|(_, other_id)| other_id)
+    }
+
     /// Remove a chunk with the given ID, returns None if the chunk doesn't exist
     fn remove(&mut self, id: ChunkId) -> Option<Arc<RwLock<CatalogChunk>>> {
         let order = self.chunk_orders.remove(&id)?;
@@ -115,6 +165,20 @@ impl ChunkCollection {
     fn is_empty(&self) -> bool {
         self.chunk_orders.is_empty()
     }
+
+    /// Returns the pair of chunks with the highest `ChunkOrder` that share
+    /// that order, if any
+    fn duplicate_order(&self) -> Option<(ChunkOrder, ChunkId, ChunkId)> {
+        let mut keys = self.chunks.keys().rev();
+        let mut next = keys.next()?;
+        for key in keys {
+            if key.0 == next.0 {
+                return Some((key.0, key.1, next.1));
+            }
+            next = key;
+        }
+        None
+    }
 }
 
 /// IOx Catalog Partition
@@ -137,6 +201,10 @@ pub struct Partition {
     /// Partition metrics
     metrics: Arc<PartitionMetrics>,
 
+    /// Publishes changes to the chunks of this partition to catalog
+    /// subscribers
+    events: CatalogEvents,
+
     /// Ingest tracking for persisting data from memory to Parquet
     persistence_windows: Option<PersistenceWindows>,
 
@@ -155,6 +223,7 @@ impl Partition {
     pub(super) fn new(
         addr: PartitionAddr,
         metrics: PartitionMetrics,
+        events: CatalogEvents,
         time_provider: Arc<dyn TimeProvider>,
     ) -> Self {
         let now = time_provider.now();
@@ -164,6 +233,7 @@ impl Partition {
             created_at: now,
             last_write_at: now,
             metrics: Arc::new(metrics),
+            events,
             persistence_windows: None,
             next_chunk_order: ChunkOrder::MIN,
             time_provider,
@@ -215,19 +285,27 @@ impl Partition {
         &mut self,
         chunk: mutable_buffer::MBChunk,
     ) -> &Arc<RwLock<CatalogChunk>> {
-        let chunk_id = ChunkId::new();
+        let chunk_id = self.new_chunk_id();
         let chunk_order = self.next_chunk_order();
 
-        self.create_open_chunk_with_specified_id_order(chunk, chunk_id, chunk_order)
+        self.create_open_chunk_with_specified_id_order(
+            chunk,
+            chunk_id,
+            chunk_order,
+            ChunkOrderCheck::Unique,
+        )
+        .expect("new chunk ID and order are unused")
     }
 
-    fn create_open_chunk_with_specified_id_order(
+    pub(super) fn create_open_chunk_with_specified_id_order(
         &mut self,
         chunk: mutable_buffer::MBChunk,
         chunk_id: ChunkId,
         chunk_order: ChunkOrder,
-    ) -> &Arc<RwLock<CatalogChunk>> {
+        order_check: ChunkOrderCheck,
+    ) -> Result<&Arc<RwLock<CatalogChunk>>> {
         assert_eq!(chunk.table_name().as_ref(), self.table_name());
+        self.ensure_chunk_insertable(chunk_id, chunk_order, order_check)?;
 
         let addr = ChunkAddr::new(&self.addr, chunk_id);
 
@@ -235,16 +313,67 @@ impl Partition {
             addr,
             chunk,
             self.metrics.new_chunk_metrics(),
+            self.events.clone(),
             chunk_order,
             Arc::clone(&self.time_provider),
         );
         let chunk = Arc::new(self.metrics.new_chunk_lock(chunk));
-        self.chunks.insert(chunk_id, chunk_order, chunk)
+        Ok(self.chunks.insert(chunk_id, chunk_order, chunk))
+    }
+
+    /// Returns a random chunk ID that is not used by any chunk of this
+    /// partition.
+    fn new_chunk_id(&self) -> ChunkId {
+        loop {
+            let chunk_id = ChunkId::new();
+            if self.chunks.get(chunk_id).is_none() {
+                return chunk_id;
+            }
+        }
+    }
+
+    /// Returns an error if a chunk with the given ID already exists in this
+    /// partition.
+    pub fn ensure_chunk_id_unused(&self, chunk_id: ChunkId) -> Result<()> {
+        match self.chunks.get(chunk_id) {
+            Some(_) => DuplicateChunkIdSnafu {
+                chunk: ChunkAddr::new(&self.addr, chunk_id),
+            }
+            .fail(),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns an error if a chunk with the given ID, or with the given order
+    /// if it must be unique, already exists in this partition.
+    fn ensure_chunk_insertable(
+        &self,
+        chunk_id: ChunkId,
+        chunk_order: ChunkOrder,
+        order_check: ChunkOrderCheck,
+    ) -> Result<()> {
+        self.ensure_chunk_id_unused(chunk_id)?;
+        if order_check == ChunkOrderCheck::AllowShared {
+            return Ok(());
+        }
+
+        match self.chunks.other_chunk_with_order(chunk_id, chunk_order) {
+            Some(other) => DuplicateChunkOrderSnafu {
+                partition: self.addr.clone(),
+                order: chunk_order,
+                first: other,
+                second: chunk_id,
+            }
+            .fail(),
+            None => Ok(()),
+        }
     }
 
     /// Create a new read buffer chunk.
     ///
-    /// Returns ID and chunk.
+    /// Returns ID and chunk, or an error if `chunk_id`, or `chunk_order`
+    /// according to `order_check`, is already used by a chunk of this
+    /// partition. A fresh ID is generated if none is provided.
     #[allow(clippy::too_many_arguments)] // TODO(marco) make it nicer
     pub fn create_rub_chunk(
         &mut self,
@@ -254,9 +383,11 @@ impl Partition {
         schema: Arc<Schema>,
         delete_predicates: Vec<Arc<DeletePredicate>>,
         chunk_order: ChunkOrder,
+        order_check: ChunkOrderCheck,
         chunk_id: Option<ChunkId>,
-    ) -> (ChunkId, &Arc<RwLock<CatalogChunk>>) {
-        let chunk_id = chunk_id.unwrap_or_else(ChunkId::new);
+    ) -> Result<(ChunkId, &Arc<RwLock<CatalogChunk>>)> {
+        let chunk_id = chunk_id.unwrap_or_else(|| self.new_chunk_id());
+        self.ensure_chunk_insertable(chunk_id, chunk_order, order_check)?;
         assert!(
             chunk_order < self.next_chunk_order,
             "chunk order for new RUB chunk ({}) is out of range [0, {})",
@@ -274,13 +405,14 @@ impl Partition {
             time_of_last_write,
             schema,
             self.metrics.new_chunk_metrics(),
+            self.events.clone(),
             delete_predicates,
             chunk_order,
             Arc::clone(&self.time_provider),
         )));
 
         let chunk = self.chunks.insert(chunk_id, chunk_order, chunk);
-        (chunk_id, chunk)
+        Ok((chunk_id, chunk))
     }
 
     /// Create new chunk that is only in object store (= parquet file).
@@ -289,7 +421,9 @@ impl Partition {
     ///
     /// The partition-specific chunk order counter will be set to `max(current, chunk_order + 1)`.
     ///
-    /// Returns the previous chunk with the given chunk_id if any
+    /// Returns an error if a chunk with the given chunk_id, or with the given
+    /// chunk_order according to `order_check`, already exists.
+    #[allow(clippy::too_many_arguments)]
     pub fn insert_object_store_only_chunk(
         &mut self,
         chunk_id: ChunkId,
@@ -298,8 +432,10 @@ impl Partition {
         time_of_last_write: Time,
         delete_predicates: Vec<Arc<DeletePredicate>>,
         chunk_order: ChunkOrder,
-    ) -> &Arc<RwLock<CatalogChunk>> {
+        order_check: ChunkOrderCheck,
+    ) -> Result<&Arc<RwLock<CatalogChunk>>> {
         assert_eq!(chunk.table_name(), self.table_name());
+        self.ensure_chunk_insertable(chunk_id, chunk_order, order_check)?;
 
         let addr = ChunkAddr::new(&self.addr, chunk_id);
 
@@ -311,6 +447,7 @@ impl Partition {
                     time_of_first_write,
                     time_of_last_write,
                     self.metrics.new_chunk_metrics(),
+                    self.events.clone(),
                     delete_predicates,
                     chunk_order,
                     Arc::clone(&self.time_provider),
@@ -322,7 +459,7 @@ impl Partition {
         // only update internal state when we know that insertion is OK
         self.next_chunk_order = self.next_chunk_order.max(chunk_order.next());
 
-        chunk
+        Ok(chunk)
     }
 
     /// Drop the specified chunk
@@ -343,7 +480,9 @@ impl Partition {
                         }
                     }
                 }
-                Ok(self.chunks.remove(chunk_id).unwrap())
+                let chunk = self.chunks.remove(chunk_id).unwrap();
+                self.publish_dropped(chunk_id);
+                Ok(chunk)
             }
         }
     }
@@ -351,9 +490,52 @@ impl Partition {
     /// Drop the specified chunk even if it has an in-progress lifecycle action
     /// returning the dropped chunk
     pub fn force_drop_chunk(&mut self, chunk_id: ChunkId) -> Result<Arc<RwLock<CatalogChunk>>> {
-        self.chunks.remove(chunk_id).context(ChunkNotFoundSnafu {
+        let chunk = self.chunks.remove(chunk_id).context(ChunkNotFoundSnafu {
             chunk: ChunkAddr::new(&self.addr, chunk_id),
-        })
+        })?;
+        self.publish_dropped(chunk_id);
+        Ok(chunk)
+    }
+
+    fn publish_dropped(&self, chunk_id: ChunkId) {
+        self.events.publish(|| CatalogEvent::ChunkDropped {
+            addr: ChunkAddr::new(&self.addr, chunk_id),
+        });
+    }
+
+    /// Removes and returns all chunks of this partition, ordered by chunk order
+    /// and then ID, so that they can be adopted by another partition
+    pub(super) fn take_chunks(&mut self) -> Vec<Arc<RwLock<CatalogChunk>>> {
+        std::mem::take(&mut self.chunks).values().cloned().collect()
+    }
+
+    /// Adds a chunk taken from another partition of the same table, giving it
+    /// the next chunk order of this partition.
+    ///
+    /// Returns an error, leaving the chunk untouched, if its ID is already used
+    /// by a chunk of this partition.
+    pub(super) fn adopt_chunk(&mut self, chunk: Arc<RwLock<CatalogChunk>>) -> Result<()> {
+        let chunk_id = chunk.read().id();
+        self.ensure_chunk_insertable(chunk_id, self.next_chunk_order, ChunkOrderCheck::Unique)?;
+
+        let chunk_order = self.next_chunk_order();
+        let (old_addr, new_addr, storage) = {
+            let mut chunk = chunk.write();
+            let old_addr = chunk.addr().clone();
+            let new_addr = ChunkAddr::new(&self.addr, old_addr.chunk_id);
+            chunk.move_to(new_addr.clone(), chunk_order);
+            (old_addr, new_addr, chunk.storage().1)
+        };
+        self.chunks.insert(new_addr.chunk_id, chunk_order, chunk);
+
+        self.events
+            .publish(|| CatalogEvent::ChunkDropped { addr: old_addr });
+        self.events.publish(|| CatalogEvent::ChunkCreated {
+            addr: new_addr,
+            storage,
+        });
+
+        Ok(())
     }
 
     /// Return the first currently open chunk, if any
@@ -407,6 +589,27 @@ impl Partition {
         Ok(true)
     }
 
+    /// Checks that no two chunks in this partition share the same `ChunkOrder`.
+    ///
+    /// If several orders are duplicated, the error reports the highest one.
+    ///
+    /// Chunks are locked in `(ChunkOrder, ChunkId)` order, so duplicates do not
+    /// cause deadlocks, but they make the relative age of the affected chunks
+    /// ambiguous. Adding a chunk rejects a duplicate order unless
+    /// [`ChunkOrderCheck::AllowShared`] is used, as by persistence.
+    pub fn validate_chunk_orders(&self) -> Result<()> {
+        match self.chunks.duplicate_order() {
+            Some((order, first, second)) => DuplicateChunkOrderSnafu {
+                partition: self.addr.clone(),
+                order,
+                first,
+                second,
+            }
+            .fail(),
+            None => Ok(()),
+        }
+    }
+
     /// Return a PartitionSummary for this partition. If the partition
     /// has no chunks, returns None.
     pub fn summary(&self) -> Option<PartitionSummary> {
@@ -493,7 +696,8 @@ mod tests {
         let mut expected_ids = vec![];
 
         // Make three chunks
-        let mut partition = Partition::new(addr, partition_metrics, time_provider);
+        let mut partition =
+            Partition::new(addr, partition_metrics, CatalogEvents::new(), time_provider);
         for _ in 0..3 {
             let chunk = partition.create_open_chunk(make_mb_chunk("t"));
             expected_ids.push(chunk.read().addr().chunk_id)
@@ -625,6 +829,98 @@ mod tests {
         assert!(!partition.contiguous_chunks(&ids, &order_range).unwrap());
     }
 
+    #[test]
+    fn test_validate_chunk_orders() {
+        let partition = make_partitition_for_chunks_with_ids_orders(&[(1, 1), (2, 2), (3, 3)]);
+        partition.validate_chunk_orders().unwrap();
+
+        let partition =
+            make_partitition_for_chunks_with_ids_orders(&[(1, 1), (2, 2), (3, 2), (4, 3)]);
+        let err = partition.validate_chunk_orders().unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::DuplicateChunkOrder { order, first, second, .. }
+                    if order == ChunkOrder::new(2).unwrap()
+                        && first == ChunkId::new_test(2)
+                        && second == ChunkId::new_test(3)
+            ),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_duplicate_chunk_id() {
+        let mut partition = make_partitition_for_chunks_with_ids_orders(&[(1, 1)]);
+
+        let err = partition
+            .create_open_chunk_with_specified_id_order(
+                make_mb_chunk("t"),
+                ChunkId::new_test(1),
+                ChunkOrder::new(2).unwrap(),
+                ChunkOrderCheck::AllowShared,
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::DuplicateChunkId { chunk } if chunk.chunk_id == ChunkId::new_test(1)),
+            "{}",
+            err
+        );
+
+        // the existing chunk is untouched
+        assert_eq!(partition.chunks().count(), 1);
+        let (_, order) = partition.chunk(ChunkId::new_test(1)).unwrap();
+        assert_eq!(order, ChunkOrder::new(1).unwrap());
+    }
+
+    #[test]
+    fn test_duplicate_chunk_order() {
+        let mut partition = make_partitition_for_chunks_with_ids_orders(&[(1, 1), (2, 3)]);
+
+        let err = partition
+            .create_open_chunk_with_specified_id_order(
+                make_mb_chunk("t"),
+                ChunkId::new_test(3),
+                ChunkOrder::new(3).unwrap(),
+                ChunkOrderCheck::Unique,
+            )
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::DuplicateChunkOrder { order, first, second, .. }
+                    if order == ChunkOrder::new(3).unwrap()
+                        && first == ChunkId::new_test(2)
+                        && second == ChunkId::new_test(3)
+            ),
+            "{}",
+            err
+        );
+        assert_eq!(partition.chunks().count(), 2);
+
+        // an unused order between existing ones is fine
+        partition
+            .create_open_chunk_with_specified_id_order(
+                make_mb_chunk("t"),
+                ChunkId::new_test(3),
+                ChunkOrder::new(2).unwrap(),
+                ChunkOrderCheck::Unique,
+            )
+            .unwrap();
+
+        // sharing must be requested explicitly
+        partition
+            .create_open_chunk_with_specified_id_order(
+                make_mb_chunk("t"),
+                ChunkId::new_test(4),
+                ChunkOrder::new(1).unwrap(),
+                ChunkOrderCheck::AllowShared,
+            )
+            .unwrap();
+        assert_eq!(partition.chunks().count(), 4);
+    }
+
     fn make_partitition_for_chunks_with_ids_orders(id_orders: &[(u128, u32)]) -> Partition {
         let addr = PartitionAddr {
             db_name: "d".into(),
@@ -641,13 +937,17 @@ mod tests {
         let partition_metrics = table_metrics.new_partition_metrics();
 
         // make chunks for given id_orders
-        let mut partition = Partition::new(addr, partition_metrics, time_provider);
+        let mut partition =
+            Partition::new(addr, partition_metrics, CatalogEvents::new(), time_provider);
         for &(id, order) in id_orders {
-            partition.create_open_chunk_with_specified_id_order(
-                make_mb_chunk("t"),
-                ChunkId::new_test(id),
-                ChunkOrder::new(order).unwrap(),
-            );
+            partition
+                .create_open_chunk_with_specified_id_order(
+                    make_mb_chunk("t"),
+                    ChunkId::new_test(id),
+                    ChunkOrder::new(order).unwrap(),
+                    ChunkOrderCheck::AllowShared,
+                )
+                .unwrap();
         }
 
         partition
@@ -0,0 +1,44 @@
+//! A consistent, immutable view of the chunk metadata held by a
+//! [`Catalog`](super::Catalog)
+use std::collections::BTreeMap;
+
+use data_types::chunk_metadata::{ChunkAddr, ChunkSummary};
+
+/// Chunk metadata captured from the catalog at a single point in time.
+///
+/// Unlike the [`Catalog`](super::Catalog) a snapshot takes no locks to query
+/// and is unaffected by chunks subsequently being created, transitioned or
+/// dropped, so several lookups against it always see the same state.
+#[derive(Debug, Clone, Default)]
+pub struct CatalogSnapshot {
+    chunks: BTreeMap<ChunkAddr, ChunkSummary>,
+}
+
+impl CatalogSnapshot {
+    pub(super) fn new(chunks: impl IntoIterator<Item = (ChunkAddr, ChunkSummary)>) -> Self {
+        Self {
+            chunks: chunks.into_iter().collect(),
+        }
+    }
+
+    /// Returns the summaries of all chunks, ordered by chunk address
+    pub fn chunk_summaries(&self) -> impl Iterator<Item = &ChunkSummary> + '_ {
+        self.chunks.values()
+    }
+
+    /// Returns the summary of the chunk at `addr`, if it existed when the
+    /// snapshot was taken
+    pub fn chunk_by_addr(&self, addr: &ChunkAddr) -> Option<&ChunkSummary> {
+        self.chunks.get(addr)
+    }
+
+    /// Returns the number of chunks in the snapshot
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns true if the snapshot contains no chunks
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+}
@@ -1,4 +1,5 @@
 use super::partition::Partition;
+use crate::catalog::events::CatalogEvents;
 use crate::catalog::metrics::TableMetrics;
 use data_types::partition_metadata::{PartitionAddr, PartitionSummary};
 use hashbrown::HashMap;
@@ -7,6 +8,7 @@ use schema::{
     merge::{Error as SchemaMergerError, SchemaMerger},
     Schema,
 };
+use snafu::{ResultExt, Snafu};
 use std::{ops::Deref, result::Result, sync::Arc};
 use time::TimeProvider;
 use tracker::{RwLock, RwLockReadGuard, RwLockWriteGuard};
@@ -33,6 +35,9 @@ pub struct Table {
     /// - the inner `Arc<Schema>` is a schema that we don't need to copy when moving it around the query stack
     schema: Arc<RwLock<Arc<Schema>>>,
 
+    /// Publishes changes to the chunks of this table to catalog subscribers
+    events: CatalogEvents,
+
     time_provider: Arc<dyn TimeProvider>,
 }
 
@@ -46,6 +51,7 @@ impl Table {
         db_name: Arc<str>,
         table_name: Arc<str>,
         metrics: TableMetrics,
+        events: CatalogEvents,
         time_provider: Arc<dyn TimeProvider>,
     ) -> Self {
         // build empty schema for this table
@@ -60,6 +66,7 @@ impl Table {
             partitions: Default::default(),
             metrics: Arc::new(metrics),
             schema,
+            events,
             time_provider,
         }
     }
@@ -79,6 +86,7 @@ impl Table {
         let metrics = &self.metrics;
         let db_name = &self.db_name;
         let table_name = &self.table_name;
+        let events = &self.events;
         let time_provider = &self.time_provider;
         let (_, partition) = self
             .partitions
@@ -94,6 +102,7 @@ impl Table {
                         partition_key: Arc::clone(&partition_key),
                     },
                     partition_metrics,
+                    events.clone(),
                     Arc::clone(time_provider),
                 );
                 let partition = Arc::new(metrics.new_partition_lock(partition));
@@ -102,6 +111,26 @@ impl Table {
         partition
     }
 
+    /// Removes the partition `partition_key` if it has no chunks and is not
+    /// referenced outside of this table.
+    pub(super) fn remove_unused_partition(&mut self, partition_key: &str) {
+        let unused = self
+            .partitions
+            .get(partition_key)
+            .map_or(false, |partition| {
+                Arc::strong_count(partition) == 1 && partition.read().chunks().next().is_none()
+            });
+        if unused {
+            self.partitions.remove(partition_key);
+        }
+    }
+
+    pub fn keyed_partitions(
+        &self,
+    ) -> impl Iterator<Item = (&Arc<str>, &Arc<RwLock<Partition>>)> + '_ {
+        self.partitions.iter()
+    }
+
     pub fn partition_keys(&self) -> impl Iterator<Item = &Arc<str>> + '_ {
         self.partitions.keys()
     }
@@ -139,6 +168,20 @@ enum TableSchemaUpsertHandleInner<'a> {
     },
 }
 
+/// Error upserting the schema of a table.
+#[derive(Debug, Snafu)]
+pub enum SchemaUpsertError {
+    /// The new schema is incompatible with the table's schema.
+    #[snafu(display("{}", source))]
+    Incompatible { source: SchemaMergerError },
+
+    /// The new schema was compatible with the table's schema, but the table's
+    /// schema was changed concurrently, e.g. by another write creating the
+    /// same columns, into one it is incompatible with.
+    #[snafu(display("table schema changed concurrently: {}", source))]
+    ConcurrentChange { source: SchemaMergerError },
+}
+
 /// Handle that can be used to modify the table-wide [schema](Schema) during new writes.
 #[derive(Debug)]
 pub struct TableSchemaUpsertHandle<'a> {
@@ -149,14 +192,15 @@ impl<'a> TableSchemaUpsertHandle<'a> {
     pub(crate) fn new(
         table_schema: &'a RwLock<Arc<Schema>>,
         new_schema: &Schema,
-    ) -> Result<Self, SchemaMergerError> {
+    ) -> Result<Self, SchemaUpsertError> {
         // Be optimistic and only get a read lock. It is rather rare that the schema will change when new data arrives
         // and we do NOT want to serialize all writes on a single lock.
         let table_schema_read = table_schema.read();
 
         // Let's see if we can merge the new schema with the existing one (this may or may not result in any schema
         // change).
-        let merged_schema = Self::try_merge(&table_schema_read, new_schema)?;
+        let merged_schema =
+            Self::try_merge(&table_schema_read, new_schema).context(IncompatibleSnafu)?;
 
         // Now check if this would actually change the schema:
         if &merged_schema == table_schema_read.deref().deref() {
@@ -176,7 +220,8 @@ impl<'a> TableSchemaUpsertHandle<'a> {
             // During the above write lock, the schema might have changed again, so we need to perform the merge again.
             // This may also lead to a failure now, e.g. when adding a column that was added with a different type
             // during the lock gap.
-            let merged_schema = Self::try_merge(&table_schema_write, new_schema)?;
+            let merged_schema =
+                Self::try_merge(&table_schema_write, new_schema).context(ConcurrentChangeSnafu)?;
 
             Ok(Self {
                 inner: TableSchemaUpsertHandleInner::MightChange {
@@ -329,7 +374,10 @@ mod tests {
             .influx_column("tag2", InfluxColumnType::Field(InfluxFieldType::String))
             .build()
             .unwrap();
-        assert!(TableSchemaUpsertHandle::new(&table_schema, &schema1).is_err());
+        assert!(matches!(
+            TableSchemaUpsertHandle::new(&table_schema, &schema1),
+            Err(SchemaUpsertError::Incompatible { .. })
+        ));
 
         // schema did not change
         assert_eq!(table_schema.read().deref().deref(), &table_schema_orig);
@@ -460,7 +460,7 @@ impl QueryChunk for DbChunk {
                 // the read buffer.
                 Ok(Some(
                     chunk
-                        .column_names(rb_predicate, vec![], columns, BTreeSet::new())
+                        .column_names(rb_predicate, vec![], columns, false, BTreeSet::new())
                         .context(ReadBufferChunkSnafu {
                             chunk_id: self.id(),
                         })?,
@@ -586,7 +586,10 @@ mod tests {
         test_helpers::write_lp,
         utils::make_db_time,
     };
-    use data_types::chunk_metadata::ChunkStorage;
+    use arrow_util::assert_batches_eq;
+    use data_types::{
+        chunk_metadata::ChunkStorage, delete_predicate::DeleteExpr, timestamp::TimestampRange,
+    };
     use std::time::Duration;
 
     async fn test_chunk_access(chunk: &CatalogChunk, time: Arc<time::MockProvider>) {
@@ -677,6 +680,50 @@ mod tests {
         test_chunk_access(&chunk, time).await
     }
 
+    #[tokio::test]
+    async fn rub_read_filter_applies_delete_predicates() {
+        let (db, _time) = make_db_time().await;
+
+        write_lp(&db, "cpu,tag=1 bar=1 1\ncpu,tag=2 bar=2 2");
+        db.compact_partition("cpu", "1970-01-01T00").await.unwrap();
+
+        let chunks = db.catalog.chunks();
+        assert_eq!(chunks.len(), 1);
+        let chunk = chunks.into_iter().next().unwrap();
+
+        let delete_predicate = Arc::new(DeletePredicate {
+            range: TimestampRange::new(0, 10),
+            exprs: vec![DeleteExpr::new(
+                "tag".to_string(),
+                data_types::delete_predicate::Op::Eq,
+                data_types::delete_predicate::Scalar::String("1".to_string()),
+            )],
+        });
+        chunk
+            .write()
+            .add_delete_predicate(Arc::clone(&delete_predicate));
+
+        let chunk = chunk.read();
+        assert_eq!(chunk.delete_predicates(), &[delete_predicate]);
+
+        // the caller does not supply the delete predicate
+        let stream = DbChunk::snapshot(&chunk)
+            .read_filter(&Default::default(), Selection::All)
+            .unwrap();
+        let batches = datafusion::physical_plan::common::collect(stream)
+            .await
+            .unwrap();
+
+        let expected = vec![
+            "+-----+-----+--------------------------------+",
+            "| bar | tag | time                           |",
+            "+-----+-----+--------------------------------+",
+            "| 2   | 2   | 1970-01-01T00:00:00.000000002Z |",
+            "+-----+-----+--------------------------------+",
+        ];
+        assert_batches_eq!(expected, &batches);
+    }
+
     #[tokio::test]
     async fn parquet_records_access() {
         let (db, time) = make_db_time().await;
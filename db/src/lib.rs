@@ -8,7 +8,7 @@ use crate::{
     catalog::{
         chunk::{CatalogChunk, ChunkStage},
         partition::Partition,
-        table::TableSchemaUpsertHandle,
+        table::{SchemaUpsertError, TableSchemaUpsertHandle},
         Catalog, TableNameFilter,
     },
     lifecycle::{LockableCatalogChunk, LockableCatalogPartition},
@@ -142,6 +142,15 @@ pub enum DmlError {
         errors.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
     ))]
     SchemaErrors { errors: Vec<schema::merge::Error> },
+
+    /// The write was compatible with the schemas of its tables, but they were
+    /// changed concurrently into schemas it is incompatible with. Like
+    /// [`DmlError::SchemaErrors`], retrying the write fails again.
+    #[snafu(display(
+        "Storing database write failed as table schemas changed concurrently: {}",
+        errors.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    ))]
+    ConcurrentSchemaChange { errors: Vec<schema::merge::Error> },
 }
 
 /// `Db` is an instance-local, queryable, possibly persisted, and possibly mutable data store
@@ -301,7 +310,7 @@ impl Db {
             Arc::clone(&catalog),
             Arc::clone(&jobs),
             Arc::clone(&time_provider),
-            metric_registry.as_ref(),
+            Arc::clone(&metric_registry),
         );
         let catalog_access = Arc::new(catalog_access);
 
@@ -821,6 +830,12 @@ impl Db {
         lifecycle::unload_read_buffer_chunk(chunk).context(LifecycleSnafu)
     }
 
+    /// Returns the number of chunks being loaded from object store into the
+    /// read buffer
+    pub fn loading_read_buffer_chunks(&self) -> usize {
+        self.catalog.metrics().loading_read_buffer_chunks()
+    }
+
     /// Load chunk from object store to read buffer
     pub fn load_read_buffer(
         self: &Arc<Self>,
@@ -1091,6 +1106,7 @@ impl Db {
         // Protect against DoS by limiting the number of errors we might collect
         const MAX_ERRORS: usize = 10;
         let mut schema_errors = vec![];
+        let mut concurrent_schema_errors = vec![];
 
         for (table_name, batch) in db_write.tables() {
             let write_schema = batch.schema(Selection::All).unwrap();
@@ -1100,9 +1116,15 @@ impl Db {
                 let schema_handle =
                     match TableSchemaUpsertHandle::new(table.schema(), &write_schema) {
                         Ok(schema_handle) => schema_handle,
-                        Err(e) => {
+                        Err(SchemaUpsertError::Incompatible { source }) => {
                             if schema_errors.len() < MAX_ERRORS {
-                                schema_errors.push(e);
+                                schema_errors.push(source);
+                            }
+                            continue;
+                        }
+                        Err(SchemaUpsertError::ConcurrentChange { source }) => {
+                            if concurrent_schema_errors.len() < MAX_ERRORS {
+                                concurrent_schema_errors.push(source);
                             }
                             continue;
                         }
@@ -1196,6 +1218,12 @@ impl Db {
                 errors: schema_errors
             }
         );
+        ensure!(
+            concurrent_schema_errors.is_empty(),
+            ConcurrentSchemaChangeSnafu {
+                errors: concurrent_schema_errors
+            }
+        );
 
         Ok(())
     }
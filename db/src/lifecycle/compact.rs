@@ -2,7 +2,10 @@
 
 use super::{error::Result, merge_schemas, LockableCatalogChunk, LockableCatalogPartition};
 use crate::{
-    catalog::{chunk::CatalogChunk, partition::Partition},
+    catalog::{
+        chunk::CatalogChunk,
+        partition::{ChunkOrderCheck, Partition},
+    },
     lifecycle::collect_rub,
     DbChunk,
 };
@@ -144,8 +147,9 @@ pub(crate) fn compact_chunks(
             schema,
             delete_predicates,
             min_order,
+            ChunkOrderCheck::AllowShared,
             None,
-        );
+        )?;
 
         // input rows per second
         let elapsed = now.elapsed();
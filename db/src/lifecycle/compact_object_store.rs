@@ -9,7 +9,10 @@ use super::{
     LockableCatalogChunk, LockableCatalogPartition, Result,
 };
 use crate::{
-    catalog::{chunk::CatalogChunk, partition::Partition},
+    catalog::{
+        chunk::CatalogChunk,
+        partition::{ChunkOrderCheck, Partition},
+    },
     lifecycle::merge_schemas,
     Db, DbChunk,
 };
@@ -145,7 +148,7 @@ pub(crate) fn compact_object_store_chunks(
             compacting_os_chunks.partition,
             delete_predicates_before,
         )
-        .await;
+        .await?;
 
         if let Some(compacted_and_persisted_chunk) = compacted_and_persisted_chunk {
             compacted_rows = compacted_and_persisted_chunk.rows();
@@ -439,7 +442,7 @@ async fn update_in_memory_catalog(
     parquet_chunk: Option<Arc<ParquetChunk>>,
     partition: Arc<RwLock<Partition>>,
     delete_predicates_before: HashSet<Arc<DeletePredicate>>,
-) -> Option<Arc<DbChunk>> {
+) -> Result<Option<Arc<DbChunk>>> {
     // Acquire write lock to drop the old chunks while also getting delete predicates added during compaction
     let mut partition = partition.write();
 
@@ -473,7 +476,8 @@ async fn update_in_memory_catalog(
                 iox_metadata.time_of_last_write,
                 delete_predicates,
                 iox_metadata.chunk_order,
-            );
+                ChunkOrderCheck::AllowShared,
+            )?;
             let dbchunk = DbChunk::parquet_file_snapshot(&*chunk.read());
             Some(dbchunk)
         }
@@ -483,7 +487,7 @@ async fn update_in_memory_catalog(
     // drop partition lock
     std::mem::drop(partition);
 
-    dbchunk
+    Ok(dbchunk)
 }
 
 ////////////////////////////////////////////////////////////
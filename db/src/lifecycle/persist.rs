@@ -2,7 +2,10 @@
 
 use super::{LockableCatalogChunk, LockableCatalogPartition, Result};
 use crate::{
-    catalog::{chunk::CatalogChunk, partition::Partition},
+    catalog::{
+        chunk::CatalogChunk,
+        partition::{ChunkOrderCheck, Partition},
+    },
     lifecycle::{collect_rub, merge_schemas, write::write_chunk_to_object_store},
     DbChunk,
 };
@@ -138,6 +141,14 @@ pub fn persist_chunks(
         let persist_fut = {
             let partition = LockableCatalogPartition::new(Arc::clone(&db), partition);
             let mut partition_write = partition.write();
+
+            // Check the ID of the persisted chunk before dropping the input
+            // chunks, so that a collision leaves the catalog untouched
+            let persisted_chunk_id = *db.persisted_chunk_id_override.lock();
+            if let Some(id) = persisted_chunk_id.filter(|id| !chunk_ids.contains(id)) {
+                partition_write.ensure_chunk_id_unused(id)?;
+            }
+
             let mut delete_predicates_after: HashSet<Arc<DeletePredicate>> = HashSet::new();
             for id in &chunk_ids {
                 let chunk = partition_write.force_drop_chunk(*id).expect(
@@ -167,8 +178,9 @@ pub fn persist_chunks(
                     Arc::clone(&schema),
                     delete_predicates.clone(),
                     min_order,
+                    ChunkOrderCheck::AllowShared,
                     None,
-                );
+                )?;
             }
 
             let to_persist = match to_persist {
@@ -190,8 +202,9 @@ pub fn persist_chunks(
                 schema,
                 delete_predicates,
                 min_order,
-                db.persisted_chunk_id_override.lock().as_ref().cloned(),
-            );
+                ChunkOrderCheck::AllowShared,
+                persisted_chunk_id,
+            )?;
             let to_persist = LockableCatalogChunk {
                 db,
                 chunk: Arc::clone(new_chunk),
@@ -226,8 +239,8 @@ pub fn persist_chunks(
 mod tests {
     use super::*;
     use crate::{
-        catalog::Catalog, load::load_or_create_preserved_catalog, test_helpers::write_lp,
-        utils::TestDb, Db,
+        catalog::Catalog, lifecycle::compact_object_store::compact_object_store_chunks,
+        load::load_or_create_preserved_catalog, test_helpers::write_lp, utils::TestDb, Db,
     };
     use data_types::{
         chunk_metadata::ChunkStorage,
@@ -305,6 +318,131 @@ mod tests {
             .is_none());
     }
 
+    #[tokio::test]
+    async fn test_persist_duplicate_chunk_id() {
+        let (db, time) = test_db().await;
+        write_lp(db.as_ref(), "cpu,tag1=cupcakes bar=1 10");
+
+        let partition_keys = partition_keys(&db);
+        assert_eq!(partition_keys.len(), 1);
+        let partition_key = partition_keys.into_iter().next().unwrap();
+        let persisted_id = db
+            .rollover_partition("cpu", &partition_key)
+            .await
+            .unwrap()
+            .unwrap()
+            .id();
+
+        time.inc(Duration::from_secs(2));
+        write_lp(db.as_ref(), "cpu,tag1=lagged bar=1 20");
+
+        let partition = db.lockable_partition("cpu", &partition_key).unwrap();
+        let other_id = partition
+            .read()
+            .open_chunk()
+            .unwrap()
+            .read()
+            .addr()
+            .chunk_id;
+        *db.persisted_chunk_id_override.lock() = Some(other_id);
+
+        let partition_guard = partition.read();
+        let chunks = LockablePartition::chunks(&partition_guard);
+        let chunks: Vec<_> = chunks
+            .iter()
+            .filter(|x| x.id() == persisted_id)
+            .map(|x| x.write())
+            .collect();
+        assert_eq!(chunks.len(), 1);
+
+        let mut partition_guard = partition_guard.upgrade();
+        let handle = LockablePartition::prepare_persist(&mut partition_guard, true)
+            .unwrap()
+            .0;
+
+        let err = persist_chunks(partition_guard, chunks, handle)
+            .unwrap()
+            .1
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                crate::lifecycle::error::Error::PartitionError {
+                    source: crate::catalog::partition::Error::DuplicateChunkId { chunk }
+                } if chunk.chunk_id == other_id
+            ),
+            "{}",
+            err
+        );
+
+        // the input chunk was not dropped
+        let partition = partition.read();
+        assert!(partition.chunk(persisted_id).is_some());
+        assert!(partition.chunk(other_id).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_compact_persisted_and_remainder() {
+        let (db, time) = test_db().await;
+
+        write_lp(db.as_ref(), "cpu,tag1=cupcakes bar=1 10");
+        time.inc(Duration::from_secs(10));
+        write_lp(db.as_ref(), "cpu,tag1=cupcakes bar=2 20");
+
+        let partition_keys = partition_keys(&db);
+        assert_eq!(partition_keys.len(), 1);
+        let partition_key = partition_keys.into_iter().next().unwrap();
+
+        // Persist the first write, leaving the second one in a remainder chunk
+        let maybe_chunk = db
+            .persist_partition("cpu", partition_key.as_str(), false)
+            .await
+            .unwrap();
+        assert!(maybe_chunk.is_some());
+
+        let partition = db.partition("cpu", partition_key.as_str()).unwrap();
+        let mut chunks: Vec<_> = partition.read().chunk_summaries().collect();
+        chunks.sort_by_key(|c| c.storage);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].storage, ChunkStorage::ReadBuffer);
+        assert_eq!(chunks[1].storage, ChunkStorage::ReadBufferAndObjectStore);
+        assert_eq!(chunks[0].order, chunks[1].order);
+        let persisted_id = chunks[1].id;
+
+        // Compact the persisted chunk, which shares its order with the remainder
+        let lockable_partition = db.lockable_partition("cpu", &partition_key).unwrap();
+        let partition_guard = lockable_partition.read();
+        let chunk = LockablePartition::chunk(&partition_guard, persisted_id).unwrap();
+        let partition_guard = partition_guard.upgrade();
+        let (_, fut) = compact_object_store_chunks(partition_guard, vec![chunk.write()]).unwrap();
+        fut.await.unwrap().unwrap();
+
+        // Compact the remainder, which shares its order with the new persisted chunk
+        let chunk = db
+            .compact_chunks("cpu", partition_key.as_str(), |chunk| {
+                chunk.storage().1 == ChunkStorage::ReadBuffer
+            })
+            .await
+            .unwrap();
+        assert!(chunk.is_some());
+
+        let mut chunks: Vec<_> = partition.read().chunk_summaries().collect();
+        chunks.sort_by_key(|c| c.storage);
+        let summaries: Vec<_> = chunks
+            .iter()
+            .map(|summary| (summary.storage, summary.row_count))
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![
+                (ChunkStorage::ReadBuffer, 1),
+                (ChunkStorage::ObjectStoreOnly, 1),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_persist_delete_all() {
         let (db, time) = test_db().await;
@@ -1,7 +1,9 @@
 //! Functionality to load a [`Catalog`](crate::catalog::Catalog) and other information from a
 //! [`PreservedCatalog`](parquet_catalog::core::PreservedCatalog).
 
-use super::catalog::{chunk::ChunkStage, table::TableSchemaUpsertHandle, Catalog};
+use super::catalog::{
+    chunk::ChunkStage, partition::ChunkOrderCheck, table::TableSchemaUpsertHandle, Catalog,
+};
 use data_types::delete_predicate::DeletePredicate;
 use iox_object_store::{IoxObjectStore, ParquetFilePath};
 use observability_deps::tracing::{error, info};
@@ -240,22 +242,25 @@ impl CatalogState for Loader {
             .context(SchemaSnafu { path: &info.path })?;
 
         let mut partition = partition.write();
-        if partition.chunk(iox_md.chunk_id).is_some() {
-            return Err(CatalogStateAddError::ParquetFileAlreadyExists { path: info.path });
-        }
 
         // Delete predicates are loaded explicitly via `CatalogState::delete_predicates` AFTER the chunk is added, so
         // we leave this list empty (for now).
         let delete_predicates: Vec<Arc<DeletePredicate>> = vec![];
 
-        partition.insert_object_store_only_chunk(
-            iox_md.chunk_id,
-            parquet_chunk,
-            iox_md.time_of_first_write,
-            iox_md.time_of_last_write,
-            delete_predicates,
-            iox_md.chunk_order,
-        );
+        if partition
+            .insert_object_store_only_chunk(
+                iox_md.chunk_id,
+                parquet_chunk,
+                iox_md.time_of_first_write,
+                iox_md.time_of_last_write,
+                delete_predicates,
+                iox_md.chunk_order,
+                ChunkOrderCheck::AllowShared,
+            )
+            .is_err()
+        {
+            return Err(CatalogStateAddError::ParquetFileAlreadyExists { path: info.path });
+        }
 
         schema_handle.commit();
 
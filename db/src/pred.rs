@@ -1,8 +1,6 @@
 //! This module contains code to convert between query predicates and
 //! the predicates required by the various storage formats
 
-use std::convert::TryFrom;
-
 use predicate::predicate::Predicate;
 use snafu::Snafu;
 
@@ -24,29 +22,28 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 /// Callers should validate predicates against chunks they are to be executed
 /// against using `read_buffer::Chunk::validate_predicate`
 pub fn to_read_buffer_predicate(predicate: &Predicate) -> Result<read_buffer::Predicate> {
-    // Try to convert non-time column expressions into binary expressions
-    // that are compatible with the read buffer.
-    match predicate
-        .exprs
-        .iter()
-        .map(read_buffer::BinaryExpr::try_from)
-        .collect::<Result<Vec<_>, _>>()
-    {
-        Ok(exprs) => {
-            // Construct a `ReadBuffer` predicate with or without
-            // InfluxDB-specific expressions on the time column.
-            Ok(match predicate.range {
-                Some(range) => {
-                    read_buffer::Predicate::with_time_range(&exprs, range.start(), range.end())
-                }
-                None => read_buffer::Predicate::new(exprs),
-            })
-        }
-        Err(e) => Err(Error::ReadBufferPredicate {
-            msg: e,
-            pred: predicate.clone(),
-        }),
+    // Lower each expression into expressions and column comparisons that are
+    // compatible with the read buffer.
+    let mut exprs = vec![];
+    let mut comparisons = vec![];
+    for expr in &predicate.exprs {
+        let lowered = read_buffer::Predicate::try_from_expr(expr).map_err(|msg| {
+            Error::ReadBufferPredicate {
+                msg,
+                pred: predicate.clone(),
+            }
+        })?;
+        exprs.extend_from_slice(lowered.expressions());
+        comparisons.extend_from_slice(lowered.column_comparisons());
     }
+
+    // Construct a `ReadBuffer` predicate with or without InfluxDB-specific
+    // expressions on the time column.
+    let rb_predicate = match predicate.range {
+        Some(range) => read_buffer::Predicate::with_time_range(&exprs, range.start(), range.end()),
+        None => read_buffer::Predicate::new(exprs),
+    };
+    Ok(rb_predicate.with_column_comparisons(comparisons))
 }
 
 #[cfg(test)]
@@ -105,6 +102,25 @@ pub mod test {
                     2000,
                 ),
             ),
+            // column comparisons
+            (
+                PredicateBuilder::default()
+                    .timestamp_range(100, 2000)
+                    .add_expr(col("src").eq(col("dst")))
+                    .add_expr(col("track").eq(lit("Star Roving")))
+                    .build(),
+                RBPredicate::with_time_range(
+                    &[RBBinaryExpr::from(("track", "=", "Star Roving"))],
+                    100,
+                    2000,
+                )
+                .with_column_comparisons(
+                    RBPredicate::try_from_expr(&col("src").eq(col("dst")))
+                        .unwrap()
+                        .column_comparisons()
+                        .to_vec(),
+                ),
+            ),
         ];
 
         for (predicate, exp) in cases {
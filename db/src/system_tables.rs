@@ -4,6 +4,7 @@
 //! system.columns
 //! system.chunk_columns
 //! system.operations
+//! system.ingest
 //!
 //! For example `SELECT * FROM system.chunks`
 
@@ -21,10 +22,12 @@ use datafusion::{
     physical_plan::{memory::MemoryExec, ExecutionPlan},
 };
 use job_registry::JobRegistry;
+use metric::Registry;
 use std::{any::Any, sync::Arc};
 
 mod chunks;
 mod columns;
+mod ingest;
 mod operations;
 mod persistence;
 mod queries;
@@ -38,6 +41,7 @@ const CHUNK_COLUMNS: &str = "chunk_columns";
 const OPERATIONS: &str = "operations";
 const PERSISTENCE_WINDOWS: &str = "persistence_windows";
 const QUERIES: &str = "queries";
+const INGEST: &str = "ingest";
 
 pub struct SystemSchemaProvider {
     chunks: Arc<dyn TableProvider>,
@@ -46,6 +50,7 @@ pub struct SystemSchemaProvider {
     operations: Arc<dyn TableProvider>,
     persistence_windows: Arc<dyn TableProvider>,
     queries: Arc<dyn TableProvider>,
+    ingest: Arc<dyn TableProvider>,
 }
 
 impl std::fmt::Debug for SystemSchemaProvider {
@@ -62,8 +67,12 @@ impl SystemSchemaProvider {
         catalog: Arc<Catalog>,
         jobs: Arc<JobRegistry>,
         query_log: Arc<QueryLog>,
+        metric_registry: Arc<Registry>,
     ) -> Self {
         let db_name = db_name.into();
+        let ingest = Arc::new(SystemTableProvider {
+            inner: ingest::IngestTable::new(Arc::from(db_name.as_str()), metric_registry),
+        });
         let chunks = Arc::new(SystemTableProvider {
             inner: chunks::ChunksTable::new(Arc::clone(&catalog)),
         });
@@ -89,17 +98,19 @@ impl SystemSchemaProvider {
             operations,
             persistence_windows,
             queries,
+            ingest,
         }
     }
 }
 
-const ALL_SYSTEM_TABLES: [&str; 6] = [
+const ALL_SYSTEM_TABLES: [&str; 7] = [
     CHUNKS,
     COLUMNS,
     CHUNK_COLUMNS,
     OPERATIONS,
     PERSISTENCE_WINDOWS,
     QUERIES,
+    INGEST,
 ];
 
 impl SchemaProvider for SystemSchemaProvider {
@@ -122,6 +133,7 @@ impl SchemaProvider for SystemSchemaProvider {
             OPERATIONS => Some(Arc::clone(&self.operations)),
             PERSISTENCE_WINDOWS => Some(Arc::clone(&self.persistence_windows)),
             QUERIES => Some(Arc::clone(&self.queries)),
+            INGEST => Some(Arc::clone(&self.ingest)),
             _ => None,
         }
     }
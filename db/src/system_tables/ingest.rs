@@ -0,0 +1,147 @@
+use crate::system_tables::IoxSystemTable;
+use arrow::{
+    array::{ArrayRef, StringArray, UInt64Array},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use data_types::error::ErrorLogger;
+use metric::{Observation, RawReporter};
+use std::sync::Arc;
+
+/// The line protocol ingest metrics reported by the table, in column order.
+const INGEST_METRICS: [&str; 3] = ["ingest_lines", "ingest_fields", "ingest_bytes"];
+
+/// Implementation of system.ingest table
+#[derive(Debug)]
+pub(super) struct IngestTable {
+    schema: SchemaRef,
+    db_name: Arc<str>,
+    metric_registry: Arc<metric::Registry>,
+}
+
+impl IngestTable {
+    pub(super) fn new(db_name: Arc<str>, metric_registry: Arc<metric::Registry>) -> Self {
+        Self {
+            schema: ingest_schema(),
+            db_name,
+            metric_registry,
+        }
+    }
+}
+
+impl IoxSystemTable for IngestTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn batch(&self) -> Result<RecordBatch> {
+        let mut reporter = RawReporter::default();
+        self.metric_registry.report(&mut reporter);
+
+        from_ingest_metrics(self.schema(), &self.db_name, &reporter)
+            .log_if_error("system.ingest table")
+    }
+}
+
+fn ingest_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("db_name", DataType::Utf8, false),
+        Field::new("lines_ok", DataType::UInt64, false),
+        Field::new("lines_error", DataType::UInt64, false),
+        Field::new("fields_ok", DataType::UInt64, false),
+        Field::new("fields_error", DataType::UInt64, false),
+        Field::new("bytes_ok", DataType::UInt64, false),
+        Field::new("bytes_error", DataType::UInt64, false),
+    ]))
+}
+
+/// Sums the ingest counters recorded for `db_name` by status, across any
+/// other attributes such as `org`.
+///
+/// Produces a single row if anything was ingested into the database, and no
+/// rows otherwise.
+fn from_ingest_metrics(
+    schema: SchemaRef,
+    db_name: &str,
+    reporter: &RawReporter,
+) -> Result<RecordBatch> {
+    let mut found = false;
+    let mut counts = [0_u64; INGEST_METRICS.len() * 2];
+    for set in reporter.observations() {
+        let metric_idx = match INGEST_METRICS.iter().position(|m| *m == set.metric_name) {
+            Some(idx) => idx,
+            None => continue,
+        };
+
+        for (attributes, observation) in &set.observations {
+            let attribute = |key: &str| {
+                attributes
+                    .iter()
+                    .find_map(|(k, v)| (*k == key).then(|| v.as_ref()))
+            };
+            if attribute("db_name") != Some(db_name) {
+                continue;
+            }
+            let status_idx = match attribute("status") {
+                Some("ok") => 0,
+                Some("error") => 1,
+                _ => continue,
+            };
+            if let Observation::U64Counter(count) = observation {
+                counts[metric_idx * 2 + status_idx] += count;
+                found = true;
+            }
+        }
+    }
+
+    let rows = usize::from(found);
+    let mut columns: Vec<ArrayRef> = vec![Arc::new(StringArray::from(vec![db_name; rows]))];
+    columns.extend(
+        counts
+            .iter()
+            .map(|count| Arc::new(UInt64Array::from(vec![*count; rows])) as ArrayRef),
+    );
+
+    RecordBatch::try_new(schema, columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_util::assert_batches_eq;
+    use metric::{Attributes, Metric, U64Counter};
+
+    #[test]
+    fn test_from_ingest_metrics() {
+        let registry = metric::Registry::new();
+        let table = IngestTable::new(Arc::from("placeholder"), Arc::new(registry));
+        assert_eq!(table.batch().unwrap().num_rows(), 0);
+
+        // simulate the metrics recorded by writes to two databases
+        let registry = Arc::new(metric::Registry::new());
+        for (idx, name) in INGEST_METRICS.iter().enumerate() {
+            let metric: Metric<U64Counter> = registry.register_metric(*name, "ingested");
+            for (db_name, org, status, count) in [
+                ("placeholder", "org1", "ok", 10),
+                ("placeholder", "org2", "ok", 5),
+                ("placeholder", "org1", "error", 2),
+                ("other", "org1", "ok", 100),
+            ] {
+                let attributes =
+                    Attributes::from(&[("db_name", db_name), ("org", org), ("status", status)]);
+                metric.recorder(attributes).inc(count * (idx as u64 + 1));
+            }
+        }
+
+        let table = IngestTable::new(Arc::from("placeholder"), registry);
+        let expected = vec![
+            "+-------------+----------+-------------+-----------+--------------+----------+-------------+",
+            "| db_name     | lines_ok | lines_error | fields_ok | fields_error | bytes_ok | bytes_error |",
+            "+-------------+----------+-------------+-----------+--------------+----------+-------------+",
+            "| placeholder | 15       | 2           | 30        | 4            | 45       | 6           |",
+            "+-------------+----------+-------------+-----------+--------------+----------+-------------+",
+        ];
+        assert_batches_eq!(&expected, &[table.batch().unwrap()]);
+    }
+}
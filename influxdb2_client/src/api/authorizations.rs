@@ -0,0 +1,134 @@
+//! Authorizations API
+
+use crate::models::{Authorization, Authorizations, Permission};
+use crate::{Client, HttpSnafu, RequestError, SerializingSnafu};
+use reqwest::{Method, StatusCode};
+use snafu::ResultExt;
+
+impl Client {
+    /// Create a new authorization (API token) in the organization specified by
+    /// `org_id` with the given `permissions`.
+    ///
+    /// The returned [`Authorization`] includes the generated token.
+    pub async fn create_authorization(
+        &self,
+        org_id: &str,
+        permissions: Vec<Permission>,
+        description: Option<String>,
+    ) -> Result<Authorization, RequestError> {
+        let create_authorization_url = format!("{}/api/v2/authorizations", self.url);
+        let body = Authorization {
+            description,
+            ..Authorization::new(org_id.into(), permissions)
+        };
+
+        let response = self
+            .request(Method::POST, &create_authorization_url)
+            .body(serde_json::to_string(&body).context(SerializingSnafu)?)
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::CREATED => Ok(response.json::<Authorization>().await?),
+            status => {
+                let text = response.text().await?;
+                HttpSnafu { status, text }.fail()?
+            }
+        }
+    }
+
+    /// List all authorizations in the organization specified by `org_id`
+    pub async fn list_authorizations(&self, org_id: &str) -> Result<Authorizations, RequestError> {
+        let authorizations_url = format!("{}/api/v2/authorizations", self.url);
+        let response = self
+            .request(Method::GET, &authorizations_url)
+            .query(&[("orgID", org_id)])
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::OK => Ok(response.json::<Authorizations>().await?),
+            status => {
+                let text = response.text().await?;
+                HttpSnafu { status, text }.fail()?
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ResourceType;
+    use mockito::mock;
+
+    const BASE_PATH: &str = "/api/v2/authorizations";
+
+    #[tokio::test]
+    async fn create_authorization() {
+        let token = "some-token";
+        let org_id = "0000111100001111";
+
+        let mock_server = mock("POST", BASE_PATH)
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .match_body(
+                format!(
+                    r#"{{"description":"some-description","orgID":"{}","permissions":[{{"action":"read","resource":{{"type":"buckets"}}}},{{"action":"write","resource":{{"type":"buckets","id":"some-bucket-id"}}}}]}}"#,
+                    org_id
+                )
+                .as_str(),
+            )
+            .with_status(201)
+            .with_body(
+                format!(
+                    r#"{{"id":"some-id","orgID":"{}","permissions":[],"token":"some-new-token"}}"#,
+                    org_id
+                )
+                .as_str(),
+            )
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let permissions = vec![
+            Permission::read(ResourceType::Buckets),
+            Permission::write(ResourceType::Buckets).with_id("some-bucket-id"),
+        ];
+
+        let result = client
+            .create_authorization(org_id, permissions, Some("some-description".to_string()))
+            .await
+            .unwrap();
+
+        mock_server.assert();
+        assert_eq!(result.id.as_deref(), Some("some-id"));
+        assert_eq!(result.token_secret(), Some("some-new-token"));
+    }
+
+    #[tokio::test]
+    async fn list_authorizations() {
+        let token = "some-token";
+        let org_id = "0000111100001111";
+
+        let mock_server = mock("GET", format!("{}?orgID={}", BASE_PATH, org_id).as_str())
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .with_body(
+                format!(
+                    r#"{{"links":{{"self":"{}"}},"authorizations":[{{"orgID":"{}","permissions":[],"token":"token-a"}},{{"orgID":"{}","permissions":[],"token":"token-b"}}]}}"#,
+                    BASE_PATH, org_id, org_id
+                )
+                .as_str(),
+            )
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let result = client.list_authorizations(org_id).await.unwrap();
+
+        mock_server.assert();
+        let tokens: Vec<_> = result
+            .authorizations
+            .iter()
+            .map(|a| a.token_secret().unwrap())
+            .collect();
+        assert_eq!(tokens, vec!["token-a", "token-b"]);
+    }
+}
@@ -1,9 +1,16 @@
 //! Buckets API
 
-use crate::models::PostBucketRequest;
-use crate::{Client, HttpSnafu, RequestError, ReqwestProcessingSnafu, SerializingSnafu};
-use reqwest::Method;
-use snafu::ResultExt;
+use crate::models::{
+    retention_rule, Bucket, Buckets, Organizations, PostBucketRequest, RetentionRule,
+};
+use crate::{
+    Client, HttpSnafu, MultipleBucketsFoundSnafu, OrganizationNotFoundSnafu, RequestError,
+    SerializingSnafu,
+};
+use reqwest::{Method, StatusCode};
+use snafu::{OptionExt, ResultExt};
+use std::convert::TryFrom;
+use std::time::Duration;
 
 impl Client {
     /// Create a new bucket in the organization specified by the 16-digit
@@ -21,17 +28,124 @@ impl Client {
                     .context(SerializingSnafu)?,
             )
             .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let text = response.text().await.context(ReqwestProcessingSnafu)?;
+            let text = response.text().await?;
             HttpSnafu { status, text }.fail()?;
         }
 
         Ok(())
     }
+
+    /// Create a new bucket named `bucket_name` in the organization named
+    /// `org_name`, resolving the organization's ID first.
+    ///
+    /// If `retention` is given, data older than it expires; retention periods
+    /// that do not fit into the API's 32 bit seconds field are clamped.
+    /// Otherwise data never expires.
+    pub async fn create_bucket_in_org(
+        &self,
+        org_name: &str,
+        bucket_name: &str,
+        retention: Option<Duration>,
+    ) -> Result<Bucket, RequestError> {
+        let org_id = self.org_id_by_name(org_name).await?;
+
+        let retention_rules = retention
+            .map(|retention| {
+                let every_seconds = i32::try_from(retention.as_secs()).unwrap_or(i32::MAX);
+                vec![RetentionRule::new(
+                    retention_rule::Type::Expire,
+                    every_seconds,
+                )]
+            })
+            .unwrap_or_default();
+        let body = PostBucketRequest {
+            retention_rules,
+            ..PostBucketRequest::new(org_id, bucket_name.into())
+        };
+
+        let create_bucket_url = format!("{}/api/v2/buckets", self.url);
+        let response = self
+            .request(Method::POST, &create_bucket_url)
+            .body(serde_json::to_string(&body).context(SerializingSnafu)?)
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::CREATED => Ok(response.json::<Bucket>().await?),
+            status => {
+                let text = response.text().await?;
+                HttpSnafu { status, text }.fail()?
+            }
+        }
+    }
+
+    /// Find the bucket named `name` in the organization named `org`, returning
+    /// `None` if there is no such bucket (or organization).
+    ///
+    /// All pages of the listing are checked; an error is returned if more than
+    /// one bucket matches.
+    pub async fn find_bucket(&self, org: &str, name: &str) -> Result<Option<Bucket>, RequestError> {
+        let buckets_url = format!("{}/api/v2/buckets", self.url);
+        let mut request = self
+            .request(Method::GET, &buckets_url)
+            .query(&[("org", org), ("name", name)]);
+
+        let mut found = vec![];
+        loop {
+            let response = request.send().await?;
+            let page = match response.status() {
+                StatusCode::OK => response.json::<Buckets>().await?,
+                // the server responds with 404 when the organization is unknown
+                StatusCode::NOT_FOUND => Buckets::new(),
+                status => {
+                    let text = response.text().await?;
+                    return HttpSnafu { status, text }.fail();
+                }
+            };
+
+            found.extend(page.buckets.into_iter().filter(|b| b.name == name));
+
+            match page.links.and_then(|links| links.next) {
+                Some(next) => {
+                    request = self.request(Method::GET, &format!("{}{}", self.url, next));
+                }
+                None => break,
+            }
+        }
+
+        match found.len() {
+            0 | 1 => Ok(found.pop()),
+            count => MultipleBucketsFoundSnafu { name, count }.fail(),
+        }
+    }
+
+    /// Look up the ID of the organization named `org_name`
+    async fn org_id_by_name(&self, org_name: &str) -> Result<String, RequestError> {
+        let orgs_url = format!("{}/api/v2/orgs", self.url);
+        let response = self
+            .request(Method::GET, &orgs_url)
+            .query(&[("org", org_name)])
+            .send()
+            .await?;
+        let orgs = match response.status() {
+            StatusCode::OK => response.json::<Organizations>().await?,
+            // the server responds with 404 when filtering by an unknown name
+            StatusCode::NOT_FOUND => Organizations::new(),
+            status => {
+                let text = response.text().await?;
+                return HttpSnafu { status, text }.fail();
+            }
+        };
+
+        orgs.orgs
+            .into_iter()
+            .find(|org| org.name == org_name)
+            .and_then(|org| org.id)
+            .context(OrganizationNotFoundSnafu { name: org_name })
+    }
 }
 
 #[cfg(test)]
@@ -64,4 +178,172 @@ mod tests {
 
         mock_server.assert();
     }
+
+    #[tokio::test]
+    async fn find_bucket() {
+        let token = "some-token";
+
+        let mock_server = mock("GET", "/api/v2/buckets?org=some-org&name=some-bucket")
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .with_body(r#"{"buckets":[{"id":"some-id","name":"some-bucket","retentionRules":[]}]}"#)
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let bucket = client
+            .find_bucket("some-org", "some-bucket")
+            .await
+            .unwrap()
+            .unwrap();
+
+        mock_server.assert();
+        assert_eq!(bucket.id.as_deref(), Some("some-id"));
+    }
+
+    #[tokio::test]
+    async fn find_bucket_not_found() {
+        let token = "some-token";
+
+        let found_none = mock("GET", "/api/v2/buckets?org=some-org&name=no-bucket")
+            .with_body(r#"{"buckets":[]}"#)
+            .create();
+        let unknown_org = mock("GET", "/api/v2/buckets?org=no-org&name=some-bucket")
+            .with_status(404)
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        assert!(client
+            .find_bucket("some-org", "no-bucket")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(client
+            .find_bucket("no-org", "some-bucket")
+            .await
+            .unwrap()
+            .is_none());
+
+        found_none.assert();
+        unknown_org.assert();
+    }
+
+    #[tokio::test]
+    async fn find_bucket_paginated() {
+        let token = "some-token";
+
+        let first_page = mock("GET", "/api/v2/buckets?org=some-org&name=some-bucket")
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .with_body(
+                r#"{"links":{"self":"/api/v2/buckets?org=some-org&name=some-bucket","next":"/api/v2/buckets?name=some-bucket&offset=1&org=some-org"},"buckets":[{"id":"other-id","name":"other-bucket","retentionRules":[]}]}"#,
+            )
+            .create();
+        let second_page = mock(
+            "GET",
+            "/api/v2/buckets?name=some-bucket&offset=1&org=some-org",
+        )
+        .match_header("Authorization", format!("Token {}", token).as_str())
+        .with_body(
+            r#"{"links":{"self":"/api/v2/buckets?name=some-bucket&offset=1&org=some-org"},"buckets":[{"id":"some-id","name":"some-bucket","retentionRules":[]}]}"#,
+        )
+        .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let bucket = client
+            .find_bucket("some-org", "some-bucket")
+            .await
+            .unwrap()
+            .unwrap();
+
+        first_page.assert();
+        second_page.assert();
+        assert_eq!(bucket.id.as_deref(), Some("some-id"));
+    }
+
+    #[tokio::test]
+    async fn find_bucket_multiple_matches() {
+        let token = "some-token";
+
+        let mock_server = mock("GET", "/api/v2/buckets?org=some-org&name=some-bucket")
+            .with_body(
+                r#"{"buckets":[{"id":"id-1","name":"some-bucket","retentionRules":[]},{"id":"id-2","name":"some-bucket","retentionRules":[]}]}"#,
+            )
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let err = client
+            .find_bucket("some-org", "some-bucket")
+            .await
+            .unwrap_err();
+
+        mock_server.assert();
+        assert!(matches!(
+            err,
+            RequestError::MultipleBucketsFound { count: 2, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn create_bucket_in_org() {
+        let org_id = "0000111100001111";
+        let token = "some-token";
+
+        let orgs_mock = mock("GET", "/api/v2/orgs?org=some-org")
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .with_body(format!(r#"{{"orgs":[{{"id":"{}","name":"some-org"}}]}}"#, org_id).as_str())
+            .create();
+        let bucket_mock = mock("POST", "/api/v2/buckets")
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .match_body(
+                format!(
+                    r#"{{"orgID":"{}","name":"some-bucket","retentionRules":[{{"type":"expire","everySeconds":3600}}]}}"#,
+                    org_id
+                )
+                .as_str(),
+            )
+            .with_status(201)
+            .with_body(
+                format!(
+                    r#"{{"id":"some-bucket-id","orgID":"{}","name":"some-bucket","retentionRules":[{{"type":"expire","everySeconds":3600}}]}}"#,
+                    org_id
+                )
+                .as_str(),
+            )
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let bucket = client
+            .create_bucket_in_org("some-org", "some-bucket", Some(Duration::from_secs(3600)))
+            .await
+            .unwrap();
+
+        orgs_mock.assert();
+        bucket_mock.assert();
+        assert_eq!(bucket.id.as_deref(), Some("some-bucket-id"));
+        assert_eq!(bucket.org_id.as_deref(), Some(org_id));
+    }
+
+    #[tokio::test]
+    async fn create_bucket_in_unknown_org() {
+        let token = "some-token";
+
+        let orgs_mock = mock("GET", "/api/v2/orgs?org=unknown-org")
+            .with_status(404)
+            .create();
+        let bucket_mock = mock("POST", "/api/v2/buckets").expect(0).create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let err = client
+            .create_bucket_in_org("unknown-org", "some-bucket", None)
+            .await
+            .unwrap_err();
+
+        orgs_mock.assert();
+        bucket_mock.assert();
+        assert!(matches!(err, RequestError::OrganizationNotFound { .. }));
+    }
 }
@@ -3,31 +3,20 @@
 //! Get health of an InfluxDB instance
 
 use crate::models::HealthCheck;
-use crate::{Client, HttpSnafu, RequestError, ReqwestProcessingSnafu};
+use crate::{Client, HttpSnafu, RequestError};
 use reqwest::{Method, StatusCode};
-use snafu::ResultExt;
 
 impl Client {
     /// Get health of an instance
     pub async fn health(&self) -> Result<HealthCheck, RequestError> {
         let health_url = format!("{}/health", self.url);
-        let response = self
-            .request(Method::GET, &health_url)
-            .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+        let response = self.request(Method::GET, &health_url).send().await?;
 
         match response.status() {
-            StatusCode::OK => Ok(response
-                .json::<HealthCheck>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
-            StatusCode::SERVICE_UNAVAILABLE => Ok(response
-                .json::<HealthCheck>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::OK => Ok(response.json::<HealthCheck>().await?),
+            StatusCode::SERVICE_UNAVAILABLE => Ok(response.json::<HealthCheck>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
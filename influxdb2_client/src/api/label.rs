@@ -1,7 +1,7 @@
 //! Labels
 
 use crate::models::{LabelCreateRequest, LabelResponse, LabelUpdate, LabelsResponse};
-use crate::{Client, HttpSnafu, RequestError, ReqwestProcessingSnafu, SerializingSnafu};
+use crate::{Client, HttpSnafu, RequestError, SerializingSnafu};
 use reqwest::{Method, StatusCode};
 use snafu::ResultExt;
 use std::collections::HashMap;
@@ -25,14 +25,11 @@ impl Client {
             request = request.query(&[("orgID", id)]);
         }
 
-        let response = request.send().await.context(ReqwestProcessingSnafu)?;
+        let response = request.send().await?;
         match response.status() {
-            StatusCode::OK => Ok(response
-                .json::<LabelsResponse>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::OK => Ok(response.json::<LabelsResponse>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -41,18 +38,11 @@ impl Client {
     /// Retrieve a label by ID
     pub async fn find_label(&self, label_id: &str) -> Result<LabelResponse, RequestError> {
         let labels_by_id_url = format!("{}/api/v2/labels/{}", self.url, label_id);
-        let response = self
-            .request(Method::GET, &labels_by_id_url)
-            .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+        let response = self.request(Method::GET, &labels_by_id_url).send().await?;
         match response.status() {
-            StatusCode::OK => Ok(response
-                .json::<LabelResponse>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::OK => Ok(response.json::<LabelResponse>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -75,15 +65,11 @@ impl Client {
             .request(Method::POST, &create_label_url)
             .body(serde_json::to_string(&body).context(SerializingSnafu)?)
             .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+            .await?;
         match response.status() {
-            StatusCode::CREATED => Ok(response
-                .json::<LabelResponse>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::CREATED => Ok(response.json::<LabelResponse>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -102,15 +88,11 @@ impl Client {
             .request(Method::PATCH, &update_label_url)
             .body(serde_json::to_string(&body).context(SerializingSnafu)?)
             .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+            .await?;
         match response.status() {
-            StatusCode::OK => Ok(response
-                .json::<LabelResponse>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::OK => Ok(response.json::<LabelResponse>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -122,12 +104,11 @@ impl Client {
         let response = self
             .request(Method::DELETE, &delete_label_url)
             .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+            .await?;
         match response.status() {
             StatusCode::NO_CONTENT => Ok(()),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -1,8 +1,10 @@
 //! InfluxDB v2.0 Client API
+pub mod authorizations;
 pub mod buckets;
 pub mod health;
 pub mod label;
 pub mod query;
 pub mod ready;
 pub mod setup;
+pub mod users;
 pub mod write;
@@ -2,31 +2,27 @@
 //!
 //! Query InfluxDB using InfluxQL or Flux Query
 
-use crate::{Client, HttpSnafu, RequestError, ReqwestProcessingSnafu, SerializingSnafu};
+use crate::flux_csv::FluxCsvParser;
+use crate::{Client, HttpSnafu, RequestError, SerializingSnafu, UnsupportedDialectSnafu};
+use futures::{Stream, StreamExt};
 use reqwest::{Method, StatusCode};
-use snafu::ResultExt;
+use snafu::{ensure, ResultExt};
 
 use crate::models::{
-    AnalyzeQueryResponse, AstResponse, FluxSuggestion, FluxSuggestions, LanguageRequest, Query,
+    AnalyzeQueryResponse, AstResponse, FluxRecord, FluxSuggestion, FluxSuggestions,
+    LanguageRequest, Query,
 };
 
 impl Client {
     /// Get Query Suggestions
     pub async fn query_suggestions(&self) -> Result<FluxSuggestions, RequestError> {
         let req_url = format!("{}/api/v2/query/suggestions", self.url);
-        let response = self
-            .request(Method::GET, &req_url)
-            .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+        let response = self.request(Method::GET, &req_url).send().await?;
 
         match response.status() {
-            StatusCode::OK => Ok(response
-                .json::<FluxSuggestions>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::OK => Ok(response.json::<FluxSuggestions>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -40,19 +36,12 @@ impl Client {
             name = crate::common::urlencode(name),
         );
 
-        let response = self
-            .request(Method::GET, &req_url)
-            .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+        let response = self.request(Method::GET, &req_url).send().await?;
 
         match response.status() {
-            StatusCode::OK => Ok(response
-                .json::<FluxSuggestion>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::OK => Ok(response.json::<FluxSuggestion>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -69,21 +58,154 @@ impl Client {
             .query(&[("org", &org)])
             .body(serde_json::to_string(&query.unwrap_or_default()).context(SerializingSnafu)?)
             .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+            .await?;
 
         match response.status() {
-            StatusCode::OK => Ok(response
-                .json::<String>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::OK => Ok(response.json::<String>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
     }
 
+    /// Query, returning a `Stream` of the result rows.
+    ///
+    /// Unlike [`query`](Self::query) the response body is not buffered:
+    /// records are parsed from the CSV response as it arrives, so results of
+    /// any size can be consumed in bounded memory.
+    ///
+    /// The query's [`Dialect`](crate::models::ast::Dialect) is sent to the
+    /// server and its delimiter is used to parse the response. Requesting
+    /// only the annotations that are needed reduces the parsing work; the
+    /// `default` annotation is the only one used when building records.
+    /// Dialects with a delimiter that is not a single character, or without
+    /// a header row, cannot be parsed and are rejected.
+    pub async fn query_flux_stream(
+        &self,
+        org: &str,
+        query: Query,
+    ) -> Result<impl Stream<Item = Result<FluxRecord, RequestError>>, RequestError> {
+        let req_url = format!("{}/api/v2/query", self.url);
+        let dialect = query.dialect.as_ref();
+        ensure!(
+            dialect.and_then(|dialect| dialect.header) != Some(false),
+            UnsupportedDialectSnafu {
+                reason: "records cannot be built without a header row",
+            }
+        );
+        let delimiter = match dialect.and_then(|dialect| dialect.delimiter.as_deref()) {
+            None => ',',
+            Some(delimiter) => {
+                let mut chars = delimiter.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => {
+                        return UnsupportedDialectSnafu {
+                            reason: format!("delimiter {:?} is not a single character", delimiter),
+                        }
+                        .fail()
+                    }
+                }
+            }
+        };
+
+        let response = self
+            .request(Method::POST, &req_url)
+            .header("Accepting-Encoding", "identity")
+            .header("Content-Type", "application/json")
+            .query(&[("org", &org)])
+            .body(serde_json::to_string(&query).context(SerializingSnafu)?)
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::OK {
+            let status = response.status();
+            let text = response.text().await?;
+            HttpSnafu { status, text }.fail()?;
+        }
+
+        let body = Box::pin(response.bytes_stream());
+        let state = (body, FluxCsvParser::new(delimiter), false);
+
+        Ok(futures::stream::unfold(
+            state,
+            |(mut body, mut parser, mut eof)| async move {
+                loop {
+                    if let Some(record) = parser.next_record(eof) {
+                        let record = record.map_err(|reason| RequestError::FluxCsv { reason });
+                        return Some((record, (body, parser, eof)));
+                    }
+                    if eof {
+                        return None;
+                    }
+
+                    match body.next().await {
+                        Some(Ok(bytes)) => parser.push(&bytes),
+                        Some(Err(source)) => {
+                            // drop any partial row, the stream ends here
+                            let err = RequestError::from(source);
+                            return Some((Err(err), (body, FluxCsvParser::default(), true)));
+                        }
+                        None => eof = true,
+                    }
+                }
+            },
+        ))
+    }
+
+    /// List the measurements in `bucket`, using the Flux
+    /// `schema.measurements` function.
+    pub async fn list_measurements(
+        &self,
+        org: &str,
+        bucket: &str,
+    ) -> Result<Vec<String>, RequestError> {
+        let flux = format!(
+            "import \"influxdata/influxdb/schema\"\n\
+             schema.measurements(bucket: {})",
+            flux_string(bucket)
+        );
+        self.query_flux_values(org, flux).await
+    }
+
+    /// List the tag keys of `measurement` in `bucket`, using the Flux
+    /// `schema.tagKeys` function.
+    ///
+    /// As with `schema.tagKeys`, the keys include the `_start`, `_stop`,
+    /// `_field` and `_measurement` group keys.
+    pub async fn list_tag_keys(
+        &self,
+        org: &str,
+        bucket: &str,
+        measurement: &str,
+    ) -> Result<Vec<String>, RequestError> {
+        let flux = format!(
+            "import \"influxdata/influxdb/schema\"\n\
+             schema.tagKeys(bucket: {}, predicate: (r) => r._measurement == {})",
+            flux_string(bucket),
+            flux_string(measurement)
+        );
+        self.query_flux_values(org, flux).await
+    }
+
+    /// Run the Flux query `flux`, returning the `_value` column of every row.
+    async fn query_flux_values(
+        &self,
+        org: &str,
+        flux: String,
+    ) -> Result<Vec<String>, RequestError> {
+        let mut records = Box::pin(self.query_flux_stream(org, Query::new(flux)).await?);
+
+        let mut values = vec![];
+        while let Some(record) = records.next().await {
+            if let Some(value) = record?.get("_value") {
+                values.push(value.to_string());
+            }
+        }
+        Ok(values)
+    }
+
     /// Analyze Query
     pub async fn query_analyze(
         &self,
@@ -96,16 +218,12 @@ impl Client {
             .header("Content-Type", "application/json")
             .body(serde_json::to_string(&query.unwrap_or_default()).context(SerializingSnafu)?)
             .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+            .await?;
 
         match response.status() {
-            StatusCode::OK => Ok(response
-                .json::<AnalyzeQueryResponse>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::OK => Ok(response.json::<AnalyzeQueryResponse>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -126,22 +244,33 @@ impl Client {
                     .context(SerializingSnafu)?,
             )
             .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+            .await?;
 
         match response.status() {
-            StatusCode::OK => Ok(response
-                .json::<AstResponse>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::OK => Ok(response.json::<AstResponse>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
     }
 }
 
+/// Quote `s` as a Flux string literal.
+fn flux_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        // `$` is escaped so that `${` doesn't start an interpolation
+        if matches!(c, '"' | '\\' | '$') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,6 +363,244 @@ mod tests {
         mock_server.assert();
     }
 
+    #[tokio::test]
+    async fn query_flux_stream() {
+        use std::io::Write;
+
+        let token = "some-token";
+        let org = "some-org";
+        let query = Query::new("some-influx-query-string".to_string());
+
+        let mock_server = mock("POST", "/api/v2/query")
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .match_query(Matcher::UrlEncoded("org".into(), org.into()))
+            .with_header("Content-Type", "text/csv")
+            // written in pieces so that the body is sent chunked, with rows
+            // split across chunks
+            .with_body_from_fn(|w| {
+                w.write_all(b"#default,_result,,\r\n,result,table,_va")?;
+                w.flush()?;
+                w.write_all(b"lue\r\n,,0,1\r\n,,0,")?;
+                w.flush()?;
+                w.write_all(b"2\r\n\r\n,error,reference\r\n,something broke,\r\n")
+            })
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let records = client
+            .query_flux_stream(org, query)
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+
+        mock_server.assert();
+        assert_eq!(records.len(), 3);
+        let values = records[..2]
+            .iter()
+            .map(|r| {
+                let r = r.as_ref().unwrap();
+                assert_eq!(r.get("result"), Some("_result"));
+                r.get("_value").unwrap().to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["1", "2"]);
+        assert!(matches!(
+            &records[2],
+            Err(RequestError::FluxCsv { reason }) if reason == "something broke"
+        ));
+    }
+
+    #[tokio::test]
+    async fn query_flux_stream_before_end_of_body() {
+        use std::io::Write;
+        use std::sync::{mpsc, Mutex};
+        use std::time::Duration;
+
+        let token = "some-token";
+        let org = "some-org";
+        let query = Query::new("some-influx-query-string".to_string());
+
+        let (first_observed, rx) = mpsc::channel();
+        let rx = Mutex::new(rx);
+        let mock_server = mock("POST", "/api/v2/query")
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .match_query(Matcher::UrlEncoded("org".into(), org.into()))
+            .with_header("Content-Type", "text/csv")
+            .with_body_from_fn(move |w| {
+                w.write_all(b",result,table,_value\r\n,_result,0,1\r\n")?;
+                w.flush()?;
+                // the rest of the body is held back until the first record
+                // has been observed
+                let _ = rx.lock().unwrap().recv_timeout(Duration::from_secs(10));
+                w.write_all(b",_result,0,2\r\n")
+            })
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let mut records = Box::pin(client.query_flux_stream(org, query).await.unwrap());
+        let first = records.next().await.unwrap().unwrap();
+        assert_eq!(first.get("_value"), Some("1"));
+        first_observed.send(()).unwrap();
+
+        let rest = records.collect::<Vec<_>>().await;
+        mock_server.assert();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].as_ref().unwrap().get("_value"), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn query_flux_stream_dialect() {
+        use crate::models::ast::{dialect::Annotations, Dialect};
+
+        let token = "some-token";
+        let org = "some-org";
+        let query = Query {
+            dialect: Some(Dialect {
+                annotations: vec![Annotations::Default],
+                delimiter: Some(";".to_string()),
+                ..Dialect::new()
+            }),
+            ..Query::new("some-influx-query-string".to_string())
+        };
+
+        let mock_server = mock("POST", "/api/v2/query")
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .match_query(Matcher::UrlEncoded("org".into(), org.into()))
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "dialect": {"annotations": ["default"], "delimiter": ";"}
+            })))
+            .with_header("Content-Type", "text/csv")
+            .with_body("#default;_result;;\r\n;result;table;_value\r\n;;0;1,5\r\n")
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let records = client
+            .query_flux_stream(org, query)
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+
+        mock_server.assert();
+        assert_eq!(records.len(), 1);
+        let record = records[0].as_ref().unwrap();
+        assert_eq!(record.get("result"), Some("_result"));
+        assert_eq!(record.get("_value"), Some("1,5"));
+    }
+
+    #[tokio::test]
+    async fn query_flux_stream_unsupported_dialect() {
+        use crate::models::ast::Dialect;
+
+        let client = Client::new(&mockito::server_url(), "some-token");
+        let query = |dialect| Query {
+            dialect: Some(dialect),
+            ..Query::new("some-influx-query-string".to_string())
+        };
+
+        let err = client
+            .query_flux_stream(
+                "some-org",
+                query(Dialect {
+                    delimiter: Some("::".to_string()),
+                    ..Dialect::new()
+                }),
+            )
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            r#"Unsupported query dialect: delimiter "::" is not a single character"#
+        );
+
+        let err = client
+            .query_flux_stream(
+                "some-org",
+                query(Dialect {
+                    header: Some(false),
+                    ..Dialect::new()
+                }),
+            )
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, RequestError::UnsupportedDialect { .. }));
+    }
+
+    #[tokio::test]
+    async fn list_measurements() {
+        let token = "some-token";
+        let org = "some-org";
+
+        let mock_server = mock("POST", "/api/v2/query")
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .match_query(Matcher::UrlEncoded("org".into(), org.into()))
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "query": "import \"influxdata/influxdb/schema\"\n\
+                          schema.measurements(bucket: \"some-bucket\")"
+            })))
+            .with_header("Content-Type", "text/csv")
+            .with_body(
+                "#datatype,string,long,string\r\n\
+                 #group,false,false,false\r\n\
+                 #default,_result,,\r\n\
+                 ,result,table,_value\r\n\
+                 ,,0,cpu\r\n\
+                 ,,0,disk\r\n\
+                 \r\n",
+            )
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let measurements = client.list_measurements(org, "some-bucket").await.unwrap();
+
+        mock_server.assert();
+        assert_eq!(measurements, vec!["cpu", "disk"]);
+    }
+
+    #[tokio::test]
+    async fn list_tag_keys() {
+        let token = "some-token";
+        let org = "some-org";
+
+        let mock_server = mock("POST", "/api/v2/query")
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .match_query(Matcher::UrlEncoded("org".into(), org.into()))
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "query": "import \"influxdata/influxdb/schema\"\n\
+                          schema.tagKeys(bucket: \"some-bucket\", \
+                          predicate: (r) => r._measurement == \"c\\\"p\\$u\")"
+            })))
+            .with_header("Content-Type", "text/csv")
+            .with_body(
+                "#datatype,string,long,string\r\n\
+                 #group,false,false,false\r\n\
+                 #default,_result,,\r\n\
+                 ,result,table,_value\r\n\
+                 ,,0,_measurement\r\n\
+                 ,,0,host\r\n\
+                 ,,0,region\r\n\
+                 \r\n",
+            )
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let tag_keys = client
+            .list_tag_keys(org, "some-bucket", "c\"p$u")
+            .await
+            .unwrap();
+
+        mock_server.assert();
+        assert_eq!(tag_keys, vec!["_measurement", "host", "region"]);
+    }
+
     #[tokio::test]
     async fn query_analyze() {
         let token = "some-token";
@@ -3,25 +3,20 @@
 //! Check readiness of an InfluxDB instance at startup
 
 use reqwest::{Method, StatusCode};
-use snafu::ResultExt;
 
-use crate::{Client, HttpSnafu, RequestError, ReqwestProcessingSnafu};
+use crate::{Client, HttpSnafu, RequestError};
 
 impl Client {
     /// Get the readiness of an instance at startup
     pub async fn ready(&self) -> Result<bool, RequestError> {
         let ready_url = format!("{}/ready", self.url);
-        let response = self
-            .request(Method::GET, &ready_url)
-            .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+        let response = self.request(Method::GET, &ready_url).send().await?;
 
         match response.status() {
             StatusCode::OK => Ok(true),
             _ => {
                 let status = response.status();
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
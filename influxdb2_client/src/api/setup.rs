@@ -2,7 +2,7 @@
 //!
 //! Initate and start onboarding process of InfluxDB server.
 
-use crate::{Client, HttpSnafu, RequestError, ReqwestProcessingSnafu, SerializingSnafu};
+use crate::{Client, HttpSnafu, RequestError, SerializingSnafu};
 use reqwest::{Method, StatusCode};
 use snafu::ResultExt;
 
@@ -12,20 +12,12 @@ impl Client {
     /// Check if database has default user, org, bucket
     pub async fn is_onboarding_allowed(&self) -> Result<bool, RequestError> {
         let setup_url = format!("{}/api/v2/setup", self.url);
-        let response = self
-            .request(Method::GET, &setup_url)
-            .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+        let response = self.request(Method::GET, &setup_url).send().await?;
 
         match response.status() {
-            StatusCode::OK => Ok(response
-                .json::<IsOnboarding>()
-                .await
-                .context(ReqwestProcessingSnafu)?
-                .allowed),
+            StatusCode::OK => Ok(response.json::<IsOnboarding>().await?.allowed),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -56,16 +48,12 @@ impl Client {
             .request(Method::POST, &setup_init_url)
             .body(serde_json::to_string(&body).context(SerializingSnafu)?)
             .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+            .await?;
 
         match response.status() {
-            StatusCode::CREATED => Ok(response
-                .json::<OnboardingResponse>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::CREATED => Ok(response.json::<OnboardingResponse>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -96,16 +84,12 @@ impl Client {
             .request(Method::POST, &setup_new_url)
             .body(serde_json::to_string(&body).context(SerializingSnafu)?)
             .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+            .await?;
 
         match response.status() {
-            StatusCode::CREATED => Ok(response
-                .json::<OnboardingResponse>()
-                .await
-                .context(ReqwestProcessingSnafu)?),
+            StatusCode::CREATED => Ok(response.json::<OnboardingResponse>().await?),
             status => {
-                let text = response.text().await.context(ReqwestProcessingSnafu)?;
+                let text = response.text().await?;
                 HttpSnafu { status, text }.fail()?
             }
         }
@@ -0,0 +1,216 @@
+//! Users API
+
+use crate::models::{PasswordResetBody, User, Users};
+use crate::{Client, HttpSnafu, RequestError, SerializingSnafu};
+use reqwest::{Method, StatusCode};
+use snafu::ResultExt;
+
+impl Client {
+    /// List all users
+    pub async fn users(&self) -> Result<Users, RequestError> {
+        let users_url = format!("{}/api/v2/users", self.url);
+        let response = self.request(Method::GET, &users_url).send().await?;
+        match response.status() {
+            StatusCode::OK => Ok(response.json::<Users>().await?),
+            status => {
+                let text = response.text().await?;
+                HttpSnafu { status, text }.fail()?
+            }
+        }
+    }
+
+    /// Create a user
+    pub async fn create_user(&self, user: User) -> Result<User, RequestError> {
+        let create_user_url = format!("{}/api/v2/users", self.url);
+        let response = self
+            .request(Method::POST, &create_user_url)
+            .body(serde_json::to_string(&user).context(SerializingSnafu)?)
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::CREATED => Ok(response.json::<User>().await?),
+            status => {
+                let text = response.text().await?;
+                HttpSnafu { status, text }.fail()?
+            }
+        }
+    }
+
+    /// Update a user
+    pub async fn update_user(&self, user_id: &str, user: User) -> Result<User, RequestError> {
+        let update_user_url = format!("{}/api/v2/users/{}", self.url, user_id);
+        let response = self
+            .request(Method::PATCH, &update_user_url)
+            .body(serde_json::to_string(&user).context(SerializingSnafu)?)
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::OK => Ok(response.json::<User>().await?),
+            status => {
+                let text = response.text().await?;
+                HttpSnafu { status, text }.fail()?
+            }
+        }
+    }
+
+    /// Delete a user
+    pub async fn delete_user(&self, user_id: &str) -> Result<(), RequestError> {
+        let delete_user_url = format!("{}/api/v2/users/{}", self.url, user_id);
+        let response = self
+            .request(Method::DELETE, &delete_user_url)
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(()),
+            status => {
+                let text = response.text().await?;
+                HttpSnafu { status, text }.fail()?
+            }
+        }
+    }
+
+    /// Update the password of a user
+    pub async fn set_password(&self, user_id: &str, password: &str) -> Result<(), RequestError> {
+        let password_url = format!("{}/api/v2/users/{}/password", self.url, user_id);
+        let body = PasswordResetBody::new(password.into());
+        let response = self
+            .request(Method::POST, &password_url)
+            .body(serde_json::to_string(&body).context(SerializingSnafu)?)
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(()),
+            status => {
+                let text = response.text().await?;
+                HttpSnafu { status, text }.fail()?
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::mock;
+
+    const BASE_PATH: &str = "/api/v2/users";
+
+    #[tokio::test]
+    async fn users() {
+        let token = "some-token";
+
+        let mock_server = mock("GET", BASE_PATH)
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .with_body(
+                r#"{"links":{"self":"/api/v2/users"},"users":[{"id":"some-id","name":"some-user","links":{"self":"/api/v2/users/some-id"}}]}"#,
+            )
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let result = client.users().await.unwrap();
+
+        mock_server.assert();
+        assert_eq!(
+            result.links.and_then(|l| l.self_).as_deref(),
+            Some("/api/v2/users")
+        );
+        assert_eq!(result.users.len(), 1);
+        assert_eq!(result.users[0].name, "some-user");
+        assert_eq!(
+            result.users[0]
+                .links
+                .as_ref()
+                .and_then(|l| l.self_.as_deref()),
+            Some("/api/v2/users/some-id")
+        );
+    }
+
+    #[tokio::test]
+    async fn create_user() {
+        let token = "some-token";
+        let name = "some-user";
+
+        let mock_server = mock("POST", BASE_PATH)
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .match_body(format!(r#"{{"name":"{}"}}"#, name).as_str())
+            .with_status(201)
+            .with_body(
+                format!(
+                    r#"{{"id":"some-id","name":"{}","status":"active","links":{{"self":"/api/v2/users/some-id"}}}}"#,
+                    name
+                )
+                .as_str(),
+            )
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let result = client
+            .create_user(User::new(name.to_string()))
+            .await
+            .unwrap();
+
+        mock_server.assert();
+        assert_eq!(result.id.as_deref(), Some("some-id"));
+        assert_eq!(
+            result.links.and_then(|l| l.self_).as_deref(),
+            Some("/api/v2/users/some-id")
+        );
+    }
+
+    #[tokio::test]
+    async fn update_user() {
+        let token = "some-token";
+        let user_id = "some-id";
+
+        let mock_server = mock("PATCH", format!("{}/{}", BASE_PATH, user_id).as_str())
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .match_body(r#"{"name":"new-name"}"#)
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let _result = client
+            .update_user(user_id, User::new("new-name".to_string()))
+            .await;
+
+        mock_server.assert();
+    }
+
+    #[tokio::test]
+    async fn delete_user() {
+        let token = "some-token";
+        let user_id = "some-id";
+
+        let mock_server = mock("DELETE", format!("{}/{}", BASE_PATH, user_id).as_str())
+            .match_header("Authorization", format!("Token {}", token).as_str())
+            .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let _result = client.delete_user(user_id).await;
+
+        mock_server.assert();
+    }
+
+    #[tokio::test]
+    async fn set_password() {
+        let token = "some-token";
+        let user_id = "some-id";
+
+        let mock_server = mock(
+            "POST",
+            format!("{}/{}/password", BASE_PATH, user_id).as_str(),
+        )
+        .match_header("Authorization", format!("Token {}", token).as_str())
+        .match_body(r#"{"password":"some-password"}"#)
+        .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let _result = client.set_password(user_id, "some-password").await;
+
+        mock_server.assert();
+    }
+}
@@ -1,11 +1,11 @@
 //! Write API
 
 use crate::models::WriteDataPoint;
-use crate::{Client, HttpSnafu, RequestError, ReqwestProcessingSnafu};
+use crate::Precision;
+use crate::{Client, HttpSnafu, RequestError};
 use bytes::BufMut;
 use futures::{Stream, StreamExt};
 use reqwest::{Body, Method};
-use snafu::ResultExt;
 use std::io::{self, Write};
 
 impl Client {
@@ -15,21 +15,46 @@ impl Client {
         org: &str,
         bucket: &str,
         body: impl Into<Body> + Send,
+    ) -> Result<(), RequestError> {
+        self.write_line_protocol_inner(org, bucket, None, body)
+            .await
+    }
+
+    /// Write line protocol data, with timestamps of the given precision, to
+    /// the specified organization and bucket.
+    pub async fn write_line_protocol_with_precision(
+        &self,
+        org: &str,
+        bucket: &str,
+        precision: Precision,
+        body: impl Into<Body> + Send,
+    ) -> Result<(), RequestError> {
+        self.write_line_protocol_inner(org, bucket, Some(precision), body)
+            .await
+    }
+
+    async fn write_line_protocol_inner(
+        &self,
+        org: &str,
+        bucket: &str,
+        precision: Option<Precision>,
+        body: impl Into<Body> + Send,
     ) -> Result<(), RequestError> {
         let body = body.into();
         let write_url = format!("{}/api/v2/write", self.url);
 
-        let response = self
+        let mut request = self
             .request(Method::POST, &write_url)
-            .query(&[("bucket", bucket), ("org", org)])
-            .body(body)
-            .send()
-            .await
-            .context(ReqwestProcessingSnafu)?;
+            .query(&[("bucket", bucket), ("org", org)]);
+        if let Some(precision) = precision {
+            request = request.query(&[("precision", precision.as_str())]);
+        }
+
+        let response = request.body(body).send().await?;
 
         if !response.status().is_success() {
             let status = response.status();
-            let text = response.text().await.context(ReqwestProcessingSnafu)?;
+            let text = response.text().await?;
             HttpSnafu { status, text }.fail()?;
         }
 
@@ -110,4 +135,32 @@ cpu,host=server01,region=us-west usage=0.87
 
         mock_server.assert();
     }
+
+    #[tokio::test]
+    async fn writing_line_protocol_with_precision() {
+        let org = "some-org";
+        let bucket = "some-bucket";
+        let token = "some-token";
+
+        let mock_server = mock(
+            "POST",
+            format!("/api/v2/write?bucket={}&org={}&precision=ms", bucket, org).as_str(),
+        )
+        .match_header("Authorization", format!("Token {}", token).as_str())
+        .match_body("cpu usage=0.5 1568756160000")
+        .create();
+
+        let client = Client::new(&mockito::server_url(), token);
+
+        let _result = client
+            .write_line_protocol_with_precision(
+                org,
+                bucket,
+                Precision::Milliseconds,
+                "cpu usage=0.5 1568756160000",
+            )
+            .await;
+
+        mock_server.assert();
+    }
 }
@@ -0,0 +1,298 @@
+//! Incremental parsing of the (annotated) CSV returned by Flux queries.
+//!
+//! The response body is made up of one or more tables separated by blank
+//! lines. Each table starts with optional annotation rows (starting with `#`),
+//! followed by a header row and the data rows.
+
+use crate::models::FluxRecord;
+
+/// Parses Flux CSV records out of a body that arrives in arbitrary chunks.
+///
+/// Only incomplete rows are buffered; complete rows are handed out as soon as
+/// they have been pushed.
+#[derive(Debug)]
+pub(crate) struct FluxCsvParser {
+    // The character separating cells, as requested by the query's dialect.
+    delimiter: char,
+    buf: Vec<u8>,
+    // Where the current row starts in `buf`; the rows before it have been
+    // returned and are only removed when the next chunk is pushed.
+    start: usize,
+    // How far `buf` has been scanned for the end of the current row, and
+    // whether that position is inside a quoted field.
+    scanned: usize,
+    in_quotes: bool,
+    header: Option<Vec<String>>,
+    defaults: Vec<String>,
+}
+
+impl Default for FluxCsvParser {
+    fn default() -> Self {
+        Self::new(',')
+    }
+}
+
+impl FluxCsvParser {
+    /// Create a parser for CSV with cells separated by `delimiter`
+    pub(crate) fn new(delimiter: char) -> Self {
+        Self {
+            delimiter,
+            buf: Default::default(),
+            start: 0,
+            scanned: 0,
+            in_quotes: false,
+            header: None,
+            defaults: vec![],
+        }
+    }
+
+    /// Append the next chunk of the body
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.buf.drain(..self.start);
+        self.scanned -= self.start;
+        self.start = 0;
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Return the next complete record, if any. Once the whole body has been
+    /// pushed `eof` must be set so that a final row without a trailing newline
+    /// is returned.
+    pub(crate) fn next_record(&mut self, eof: bool) -> Option<Result<FluxRecord, String>> {
+        loop {
+            let row = match self.next_row(eof)? {
+                Ok(row) => row,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if row.len() == 1 && row[0].is_empty() {
+                // a blank line ends the current table
+                self.header = None;
+                self.defaults.clear();
+                continue;
+            }
+
+            if row[0].starts_with('#') {
+                // annotations always start a new table
+                self.header = None;
+                if row[0] == "#default" {
+                    self.defaults = row;
+                }
+                continue;
+            }
+
+            let header = match &self.header {
+                Some(header) => header,
+                None => {
+                    self.header = Some(row);
+                    continue;
+                }
+            };
+
+            if row.len() != header.len() {
+                return Some(Err(format!(
+                    "expected {} columns but got {}",
+                    header.len(),
+                    row.len()
+                )));
+            }
+
+            let mut values = header
+                .iter()
+                .zip(row)
+                .enumerate()
+                .filter(|(_, (name, _))| !name.is_empty())
+                .map(|(idx, (name, value))| {
+                    let value = match (value.is_empty(), self.defaults.get(idx)) {
+                        (true, Some(default)) => default.clone(),
+                        _ => value,
+                    };
+                    (name.clone(), value)
+                })
+                .collect::<std::collections::BTreeMap<_, _>>();
+
+            // queries that fail after the response has started report the
+            // error in a table of its own
+            if values.len() == 2 && values.contains_key("reference") {
+                if let Some(error) = values.remove("error") {
+                    return Some(Err(error));
+                }
+            }
+
+            return Some(Ok(FluxRecord { values }));
+        }
+    }
+
+    /// Split the next complete row off the buffer
+    fn next_row(&mut self, eof: bool) -> Option<Result<Vec<String>, String>> {
+        let end = self.buf[self.scanned..]
+            .iter()
+            .position(|&b| {
+                if b == b'"' {
+                    self.in_quotes = !self.in_quotes;
+                }
+                b == b'\n' && !self.in_quotes
+            })
+            .map(|pos| self.scanned + pos);
+
+        let row_start = self.start;
+        let row_end = match end {
+            Some(end) => {
+                self.start = end + 1;
+                end
+            }
+            None if eof && self.start < self.buf.len() => {
+                self.start = self.buf.len();
+                self.buf.len()
+            }
+            None => {
+                self.scanned = self.buf.len();
+                return None;
+            }
+        };
+        self.scanned = self.start;
+        self.in_quotes = false;
+
+        let line = match std::str::from_utf8(&self.buf[row_start..row_end]) {
+            Ok(line) => line,
+            Err(_) => return Some(Err("row is not valid UTF-8".to_string())),
+        };
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        Some(Ok(split_fields(line, self.delimiter)))
+    }
+}
+
+/// Split a CSV row into its fields, removing quotes and unescaping `""`
+fn split_fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => in_quotes = !in_quotes,
+            (c, false) if c == delimiter => fields.push(std::mem::take(&mut field)),
+            (c, _) => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(values: &[(&str, &str)]) -> FluxRecord {
+        FluxRecord {
+            values: values
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn annotated_csv() {
+        let body = "#datatype,string,long,string,double\r\n\
+                    #group,false,false,true,false\r\n\
+                    #default,_result,,,\r\n\
+                    ,result,table,host,_value\r\n\
+                    ,,0,a,1.5\r\n\
+                    ,,0,\"b,\"\"c\"\"\",2\r\n\
+                    \r\n\
+                    #datatype,string,long,string\r\n\
+                    #group,false,false,true\r\n\
+                    #default,_result,,\r\n\
+                    ,result,table,region\r\n\
+                    ,,1,west\r\n";
+
+        let mut parser = FluxCsvParser::default();
+        parser.push(body.as_bytes());
+
+        let mut records = vec![];
+        while let Some(r) = parser.next_record(true) {
+            records.push(r.unwrap());
+        }
+
+        assert_eq!(
+            records,
+            vec![
+                record(&[
+                    ("result", "_result"),
+                    ("table", "0"),
+                    ("host", "a"),
+                    ("_value", "1.5")
+                ]),
+                record(&[
+                    ("result", "_result"),
+                    ("table", "0"),
+                    ("host", "b,\"c\""),
+                    ("_value", "2")
+                ]),
+                record(&[("result", "_result"), ("table", "1"), ("region", "west")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn records_are_returned_incrementally() {
+        let mut parser = FluxCsvParser::default();
+
+        parser.push(b",result,table,_val");
+        assert!(parser.next_record(false).is_none());
+        parser.push(b"ue\n,_result,0,\"multi\nline");
+        assert!(parser.next_record(false).is_none());
+        parser.push(b"\"\n,_result,0,2\n,_resu");
+
+        let expected = |value| record(&[("result", "_result"), ("table", "0"), ("_value", value)]);
+        assert_eq!(
+            parser.next_record(false).unwrap().unwrap(),
+            expected("multi\nline")
+        );
+        assert_eq!(parser.next_record(false).unwrap().unwrap(), expected("2"));
+        assert!(parser.next_record(false).is_none());
+
+        // the last row may not end with a newline
+        parser.push(b"lt,0,3");
+        // rows that were returned are dropped from the buffer
+        assert_eq!(parser.buf, b",_result,0,3");
+        assert!(parser.next_record(false).is_none());
+        assert_eq!(parser.next_record(true).unwrap().unwrap(), expected("3"));
+        assert!(parser.next_record(true).is_none());
+    }
+
+    #[test]
+    fn delimiter() {
+        let mut parser = FluxCsvParser::new(';');
+        parser.push(b"#default;_result;;\n;result;table;_value\n;;0;\"1;5\"\n;;0;2,5\n");
+
+        let expected = |value| record(&[("result", "_result"), ("table", "0"), ("_value", value)]);
+        assert_eq!(parser.next_record(true).unwrap().unwrap(), expected("1;5"));
+        assert_eq!(parser.next_record(true).unwrap().unwrap(), expected("2,5"));
+        assert!(parser.next_record(true).is_none());
+    }
+
+    #[test]
+    fn errors() {
+        let mut parser = FluxCsvParser::default();
+        parser.push(b",result,table\n,_result,0,extra\n");
+        assert_eq!(
+            parser.next_record(true).unwrap().unwrap_err(),
+            "expected 3 columns but got 4"
+        );
+
+        let mut parser = FluxCsvParser::default();
+        parser.push(b",error,reference\n,query timed out,\n");
+        assert_eq!(
+            parser.next_record(true).unwrap().unwrap_err(),
+            "query timed out"
+        );
+    }
+}
@@ -63,6 +63,7 @@
 
 use reqwest::Method;
 use snafu::Snafu;
+use std::time::Duration;
 
 /// Errors that occur while making requests to the Influx server.
 #[derive(Debug, Snafu)]
@@ -74,6 +75,15 @@ pub enum RequestError {
         /// The underlying error object from `reqwest`.
         source: reqwest::Error,
     },
+    /// No response was received within the configured
+    /// [request timeout](Client::with_request_timeout), or no connection could
+    /// be established within the [connect timeout](Client::with_connect_timeout).
+    #[snafu(display("HTTP request timed out: {}", source))]
+    Timeout {
+        /// The underlying error object from `reqwest`.
+        source: reqwest::Error,
+    },
+
     /// The underlying `reqwest` library returned an HTTP error with code 400
     /// (meaning a client error) or 500 (meaning a server error).
     #[snafu(display("HTTP request returned an error: {}, `{}`", status, text))]
@@ -91,6 +101,49 @@ pub enum RequestError {
         /// The underlying error object from `serde_json`.
         source: serde_json::error::Error,
     },
+
+    /// No organization with the given name exists on the Influx server.
+    #[snafu(display("Organization '{}' not found", name))]
+    OrganizationNotFound {
+        /// The name of the organization that was looked up
+        name: String,
+    },
+
+    /// More than one bucket matched a lookup that expects at most one.
+    #[snafu(display("Found {} buckets named '{}', expected at most one", count, name))]
+    MultipleBucketsFound {
+        /// The name of the bucket that was looked up
+        name: String,
+        /// How many buckets matched
+        count: usize,
+    },
+
+    /// The dialect of a Flux query requests results the client cannot parse.
+    #[snafu(display("Unsupported query dialect: {}", reason))]
+    UnsupportedDialect {
+        /// Why the dialect is not supported
+        reason: String,
+    },
+
+    /// The annotated CSV returned by a Flux query could not be parsed, or the
+    /// query failed part way through.
+    #[snafu(display("Error while reading Flux query results: {}", reason))]
+    FluxCsv {
+        /// Why the results could not be read
+        reason: String,
+    },
+}
+
+// Request timeouts also apply to reading the response body, so errors from
+// both sending a request and reading its response are classified here.
+impl From<reqwest::Error> for RequestError {
+    fn from(source: reqwest::Error) -> Self {
+        if source.is_timeout() {
+            Self::Timeout { source }
+        } else {
+            Self::ReqwestProcessing { source }
+        }
+    }
 }
 
 /// Client to a server supporting the InfluxData 2.0 API.
@@ -101,6 +154,7 @@ pub struct Client {
     auth_header: Option<String>,
     reqwest: reqwest::Client,
     jaeger_debug_header: Option<String>,
+    request_timeout: Option<Duration>,
 }
 
 impl Client {
@@ -129,6 +183,28 @@ impl Client {
             auth_header,
             reqwest: reqwest::Client::new(),
             jaeger_debug_header: None,
+            request_timeout: None,
+        }
+    }
+
+    /// Fail requests with [`RequestError::Timeout`] if a connection to the
+    /// server cannot be established within `timeout`.
+    pub fn with_connect_timeout(self, timeout: Duration) -> Self {
+        let reqwest = reqwest::Client::builder()
+            .connect_timeout(timeout)
+            .build()
+            .expect("failed to build HTTP client");
+
+        Self { reqwest, ..self }
+    }
+
+    /// Fail requests if they do not complete, including reading the response
+    /// body, within `timeout`. Requests that receive no response in time fail
+    /// with [`RequestError::Timeout`].
+    pub fn with_request_timeout(self, timeout: Duration) -> Self {
+        Self {
+            request_timeout: Some(timeout),
+            ..self
         }
     }
 
@@ -150,12 +226,66 @@ impl Client {
         if let Some(header) = &self.jaeger_debug_header {
             req = req.header(header, format!("influxdb_client-{}", uuid::Uuid::new_v4()));
         }
+        if let Some(timeout) = self.request_timeout {
+            req = req.timeout(timeout);
+        }
 
         req
     }
 }
 
 pub mod common;
+mod flux_csv;
 
 pub mod api;
 pub mod models;
+
+pub use data_types::precision::Precision;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    #[tokio::test]
+    async fn request_timeout() {
+        // A server that accepts connections (via the listen backlog) but never
+        // responds
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let client = Client::new(url, "some-token")
+            .with_connect_timeout(Duration::from_secs(5))
+            .with_request_timeout(Duration::from_millis(100));
+
+        let err = client.health().await.unwrap_err();
+        assert!(matches!(err, RequestError::Timeout { .. }), "{:?}", err);
+
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn response_body_timeout() {
+        // A server that sends the response headers but stalls on the body
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\n{")
+                .unwrap();
+            std::thread::sleep(Duration::from_secs(1));
+        });
+
+        let client =
+            Client::new(url, "some-token").with_request_timeout(Duration::from_millis(100));
+
+        let err = client.health().await.unwrap_err();
+        assert!(matches!(err, RequestError::Timeout { .. }), "{:?}", err);
+
+        server.join().unwrap();
+    }
+}
@@ -13,9 +13,11 @@ pub struct Dialect {
     /// Separator between cells; the default is ,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delimiter: Option<String>,
+    /// Annotation rows to include before each table; none are included if
+    /// empty.
     /// <https://www.w3.org/TR/2015/REC-tabular-data-model-20151217/#columns>
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub annotations: Option<Annotations>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<Annotations>,
     /// Character prefixed to comment strings
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comment_prefix: Option<String>,
@@ -48,7 +50,27 @@ pub enum Annotations {
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub enum DateTimeFormat {
     /// RFC3339
+    #[serde(rename = "RFC3339")]
     Rfc3339,
     /// RFC3339Nano
+    #[serde(rename = "RFC3339Nano")]
     Rfc3339Nano,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotations() {
+        assert_eq!(serde_json::to_string(&Dialect::new()).unwrap(), "{}");
+
+        let dialect = Dialect {
+            annotations: vec![Annotations::Group, Annotations::Default],
+            ..Dialect::new()
+        };
+        let json = serde_json::to_string(&dialect).unwrap();
+        assert_eq!(json, r#"{"annotations":["group","default"]}"#);
+        assert_eq!(serde_json::from_str::<Dialect>(&json).unwrap(), dialect);
+    }
+}
@@ -3,9 +3,13 @@
 //! Auth tokens for InfluxDB
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Authorization to create
-#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+///
+/// The `Debug` and `Display` implementations redact the token so that it is
+/// not leaked into logs; use [`Authorization::token_secret`] to access it.
+#[derive(Clone, PartialEq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Authorization {
     /// If inactive the token is inactive and requests using the token will be
@@ -56,6 +60,79 @@ impl Authorization {
             ..Default::default()
         }
     }
+
+    /// Returns the secret token of this authorization, if the server provided
+    /// one.
+    pub fn token_secret(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Returns the token with all but its first few characters masked
+    fn redacted_token(&self) -> Option<String> {
+        const VISIBLE_PREFIX: usize = 4;
+
+        self.token.as_ref().map(|token| {
+            // don't reveal a substantial part of short tokens
+            let visible = if token.chars().count() > 2 * VISIBLE_PREFIX {
+                VISIBLE_PREFIX
+            } else {
+                0
+            };
+            format!("{}****", token.chars().take(visible).collect::<String>())
+        })
+    }
+}
+
+impl fmt::Debug for Authorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Authorization")
+            .field("status", &self.status)
+            .field("description", &self.description)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .field("org_id", &self.org_id)
+            .field("permissions", &self.permissions)
+            .field("id", &self.id)
+            .field("token", &self.redacted_token())
+            .field("user_id", &self.user_id)
+            .field("user", &self.user)
+            .field("org", &self.org)
+            .field("links", &self.links)
+            .finish()
+    }
+}
+
+impl fmt::Display for Authorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "authorization {} for org {}",
+            self.id.as_deref().unwrap_or("<unknown>"),
+            self.org_id
+        )?;
+        if let Some(token) = self.redacted_token() {
+            write!(f, " with token {}", token)?;
+        }
+        Ok(())
+    }
+}
+
+/// List of Authorizations
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Authorizations {
+    /// Links
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<crate::models::Links>,
+    /// List of authorizations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authorizations: Vec<Authorization>,
+}
+
+impl Authorizations {
+    /// Returns instance of Authorizations
+    pub fn new() -> Self {
+        Self::default()
+    }
 }
 
 /// If inactive the token is inactive and requests using the token will be
@@ -86,3 +163,56 @@ impl AuthorizationAllOfLinks {
         Self::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_is_redacted() {
+        let auth: Authorization = serde_json::from_str(
+            r#"{"id":"some-id","orgID":"some-org","permissions":[],"token":"tok_supersecret"}"#,
+        )
+        .unwrap();
+
+        let debug = format!("{:?}", auth);
+        assert!(!debug.contains("supersecret"), "{}", debug);
+        assert!(debug.contains("tok_****"), "{}", debug);
+
+        let display = auth.to_string();
+        assert_eq!(
+            display,
+            "authorization some-id for org some-org with token tok_****"
+        );
+
+        assert_eq!(auth.token_secret(), Some("tok_supersecret"));
+
+        // serialization still includes the token
+        assert!(serde_json::to_string(&auth)
+            .unwrap()
+            .contains("tok_supersecret"));
+    }
+
+    #[test]
+    fn token_secret() {
+        let auth: Authorization =
+            serde_json::from_str(r#"{"orgID":"some-org","permissions":[],"token":"abcdef"}"#)
+                .unwrap();
+        assert_eq!(auth.token_secret(), Some("abcdef"));
+
+        assert_eq!(Authorization::default().token_secret(), None);
+    }
+
+    #[test]
+    fn short_token_is_fully_redacted() {
+        let auth: Authorization =
+            serde_json::from_str(r#"{"orgID":"some-org","permissions":[],"token":"abcdef"}"#)
+                .unwrap();
+
+        assert!(!format!("{:?}", auth).contains("abcd"));
+        assert_eq!(
+            auth.to_string(),
+            "authorization <unknown> for org some-org with token ****"
+        );
+    }
+}
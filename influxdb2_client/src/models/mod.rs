@@ -5,7 +5,7 @@
 pub mod ast;
 
 pub mod user;
-pub use self::user::{User, UserLinks, Users, UsersLinks};
+pub use self::user::{PasswordResetBody, User, UserLinks, Users, UsersLinks};
 pub mod organization;
 pub use self::organization::{Organization, OrganizationLinks, Organizations};
 pub mod bucket;
@@ -19,15 +19,15 @@ pub use self::permission::Permission;
 pub mod label;
 pub use self::label::{Label, LabelCreateRequest, LabelResponse, LabelUpdate, LabelsResponse};
 pub mod authorization;
-pub use self::authorization::{Authorization, AuthorizationAllOfLinks};
+pub use self::authorization::{Authorization, AuthorizationAllOfLinks, Authorizations};
 pub mod resource;
-pub use self::resource::Resource;
+pub use self::resource::{Resource, Type as ResourceType};
 pub mod retention_rule;
 pub use self::retention_rule::RetentionRule;
 pub mod query;
 pub use self::query::{
-    AnalyzeQueryResponse, AnalyzeQueryResponseErrors, AstResponse, FluxSuggestion, FluxSuggestions,
-    LanguageRequest, Query,
+    AnalyzeQueryResponse, AnalyzeQueryResponseErrors, AstResponse, FluxRecord, FluxSuggestion,
+    FluxSuggestions, LanguageRequest, Query,
 };
 pub mod file;
 pub use self::file::File;
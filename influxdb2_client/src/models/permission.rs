@@ -16,6 +16,37 @@ impl Permission {
     pub fn new(action: Action, resource: crate::models::Resource) -> Self {
         Self { action, resource }
     }
+
+    /// Return a Permission to read all resources of `resource_type`. Use
+    /// [`in_org`](Self::in_org) and [`with_id`](Self::with_id) to narrow it.
+    ///
+    /// ```
+    /// use influxdb2_client::models::{Permission, ResourceType};
+    ///
+    /// let permission = Permission::read(ResourceType::Buckets).in_org("0000111100001111");
+    /// ```
+    pub fn read(resource_type: crate::models::ResourceType) -> Self {
+        Self::new(Action::Read, crate::models::Resource::new(resource_type))
+    }
+
+    /// Return a Permission to write all resources of `resource_type`. Use
+    /// [`in_org`](Self::in_org) and [`with_id`](Self::with_id) to narrow it.
+    pub fn write(resource_type: crate::models::ResourceType) -> Self {
+        Self::new(Action::Write, crate::models::Resource::new(resource_type))
+    }
+
+    /// Restrict the permission to resources owned by the organization with ID
+    /// `org_id`.
+    pub fn in_org(mut self, org_id: impl Into<String>) -> Self {
+        self.resource.org_id = Some(org_id.into());
+        self
+    }
+
+    /// Restrict the permission to the single resource with ID `id`.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.resource.id = Some(id.into());
+        self
+    }
 }
 
 /// Allowed Permission Action
@@ -27,3 +58,41 @@ pub enum Action {
     /// Write access
     Write,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ResourceType;
+
+    #[test]
+    fn read_all_buckets_in_org() {
+        let permission = Permission::read(ResourceType::Buckets).in_org("0000111100001111");
+
+        assert_eq!(
+            serde_json::to_string(&permission).unwrap(),
+            r#"{"action":"read","resource":{"type":"buckets","orgID":"0000111100001111"}}"#
+        );
+    }
+
+    #[test]
+    fn write_single_bucket() {
+        let permission = Permission::write(ResourceType::Buckets)
+            .in_org("0000111100001111")
+            .with_id("1111000011110000");
+
+        assert_eq!(
+            serde_json::to_string(&permission).unwrap(),
+            r#"{"action":"write","resource":{"type":"buckets","id":"1111000011110000","orgID":"0000111100001111"}}"#
+        );
+    }
+
+    #[test]
+    fn read_all_orgs() {
+        let permission = Permission::read(ResourceType::Orgs);
+
+        assert_eq!(
+            serde_json::to_string(&permission).unwrap(),
+            r#"{"action":"read","resource":{"type":"orgs"}}"#
+        );
+    }
+}
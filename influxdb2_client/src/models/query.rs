@@ -3,7 +3,7 @@
 use crate::models::ast::Package;
 use crate::models::File;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Query influx using the Flux language
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
@@ -143,3 +143,18 @@ impl LanguageRequest {
         Self { query }
     }
 }
+
+/// A single row of a Flux query result
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct FluxRecord {
+    /// The values of the row keyed by column name. Values are the raw strings
+    /// returned by the server, with any `#default` annotation applied.
+    pub values: BTreeMap<String, String>,
+}
+
+impl FluxRecord {
+    /// Return the value of `column`, if the result has such a column
+    pub fn get(&self, column: &str) -> Option<&str> {
+        self.values.get(column).map(String::as_str)
+    }
+}
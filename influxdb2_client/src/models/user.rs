@@ -88,3 +88,17 @@ impl UsersLinks {
         Self::default()
     }
 }
+
+/// Request body to update a user's password
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct PasswordResetBody {
+    /// New password
+    pub password: String,
+}
+
+impl PasswordResetBody {
+    /// Returns instance of PasswordResetBody
+    pub fn new(password: String) -> Self {
+        Self { password }
+    }
+}
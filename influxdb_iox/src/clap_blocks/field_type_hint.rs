@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use mutable_batch_lp::SchemaHint;
+use schema::InfluxFieldType;
+
+/// Parsable field type hint of the form `<database>/<measurement>/<field>=<type>`.
+///
+/// `<type>` is one of `float`, `integer`, `unsigned`, `string` or `boolean`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldTypeHint {
+    pub database: String,
+    pub measurement: String,
+    pub field: String,
+    pub field_type: InfluxFieldType,
+}
+
+impl std::str::FromStr for FieldTypeHint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid field type hint '{}', expected '<database>/<measurement>/<field>=<type>'",
+                s
+            )
+        };
+
+        let (path, field_type) = s.rsplit_once('=').ok_or_else(invalid)?;
+        let mut parts = path.splitn(3, '/');
+        let (database, measurement, field) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(d), Some(m), Some(f)) if !d.is_empty() && !m.is_empty() && !f.is_empty() => {
+                (d, m, f)
+            }
+            _ => return Err(invalid()),
+        };
+
+        let field_type = match field_type.to_lowercase().as_str() {
+            "float" => InfluxFieldType::Float,
+            "integer" => InfluxFieldType::Integer,
+            "unsigned" => InfluxFieldType::UInteger,
+            "string" => InfluxFieldType::String,
+            "boolean" => InfluxFieldType::Boolean,
+            _ => return Err(format!("Unknown field type '{}' in '{}'", field_type, s)),
+        };
+
+        Ok(Self {
+            database: database.to_string(),
+            measurement: measurement.to_string(),
+            field: field.to_string(),
+            field_type,
+        })
+    }
+}
+
+/// Groups the provided hints into a [`SchemaHint`] per database name.
+pub fn schema_hints_by_database(hints: &[FieldTypeHint]) -> HashMap<String, SchemaHint> {
+    let mut by_database: HashMap<String, SchemaHint> = HashMap::new();
+    for hint in hints {
+        by_database
+            .entry(hint.database.clone())
+            .or_default()
+            .pin_field(&hint.measurement, &hint.field, hint.field_type);
+    }
+    by_database
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+
+    #[test]
+    fn test_field_type_hint() {
+        let hint = FieldTypeHint::from_str("MyOrg_MyBucket/h2o/temp=Float").unwrap();
+        assert_eq!(
+            hint,
+            FieldTypeHint {
+                database: "MyOrg_MyBucket".to_string(),
+                measurement: "h2o".to_string(),
+                field: "temp".to_string(),
+                field_type: InfluxFieldType::Float,
+            }
+        );
+
+        assert!(FieldTypeHint::from_str("db/h2o=float").is_err());
+        assert!(FieldTypeHint::from_str("db/h2o/temp").is_err());
+        assert!(FieldTypeHint::from_str("db//temp=float").is_err());
+        assert!(FieldTypeHint::from_str("db/h2o/temp=decimal").is_err());
+    }
+
+    #[test]
+    fn test_schema_hints_by_database() {
+        let hints = [
+            FieldTypeHint::from_str("db1/h2o/temp=float").unwrap(),
+            FieldTypeHint::from_str("db1/cpu/usage=integer").unwrap(),
+            FieldTypeHint::from_str("db2/h2o/temp=string").unwrap(),
+        ];
+
+        let by_database = schema_hints_by_database(&hints);
+        assert_eq!(by_database.len(), 2);
+        assert_eq!(
+            by_database["db1"].field_type("h2o", "temp"),
+            Some(InfluxFieldType::Float)
+        );
+        assert_eq!(
+            by_database["db1"].field_type("cpu", "usage"),
+            Some(InfluxFieldType::Integer)
+        );
+        assert_eq!(by_database["db1"].field_type("cpu", "temp"), None);
+        assert_eq!(
+            by_database["db2"].field_type("h2o", "temp"),
+            Some(InfluxFieldType::String)
+        );
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! They can easily be re-used using `#[clap(flatten)]`.
 pub mod boolean_flag;
+pub mod field_type_hint;
 pub mod object_store;
 pub mod run_config;
 pub mod server_id;
@@ -3,7 +3,8 @@ use trogging::cli::LoggingConfig;
 
 use crate::{
     clap_blocks::{
-        object_store::ObjectStoreConfig, server_id::ServerIdConfig, socket_addr::SocketAddr,
+        boolean_flag::BooleanFlag, field_type_hint::FieldTypeHint, object_store::ObjectStoreConfig,
+        server_id::ServerIdConfig, socket_addr::SocketAddr,
     },
     influxdb_ioxd::serving_readiness::ServingReadinessState,
 };
@@ -62,6 +63,140 @@ pub struct RunConfig {
     )]
     pub max_http_request_size: usize,
 
+    /// Maximum size in bytes of the formatted response to an HTTP query.
+    ///
+    /// Queries whose response exceeds this are aborted with an error. If not
+    /// set, the responses to HTTP queries are unlimited.
+    #[clap(
+        long = "--max-http-response-size",
+        env = "INFLUXDB_IOX_MAX_HTTP_RESPONSE_SIZE"
+    )]
+    pub max_http_response_size: Option<usize>,
+
+    /// Add an `org` attribute to the `ingest_*` metrics.
+    ///
+    /// This allows slicing ingest by organization but increases the metric
+    /// cardinality, so it is disabled by default.
+    #[clap(
+        long = "--ingest-metrics-org-attribute",
+        env = "INFLUXDB_IOX_INGEST_METRICS_ORG_ATTRIBUTE",
+        default_value = "no"
+    )]
+    pub ingest_metrics_org_attribute: BooleanFlag,
+
+    /// Lowercase the org and bucket of write and delete requests before
+    /// mapping them to a database name.
+    ///
+    /// When enabled, `MyOrg`/`MyBucket` and `myorg`/`mybucket` refer to the
+    /// same database. Disabled by default to keep names case-sensitive.
+    #[clap(
+        long = "--normalize-org-bucket-case",
+        env = "INFLUXDB_IOX_NORMALIZE_ORG_BUCKET_CASE",
+        default_value = "no"
+    )]
+    pub normalize_org_bucket_case: BooleanFlag,
+
+    /// Collapse rows of a write request that are exact duplicates of an
+    /// earlier row in the same request before the write is applied.
+    ///
+    /// Rows are duplicates if they have the same measurement, tag set, field
+    /// values and timestamp. Disabled by default.
+    #[clap(
+        long = "--dedup-writes",
+        env = "INFLUXDB_IOX_DEDUP_WRITES",
+        default_value = "no"
+    )]
+    pub dedup_writes: BooleanFlag,
+
+    /// Treat string field values equal to this sentinel as NULL on write, as
+    /// if the field had been omitted from the line.
+    ///
+    /// Useful for clients that encode a missing value as, for example,
+    /// `"null"` or an empty string. May be given multiple times. By default no
+    /// values are treated as NULL.
+    #[clap(
+        long = "--null-string-sentinel",
+        env = "INFLUXDB_IOX_NULL_STRING_SENTINELS",
+        multiple_occurrences = true,
+        use_delimiter = true
+    )]
+    pub null_string_sentinels: Vec<String>,
+
+    /// Reject writes using measurement, tag key or field key names reserved
+    /// by InfluxDB.
+    ///
+    /// Names beginning with an underscore are reserved. Disabled by default.
+    /// Tag and field keys named `time` are always rejected, as they collide
+    /// with the timestamp column.
+    #[clap(
+        long = "--validate-write-names",
+        env = "INFLUXDB_IOX_VALIDATE_WRITE_NAMES",
+        default_value = "no"
+    )]
+    pub validate_write_names: BooleanFlag,
+
+    /// Reject writes that would give a measurement more than this many
+    /// distinct field keys.
+    ///
+    /// The projected count combines the fields already known for the
+    /// measurement with those in the write. The router holds no catalog and
+    /// so only counts the fields of the write itself. If not set,
+    /// measurements may have any number of fields.
+    #[clap(
+        long = "--max-fields-per-measurement",
+        env = "INFLUXDB_IOX_MAX_FIELDS_PER_MEASUREMENT"
+    )]
+    pub max_fields_per_measurement: Option<usize>,
+
+    /// Retry a write up to this many times, with a small increasing backoff,
+    /// if it fails with a transient error such as contention on a table's
+    /// schema while another write creates it.
+    ///
+    /// Writes are not retried by default.
+    #[clap(
+        long = "--write-retries",
+        env = "INFLUXDB_IOX_WRITE_RETRIES",
+        default_value = "0"
+    )]
+    pub write_retries: usize,
+
+    /// Reject write requests with query parameters other than those known to
+    /// the write API, such as a misspelled `precision`.
+    ///
+    /// Disabled by default, in which case unknown parameters are ignored.
+    #[clap(
+        long = "--strict-write-params",
+        env = "INFLUXDB_IOX_STRICT_WRITE_PARAMS",
+        default_value = "no"
+    )]
+    pub strict_write_params: BooleanFlag,
+
+    /// Pin the type of a field before it is first written, in the form
+    /// `<database>/<measurement>/<field>=<type>`.
+    ///
+    /// `<type>` is one of `float`, `integer`, `unsigned`, `string` or
+    /// `boolean`. Integer values written to a field pinned as `float` are
+    /// converted, and other values not matching the pinned type are
+    /// rejected. May be given multiple times.
+    #[clap(
+        long = "--field-type-hint",
+        env = "INFLUXDB_IOX_FIELD_TYPE_HINTS",
+        multiple_occurrences = true,
+        use_delimiter = true
+    )]
+    pub field_type_hints: Vec<FieldTypeHint>,
+
+    /// Report the server as unhealthy on `/health` while more than this many
+    /// persisted chunks are being loaded back into the read buffer from
+    /// object storage.
+    ///
+    /// If not set, the catalog does not affect the reported health.
+    #[clap(
+        long = "--health-max-loading-chunks",
+        env = "INFLUXDB_IOX_HEALTH_MAX_LOADING_CHUNKS"
+    )]
+    pub health_max_loading_chunks: Option<usize>,
+
     /// object store config
     #[clap(flatten)]
     pub(crate) object_store_config: ObjectStoreConfig,
@@ -1,24 +1,35 @@
-use std::sync::Arc;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    future::Future,
+    sync::Arc,
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use chrono::Utc;
 use data_types::{
     names::{org_and_bucket_to_database, OrgBucketMappingError},
     non_empty::NonEmptyString,
+    precision::Precision,
     DatabaseName,
 };
 use dml::{DmlDelete, DmlMeta, DmlOperation, DmlWrite};
-use hyper::{Body, Method, Request, Response, StatusCode};
+use hyper::{header::CONTENT_TYPE, Body, Method, Request, Response, StatusCode};
+use mutable_batch::MutableBatch;
+use mutable_batch_lp::{FieldCoercion, SchemaHint};
 use observability_deps::tracing::debug;
 use predicate::delete_predicate::{parse_delete_predicate, parse_http_delete_request};
-use serde::Deserialize;
-use snafu::{OptionExt, ResultExt, Snafu};
+use schema::{InfluxColumnType, Schema};
+use serde::{Deserialize, Deserializer, Serialize};
+use snafu::{ensure, OptionExt, ResultExt, Snafu};
 
 use crate::influxdb_ioxd::{http::utils::parse_body, server_type::ServerType};
 
 use super::{
     error::{HttpApiError, HttpApiErrorExt, HttpApiErrorSource},
     metrics::LineProtocolMetrics,
+    ndjson::{is_ndjson_content_type, line_protocol_error, ndjson_to_line_protocol, NdjsonError},
 };
 
 #[allow(clippy::large_enum_variant)]
@@ -74,15 +85,49 @@ pub enum HttpDmlError {
         source: serde_urlencoded::de::Error,
     },
 
+    #[snafu(display("Unknown query parameters: {}", names.join(", ")))]
+    UnknownQueryParameters { names: Vec<String> },
+
     #[snafu(display("Error reading request body as utf8: {}", source))]
     ReadingBodyAsUtf8 { source: std::str::Utf8Error },
 
     #[snafu(display("Error parsing line protocol: {}", source))]
     ParsingLineProtocol { source: mutable_batch_lp::Error },
 
+    #[snafu(display("Error parsing NDJSON: {}", source))]
+    ParsingNdjson { source: NdjsonError },
+
+    #[snafu(display("Invalid {} '{}': {}", kind, name, reason))]
+    InvalidName {
+        kind: &'static str,
+        name: String,
+        reason: &'static str,
+    },
+
+    #[snafu(display(
+        "Measurement '{}' would have {} fields, exceeding the limit of {}",
+        measurement,
+        field_count,
+        max_fields
+    ))]
+    TooManyFields {
+        measurement: String,
+        field_count: usize,
+        max_fields: usize,
+    },
+
+    #[snafu(display("Explaining writes is not supported by this server"))]
+    ExplainUnsupported {},
+
     #[snafu(display("Database {} not found", db_name))]
     NotFoundDatabase { db_name: String },
 
+    #[snafu(display("Error deduplicating rows of table {}: {}", table_name, source))]
+    DedupRows {
+        table_name: String,
+        source: mutable_batch::Error,
+    },
+
     #[snafu(display("Cannot parse body: {}", source))]
     ParseBody {
         source: crate::influxdb_ioxd::http::utils::ParseBodyError,
@@ -111,9 +156,21 @@ impl HttpApiErrorSource for HttpDmlError {
             e @ Self::DeletingPointsUser { .. } => e.invalid(),
             e @ Self::ExpectedQueryString { .. } => e.invalid(),
             e @ Self::InvalidQueryString { .. } => e.invalid(),
+            e @ Self::UnknownQueryParameters { .. } => e.invalid(),
             e @ Self::ReadingBodyAsUtf8 { .. } => e.invalid(),
-            e @ Self::ParsingLineProtocol { .. } => e.invalid(),
+            Self::ParsingLineProtocol { source } => match source.position() {
+                Some((line, offset)) => self.invalid().with_line_position(line, offset),
+                None => self.invalid(),
+            },
+            Self::ParsingNdjson { source } => match source.position() {
+                Some((line, offset)) => self.invalid().with_line_position(line, offset),
+                None => self.invalid(),
+            },
+            e @ Self::InvalidName { .. } => e.invalid(),
+            e @ Self::TooManyFields { .. } => e.invalid(),
+            e @ Self::ExplainUnsupported { .. } => e.invalid(),
             e @ Self::NotFoundDatabase { .. } => e.not_found(),
+            e @ Self::DedupRows { .. } => e.internal_error(),
             Self::ParseBody { source } => source.to_http_api_error(),
             e @ Self::ParsingDelete { .. } => e.invalid(),
             e @ Self::BuildingDeletePredicate { .. } => e.invalid(),
@@ -138,6 +195,13 @@ pub enum InnerDmlError {
         db_name: String,
         source: Box<dyn std::error::Error + Send + Sync>,
     },
+
+    /// An error that may not occur if the request is retried.
+    #[snafu(display("Transient error while processing DML request: {}", source))]
+    TransientError {
+        db_name: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
 }
 
 impl From<InnerDmlError> for HttpDmlError {
@@ -151,7 +215,8 @@ impl From<InnerDmlError> for HttpDmlError {
                 debug!(e=%source, %db_name, "error writing lines");
                 Self::WritingPointsUser { db_name, source }
             }
-            InnerDmlError::InternalError { db_name, source } => {
+            InnerDmlError::InternalError { db_name, source }
+            | InnerDmlError::TransientError { db_name, source } => {
                 debug!(e=%source, %db_name, "error writing lines");
                 Self::WritingPointsInternal { db_name, source }
             }
@@ -191,36 +256,111 @@ pub trait HttpDrivenDml: ServerType {
 
         let query = req.uri().query().context(ExpectedQueryStringSnafu)?;
 
-        let write_info: WriteInfo =
+        let mut write_info: WriteInfo =
             serde_urlencoded::from_str(query).context(InvalidQueryStringSnafu {
                 query_string: String::from(query),
             })?;
+        if self.strict_write_params() {
+            WriteInfo::validate_params(query)?;
+        }
+        if self.normalize_org_bucket_case() {
+            write_info = write_info.normalize_case();
+        }
+        ensure!(
+            !write_info.explain || self.explain_writes(),
+            ExplainUnsupportedSnafu
+        );
 
         let db_name = org_and_bucket_to_database(&write_info.org, &write_info.bucket)
             .context(BucketMappingSnafu)?;
 
+        let is_ndjson = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(is_ndjson_content_type)
+            .unwrap_or(false);
+
         let body = parse_body(req, max_request_size)
             .await
             .context(ParseBodySnafu)?;
 
         let body = std::str::from_utf8(&body).context(ReadingBodyAsUtf8Snafu)?;
 
+        // Line protocol is assumed unless the body is explicitly NDJSON
+        let lp = match is_ndjson {
+            true => Cow::Owned(ndjson_to_line_protocol(body).context(ParsingNdjsonSnafu)?),
+            false => Cow::Borrowed(body),
+        };
+
         // The time, in nanoseconds since the epoch, to assign to any points that don't
         // contain a timestamp
         let default_time = Utc::now().timestamp_nanos();
 
-        let (tables, stats) = match mutable_batch_lp::lines_to_batches_stats(body, default_time) {
-            Ok(x) => x,
-            Err(mutable_batch_lp::Error::EmptyPayload) => {
-                debug!("nothing to write");
-                return Ok(RequestOrResponse::Response(
-                    Response::builder()
-                        .status(StatusCode::NO_CONTENT)
-                        .body(Body::empty())
-                        .unwrap(),
-                ));
+        let schema_hint = self.schema_hint(&db_name);
+        let (mut tables, stats, coercions) =
+            match mutable_batch_lp::lines_to_batches_stats_with_hint(
+                &lp,
+                default_time,
+                write_info.precision,
+                schema_hint,
+            ) {
+                Ok(x) => x,
+                Err(mutable_batch_lp::Error::EmptyPayload) => {
+                    debug!("nothing to write");
+                    return Ok(RequestOrResponse::Response(
+                        Response::builder()
+                            .status(StatusCode::NO_CONTENT)
+                            .body(Body::empty())
+                            .unwrap(),
+                    ));
+                }
+                Err(source) if is_ndjson => {
+                    return Err(HttpDmlError::ParsingNdjson {
+                        source: line_protocol_error(body, source),
+                    })
+                }
+                Err(source) => return Err(HttpDmlError::ParsingLineProtocol { source }),
+            };
+
+        if self.validate_write_names() {
+            validate_table_names(tables.iter())?;
+        }
+
+        if let Some(max_fields) = self.max_fields_per_measurement() {
+            self.validate_field_counts(&db_name, tables.iter(), max_fields)?;
+        }
+
+        if write_info.explain {
+            let explanation = self.explain_write(&db_name, tables.iter(), coercions);
+            let body = serde_json::to_string(&explanation).expect("explanation serializes");
+            return Ok(RequestOrResponse::Response(
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            ));
+        }
+
+        let null_string_sentinels = self.null_string_sentinels();
+        if !null_string_sentinels.is_empty() {
+            for batch in tables.values_mut() {
+                batch.nullify_string_sentinels(null_string_sentinels);
             }
-            Err(source) => return Err(HttpDmlError::ParsingLineProtocol { source }),
+        }
+
+        let tables = match self.dedup_writes() {
+            true => tables
+                .into_iter()
+                .map(|(table_name, batch)| {
+                    let batch = batch.dedup_rows().context(DedupRowsSnafu {
+                        table_name: &table_name,
+                    })?;
+                    Ok((table_name, batch))
+                })
+                .collect::<Result<_, HttpDmlError>>()?,
+            false => tables,
         };
 
         debug!(
@@ -233,17 +373,35 @@ pub trait HttpDrivenDml: ServerType {
             "inserting lines into database",
         );
 
-        let write = DmlWrite::new(tables, DmlMeta::unsequenced(span_ctx));
+        let mut timestamps: Vec<_> = tables
+            .values()
+            .filter_map(|batch| batch.timestamps())
+            .flatten()
+            .copied()
+            .collect();
+
+        let op = DmlOperation::Write(DmlWrite::new(tables, DmlMeta::unsequenced(span_ctx)));
+        let result = retry_transient(self.write_retries(), Arc::new(op), |op| {
+            self.write(&db_name, op)
+        })
+        .await;
 
-        match self.write(&db_name, DmlOperation::Write(write)).await {
+        match result {
             Ok(_) => {
                 lp_metrics.record_write(
                     &db_name,
+                    &write_info.org,
                     stats.num_lines,
                     stats.num_fields,
                     body.len(),
                     true,
                 );
+                lp_metrics.record_receive_lag(
+                    &db_name,
+                    &write_info.org,
+                    default_time,
+                    &mut timestamps,
+                );
                 Ok(RequestOrResponse::Response(
                     Response::builder()
                         .status(StatusCode::NO_CONTENT)
@@ -255,9 +413,14 @@ pub trait HttpDrivenDml: ServerType {
                 // Purposefully do not record ingest metrics
                 Err(e.into())
             }
-            Err(e @ (InnerDmlError::UserError { .. } | InnerDmlError::InternalError { .. })) => {
+            Err(
+                e @ (InnerDmlError::UserError { .. }
+                | InnerDmlError::InternalError { .. }
+                | InnerDmlError::TransientError { .. }),
+            ) => {
                 lp_metrics.record_write(
                     &db_name,
+                    &write_info.org,
                     stats.num_lines,
                     stats.num_fields,
                     body.len(),
@@ -287,10 +450,13 @@ pub trait HttpDrivenDml: ServerType {
         // Extract the DB name from the request
         // db_name = orrID_bucketID
         let query = req.uri().query().context(ExpectedQueryStringSnafu)?;
-        let delete_info: WriteInfo =
+        let mut delete_info: WriteInfo =
             serde_urlencoded::from_str(query).context(InvalidQueryStringSnafu {
                 query_string: String::from(query),
             })?;
+        if self.normalize_org_bucket_case() {
+            delete_info = delete_info.normalize_case();
+        }
         let db_name = org_and_bucket_to_database(&delete_info.org, &delete_info.bucket)
             .context(BucketMappingSnafu)?;
 
@@ -320,7 +486,10 @@ pub trait HttpDrivenDml: ServerType {
             DmlMeta::unsequenced(span_ctx),
         );
 
-        match self.write(&db_name, DmlOperation::Delete(delete)).await {
+        match self
+            .write(&db_name, Arc::new(DmlOperation::Delete(delete)))
+            .await
+        {
             Ok(_) => Ok(RequestOrResponse::Response(
                 Response::builder()
                     .status(StatusCode::NO_CONTENT)
@@ -355,25 +524,340 @@ pub trait HttpDrivenDml: ServerType {
     /// Line protocol metrics.
     fn lp_metrics(&self) -> Arc<LineProtocolMetrics>;
 
+    /// Lowercase org and bucket names before mapping them to a database name.
+    fn normalize_org_bucket_case(&self) -> bool;
+
+    /// Collapse exact duplicate rows within a write before applying it.
+    fn dedup_writes(&self) -> bool;
+
+    /// String field values that are written as NULL.
+    fn null_string_sentinels(&self) -> &[String];
+
+    /// Reject writes with measurement, tag key or field key names that are
+    /// reserved by InfluxDB.
+    fn validate_write_names(&self) -> bool;
+
+    /// Reject writes that would give a measurement more than this many
+    /// distinct field keys, if set.
+    fn max_fields_per_measurement(&self) -> Option<usize>;
+
+    /// Field keys already known for `table_name` in the given database.
+    ///
+    /// Servers without a catalog, such as the router, know no fields, so
+    /// [`validate_field_counts`](Self::validate_field_counts) only counts the
+    /// fields of the write itself.
+    fn known_field_names(&self, db_name: &DatabaseName<'_>, table_name: &str) -> Vec<String>;
+
+    /// Ensure that no measurement written to by `tables` would end up with
+    /// more than `max_fields` distinct field keys once the write is applied.
+    fn validate_field_counts<'a>(
+        &self,
+        db_name: &DatabaseName<'_>,
+        tables: impl Iterator<Item = (&'a String, &'a MutableBatch)>,
+        max_fields: usize,
+    ) -> Result<(), HttpDmlError> {
+        for (measurement, batch) in tables {
+            let mut fields: BTreeSet<String> = batch
+                .columns()
+                .filter(|(_, column)| matches!(column.influx_type(), InfluxColumnType::Field(_)))
+                .map(|(name, _)| name.clone())
+                .collect();
+            fields.extend(self.known_field_names(db_name, measurement));
+            ensure!(
+                fields.len() <= max_fields,
+                TooManyFieldsSnafu {
+                    measurement,
+                    field_count: fields.len(),
+                    max_fields,
+                }
+            );
+        }
+        Ok(())
+    }
+
+    /// Number of times a write failing with a transient error is retried.
+    fn write_retries(&self) -> usize;
+
+    /// Reject write requests with query parameters not known to [`WriteInfo`].
+    fn strict_write_params(&self) -> bool;
+
+    /// Whether writes can be explained, i.e. [`table_schema`](Self::table_schema)
+    /// reports the schemas of existing tables. Writes with `explain=true` are
+    /// rejected otherwise.
+    fn explain_writes(&self) -> bool;
+
+    /// Schema of `table_name` in the given database, if the table exists.
+    fn table_schema(&self, db_name: &DatabaseName<'_>, table_name: &str) -> Option<Arc<Schema>>;
+
+    /// Describes what applying a write, converted to `tables` with the given
+    /// field `coercions`, would do to the schema of the given database.
+    fn explain_write<'a>(
+        &self,
+        db_name: &DatabaseName<'_>,
+        tables: impl Iterator<Item = (&'a String, &'a MutableBatch)>,
+        mut coercions: Vec<FieldCoercion>,
+    ) -> WriteExplanation {
+        let mut measurements = BTreeMap::new();
+        for (table_name, batch) in tables {
+            let existing = self.table_schema(db_name, table_name);
+
+            let mut explanation = MeasurementExplanation {
+                name: table_name.clone(),
+                lines: batch.rows(),
+                new_measurement: existing.is_none(),
+                ..Default::default()
+            };
+            for (column_name, column) in batch.columns() {
+                let written = column.influx_type();
+                let existing_type = existing.as_ref().and_then(|schema| {
+                    let idx = schema.find_index_of(column_name)?;
+                    schema.field(idx).0
+                });
+                match existing_type {
+                    None => explanation.new_columns.push(ColumnExplanation {
+                        name: column_name.clone(),
+                        column_type: column_type_name(&written),
+                    }),
+                    Some(existing) if existing != written => {
+                        explanation.conflicts.push(ColumnConflict {
+                            name: column_name.clone(),
+                            existing_type: column_type_name(&existing),
+                            written_type: column_type_name(&written),
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+            explanation.new_columns.sort_by(|a, b| a.name.cmp(&b.name));
+            explanation.conflicts.sort_by(|a, b| a.name.cmp(&b.name));
+
+            measurements.insert(table_name.clone(), explanation);
+        }
+
+        coercions.sort_by(|a, b| (&a.measurement, &a.field).cmp(&(&b.measurement, &b.field)));
+        for coercion in coercions {
+            if let Some(explanation) = measurements.get_mut(&coercion.measurement) {
+                explanation.coercions.push(ColumnCoercion {
+                    name: coercion.field,
+                    from_type: column_type_name(&InfluxColumnType::Field(coercion.from)),
+                    to_type: column_type_name(&InfluxColumnType::Field(coercion.to)),
+                });
+            }
+        }
+
+        WriteExplanation {
+            measurements: measurements.into_values().collect(),
+        }
+    }
+
+    /// Field types pinned for the measurements of the given database, if any.
+    fn schema_hint(&self, db_name: &DatabaseName<'_>) -> Option<&SchemaHint>;
+
     /// Perform DML operation.
+    ///
+    /// The operation is shared so that it can be retried without copying it.
     async fn write(
         &self,
         db_name: &DatabaseName<'_>,
-        op: DmlOperation,
+        op: Arc<DmlOperation>,
     ) -> Result<(), InnerDmlError>;
 }
 
+/// Checks the measurement, tag key and field key names of each table against
+/// InfluxDB's naming rules, returning an error for the first invalid name.
+///
+/// Tag and field keys named `time` are always rejected, regardless of this
+/// check, when the line protocol is converted.
+fn validate_table_names<'a>(
+    tables: impl Iterator<Item = (&'a String, &'a MutableBatch)>,
+) -> Result<(), HttpDmlError> {
+    for (measurement, batch) in tables {
+        validate_name("measurement", measurement)?;
+
+        for (column_name, column) in batch.columns() {
+            match column.influx_type() {
+                InfluxColumnType::Tag => validate_name("tag key", column_name)?,
+                InfluxColumnType::Field(_) => validate_name("field key", column_name)?,
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rules for measurement, tag key and field key names.
+fn validate_name(kind: &'static str, name: &str) -> Result<(), HttpDmlError> {
+    ensure!(
+        !name.starts_with('_'),
+        InvalidNameSnafu {
+            kind,
+            name,
+            reason: "names beginning with an underscore are reserved",
+        }
+    );
+    Ok(())
+}
+
+/// The summary returned for a write with `explain=true`, describing the
+/// effect the write would have without applying it.
+#[derive(Debug, Serialize)]
+pub struct WriteExplanation {
+    pub measurements: Vec<MeasurementExplanation>,
+}
+
+/// The effect of a write on a single measurement.
+#[derive(Debug, Default, Serialize)]
+pub struct MeasurementExplanation {
+    pub name: String,
+    /// The number of lines written to the measurement.
+    pub lines: usize,
+    /// Whether the measurement does not exist yet.
+    pub new_measurement: bool,
+    /// Columns that the write would create.
+    pub new_columns: Vec<ColumnExplanation>,
+    /// Field values that would be converted to the type pinned for the field.
+    pub coercions: Vec<ColumnCoercion>,
+    /// Columns that the write would give a different type than they have.
+    pub conflicts: Vec<ColumnConflict>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnExplanation {
+    pub name: String,
+    pub column_type: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnCoercion {
+    pub name: String,
+    pub from_type: String,
+    pub to_type: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnConflict {
+    pub name: String,
+    pub existing_type: String,
+    pub written_type: String,
+}
+
+/// The name of a column type as reported by [`WriteExplanation`], e.g.
+/// `tag` or `field::float`.
+fn column_type_name(column_type: &InfluxColumnType) -> String {
+    column_type
+        .to_string()
+        .trim_start_matches("iox::column_type::")
+        .to_string()
+}
+
+/// Backoff before the first retry of a write, doubled for each further retry
+/// up to [`WRITE_RETRY_MAX_BACKOFF`].
+const WRITE_RETRY_BACKOFF: Duration = Duration::from_millis(10);
+const WRITE_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Invokes `write` with `op` until it succeeds, fails with an error other
+/// than [`InnerDmlError::TransientError`], or has been retried `retries`
+/// times.
+///
+/// A reference to `op` is only kept while a retry may follow, so that the
+/// last attempt can take ownership of it without copying it.
+async fn retry_transient<T, F, Fut>(
+    retries: usize,
+    op: Arc<T>,
+    mut write: F,
+) -> Result<(), InnerDmlError>
+where
+    T: Send + Sync,
+    F: FnMut(Arc<T>) -> Fut + Send,
+    Fut: Future<Output = Result<(), InnerDmlError>> + Send,
+{
+    let mut backoff = WRITE_RETRY_BACKOFF;
+    let mut attempt = 0;
+    let mut op = Some(op);
+    loop {
+        let next_op = match attempt < retries {
+            true => op.clone(),
+            false => None,
+        };
+        match write(op.take().expect("operation for the attempt")).await {
+            Err(e @ InnerDmlError::TransientError { .. }) if attempt < retries => {
+                debug!(%e, attempt, ?backoff, "retrying write after transient error");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(WRITE_RETRY_MAX_BACKOFF);
+                attempt += 1;
+                op = next_op;
+            }
+            result => return result,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 /// Body of the request to the dml endpoints
 pub struct WriteInfo {
     pub org: String,
     pub bucket: String,
+    #[serde(default, deserialize_with = "deserialize_precision")]
+    pub precision: Precision,
+    /// Describe the effect of the write instead of applying it.
+    #[serde(default)]
+    pub explain: bool,
+}
+
+impl WriteInfo {
+    /// Names of the query parameters understood by the write endpoint, which
+    /// must list every field; `test_write_info_params` keeps them in sync.
+    const PARAMS: &'static [&'static str] = &["org", "bucket", "precision", "explain"];
+
+    /// Ensure that `query` only contains parameters known to the write
+    /// endpoint, listing any unknown ones in the error.
+    pub fn validate_params(query: &str) -> Result<(), HttpDmlError> {
+        let params: Vec<(String, String)> =
+            serde_urlencoded::from_str(query).context(InvalidQueryStringSnafu {
+                query_string: query,
+            })?;
+
+        let names: BTreeSet<_> = params
+            .into_iter()
+            .map(|(name, _)| name)
+            .filter(|name| !Self::PARAMS.contains(&name.as_str()))
+            .collect();
+        ensure!(
+            names.is_empty(),
+            UnknownQueryParametersSnafu {
+                names: names.into_iter().collect::<Vec<_>>(),
+            }
+        );
+        Ok(())
+    }
+
+    /// Lowercase org and bucket so that names differing only in case map to
+    /// the same database.
+    pub fn normalize_case(self) -> Self {
+        Self {
+            org: self.org.to_lowercase(),
+            bucket: self.bucket.to_lowercase(),
+            precision: self.precision,
+            explain: self.explain,
+        }
+    }
+}
+
+fn deserialize_precision<'de, D>(deserializer: D) -> Result<Precision, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
 }
 
 #[cfg(test)]
 pub mod test_utils {
     use dml::DmlWrite;
-    use http::{header::CONTENT_ENCODING, StatusCode};
+    use http::{
+        header::{CONTENT_ENCODING, CONTENT_TYPE},
+        StatusCode,
+    };
     use metric::{Attributes, DurationHistogram, Metric, U64Counter, U64Histogram};
     use mutable_batch_lp::lines_to_batches;
     use reqwest::Client;
@@ -415,6 +899,67 @@ pub mod test_utils {
         DmlWrite::new(lines_to_batches(lp_data, 0).unwrap(), Default::default())
     }
 
+    /// Assert that writes with a `precision` parameter have their timestamps
+    /// converted to nanoseconds, and that an unknown precision is rejected.
+    ///
+    /// The database `bucket_name="MyBucket", org_name="MyOrg"` must exist for this test to work.
+    ///
+    /// Returns write that was generated. The caller MUST check that the write is actually present.
+    pub async fn assert_precision_write<T>(test_server: &TestServer<T>) -> DmlWrite
+    where
+        T: ServerType,
+    {
+        let client = Client::new();
+
+        let lp_data = "h2o_temperature,location=santa_monica,state=CA surface_degrees=65.2,bottom_degrees=50.4 1617286224";
+
+        let bucket_name = "MyBucket";
+        let org_name = "MyOrg";
+        let response = client
+            .post(&format!(
+                "{}/api/v2/write?bucket={}&org={}&precision=h",
+                test_server.url(),
+                bucket_name,
+                org_name
+            ))
+            .body(lp_data)
+            .send()
+            .await;
+
+        check_response(
+            "precision_write",
+            response,
+            StatusCode::BAD_REQUEST,
+            Some("invalid precision 'h'"),
+        )
+        .await;
+
+        let response = client
+            .post(&format!(
+                "{}/api/v2/write?bucket={}&org={}&precision=s",
+                test_server.url(),
+                bucket_name,
+                org_name
+            ))
+            .body(lp_data)
+            .send()
+            .await;
+
+        check_response(
+            "precision_write",
+            response,
+            StatusCode::NO_CONTENT,
+            Some(""),
+        )
+        .await;
+
+        let lp_data_nanos = "h2o_temperature,location=santa_monica,state=CA surface_degrees=65.2,bottom_degrees=50.4 1617286224000000000";
+        DmlWrite::new(
+            lines_to_batches(lp_data_nanos, 0).unwrap(),
+            Default::default(),
+        )
+    }
+
     /// Assert that GZIP-compressed writes work.
     ///
     /// The database `bucket_name="MyBucket", org_name="MyOrg"` must exist for this test to work.
@@ -447,6 +992,171 @@ pub mod test_utils {
         DmlWrite::new(lines_to_batches(lp_data, 0).unwrap(), Default::default())
     }
 
+    /// Assert that a truncated GZIP-compressed write is rejected rather than
+    /// treated as an empty payload.
+    ///
+    /// The database `bucket_name="MyBucket", org_name="MyOrg"` must exist for this test to work.
+    pub async fn assert_truncated_gzip_write<T>(test_server: &TestServer<T>)
+    where
+        T: ServerType,
+    {
+        let client = Client::new();
+        let mut body = gzip_str("cpu bar=1 10");
+        body.truncate(5);
+
+        let bucket_name = "MyBucket";
+        let org_name = "MyOrg";
+        let response = client
+            .post(&format!(
+                "{}/api/v2/write?bucket={}&org={}",
+                test_server.url(),
+                bucket_name,
+                org_name
+            ))
+            .header(CONTENT_ENCODING, "gzip")
+            .body(body)
+            .send()
+            .await;
+
+        check_response(
+            "truncated_gzip_write",
+            response,
+            StatusCode::BAD_REQUEST,
+            Some("Gzip body is empty or truncated"),
+        )
+        .await;
+    }
+
+    /// Assert that NDJSON writes work.
+    ///
+    /// The database `bucket_name="MyBucket", org_name="MyOrg"` must exist for this test to work.
+    ///
+    /// Returns the equivalent line protocol write. The caller MUST check that the write is actually present.
+    pub async fn assert_ndjson_write<T>(test_server: &TestServer<T>) -> DmlWrite
+    where
+        T: ServerType,
+    {
+        let client = Client::new();
+        let ndjson_data = r#"{"measurement": "h2o_temperature", "tags": {"location": "santa_monica", "state": "CA"}, "fields": {"surface_degrees": 65.2, "bottom_degrees": 50.4}, "timestamp": 1617286224000000000}"#;
+        let lp_data = "h2o_temperature,location=santa_monica,state=CA surface_degrees=65.2,bottom_degrees=50.4 1617286224000000000";
+
+        let bucket_name = "MyBucket";
+        let org_name = "MyOrg";
+        let response = client
+            .post(&format!(
+                "{}/api/v2/write?bucket={}&org={}",
+                test_server.url(),
+                bucket_name,
+                org_name
+            ))
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .body(ndjson_data)
+            .send()
+            .await;
+
+        check_response("ndjson_write", response, StatusCode::NO_CONTENT, Some("")).await;
+
+        DmlWrite::new(lines_to_batches(lp_data, 0).unwrap(), Default::default())
+    }
+
+    /// Assert that a malformed NDJSON record is rejected, identifying the
+    /// offending line.
+    ///
+    /// The database `bucket_name="MyBucket", org_name="MyOrg"` must exist for this test to work.
+    pub async fn assert_malformed_ndjson_write<T>(test_server: &TestServer<T>)
+    where
+        T: ServerType,
+    {
+        let client = Client::new();
+        let ndjson_data =
+            "{\"measurement\": \"cpu\", \"fields\": {\"bar\": 1}, \"timestamp\": 10}\n\
+                           {\"measurement\": \"cpu\", \"fields\": {\"bar\": null}}";
+
+        let bucket_name = "MyBucket";
+        let org_name = "MyOrg";
+        let response = client
+            .post(&format!(
+                "{}/api/v2/write?bucket={}&org={}",
+                test_server.url(),
+                bucket_name,
+                org_name
+            ))
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .body(ndjson_data)
+            .send()
+            .await;
+
+        check_response(
+            "malformed_ndjson_write",
+            response,
+            StatusCode::BAD_REQUEST,
+            Some("error converting NDJSON line 2: unsupported value for field 'bar'"),
+        )
+        .await;
+
+        // Errors converting the resulting line protocol are positioned within
+        // the NDJSON payload
+        let ndjson_data = "{\"measurement\": \"cpu\", \"fields\": {\"bar\": 1}}\n\n\
+                           {\"measurement\": \"cpu\", \"fields\": {\"bar\": \"a\"}}";
+        let response = client
+            .post(&format!(
+                "{}/api/v2/write?bucket={}&org={}",
+                test_server.url(),
+                bucket_name,
+                org_name
+            ))
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .body(ndjson_data)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["line"], 3);
+        assert_eq!(body["offset"], 46);
+        assert!(body["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Error parsing NDJSON: error writing line 3"));
+    }
+
+    /// Assert that a malformed line protocol write is rejected with the
+    /// position of the offending line in the error body.
+    ///
+    /// The database `bucket_name="MyBucket", org_name="MyOrg"` must exist for this test to work.
+    pub async fn assert_malformed_lp_write<T>(test_server: &TestServer<T>)
+    where
+        T: ServerType,
+    {
+        let client = Client::new();
+        let lp_data = "cpu bar=1 10\n\ncpu bar 20";
+
+        let bucket_name = "MyBucket";
+        let org_name = "MyOrg";
+        let response = client
+            .post(&format!(
+                "{}/api/v2/write?bucket={}&org={}",
+                test_server.url(),
+                bucket_name,
+                org_name
+            ))
+            .body(lp_data)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["code"], "invalid");
+        assert_eq!(body["line"], 3);
+        assert_eq!(body["offset"], 14);
+        assert!(body["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Error parsing line protocol: error parsing line 3"));
+    }
+
     /// Assert that write to an invalid database behave as expected.
     pub async fn assert_write_to_invalid_database<T>(test_server: TestServer<T>)
     where
@@ -614,6 +1324,19 @@ pub mod test_utils {
         assert_eq!(observation.buckets[0].count, 1);
         assert_eq!(observation.buckets[1].count, 0);
 
+        // The point is timestamped decades before it was received
+        let observation = metric_registry
+            .get_instrument::<Metric<DurationHistogram>>("ingest_receive_lag")
+            .unwrap()
+            .get_observer(&Attributes::from(&[
+                ("db_name", "MyOrg_MyBucket"),
+                ("stat", "max"),
+            ]))
+            .unwrap()
+            .fetch();
+        assert_eq!(observation.sample_count(), 1);
+        assert_eq!(observation.buckets.last().unwrap().count, 1);
+
         // Write to a non-existent database
         client
             .post(&format!(
@@ -748,3 +1471,262 @@ pub mod test_utils {
         encoder.finish().expect("successfully encoding gzip data")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use metric::Registry;
+    use trace::TraceCollector;
+
+    use super::*;
+    use crate::influxdb_ioxd::{rpc::RpcBuilderInput, server_type::RpcError};
+
+    /// A write backend that fails with a transient error a fixed number of
+    /// times before succeeding.
+    #[derive(Debug)]
+    struct FlakyBackend {
+        failures: usize,
+        calls: AtomicUsize,
+    }
+
+    impl FlakyBackend {
+        fn new(failures: usize) -> Self {
+            Self {
+                failures,
+                calls: AtomicUsize::new(0),
+            }
+        }
+
+        async fn write(&self) -> Result<(), InnerDmlError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            match call < self.failures {
+                true => Err(InnerDmlError::TransientError {
+                    db_name: "db".to_string(),
+                    source: "schema lock held".into(),
+                }),
+                false => Ok(()),
+            }
+        }
+    }
+
+    /// A server type that only routes writes, to a [`FlakyBackend`].
+    #[derive(Debug)]
+    struct FlakyServerType {
+        backend: FlakyBackend,
+        write_retries: usize,
+        metric_registry: Arc<Registry>,
+        lp_metrics: Arc<LineProtocolMetrics>,
+    }
+
+    impl FlakyServerType {
+        fn new(failures: usize, write_retries: usize) -> Self {
+            let metric_registry = Arc::new(Registry::new());
+            let lp_metrics = Arc::new(LineProtocolMetrics::new(&metric_registry, false));
+            Self {
+                backend: FlakyBackend::new(failures),
+                write_retries,
+                metric_registry,
+                lp_metrics,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ServerType for FlakyServerType {
+        type RouteError = HttpDmlError;
+
+        fn metric_registry(&self) -> Arc<Registry> {
+            Arc::clone(&self.metric_registry)
+        }
+
+        fn trace_collector(&self) -> Option<Arc<dyn TraceCollector>> {
+            None
+        }
+
+        async fn route_http_request(
+            &self,
+            _req: Request<Body>,
+        ) -> Result<Response<Body>, Self::RouteError> {
+            unimplemented!()
+        }
+
+        async fn server_grpc(
+            self: Arc<Self>,
+            _builder_input: RpcBuilderInput,
+        ) -> Result<(), RpcError> {
+            unimplemented!()
+        }
+
+        async fn join(self: Arc<Self>) {
+            unimplemented!()
+        }
+
+        fn shutdown(&self) {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait]
+    impl HttpDrivenDml for FlakyServerType {
+        fn max_request_size(&self) -> usize {
+            1024
+        }
+
+        fn lp_metrics(&self) -> Arc<LineProtocolMetrics> {
+            Arc::clone(&self.lp_metrics)
+        }
+
+        fn normalize_org_bucket_case(&self) -> bool {
+            false
+        }
+
+        fn dedup_writes(&self) -> bool {
+            false
+        }
+
+        fn null_string_sentinels(&self) -> &[String] {
+            &[]
+        }
+
+        fn validate_write_names(&self) -> bool {
+            false
+        }
+
+        fn max_fields_per_measurement(&self) -> Option<usize> {
+            None
+        }
+
+        fn known_field_names(&self, _db_name: &DatabaseName<'_>, _table_name: &str) -> Vec<String> {
+            vec![]
+        }
+
+        fn write_retries(&self) -> usize {
+            self.write_retries
+        }
+
+        fn strict_write_params(&self) -> bool {
+            false
+        }
+
+        fn explain_writes(&self) -> bool {
+            false
+        }
+
+        fn table_schema(
+            &self,
+            _db_name: &DatabaseName<'_>,
+            _table_name: &str,
+        ) -> Option<Arc<Schema>> {
+            None
+        }
+
+        fn schema_hint(&self, _db_name: &DatabaseName<'_>) -> Option<&SchemaHint> {
+            None
+        }
+
+        async fn write(
+            &self,
+            _db_name: &DatabaseName<'_>,
+            _op: Arc<DmlOperation>,
+        ) -> Result<(), InnerDmlError> {
+            self.backend.write().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_route_write_retries_transient_errors() {
+        async fn write(server_type: &FlakyServerType) -> Result<RequestOrResponse, HttpDmlError> {
+            let request = Request::builder()
+                .method(Method::POST)
+                .uri("http://localhost/api/v2/write?org=MyOrg&bucket=MyBucket")
+                .body(Body::from("cpu,host=a usage=1 10"))
+                .unwrap();
+            server_type.route_write_http_request(request).await
+        }
+
+        // retries are off by default, so the transient error is returned
+        let server_type = FlakyServerType::new(1, 0);
+        let err = write(&server_type).await.unwrap_err();
+        assert!(matches!(err, HttpDmlError::WritingPointsInternal { .. }));
+        assert_eq!(server_type.backend.calls.load(Ordering::SeqCst), 1);
+
+        // the write fails once, then succeeds when retried
+        let server_type = FlakyServerType::new(1, 2);
+        match write(&server_type).await.unwrap() {
+            RequestOrResponse::Response(response) => {
+                assert_eq!(response.status(), StatusCode::NO_CONTENT)
+            }
+            RequestOrResponse::Request(_) => panic!("write was not routed"),
+        }
+        assert_eq!(server_type.backend.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient() {
+        // fails once, then succeeds on the retry
+        let backend = FlakyBackend::new(1);
+        retry_transient(3, Arc::new(()), |_| backend.write())
+            .await
+            .unwrap();
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 2);
+
+        // retries are off by default
+        let backend = FlakyBackend::new(1);
+        let err = retry_transient(0, Arc::new(()), |_| backend.write())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, InnerDmlError::TransientError { .. }));
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 1);
+
+        // retries are bounded
+        let backend = FlakyBackend::new(5);
+        let err = retry_transient(2, Arc::new(()), |_| backend.write())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, InnerDmlError::TransientError { .. }));
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_ignores_other_errors() {
+        let calls = AtomicUsize::new(0);
+        let err = retry_transient(3, Arc::new(()), |_| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(InnerDmlError::UserError {
+                db_name: "db".to_string(),
+                source: "bad write".into(),
+            })
+        })
+        .await
+        .unwrap_err();
+
+        assert!(matches!(err, InnerDmlError::UserError { .. }));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_write_info_params() {
+        let params = [
+            ("org", "MyOrg"),
+            ("bucket", "MyBucket"),
+            ("precision", "s"),
+            ("explain", "true"),
+        ];
+        assert_eq!(WriteInfo::PARAMS, params.map(|(name, _)| name));
+
+        // The destructuring fails to compile when a field is added to
+        // `WriteInfo`, which then needs adding to `WriteInfo::PARAMS` too
+        let query = params.map(|(name, value)| format!("{}={}", name, value));
+        let WriteInfo {
+            org,
+            bucket,
+            precision,
+            explain,
+        } = serde_urlencoded::from_str(&query.join("&")).unwrap();
+        assert_eq!(org, "MyOrg");
+        assert_eq!(bucket, "MyBucket");
+        assert_eq!(precision, Precision::Seconds);
+        assert!(explain);
+    }
+}
@@ -19,6 +19,7 @@ pub enum HttpApiErrorCode {
     Unauthorized,
     MethodNotAllowed,
     RequestTooLarge,
+    ResponseTooLarge,
     UnsupportedMediaType,
 }
 
@@ -38,6 +39,7 @@ impl HttpApiErrorCode {
             Self::Unauthorized => "unauthorized",
             Self::MethodNotAllowed => "method not allowed",
             Self::RequestTooLarge => "request too large",
+            Self::ResponseTooLarge => "response too large",
             Self::UnsupportedMediaType => "unsupported media type",
         }
     }
@@ -57,6 +59,7 @@ impl HttpApiErrorCode {
             Self::Unauthorized => StatusCode::UNAUTHORIZED,
             Self::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
             Self::RequestTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::ResponseTooLarge => StatusCode::UNPROCESSABLE_ENTITY,
             Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
         }
     }
@@ -101,6 +104,9 @@ pub struct HttpApiError {
 
     /// Human-readable message.
     msg: String,
+
+    /// Line number and byte offset of the offending line in the request body.
+    line_position: Option<(usize, usize)>,
 }
 
 impl HttpApiError {
@@ -109,18 +115,29 @@ impl HttpApiError {
         Self {
             code: code.into(),
             msg: msg.into(),
+            line_position: None,
         }
     }
 
+    /// Attach the 1-based line number and byte offset of the line in the
+    /// request body that caused this error.
+    pub fn with_line_position(mut self, line: usize, offset: usize) -> Self {
+        self.line_position = Some((line, offset));
+        self
+    }
+
     /// Generate response body for this error.
     fn body(&self) -> Body {
-        let json = serde_json::json!({
+        let mut json = serde_json::json!({
             "code": self.code.as_text().to_string(),
             "message": self.msg.clone(),
-        })
-        .to_string();
+        });
+        if let Some((line, offset)) = self.line_position {
+            json["line"] = line.into();
+            json["offset"] = offset.into();
+        }
 
-        Body::from(json)
+        Body::from(json.to_string())
     }
 
     /// Generate response for this error.
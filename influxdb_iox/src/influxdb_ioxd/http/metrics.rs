@@ -1,5 +1,10 @@
+use std::time::Duration;
+
 use hashbrown::HashMap;
-use metric::{Attributes, Metric, U64Counter, U64Histogram, U64HistogramOptions};
+use metric::{
+    Attributes, DurationHistogram, DurationHistogramOptions, Metric, U64Counter, U64Histogram,
+    U64HistogramOptions, DURATION_MAX,
+};
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 
 /// Line protocol ingest metrics
@@ -17,8 +22,17 @@ pub struct LineProtocolMetrics {
     /// Distribution of LP batch sizes.
     ingest_batch_size_bytes: Metric<U64Histogram>,
 
+    /// Distribution of the lag between the timestamps of ingested points and
+    /// the time they were received.
+    ingest_receive_lag: Metric<DurationHistogram>,
+
     /// Database metrics keyed by database name
     databases: Mutex<HashMap<String, LineProtocolDatabaseMetrics>>,
+
+    /// Whether to add an additional `org` attribute to the per-database metrics.
+    ///
+    /// This is opt-in as it increases the metric cardinality.
+    org_attribute: bool,
 }
 
 /// Line protocol metrics for a given database
@@ -47,10 +61,19 @@ struct LineProtocolDatabaseMetrics {
 
     /// Distribution of LP batch sizes ingested unsuccessfully
     ingest_batch_size_bytes_error: U64Histogram,
+
+    /// Distribution of the min, median and max lag of the points of ingested
+    /// batches, and of how far ahead of their receive time batches with
+    /// future points are timestamped
+    receive_lag: [DurationHistogram; 4],
 }
 
+/// The statistics recorded for the receive lag of a batch, in the order of
+/// [`LineProtocolDatabaseMetrics::receive_lag`]
+const RECEIVE_LAG_STATS: [&str; 4] = ["min", "median", "max", "future"];
+
 impl LineProtocolMetrics {
-    pub fn new(registry: &metric::Registry) -> Self {
+    pub fn new(registry: &metric::Registry, org_attribute: bool) -> Self {
         Self {
             ingest_lines: registry.register_metric("ingest_lines", "total LP points ingested"),
             ingest_fields: registry
@@ -78,19 +101,37 @@ impl LineProtocolMetrics {
                     ])
                 },
             ),
+            ingest_receive_lag: registry.register_metric_with_options(
+                "ingest_receive_lag",
+                "distribution of the lag between ingested point timestamps and their receive time",
+                || {
+                    DurationHistogramOptions::new([
+                        Duration::from_secs(1),
+                        Duration::from_secs(10),
+                        Duration::from_secs(60),
+                        Duration::from_secs(10 * 60),
+                        Duration::from_secs(60 * 60),
+                        Duration::from_secs(24 * 60 * 60),
+                        Duration::from_secs(7 * 24 * 60 * 60),
+                        DURATION_MAX,
+                    ])
+                },
+            ),
             databases: Default::default(),
+            org_attribute,
         }
     }
 
     pub fn record_write(
         &self,
         db_name: &str,
+        org: &str,
         lines: usize,
         fields: usize,
         bytes: usize,
         success: bool,
     ) {
-        let metrics = self.database_metrics(db_name);
+        let metrics = self.database_metrics(db_name, org);
 
         match success {
             true => {
@@ -108,13 +149,52 @@ impl LineProtocolMetrics {
         }
     }
 
-    fn database_metrics(&self, db_name: &str) -> MappedMutexGuard<'_, LineProtocolDatabaseMetrics> {
+    /// Records the min, median and max lag between `timestamps` and
+    /// `receive_time`, both in nanoseconds since the epoch
+    ///
+    /// Points timestamped after they were received have no lag. Instead, how
+    /// far the newest of them is ahead of `receive_time` is recorded with the
+    /// `future` statistic, so that sources with skewed clocks can be detected.
+    pub fn record_receive_lag(
+        &self,
+        db_name: &str,
+        org: &str,
+        receive_time: i64,
+        timestamps: &mut [i64],
+    ) {
+        if timestamps.is_empty() {
+            return;
+        }
+
+        let mid = timestamps.len() / 2;
+        let median_time = *timestamps.select_nth_unstable(mid).1;
+        let min_time = *timestamps.iter().min().unwrap();
+        let max_time = *timestamps.iter().max().unwrap();
+
+        let lag = |time: i64| Duration::from_nanos(receive_time.saturating_sub(time).max(0) as u64);
+
+        let metrics = self.database_metrics(db_name, org);
+        metrics.receive_lag[0].record(lag(max_time));
+        metrics.receive_lag[1].record(lag(median_time));
+        metrics.receive_lag[2].record(lag(min_time));
+        if max_time > receive_time {
+            let ahead = max_time.saturating_sub(receive_time);
+            metrics.receive_lag[3].record(Duration::from_nanos(ahead as u64));
+        }
+    }
+
+    fn database_metrics(
+        &self,
+        db_name: &str,
+        org: &str,
+    ) -> MappedMutexGuard<'_, LineProtocolDatabaseMetrics> {
         MutexGuard::map(self.databases.lock(), |databases| {
             let (_, metrics) = databases
                 .raw_entry_mut()
                 .from_key(db_name)
                 .or_insert_with(|| {
-                    let metrics = LineProtocolDatabaseMetrics::new(self, db_name);
+                    let org = self.org_attribute.then(|| org);
+                    let metrics = LineProtocolDatabaseMetrics::new(self, db_name, org);
                     (db_name.to_string(), metrics)
                 });
             metrics
@@ -123,8 +203,17 @@ impl LineProtocolMetrics {
 }
 
 impl LineProtocolDatabaseMetrics {
-    fn new(metrics: &LineProtocolMetrics, db_name: &str) -> Self {
+    fn new(metrics: &LineProtocolMetrics, db_name: &str, org: Option<&str>) -> Self {
         let mut attributes = Attributes::from([("db_name", db_name.to_string().into())]);
+        if let Some(org) = org {
+            attributes.insert("org", org.to_string());
+        }
+
+        let receive_lag = RECEIVE_LAG_STATS.map(|stat| {
+            let mut attributes = attributes.clone();
+            attributes.insert("stat", stat);
+            metrics.ingest_receive_lag.recorder(attributes)
+        });
 
         attributes.insert("status", "ok");
         let ingest_lines_ok = metrics.ingest_lines.recorder(attributes.clone());
@@ -149,6 +238,128 @@ impl LineProtocolDatabaseMetrics {
             ingest_bytes_error,
             ingest_batch_size_bytes_ok,
             ingest_batch_size_bytes_error,
+            receive_lag,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ingest_lines_ok<const N: usize>(
+        registry: &metric::Registry,
+        attributes: &[(&'static str, &'static str); N],
+    ) -> Option<u64> {
+        registry
+            .get_instrument::<Metric<U64Counter>>("ingest_lines")
+            .unwrap()
+            .get_observer(&Attributes::from(attributes))
+            .map(|observer| observer.fetch())
+    }
+
+    #[test]
+    fn test_org_attribute() {
+        let registry = metric::Registry::new();
+        let metrics = LineProtocolMetrics::new(&registry, true);
+        metrics.record_write("MyOrg_MyBucket", "MyOrg", 3, 6, 100, true);
+
+        assert_eq!(
+            ingest_lines_ok(
+                &registry,
+                &[
+                    ("db_name", "MyOrg_MyBucket"),
+                    ("org", "MyOrg"),
+                    ("status", "ok")
+                ]
+            ),
+            Some(3)
+        );
+        assert_eq!(
+            ingest_lines_ok(
+                &registry,
+                &[("db_name", "MyOrg_MyBucket"), ("status", "ok")]
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_receive_lag() {
+        let registry = metric::Registry::new();
+        let metrics = LineProtocolMetrics::new(&registry, false);
+
+        let lag = |stat: &'static str| {
+            registry
+                .get_instrument::<Metric<DurationHistogram>>("ingest_receive_lag")
+                .unwrap()
+                .get_observer(&Attributes::from(&[
+                    ("db_name", "MyOrg_MyBucket"),
+                    ("stat", stat),
+                ]))
+                .unwrap()
+                .fetch()
+        };
+
+        // the oldest point lags by 3s, the median by 2s and the newest by 1s
+        let receive_time = 1_000_000_000_000;
+        let mut timestamps = [
+            receive_time - 1_000_000_000,
+            receive_time - 3_000_000_000,
+            receive_time - 2_000_000_000,
+        ];
+        metrics.record_receive_lag("MyOrg_MyBucket", "MyOrg", receive_time, &mut timestamps);
+        assert_eq!(lag("min").sample_count(), 1);
+        assert_eq!(lag("min").total, Duration::from_secs(1));
+        assert_eq!(lag("median").sample_count(), 1);
+        assert_eq!(lag("median").total, Duration::from_secs(2));
+        assert_eq!(lag("max").sample_count(), 1);
+        assert_eq!(lag("max").total, Duration::from_secs(3));
+        assert_eq!(lag("future").sample_count(), 0);
+
+        // points timestamped in the future have no lag, but how far the
+        // newest of them is ahead is recorded separately
+        let mut timestamps = [
+            receive_time + 30_000_000_000,
+            receive_time - 2_000_000_000,
+            receive_time + 10_000_000_000,
+        ];
+        metrics.record_receive_lag("MyOrg_MyBucket", "MyOrg", receive_time, &mut timestamps);
+        assert_eq!(lag("min").sample_count(), 2);
+        assert_eq!(lag("min").total, Duration::from_secs(1));
+        assert_eq!(lag("median").total, Duration::from_secs(2));
+        assert_eq!(lag("max").total, Duration::from_secs(5));
+        assert_eq!(lag("future").sample_count(), 1);
+        assert_eq!(lag("future").total, Duration::from_secs(30));
+
+        // nothing is recorded for a batch without timestamps
+        metrics.record_receive_lag("MyOrg_MyBucket", "MyOrg", receive_time, &mut []);
+        assert_eq!(lag("max").sample_count(), 2);
+    }
+
+    #[test]
+    fn test_no_org_attribute() {
+        let registry = metric::Registry::new();
+        let metrics = LineProtocolMetrics::new(&registry, false);
+        metrics.record_write("MyOrg_MyBucket", "MyOrg", 3, 6, 100, true);
+
+        assert_eq!(
+            ingest_lines_ok(
+                &registry,
+                &[("db_name", "MyOrg_MyBucket"), ("status", "ok")]
+            ),
+            Some(3)
+        );
+        assert_eq!(
+            ingest_lines_ok(
+                &registry,
+                &[
+                    ("db_name", "MyOrg_MyBucket"),
+                    ("org", "MyOrg"),
+                    ("status", "ok")
+                ]
+            ),
+            None
+        );
+    }
+}
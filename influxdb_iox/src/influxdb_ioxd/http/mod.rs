@@ -13,7 +13,7 @@ use tower::Layer;
 use trace_http::{ctx::TraceHeaderParser, tower::TraceLayer};
 
 use crate::influxdb_ioxd::{
-    http::error::{HttpApiError, HttpApiErrorExt, HttpApiErrorSource},
+    http::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorExt, HttpApiErrorSource},
     server_type::ServerType,
 };
 
@@ -26,6 +26,7 @@ mod pprof;
 pub mod dml;
 pub mod error;
 pub mod metrics;
+pub mod ndjson;
 pub mod utils;
 
 #[cfg(test)]
@@ -66,6 +67,9 @@ pub enum ApplicationError {
     #[snafu(display("pprof support is not compiled"))]
     PProfIsNotCompiled,
 
+    #[snafu(display("Server is not healthy: {}", reason))]
+    NotHealthy { reason: String },
+
     #[snafu(display("Route error from run mode: {}", e))]
     RunModeRouteError { e: Box<dyn HttpApiErrorSource> },
 }
@@ -80,6 +84,9 @@ impl HttpApiErrorSource for ApplicationError {
             e @ Self::EmptyFlamegraph => e.empty_value(),
             e @ Self::HeappyIsNotCompiled => e.internal_error(),
             e @ Self::PProfIsNotCompiled => e.internal_error(),
+            e @ Self::NotHealthy { .. } => {
+                HttpApiError::new(HttpApiErrorCode::Unavailable, e.to_string())
+            }
             #[cfg(feature = "heappy")]
             e @ Self::HeappyError { .. } => e.internal_error(),
             Self::RunModeRouteError { e } => e.to_http_api_error(),
@@ -131,7 +138,7 @@ where
     let content_length = req.headers().get("content-length").cloned();
 
     let response = match (method.clone(), uri.path()) {
-        (Method::GET, "/health") => health(),
+        (Method::GET, "/health") => health(server_type.as_ref()),
         (Method::GET, "/metrics") => handle_metrics(server_type.as_ref()),
         (Method::GET, "/debug/pprof") => pprof_home(req).await,
         (Method::GET, "/debug/pprof/profile") => pprof_profile(req).await,
@@ -160,7 +167,14 @@ where
     }
 }
 
-fn health() -> Result<Response<Body>, ApplicationError> {
+fn health<M>(server_type: &M) -> Result<Response<Body>, ApplicationError>
+where
+    M: ServerType,
+{
+    server_type
+        .health()
+        .map_err(|reason| ApplicationError::NotHealthy { reason })?;
+
     let response_body = "OK";
     Ok(Response::new(Body::from(response_body.to_string())))
 }
@@ -0,0 +1,293 @@
+//! Conversion of JSON Lines (`application/x-ndjson`) write payloads to line
+//! protocol.
+//!
+//! Each non-empty line of the payload is a JSON object of the form
+//!
+//! ```text
+//! {"measurement": "cpu", "tags": {"host": "a"}, "fields": {"usage": 0.5}, "timestamp": 1}
+//! ```
+//!
+//! where `tags` and `timestamp` are optional. Field values may be numbers,
+//! strings or booleans. As with unsuffixed line protocol numbers, JSON
+//! numbers are written as floats.
+//!
+//! Each line of the payload, including blank ones, becomes exactly one line
+//! of line protocol so that line numbers are the same in both.
+use std::{collections::BTreeMap, fmt::Write};
+
+use serde::Deserialize;
+use serde_json::Value;
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum NdjsonError {
+    #[snafu(display("error parsing NDJSON line {}: {}", line, source))]
+    InvalidJson {
+        line: usize,
+        offset: usize,
+        source: serde_json::Error,
+    },
+
+    #[snafu(display("error converting NDJSON line {}: {}", line, reason))]
+    InvalidRecord {
+        line: usize,
+        offset: usize,
+        reason: String,
+    },
+
+    /// An error converting the line protocol produced from the payload, whose
+    /// line numbers are those of the payload.
+    #[snafu(display("{}", source))]
+    LineProtocol {
+        position: Option<(usize, usize)>,
+        source: mutable_batch_lp::Error,
+    },
+}
+
+impl NdjsonError {
+    /// Returns the position of the line that caused this error, if any, as
+    /// its 1-based line number and the byte offset at which it starts within
+    /// the payload.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::InvalidJson { line, offset, .. } | Self::InvalidRecord { line, offset, .. } => {
+                Some((*line, *offset))
+            }
+            Self::LineProtocol { position, .. } => *position,
+        }
+    }
+}
+
+/// A single NDJSON record
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Record {
+    measurement: String,
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
+    fields: BTreeMap<String, Value>,
+    timestamp: Option<i64>,
+}
+
+const MEASUREMENT_DELIMITERS: &[char] = &[',', ' '];
+const KEY_DELIMITERS: &[char] = &[',', '=', ' '];
+const STRING_FIELD_DELIMITERS: &[char] = &['"', '\\'];
+
+/// Returns true if `content_type` is the NDJSON media type, ignoring case
+/// and any parameters.
+pub fn is_ndjson_content_type(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .eq_ignore_ascii_case("application/x-ndjson")
+}
+
+/// Converts an NDJSON payload into the equivalent line protocol, with one
+/// line of line protocol per line of the payload.
+pub fn ndjson_to_line_protocol(body: &str) -> Result<String, NdjsonError> {
+    let mut lp = String::with_capacity(body.len());
+
+    for (line_idx, (offset, json)) in lines_with_offsets(body).enumerate() {
+        let line = line_idx + 1;
+        if json.trim().is_empty() {
+            lp.push('\n');
+            continue;
+        }
+
+        let record: Record =
+            serde_json::from_str(json).context(InvalidJsonSnafu { line, offset })?;
+        write_record(&mut lp, &record).map_err(|reason| NdjsonError::InvalidRecord {
+            line,
+            offset,
+            reason,
+        })?;
+    }
+
+    Ok(lp)
+}
+
+/// Wraps an error converting the line protocol that
+/// [`ndjson_to_line_protocol`] produced from `body`, positioning it within
+/// `body`.
+pub fn line_protocol_error(body: &str, source: mutable_batch_lp::Error) -> NdjsonError {
+    // the line numbers of the line protocol are those of the payload
+    let position = source.position().and_then(|(line, _)| {
+        lines_with_offsets(body)
+            .nth(line - 1)
+            .map(|(offset, _)| (line, offset))
+    });
+    NdjsonError::LineProtocol { position, source }
+}
+
+/// Returns the lines of `body`, as split by [`str::lines`], with the byte
+/// offset at which each starts.
+fn lines_with_offsets(body: &str) -> impl Iterator<Item = (usize, &str)> {
+    body.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        Some((start, line.strip_suffix('\r').unwrap_or(line)))
+    })
+}
+
+/// Appends `record` to `lp` as a line of line protocol
+fn write_record(lp: &mut String, record: &Record) -> Result<(), String> {
+    if record.measurement.is_empty() {
+        return Err("measurement must not be empty".to_string());
+    }
+    // line protocol treats such a line as a comment
+    if record.measurement.starts_with('#') {
+        return Err("measurement must not start with '#'".to_string());
+    }
+    if record.fields.is_empty() {
+        return Err("at least one field is required".to_string());
+    }
+
+    write_escaped(lp, &record.measurement, MEASUREMENT_DELIMITERS)?;
+    for (key, value) in &record.tags {
+        lp.push(',');
+        write_escaped(lp, key, KEY_DELIMITERS)?;
+        lp.push('=');
+        write_escaped(lp, value, KEY_DELIMITERS)?;
+    }
+
+    for (idx, (key, value)) in record.fields.iter().enumerate() {
+        lp.push(if idx == 0 { ' ' } else { ',' });
+        write_escaped(lp, key, KEY_DELIMITERS)?;
+        lp.push('=');
+
+        match value {
+            Value::Number(n) => {
+                let n = n
+                    .as_f64()
+                    .ok_or_else(|| format!("invalid number for field '{}'", key))?;
+                write!(lp, "{}", n).expect("writing to string");
+            }
+            Value::String(s) => {
+                lp.push('"');
+                for c in s.chars() {
+                    if STRING_FIELD_DELIMITERS.contains(&c) {
+                        lp.push('\\');
+                    }
+                    lp.push(c);
+                }
+                lp.push('"');
+            }
+            Value::Bool(b) => write!(lp, "{}", b).expect("writing to string"),
+            Value::Null | Value::Array(_) | Value::Object(_) => {
+                return Err(format!(
+                    "unsupported value for field '{}', expected a number, string or boolean",
+                    key
+                ))
+            }
+        }
+    }
+
+    if let Some(timestamp) = record.timestamp {
+        write!(lp, " {}", timestamp).expect("writing to string");
+    }
+    lp.push('\n');
+
+    Ok(())
+}
+
+/// Appends `value` to `lp`, escaping any of `delimiters`
+fn write_escaped(lp: &mut String, value: &str, delimiters: &[char]) -> Result<(), String> {
+    if value.contains('\n') || value.ends_with('\\') {
+        return Err(format!(
+            "'{}' must not contain a newline or end with a backslash",
+            value.escape_default()
+        ));
+    }
+
+    for c in value.chars() {
+        if delimiters.contains(&c) {
+            lp.push('\\');
+        }
+        lp.push(c);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ndjson_to_line_protocol() {
+        let body = r#"{"measurement": "cpu", "tags": {"region": "us west", "host": "a"}, "fields": {"usage": 1, "msg": "say \"hi\"", "ok": true}, "timestamp": 10}
+
+{"measurement": "mem,total", "fields": {"used=": 2.5}}
+"#;
+
+        assert_eq!(
+            ndjson_to_line_protocol(body).unwrap(),
+            "cpu,host=a,region=us\\ west msg=\"say \\\"hi\\\"\",ok=true,usage=1 10\n\
+             \n\
+             mem\\,total used\\==2.5\n"
+        );
+    }
+
+    #[test]
+    fn test_is_ndjson_content_type() {
+        assert!(is_ndjson_content_type("application/x-ndjson"));
+        assert!(is_ndjson_content_type(
+            "Application/X-NDJSON; charset=utf-8"
+        ));
+        assert!(!is_ndjson_content_type("text/plain"));
+        assert!(!is_ndjson_content_type("application/x-ndjson-seq"));
+    }
+
+    #[test]
+    fn test_ndjson_errors() {
+        let err =
+            ndjson_to_line_protocol("{\"measurement\": \"cpu\", \"fields\": {\"a\": 1}}\nnot json")
+                .unwrap_err();
+        assert!(matches!(err, NdjsonError::InvalidJson { line: 2, .. }));
+        assert_eq!(err.position(), Some((2, 43)));
+
+        let err = ndjson_to_line_protocol(r#"{"measurement": "cpu", "fields": {}}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error converting NDJSON line 1: at least one field is required"
+        );
+
+        let err =
+            ndjson_to_line_protocol(r#"{"measurement": "cpu", "fields": {"a": [1]}}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error converting NDJSON line 1: unsupported value for field 'a', expected a number, string or boolean"
+        );
+
+        let err =
+            ndjson_to_line_protocol(r#"{"measurement": "cpu", "field": {"a": 1}}"#).unwrap_err();
+        assert!(matches!(err, NdjsonError::InvalidJson { line: 1, .. }));
+
+        let err =
+            ndjson_to_line_protocol("\n\n{\"measurement\": \"#cpu\", \"fields\": {\"a\": 1}}")
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error converting NDJSON line 3: measurement must not start with '#'"
+        );
+        assert_eq!(err.position(), Some((3, 2)));
+    }
+
+    #[test]
+    fn test_line_protocol_error() {
+        let body = "{\"measurement\": \"cpu\", \"fields\": {\"a\": 1}}\r\n\r\n\
+                    {\"measurement\": \"cpu\", \"fields\": {\"a\": \"x\"}}";
+        let lp = ndjson_to_line_protocol(body).unwrap();
+        let source = mutable_batch_lp::lines_to_batches(&lp, 0).unwrap_err();
+
+        let err = line_protocol_error(body, source);
+        assert_eq!(err.position(), Some((3, 46)));
+        assert!(err.to_string().starts_with("error writing line 3: "));
+        assert_eq!(
+            &body[46..],
+            "{\"measurement\": \"cpu\", \"fields\": {\"a\": \"x\"}}"
+        );
+    }
+}
@@ -4,7 +4,7 @@ use http::header::CONTENT_ENCODING;
 use hyper::Body;
 use snafu::{ResultExt, Snafu};
 
-use super::error::{HttpApiError, HttpApiErrorExt, HttpApiErrorSource};
+use super::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorExt, HttpApiErrorSource};
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Snafu)]
@@ -12,6 +12,9 @@ pub enum ParseBodyError {
     #[snafu(display("Body exceeds limit of {} bytes", max_body_size))]
     RequestSizeExceeded { max_body_size: usize },
 
+    #[snafu(display("Decompressed body exceeds limit of {} bytes", max_decompressed_size))]
+    DecompressedSizeExceeded { max_decompressed_size: usize },
+
     #[snafu(display("Invalid content encoding: {}", content_encoding))]
     InvalidContentEncoding { content_encoding: String },
 
@@ -24,6 +27,9 @@ pub enum ParseBodyError {
     #[snafu(display("Error decompressing body as gzip: {}", source))]
     ReadingBodyAsGzip { source: std::io::Error },
 
+    #[snafu(display("Gzip body is empty or truncated"))]
+    TruncatedGzipBody {},
+
     #[snafu(display("Client hung up while sending body: {}", source))]
     ClientHangup { source: hyper::Error },
 }
@@ -31,10 +37,13 @@ pub enum ParseBodyError {
 impl HttpApiErrorSource for ParseBodyError {
     fn to_http_api_error(&self) -> HttpApiError {
         match self {
-            e @ Self::RequestSizeExceeded { .. } => e.invalid(),
+            e @ (Self::RequestSizeExceeded { .. } | Self::DecompressedSizeExceeded { .. }) => {
+                HttpApiError::new(HttpApiErrorCode::RequestTooLarge, e.to_string())
+            }
             e @ Self::InvalidContentEncoding { .. } => e.invalid(),
             e @ Self::ReadingHeaderAsUtf8 { .. } => e.invalid(),
             e @ Self::ReadingBodyAsGzip { .. } => e.invalid(),
+            e @ Self::TruncatedGzipBody { .. } => e.invalid(),
             e @ Self::ClientHangup { .. } => e.invalid(),
         }
     }
@@ -85,7 +94,9 @@ pub async fn parse_body(
         let decoder = flate2::read::GzDecoder::new(&body[..]);
 
         // Read at most max_size bytes to prevent a decompression bomb based
-        // DoS.
+        // DoS. Decompression stops once the limit is reached, so at most
+        // max_size + 1 bytes are ever allocated regardless of how well the
+        // body compresses.
         //
         // In order to detect if the entire stream has been read, or truncated,
         // read an extra byte beyond the limit and check the resulting data
@@ -94,13 +105,23 @@ pub async fn parse_body(
         let mut decoded_data = Vec::new();
         decoder
             .read_to_end(&mut decoded_data)
-            .context(ReadingBodyAsGzipSnafu)?;
+            .map_err(|source| match source.kind() {
+                std::io::ErrorKind::UnexpectedEof => ParseBodyError::TruncatedGzipBody {},
+                _ => ParseBodyError::ReadingBodyAsGzip { source },
+            })?;
+
+        // A body that ends before the gzip header is complete may also decode
+        // to no bytes without an error. Reject it rather than treating it as
+        // an empty payload.
+        if decoded_data.is_empty() && decoder.get_ref().header().is_none() {
+            return Err(ParseBodyError::TruncatedGzipBody {});
+        }
 
         // If the length is max_size+1, the body is at least max_size+1 bytes in
         // length, and possibly longer, but truncated.
         if decoded_data.len() > max_size {
-            return Err(ParseBodyError::RequestSizeExceeded {
-                max_body_size: max_size,
+            return Err(ParseBodyError::DecompressedSizeExceeded {
+                max_decompressed_size: max_size,
             });
         }
 
@@ -152,6 +173,34 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_read_truncated_gzip_header() {
+        let mut e = GzEncoder::new(Vec::new(), Compression::default());
+        e.write_all(b"bananas B=42\n").unwrap();
+        let mut body = e.finish().expect("failed to compress test body");
+
+        // Cut the body off part way through the gzip header
+        body.truncate(5);
+
+        let body: Result<_, std::io::Error> = Ok(body);
+        let body = Body::wrap_stream(stream::iter(iter::once(body)));
+
+        let mut request = Request::builder()
+            .uri("https://explosions.example/")
+            .body(body)
+            .unwrap();
+
+        request
+            .headers_mut()
+            .insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+
+        let got = parse_body(request, TEST_MAX_REQUEST_SIZE).await;
+
+        let err = got.unwrap_err();
+        assert!(matches!(err, ParseBodyError::TruncatedGzipBody { .. }));
+        assert_eq!(err.to_string(), "Gzip body is empty or truncated");
+    }
+
     #[tokio::test]
     async fn test_read_gzipped_body_truncation() {
         const MAX_BYTES: usize = 1024;
@@ -196,7 +245,70 @@ mod tests {
 
         assert!(matches!(
             got,
-            Err(ParseBodyError::RequestSizeExceeded { .. })
+            Err(ParseBodyError::DecompressedSizeExceeded { .. })
         ));
     }
+
+    #[tokio::test]
+    async fn test_read_body_too_large() {
+        const MAX_BYTES: usize = 1024;
+
+        let body: Result<_, std::io::Error> = Ok(vec![b'A'; MAX_BYTES + 1]);
+        let body = Body::wrap_stream(stream::iter(iter::once(body)));
+
+        let request = Request::builder()
+            .uri("https://explosions.example/")
+            .body(body)
+            .unwrap();
+
+        let err = parse_body(request, MAX_BYTES).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ParseBodyError::RequestSizeExceeded {
+                max_body_size: MAX_BYTES
+            }
+        ));
+        assert_eq!(
+            err.to_http_api_error().response().status(),
+            http::StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_gzipped_body_bomb() {
+        const MAX_BYTES: usize = 64 * 1024;
+
+        // 16MiB of zeroes compresses to well under MAX_BYTES
+        let mut e = GzEncoder::new(Vec::new(), Compression::best());
+        let zeroes = vec![0_u8; 1024 * 1024];
+        for _ in 0..16 {
+            e.write_all(&zeroes).unwrap();
+        }
+        let body = e.finish().expect("failed to compress test body");
+        assert!(body.len() < MAX_BYTES);
+
+        let body: Result<_, std::io::Error> = Ok(body);
+        let body = Body::wrap_stream(stream::iter(iter::once(body)));
+
+        let mut request = Request::builder()
+            .uri("https://explosions.example/")
+            .body(body)
+            .unwrap();
+
+        request
+            .headers_mut()
+            .insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+
+        let err = parse_body(request, MAX_BYTES).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ParseBodyError::DecompressedSizeExceeded {
+                max_decompressed_size: MAX_BYTES
+            }
+        ));
+        assert_eq!(
+            err.to_http_api_error().response().status(),
+            http::StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
 }
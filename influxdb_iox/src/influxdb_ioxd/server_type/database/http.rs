@@ -13,11 +13,16 @@
 // Influx crates
 use data_types::{names::OrgBucketMappingError, DatabaseName};
 use influxdb_iox_client::format::QueryOutputFormat;
+use mutable_batch_lp::SchemaHint;
 use query::{exec::ExecutionContextProvider, QueryDatabase};
+use schema::Schema;
 use server::Error;
 
 // External crates
+use arrow::{csv::WriterBuilder, json::ArrayWriter};
 use async_trait::async_trait;
+use datafusion::{error::DataFusionError, physical_plan::SendableRecordBatchStream};
+use futures::{StreamExt, TryStreamExt};
 use http::header::CONTENT_TYPE;
 use hyper::{Body, Method, Request, Response};
 use observability_deps::tracing::{debug, error};
@@ -27,7 +32,7 @@ use snafu::{OptionExt, ResultExt, Snafu};
 use crate::influxdb_ioxd::{
     http::{
         dml::{HttpDrivenDml, InnerDmlError, RequestOrResponse},
-        error::{HttpApiError, HttpApiErrorExt, HttpApiErrorSource},
+        error::{HttpApiError, HttpApiErrorCode, HttpApiErrorExt, HttpApiErrorSource},
         metrics::LineProtocolMetrics,
     },
     planner::Planner,
@@ -96,6 +101,12 @@ pub enum ApplicationError {
         source: influxdb_iox_client::format::Error,
     },
 
+    #[snafu(display(
+        "Formatted results of SQL query exceed the maximum response size of {} bytes",
+        max_response_bytes
+    ))]
+    ResponseTooLarge { max_response_bytes: usize },
+
     #[snafu(display("Error while planning query: {}", source))]
     Planning {
         source: crate::influxdb_ioxd::planner::Error,
@@ -134,6 +145,9 @@ impl HttpApiErrorSource for ApplicationError {
             e @ Self::CreatingResponse { .. } => e.internal_error(),
             e @ Self::FormattingResult { .. } => e.internal_error(),
             e @ Self::ParsingFormat { .. } => e.invalid(),
+            e @ Self::ResponseTooLarge { .. } => {
+                HttpApiError::new(HttpApiErrorCode::ResponseTooLarge, e.to_string())
+            }
             e @ Self::Planning { .. } => e.invalid(),
             e @ Self::ServerIdNotSet => e.invalid(),
             e @ Self::ServerNotInitialized => e.invalid(),
@@ -171,10 +185,67 @@ impl HttpDrivenDml for DatabaseServerType {
         Arc::clone(&self.lp_metrics)
     }
 
+    fn normalize_org_bucket_case(&self) -> bool {
+        self.normalize_org_bucket_case
+    }
+
+    fn dedup_writes(&self) -> bool {
+        self.dedup_writes
+    }
+
+    fn null_string_sentinels(&self) -> &[String] {
+        &self.null_string_sentinels
+    }
+
+    fn validate_write_names(&self) -> bool {
+        self.validate_write_names
+    }
+
+    fn max_fields_per_measurement(&self) -> Option<usize> {
+        self.max_fields_per_measurement
+    }
+
+    fn write_retries(&self) -> usize {
+        self.write_retries
+    }
+
+    fn strict_write_params(&self) -> bool {
+        self.strict_write_params
+    }
+
+    fn known_field_names(&self, db_name: &DatabaseName<'_>, table_name: &str) -> Vec<String> {
+        self.server
+            .db(db_name)
+            .ok()
+            .and_then(|db| db.table_schema(table_name))
+            .map(|schema| {
+                schema
+                    .fields_iter()
+                    .map(|field| field.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn explain_writes(&self) -> bool {
+        true
+    }
+
+    fn table_schema(&self, db_name: &DatabaseName<'_>, table_name: &str) -> Option<Arc<Schema>> {
+        self.server
+            .db(db_name)
+            .ok()
+            .and_then(|db| db.table_schema(table_name))
+    }
+
+    fn schema_hint(&self, db_name: &DatabaseName<'_>) -> Option<&SchemaHint> {
+        self.schema_hints.get(db_name.as_str())
+    }
+
     async fn write(
         &self,
         db_name: &DatabaseName<'_>,
-        op: DmlOperation,
+        op: Arc<DmlOperation>,
     ) -> Result<(), InnerDmlError> {
         let db = self
             .server
@@ -217,6 +288,93 @@ pub async fn route_request(
     }
 }
 
+/// A buffer for a formatted response that rejects writes taking it beyond
+/// `max_bytes`.
+#[derive(Debug)]
+struct LimitedBuffer {
+    bytes: Vec<u8>,
+    max_bytes: usize,
+    exceeded: bool,
+}
+
+impl std::io::Write for LimitedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.bytes.len() + buf.len() > self.max_bytes {
+            self.exceeded = true;
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "response size limit exceeded",
+            ));
+        }
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Formats the batches of `stream`, returning `None` if the formatted
+/// response exceeds `max_bytes`.
+///
+/// CSV and JSON are written batch by batch, so an oversized response is
+/// abandoned without reading the remaining results. A pretty-printed table
+/// can only be laid out once all results have been read.
+async fn format_limited(
+    mut stream: SendableRecordBatchStream,
+    format: QueryOutputFormat,
+    max_bytes: usize,
+) -> datafusion::error::Result<Option<String>> {
+    let mut buffer = LimitedBuffer {
+        bytes: vec![],
+        max_bytes,
+        exceeded: false,
+    };
+
+    let written = match format {
+        QueryOutputFormat::Pretty => {
+            let batches: Vec<_> = stream.try_collect().await?;
+            let pretty = format
+                .format(&batches)
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            return Ok((pretty.len() <= max_bytes).then(|| pretty));
+        }
+        QueryOutputFormat::Csv => {
+            let mut writer = WriterBuilder::new().has_headers(true).build(&mut buffer);
+            let mut written = Ok(());
+            while let Some(batch) = stream.next().await {
+                written = writer.write(&batch?);
+                if written.is_err() {
+                    break;
+                }
+            }
+            // dropping the writer flushes any rows it still buffers
+            written
+        }
+        QueryOutputFormat::Json => {
+            let mut writer = ArrayWriter::new(&mut buffer);
+            let mut written = Ok(());
+            while let Some(batch) = stream.next().await {
+                written = writer.write_batches(&[batch?]);
+                if written.is_err() {
+                    break;
+                }
+            }
+            written.and_then(|_| writer.finish())
+        }
+    };
+
+    if buffer.exceeded {
+        return Ok(None);
+    }
+    written?;
+
+    String::from_utf8(buffer.bytes)
+        .map(Some)
+        .map_err(|e| DataFusionError::External(Box::new(e)))
+}
+
 #[derive(Deserialize, Debug, PartialEq)]
 /// Parsed URI Parameters of the request to the .../query endpoint
 struct QueryParams {
@@ -259,15 +417,32 @@ async fn query(
 
     // TODO: stream read results out rather than rendering the
     // whole thing in mem
-    let batches = ctx
-        .collect(physical_plan)
-        .await
-        .map_err(|e| Box::new(e) as _)
-        .context(QuerySnafu { db_name })?;
+    let results = match server_type.max_response_bytes {
+        None => {
+            let batches = ctx
+                .collect(physical_plan)
+                .await
+                .map_err(|e| Box::new(e) as _)
+                .context(QuerySnafu { db_name })?;
+
+            format
+                .format(&batches)
+                .context(FormattingResultSnafu { q, format })?
+        }
+        Some(max_response_bytes) => {
+            let results = async {
+                let stream = ctx.execute_stream(physical_plan).await?;
+                ctx.run(format_limited(stream, format, max_response_bytes))
+                    .await
+            }
+            .await
+            .map_err(|e| Box::new(e) as _)
+            .context(QuerySnafu { db_name })?;
 
-    let results = format
-        .format(&batches)
-        .context(FormattingResultSnafu { q, format })?;
+            // only `None` if the formatted results exceeded the limit
+            results.context(ResponseTooLargeSnafu { max_response_bytes })?
+        }
+    };
 
     let body = Body::from(results.into_bytes());
 
@@ -286,7 +461,9 @@ mod tests {
         http::{
             dml::test_utils::{
                 assert_delete_bad_request, assert_delete_unknown_database,
-                assert_delete_unknown_table, assert_gzip_write, assert_write, assert_write_metrics,
+                assert_delete_unknown_table, assert_gzip_write, assert_malformed_lp_write,
+                assert_malformed_ndjson_write, assert_ndjson_write, assert_precision_write,
+                assert_truncated_gzip_write, assert_write, assert_write_metrics,
                 assert_write_to_invalid_database,
             },
             test_utils::{
@@ -304,10 +481,11 @@ mod tests {
     use http::StatusCode;
     use object_store::ObjectStore;
     use reqwest::Client;
-    use schema::selection::Selection;
+    use schema::{selection::Selection, InfluxFieldType};
     use server::{rules::ProvidedDatabaseRules, ApplicationState, Server};
     use std::convert::TryFrom;
     use trace::RingBufferTraceCollector;
+    use tracker::TaskRegistration;
 
     fn make_application() -> Arc<ApplicationState> {
         Arc::new(ApplicationState::new(
@@ -326,6 +504,72 @@ mod tests {
         assert_health(setup_server().await).await;
     }
 
+    #[tokio::test]
+    async fn test_health_loading_chunks() {
+        let mut server_type = setup_server_type("MyOrg_MyBucket").await;
+        server_type.health_max_loading_chunks = Some(0);
+        let test_server = TestServer::new(Arc::new(server_type));
+        let client = Client::new();
+        let health_url = format!("{}/health", test_server.url());
+
+        assert_write(&test_server).await;
+        let response = client.get(&health_url).send().await;
+        check_response("health", response, StatusCode::OK, Some("OK")).await;
+
+        let db = test_server
+            .server_type()
+            .server
+            .db(&DatabaseName::new("MyOrg_MyBucket").unwrap())
+            .unwrap();
+        let summary = db.chunk_summaries().into_iter().next().unwrap();
+        let (chunk, _) = db
+            .chunk(&summary.table_name, &summary.partition_key, summary.id)
+            .unwrap();
+
+        // Compacting chunks are not counted
+        let registration = TaskRegistration::new(Arc::new(time::SystemProvider::new()));
+        chunk.write().set_compacting(&registration).unwrap();
+
+        let response = client.get(&health_url).send().await;
+        check_response("health", response, StatusCode::OK, Some("OK")).await;
+
+        registration.into_tracker(1).cancel();
+        chunk.write().clear_lifecycle_action().unwrap();
+
+        // Start loading the persisted chunk back into the read buffer
+        let persisted = db
+            .persist_partition(&summary.table_name, &summary.partition_key, true)
+            .await
+            .unwrap()
+            .unwrap();
+        db.unload_read_buffer(&summary.table_name, &summary.partition_key, persisted.id())
+            .unwrap();
+        let (chunk, _) = db
+            .chunk(&summary.table_name, &summary.partition_key, persisted.id())
+            .unwrap();
+        let registration = TaskRegistration::new(Arc::new(time::SystemProvider::new()));
+        chunk
+            .write()
+            .set_loading_to_read_buffer(&registration)
+            .unwrap();
+
+        let response = client.get(&health_url).send().await;
+        check_response(
+            "health",
+            response,
+            StatusCode::SERVICE_UNAVAILABLE,
+            Some("1 chunks are being loaded into the read buffer, more than the limit of 0"),
+        )
+        .await;
+
+        // Once the action is cleared the server is healthy again
+        registration.into_tracker(1).cancel();
+        chunk.write().clear_lifecycle_action().unwrap();
+
+        let response = client.get(&health_url).send().await;
+        check_response("health", response, StatusCode::OK, Some("OK")).await;
+    }
+
     #[tokio::test]
     async fn test_metrics() {
         assert_metrics(setup_server().await).await;
@@ -368,6 +612,13 @@ mod tests {
         assert_write_metrics(setup_server().await, true).await;
     }
 
+    #[tokio::test]
+    async fn test_precision_write() {
+        let test_server = setup_server().await;
+        let write = assert_precision_write(&test_server).await;
+        assert_dbwrite(test_server, write).await;
+    }
+
     #[tokio::test]
     async fn test_gzip_write() {
         let test_server = setup_server().await;
@@ -376,11 +627,229 @@ mod tests {
         assert_dbwrite(test_server, write).await;
     }
 
+    #[tokio::test]
+    async fn test_ndjson_write() {
+        let test_server = setup_server().await;
+        let write = assert_ndjson_write(&test_server).await;
+        assert_dbwrite(test_server, write).await;
+    }
+
+    #[tokio::test]
+    async fn test_malformed_ndjson_write() {
+        assert_malformed_ndjson_write(&setup_server().await).await;
+    }
+
+    #[tokio::test]
+    async fn test_malformed_lp_write() {
+        assert_malformed_lp_write(&setup_server().await).await;
+    }
+
+    #[tokio::test]
+    async fn test_truncated_gzip_write() {
+        assert_truncated_gzip_write(&setup_server().await).await;
+    }
+
     #[tokio::test]
     async fn write_to_invalid_database() {
         assert_write_to_invalid_database(setup_server().await).await;
     }
 
+    #[tokio::test]
+    async fn test_write_explain() {
+        let test_server = setup_server().await;
+        assert_write(&test_server).await;
+
+        let client = Client::new();
+        let lp_data = "h2o_temperature,location=santa_monica surface_degrees=65i,salinity=3i 1617286224000000001\ncpu usage=0.5 1";
+        let response = client
+            .post(&format!(
+                "{}/api/v2/write?bucket=MyBucket&org=MyOrg&explain=true",
+                test_server.url(),
+            ))
+            .body(lp_data)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "measurements": [
+                    {
+                        "name": "cpu",
+                        "lines": 1,
+                        "new_measurement": true,
+                        "new_columns": [
+                            {"name": "time", "column_type": "timestamp"},
+                            {"name": "usage", "column_type": "field::float"},
+                        ],
+                        "coercions": [],
+                        "conflicts": [],
+                    },
+                    {
+                        "name": "h2o_temperature",
+                        "lines": 1,
+                        "new_measurement": false,
+                        "new_columns": [
+                            {"name": "salinity", "column_type": "field::integer"},
+                        ],
+                        "coercions": [],
+                        "conflicts": [
+                            {
+                                "name": "surface_degrees",
+                                "existing_type": "field::float",
+                                "written_type": "field::integer",
+                            },
+                        ],
+                    },
+                ]
+            })
+        );
+
+        // Nothing was written
+        let db = test_server
+            .server_type()
+            .server
+            .db(&DatabaseName::new("MyOrg_MyBucket").unwrap())
+            .unwrap();
+        assert!(db.table_schema("cpu").is_none());
+        assert!(db
+            .table_schema("h2o_temperature")
+            .unwrap()
+            .find_index_of("salinity")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_write_normalize_org_bucket_case() {
+        let test_server = setup_lowercase_db_server(true).await;
+        let client = Client::new();
+
+        // Both casings resolve to the lowercase database
+        for (org_name, bucket_name) in [("MyOrg", "MyBucket"), ("myorg", "mybucket")] {
+            let response = client
+                .post(&format!(
+                    "{}/api/v2/write?bucket={}&org={}",
+                    test_server.url(),
+                    bucket_name,
+                    org_name
+                ))
+                .body("cpu bar=1 10")
+                .send()
+                .await;
+            check_response("write", response, StatusCode::NO_CONTENT, Some("")).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_case_sensitive_org_bucket() {
+        let test_server = setup_lowercase_db_server(false).await;
+        let client = Client::new();
+
+        let response = client
+            .post(&format!(
+                "{}/api/v2/write?bucket=mybucket&org=myorg",
+                test_server.url(),
+            ))
+            .body("cpu bar=1 10")
+            .send()
+            .await;
+        check_response("write", response, StatusCode::NO_CONTENT, Some("")).await;
+
+        // `MyOrg_MyBucket` is a distinct database that does not exist
+        let response = client
+            .post(&format!(
+                "{}/api/v2/write?bucket=MyBucket&org=MyOrg",
+                test_server.url(),
+            ))
+            .body("cpu bar=1 10")
+            .send()
+            .await;
+        check_response("write", response, StatusCode::NOT_FOUND, Some("")).await;
+    }
+
+    #[tokio::test]
+    async fn test_write_schema_hint() {
+        let mut server_type = setup_server_type("MyOrg_MyBucket").await;
+        let mut hint = SchemaHint::default();
+        hint.pin_field("h2o", "temp", InfluxFieldType::Float);
+        server_type
+            .schema_hints
+            .insert("MyOrg_MyBucket".to_string(), hint);
+        let test_server = TestServer::new(Arc::new(server_type));
+
+        // The first write creates `temp` as a float despite the integer value,
+        // so the subsequent float value does not conflict
+        let client = Client::new();
+        for lp_data in ["h2o temp=1i 10", "h2o temp=1.5 20"] {
+            let response = client
+                .post(&format!(
+                    "{}/api/v2/write?bucket=MyBucket&org=MyOrg",
+                    test_server.url(),
+                ))
+                .body(lp_data)
+                .send()
+                .await;
+            check_response("write", response, StatusCode::NO_CONTENT, Some("")).await;
+        }
+
+        let test_db = test_server
+            .server_type()
+            .server
+            .db(&DatabaseName::new("MyOrg_MyBucket").unwrap())
+            .expect("Database exists");
+        let batches = run_query(test_db, "select * from h2o order by time").await;
+
+        let expected = vec![
+            "+------+--------------------------------+",
+            "| temp | time                           |",
+            "+------+--------------------------------+",
+            "| 1    | 1970-01-01T00:00:00.000000010Z |",
+            "| 1.5  | 1970-01-01T00:00:00.000000020Z |",
+            "+------+--------------------------------+",
+        ];
+        assert_batches_eq!(expected, &batches);
+    }
+
+    #[tokio::test]
+    async fn test_write_max_fields_per_measurement() {
+        let mut server_type = setup_server_type("MyOrg_MyBucket").await;
+        server_type.max_fields_per_measurement = Some(2);
+        let test_server = TestServer::new(Arc::new(server_type));
+        let client = Client::new();
+        let url = format!(
+            "{}/api/v2/write?bucket=MyBucket&org=MyOrg",
+            test_server.url()
+        );
+
+        let response = client.post(&url).body("h2o temp=1,level=2 10").send().await;
+        check_response("write", response, StatusCode::NO_CONTENT, Some("")).await;
+
+        // `h2o` already has two fields in the catalog, so a third is rejected
+        // even though the write itself only contains one field
+        let response = client
+            .post(&url)
+            .body("h2o pressure=3 20")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["code"], "invalid");
+        assert_eq!(
+            body["message"],
+            "Measurement 'h2o' would have 3 fields, exceeding the limit of 2"
+        );
+
+        // writes to known fields and to other measurements are unaffected
+        for lp_data in ["h2o temp=4 30", "air pressure=5,humidity=6 40"] {
+            let response = client.post(&url).body(lp_data).send().await;
+            check_response("write", response, StatusCode::NO_CONTENT, Some("")).await;
+        }
+    }
+
     #[tokio::test]
     async fn test_delete() {
         // Set up server
@@ -569,6 +1038,51 @@ mod tests {
         check_response("query", response, StatusCode::OK, Some(res)).await;
     }
 
+    #[tokio::test]
+    async fn test_query_max_response_bytes() {
+        async fn query_with_limit(max_response_bytes: usize) -> (StatusCode, String) {
+            let mut server_type = setup_server_type("MyOrg_MyBucket").await;
+            server_type.max_response_bytes = Some(max_response_bytes);
+            let test_server = TestServer::new(Arc::new(server_type));
+            let client = Client::new();
+
+            let response = client
+                .post(&format!(
+                    "{}/api/v2/write?bucket=MyBucket&org=MyOrg",
+                    test_server.url()
+                ))
+                .body("h2o_temperature,location=santa_monica,state=CA surface_degrees=65.2,bottom_degrees=50.4 1617286224000000000")
+                .send()
+                .await;
+            check_response("write", response, StatusCode::NO_CONTENT, Some("")).await;
+
+            let response = client
+                .get(&format!(
+                    "{}/api/v3/query?d=MyOrg_MyBucket&q={}&format=csv",
+                    test_server.url(),
+                    "select%20state%20from%20h2o_temperature"
+                ))
+                .send()
+                .await
+                .unwrap();
+            (response.status(), response.text().await.unwrap())
+        }
+
+        // the limit applies to the rendered CSV, which is 9 bytes
+        let (status, body) = query_with_limit(8).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["code"], "response too large");
+        assert_eq!(
+            body["message"],
+            "Formatted results of SQL query exceed the maximum response size of 8 bytes"
+        );
+
+        let (status, body) = query_with_limit(9).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "state\nCA\n");
+    }
+
     #[tokio::test]
     async fn test_query_json() {
         let (client, test_server) = setup_test_data().await;
@@ -643,18 +1157,30 @@ mod tests {
 
     /// return a test server and the url to contact it for `MyOrg_MyBucket`
     async fn setup_server() -> TestServer<DatabaseServerType> {
+        TestServer::new(Arc::new(setup_server_type("MyOrg_MyBucket").await))
+    }
+
+    /// Set up a server type with only the database `db_name`.
+    async fn setup_server_type(db_name: &str) -> DatabaseServerType {
         let application = make_application();
 
         let app_server = make_server(Arc::clone(&application));
         app_server.set_id(ServerId::try_from(1).unwrap()).unwrap();
         app_server.wait_for_init().await.unwrap();
         app_server
-            .create_database(make_rules("MyOrg_MyBucket"))
+            .create_database(make_rules(db_name))
             .await
             .unwrap();
 
-        let server_type =
-            DatabaseServerType::new(application, app_server, &CommonServerState::for_testing());
+        DatabaseServerType::new(application, app_server, &CommonServerState::for_testing())
+    }
+
+    /// Set up a server with only the database `myorg_mybucket`.
+    async fn setup_lowercase_db_server(
+        normalize_org_bucket_case: bool,
+    ) -> TestServer<DatabaseServerType> {
+        let mut server_type = setup_server_type("myorg_mybucket").await;
+        server_type.normalize_org_bucket_case = normalize_org_bucket_case;
 
         TestServer::new(Arc::new(server_type))
     }
@@ -1,16 +1,20 @@
-use crate::influxdb_ioxd::{
-    http::metrics::LineProtocolMetrics,
-    rpc::RpcBuilderInput,
-    server_type::{RpcError, ServerType},
-    serving_readiness::ServingReadiness,
+use crate::{
+    clap_blocks::field_type_hint::schema_hints_by_database,
+    influxdb_ioxd::{
+        http::metrics::LineProtocolMetrics,
+        rpc::RpcBuilderInput,
+        server_type::{RpcError, ServerType},
+        serving_readiness::ServingReadiness,
+    },
 };
 use async_trait::async_trait;
 use futures::{future::FusedFuture, FutureExt};
 use hyper::{Body, Request, Response};
 use metric::Registry;
+use mutable_batch_lp::SchemaHint;
 use observability_deps::tracing::{error, info};
 use server::{ApplicationState, Server};
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio_util::sync::CancellationToken;
 use trace::TraceCollector;
 
@@ -27,6 +31,16 @@ pub struct DatabaseServerType {
     pub server: Arc<Server>,
     pub lp_metrics: Arc<LineProtocolMetrics>,
     pub max_request_size: usize,
+    pub max_response_bytes: Option<usize>,
+    pub normalize_org_bucket_case: bool,
+    pub dedup_writes: bool,
+    pub null_string_sentinels: Vec<String>,
+    pub validate_write_names: bool,
+    pub max_fields_per_measurement: Option<usize>,
+    pub write_retries: usize,
+    pub strict_write_params: bool,
+    pub schema_hints: HashMap<String, SchemaHint>,
+    pub health_max_loading_chunks: Option<usize>,
     pub serving_readiness: ServingReadiness,
     shutdown: CancellationToken,
 }
@@ -39,6 +53,10 @@ impl DatabaseServerType {
     ) -> Self {
         let lp_metrics = Arc::new(LineProtocolMetrics::new(
             application.metric_registry().as_ref(),
+            common_state
+                .run_config()
+                .ingest_metrics_org_attribute
+                .into(),
         ));
 
         Self {
@@ -46,6 +64,16 @@ impl DatabaseServerType {
             server,
             lp_metrics,
             max_request_size: common_state.run_config().max_http_request_size,
+            max_response_bytes: common_state.run_config().max_http_response_size,
+            normalize_org_bucket_case: common_state.run_config().normalize_org_bucket_case.into(),
+            dedup_writes: common_state.run_config().dedup_writes.into(),
+            null_string_sentinels: common_state.run_config().null_string_sentinels.clone(),
+            validate_write_names: common_state.run_config().validate_write_names.into(),
+            max_fields_per_measurement: common_state.run_config().max_fields_per_measurement,
+            write_retries: common_state.run_config().write_retries,
+            strict_write_params: common_state.run_config().strict_write_params.into(),
+            schema_hints: schema_hints_by_database(&common_state.run_config().field_type_hints),
+            health_max_loading_chunks: common_state.run_config().health_max_loading_chunks,
             serving_readiness: common_state.serving_readiness().clone(),
             shutdown: CancellationToken::new(),
         }
@@ -71,6 +99,30 @@ impl ServerType for DatabaseServerType {
         self::http::route_request(self, req).await
     }
 
+    fn health(&self) -> Result<(), String> {
+        let max_loading_chunks = match self.health_max_loading_chunks {
+            Some(max_loading_chunks) => max_loading_chunks,
+            None => return Ok(()),
+        };
+
+        let loading_chunks: usize = self
+            .server
+            .databases()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|database| database.initialized_db())
+            .map(|db| db.loading_read_buffer_chunks())
+            .sum();
+
+        if loading_chunks > max_loading_chunks {
+            return Err(format!(
+                "{} chunks are being loaded into the read buffer, more than the limit of {}",
+                loading_chunks, max_loading_chunks
+            ));
+        }
+        Ok(())
+    }
+
     async fn server_grpc(self: Arc<Self>, builder_input: RpcBuilderInput) -> Result<(), RpcError> {
         self::rpc::server_grpc(self, builder_input).await
     }
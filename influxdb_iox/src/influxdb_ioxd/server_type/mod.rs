@@ -56,6 +56,13 @@ pub trait ServerType: std::fmt::Debug + Send + Sync + 'static {
         req: Request<Body>,
     ) -> Result<Response<Body>, Self::RouteError>;
 
+    /// Check whether the server is healthy, returning the reason if not.
+    ///
+    /// This is consulted by the shared `/health` route. Servers are always healthy by default.
+    fn health(&self) -> Result<(), String> {
+        Ok(())
+    }
+
     /// Construct and serve gRPC subsystem.
     async fn server_grpc(self: Arc<Self>, builder_input: RpcBuilderInput) -> Result<(), RpcError>;
 
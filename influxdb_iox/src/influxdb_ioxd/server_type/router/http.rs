@@ -4,6 +4,8 @@ use async_trait::async_trait;
 use data_types::DatabaseName;
 use dml::DmlOperation;
 use hyper::{Body, Method, Request, Response};
+use mutable_batch_lp::SchemaHint;
+use schema::Schema;
 use snafu::{ResultExt, Snafu};
 
 use crate::influxdb_ioxd::http::{
@@ -44,14 +46,61 @@ impl HttpDrivenDml for RouterServerType {
         Arc::clone(&self.lp_metrics)
     }
 
+    fn normalize_org_bucket_case(&self) -> bool {
+        self.normalize_org_bucket_case
+    }
+
+    fn dedup_writes(&self) -> bool {
+        self.dedup_writes
+    }
+
+    fn null_string_sentinels(&self) -> &[String] {
+        &self.null_string_sentinels
+    }
+
+    fn validate_write_names(&self) -> bool {
+        self.validate_write_names
+    }
+
+    fn max_fields_per_measurement(&self) -> Option<usize> {
+        self.max_fields_per_measurement
+    }
+
+    fn write_retries(&self) -> usize {
+        self.write_retries
+    }
+
+    fn strict_write_params(&self) -> bool {
+        self.strict_write_params
+    }
+
+    fn known_field_names(&self, _db_name: &DatabaseName<'_>, _table_name: &str) -> Vec<String> {
+        // The router does not hold a catalog
+        vec![]
+    }
+
+    fn explain_writes(&self) -> bool {
+        // The router does not hold a catalog to explain writes against
+        false
+    }
+
+    fn table_schema(&self, _db_name: &DatabaseName<'_>, _table_name: &str) -> Option<Arc<Schema>> {
+        // The router does not hold a catalog
+        None
+    }
+
+    fn schema_hint(&self, db_name: &DatabaseName<'_>) -> Option<&SchemaHint> {
+        self.schema_hints.get(db_name.as_str())
+    }
+
     async fn write(
         &self,
         db_name: &DatabaseName<'_>,
-        op: DmlOperation,
+        op: Arc<DmlOperation>,
     ) -> Result<(), InnerDmlError> {
         match self.server.router(db_name) {
             Some(router) => router
-                .write(op)
+                .write(Arc::try_unwrap(op).unwrap_or_else(|op| op.as_ref().clone()))
                 .await
                 .map_err(|e| InnerDmlError::InternalError {
                     db_name: db_name.to_string(),
@@ -91,8 +140,9 @@ mod tests {
         server_id::ServerId,
         timestamp::TimestampRange,
     };
-    use dml::{DmlDelete, DmlMeta, DmlOperation};
+    use dml::{DmlDelete, DmlMeta, DmlOperation, DmlWrite};
     use http::StatusCode;
+    use mutable_batch_lp::lines_to_batches;
     use reqwest::Client;
     use router::{grpc_client::MockClient, resolver::RemoteTemplate, server::RouterServer};
     use time::SystemProvider;
@@ -102,7 +152,9 @@ mod tests {
         http::{
             dml::test_utils::{
                 assert_delete_bad_request, assert_delete_unknown_database, assert_gzip_write,
-                assert_write, assert_write_metrics, assert_write_to_invalid_database,
+                assert_malformed_lp_write, assert_malformed_ndjson_write, assert_ndjson_write,
+                assert_precision_write, assert_truncated_gzip_write, assert_write,
+                assert_write_metrics, assert_write_to_invalid_database,
             },
             test_utils::{
                 assert_health, assert_metrics, assert_tracing, check_response, TestServer,
@@ -135,6 +187,13 @@ mod tests {
         assert_dbwrite(test_server, DmlOperation::Write(write)).await;
     }
 
+    #[tokio::test]
+    async fn test_precision_write() {
+        let test_server = test_server().await;
+        let write = assert_precision_write(&test_server).await;
+        assert_dbwrite(test_server, DmlOperation::Write(write)).await;
+    }
+
     #[tokio::test]
     async fn test_gzip_write() {
         let test_server = test_server().await;
@@ -142,6 +201,28 @@ mod tests {
         assert_dbwrite(test_server, DmlOperation::Write(write)).await;
     }
 
+    #[tokio::test]
+    async fn test_ndjson_write() {
+        let test_server = test_server().await;
+        let write = assert_ndjson_write(&test_server).await;
+        assert_dbwrite(test_server, DmlOperation::Write(write)).await;
+    }
+
+    #[tokio::test]
+    async fn test_malformed_ndjson_write() {
+        assert_malformed_ndjson_write(&test_server().await).await;
+    }
+
+    #[tokio::test]
+    async fn test_malformed_lp_write() {
+        assert_malformed_lp_write(&test_server().await).await;
+    }
+
+    #[tokio::test]
+    async fn test_truncated_gzip_write() {
+        assert_truncated_gzip_write(&test_server().await).await;
+    }
+
     #[tokio::test]
     async fn test_write_metrics() {
         assert_write_metrics(test_server().await, false).await;
@@ -152,6 +233,166 @@ mod tests {
         assert_write_to_invalid_database(test_server().await).await;
     }
 
+    #[tokio::test]
+    async fn test_write_dedup() {
+        let lp_data = "cpu,host=a usage=1 10\ncpu,host=b usage=1 10\ncpu,host=a usage=1 10\ncpu,host=a usage=2 10";
+        let deduped = "cpu,host=a usage=1 10\ncpu,host=b usage=1 10\ncpu,host=a usage=2 10";
+
+        for (dedup_writes, expected) in [(true, deduped), (false, lp_data)] {
+            let mut server_type = test_server_type().await;
+            server_type.dedup_writes = dedup_writes;
+            let test_server = TestServer::new(Arc::new(server_type));
+
+            let response = Client::new()
+                .post(&format!(
+                    "{}/api/v2/write?bucket=MyBucket&org=MyOrg",
+                    test_server.url(),
+                ))
+                .body(lp_data)
+                .send()
+                .await;
+            check_response("write", response, StatusCode::NO_CONTENT, Some("")).await;
+
+            let write = DmlWrite::new(lines_to_batches(expected, 0).unwrap(), Default::default());
+            assert_dbwrite(test_server, DmlOperation::Write(write)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_null_string_sentinel() {
+        let mut server_type = test_server_type().await;
+        server_type.null_string_sentinels = vec!["null".to_string()];
+        let test_server = TestServer::new(Arc::new(server_type));
+
+        let response = Client::new()
+            .post(&format!(
+                "{}/api/v2/write?bucket=MyBucket&org=MyOrg",
+                test_server.url(),
+            ))
+            .body("cpu,host=a status=\"null\",usage=1 10\ncpu,host=b status=\"ok\",usage=2 10")
+            .send()
+            .await;
+        check_response("write", response, StatusCode::NO_CONTENT, Some("")).await;
+
+        // the sentinel is written as if `status` was omitted from the line
+        let expected = "cpu,host=a usage=1 10\ncpu,host=b status=\"ok\",usage=2 10";
+        let write = DmlWrite::new(lines_to_batches(expected, 0).unwrap(), Default::default());
+        assert_dbwrite(test_server, DmlOperation::Write(write)).await;
+    }
+
+    #[tokio::test]
+    async fn test_write_strict_params() {
+        let lp_data = "cpu,host=a usage=1 10";
+        let url = |test_server: &TestServer<RouterServerType>| {
+            format!(
+                "{}/api/v2/write?bucket=MyBucket&org=MyOrg&precision=ns&rp=autogen&presicion=s",
+                test_server.url(),
+            )
+        };
+
+        let mut server_type = test_server_type().await;
+        server_type.strict_write_params = true;
+        let strict_server = TestServer::new(Arc::new(server_type));
+
+        let response = Client::new()
+            .post(&url(&strict_server))
+            .body(lp_data)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["code"], "invalid");
+        assert_eq!(body["message"], "Unknown query parameters: presicion, rp");
+
+        // unknown parameters are ignored by default
+        let test_server = test_server().await;
+        let response = Client::new()
+            .post(&url(&test_server))
+            .body(lp_data)
+            .send()
+            .await;
+        check_response("write", response, StatusCode::NO_CONTENT, Some("")).await;
+
+        let write = DmlWrite::new(lines_to_batches(lp_data, 0).unwrap(), Default::default());
+        assert_dbwrite(test_server, DmlOperation::Write(write)).await;
+    }
+
+    #[tokio::test]
+    async fn test_write_explain_unsupported() {
+        let test_server = test_server().await;
+
+        let response = Client::new()
+            .post(&format!(
+                "{}/api/v2/write?bucket=MyBucket&org=MyOrg&explain=true",
+                test_server.url(),
+            ))
+            .body("cpu,host=a usage=1 10")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["code"], "invalid");
+        assert_eq!(
+            body["message"],
+            "Explaining writes is not supported by this server"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_validate_names() {
+        let mut server_type = test_server_type().await;
+        server_type.validate_write_names = true;
+        let test_server = TestServer::new(Arc::new(server_type));
+
+        for (lp_data, message) in [
+            (
+                "cpu,time=a usage=1 10",
+                "Error parsing line protocol: error writing line 1: \
+                 tag key 'time' collides with the timestamp column",
+            ),
+            (
+                "cpu,host=a _usage=1 10",
+                "Invalid field key '_usage': names beginning with an underscore are reserved",
+            ),
+            (
+                "_cpu,host=a usage=1 10",
+                "Invalid measurement '_cpu': names beginning with an underscore are reserved",
+            ),
+        ] {
+            let response = Client::new()
+                .post(&format!(
+                    "{}/api/v2/write?bucket=MyBucket&org=MyOrg",
+                    test_server.url(),
+                ))
+                .body(lp_data)
+                .send()
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["code"], "invalid");
+            assert_eq!(body["message"], message);
+        }
+
+        // valid names are written as usual
+        let lp_data = "cpu,host=a usage=1 10";
+        let response = Client::new()
+            .post(&format!(
+                "{}/api/v2/write?bucket=MyBucket&org=MyOrg",
+                test_server.url(),
+            ))
+            .body(lp_data)
+            .send()
+            .await;
+        check_response("write", response, StatusCode::NO_CONTENT, Some("")).await;
+
+        let write = DmlWrite::new(lines_to_batches(lp_data, 0).unwrap(), Default::default());
+        assert_dbwrite(test_server, DmlOperation::Write(write)).await;
+    }
+
     #[tokio::test]
     async fn test_delete() {
         // Set up server
@@ -199,6 +440,10 @@ mod tests {
     }
 
     async fn test_server() -> TestServer<RouterServerType> {
+        TestServer::new(Arc::new(test_server_type().await))
+    }
+
+    async fn test_server_type() -> RouterServerType {
         use data_types::router::{
             Matcher, MatcherToShard, Router, ShardConfig, ShardId, WriteSink, WriteSinkSet,
             WriteSinkVariant,
@@ -242,8 +487,7 @@ mod tests {
             query_sinks: Default::default(),
         });
 
-        let server_type = Arc::new(RouterServerType::new(server, &common_state));
-        TestServer::new(server_type)
+        RouterServerType::new(server, &common_state)
     }
 
     async fn assert_dbwrite(test_server: TestServer<RouterServerType>, write: DmlOperation) {
@@ -1,17 +1,21 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
 use hyper::{Body, Request, Response};
 use metric::Registry;
+use mutable_batch_lp::SchemaHint;
 use router::server::RouterServer;
 use tokio_util::sync::CancellationToken;
 use trace::TraceCollector;
 
-use crate::influxdb_ioxd::{
-    http::metrics::LineProtocolMetrics,
-    rpc::RpcBuilderInput,
-    server_type::{common_state::CommonServerState, RpcError, ServerType},
-    serving_readiness::ServingReadiness,
+use crate::{
+    clap_blocks::field_type_hint::schema_hints_by_database,
+    influxdb_ioxd::{
+        http::metrics::LineProtocolMetrics,
+        rpc::RpcBuilderInput,
+        server_type::{common_state::CommonServerState, RpcError, ServerType},
+        serving_readiness::ServingReadiness,
+    },
 };
 
 mod http;
@@ -25,18 +29,40 @@ pub struct RouterServerType {
     serving_readiness: ServingReadiness,
     shutdown: CancellationToken,
     max_request_size: usize,
+    normalize_org_bucket_case: bool,
+    dedup_writes: bool,
+    null_string_sentinels: Vec<String>,
+    validate_write_names: bool,
+    max_fields_per_measurement: Option<usize>,
+    write_retries: usize,
+    strict_write_params: bool,
+    schema_hints: HashMap<String, SchemaHint>,
     lp_metrics: Arc<LineProtocolMetrics>,
 }
 
 impl RouterServerType {
     pub fn new(server: Arc<RouterServer>, common_state: &CommonServerState) -> Self {
-        let lp_metrics = Arc::new(LineProtocolMetrics::new(server.metric_registry().as_ref()));
+        let lp_metrics = Arc::new(LineProtocolMetrics::new(
+            server.metric_registry().as_ref(),
+            common_state
+                .run_config()
+                .ingest_metrics_org_attribute
+                .into(),
+        ));
 
         Self {
             server,
             serving_readiness: common_state.serving_readiness().clone(),
             shutdown: CancellationToken::new(),
             max_request_size: common_state.run_config().max_http_request_size,
+            normalize_org_bucket_case: common_state.run_config().normalize_org_bucket_case.into(),
+            dedup_writes: common_state.run_config().dedup_writes.into(),
+            null_string_sentinels: common_state.run_config().null_string_sentinels.clone(),
+            validate_write_names: common_state.run_config().validate_write_names.into(),
+            max_fields_per_measurement: common_state.run_config().max_fields_per_measurement,
+            write_retries: common_state.run_config().write_retries,
+            strict_write_params: common_state.run_config().strict_write_params.into(),
+            schema_hints: schema_hints_by_database(&common_state.run_config().field_type_hints),
             lp_metrics,
         }
     }
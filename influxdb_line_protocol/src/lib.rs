@@ -472,12 +472,21 @@ impl PartialEq<String> for EscapedStr<'_> {
 }
 
 pub fn parse_lines(input: &str) -> impl Iterator<Item = Result<ParsedLine<'_>>> {
-    split_lines(input).filter_map(|line| {
+    parse_lines_with_offsets(input).map(|(_, res)| res)
+}
+
+/// As [`parse_lines`], but also yields the byte offset within `input` at which
+/// each parsed line starts, ignoring leading whitespace and comments.
+pub fn parse_lines_with_offsets(
+    input: &str,
+) -> impl Iterator<Item = (usize, Result<ParsedLine<'_>>)> {
+    split_lines(input).filter_map(move |line| {
         let i = trim_leading(line);
 
         if i.is_empty() {
             return None;
         }
+        let offset = i.as_ptr() as usize - input.as_ptr() as usize;
 
         let res = match parse_line(i) {
             Ok((remaining, line)) => {
@@ -500,7 +509,7 @@ pub fn parse_lines(input: &str) -> impl Iterator<Item = Result<ParsedLine<'_>>>
         if let Some(Err(r)) = &res {
             debug!("Error parsing line: '{}'. Error was {:?}", line, r);
         }
-        res
+        res.map(|res| (offset, res))
     })
 }
 
@@ -1209,6 +1218,18 @@ mod test {
         assert_eq!(vals.unwrap().len(), 0);
     }
 
+    #[test]
+    fn parse_with_offsets() {
+        let input = "foo a=1 1\n\n  # comment\n  bar b=2 2\nbaz 3";
+        let offsets = parse_lines_with_offsets(input)
+            .map(|(offset, res)| (offset, res.is_ok()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(offsets, vec![(0, true), (25, true), (35, false)]);
+        assert!(input[25..].starts_with("bar"));
+        assert!(input[35..].starts_with("baz"));
+    }
+
     #[test]
     fn parse_no_fields() {
         let input = "foo 1234";
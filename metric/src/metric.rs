@@ -247,6 +247,11 @@ impl<T: MetricObserver> RecorderCollection<T> {
             .entry(attributes.into())
             .or_insert_with_key(|key| metric.recorder(key.clone()))
     }
+
+    /// Drops all the recorders cached on this `RecorderCollection`
+    pub fn clear(&mut self) {
+        self.recorders.clear()
+    }
 }
 
 impl<T: MetricObserver> RecorderCollection<T>
@@ -60,6 +60,22 @@ pub enum Error {
 /// A specialized `Error` for [`Column`] errors
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// The value of a single row of a [`Column`], in a form that can be hashed
+/// and compared without formatting it
+///
+/// Floats are compared by their bit pattern and tags by their [`DID`], which
+/// is only meaningful when comparing rows of the same column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum RowValue<'a> {
+    Null,
+    F64(u64),
+    I64(i64),
+    U64(u64),
+    String(&'a str),
+    Bool(bool),
+    Tag(DID),
+}
+
 /// Stores the actual data for columns in a chunk along with summary
 /// statistics
 #[derive(Debug, Clone)]
@@ -165,6 +181,22 @@ impl Column {
         &self.data
     }
 
+    /// Returns the value of `row` in this column
+    pub(crate) fn row_value(&self, row: usize) -> RowValue<'_> {
+        if !self.valid.get(row) {
+            return RowValue::Null;
+        }
+
+        match &self.data {
+            ColumnData::F64(data, _) => RowValue::F64(data[row].to_bits()),
+            ColumnData::I64(data, _) => RowValue::I64(data[row]),
+            ColumnData::U64(data, _) => RowValue::U64(data[row]),
+            ColumnData::String(data, _) => RowValue::String(data.get(row).unwrap_or_default()),
+            ColumnData::Bool(data, _) => RowValue::Bool(data.get(row)),
+            ColumnData::Tag(data, _, _) => RowValue::Tag(data[row]),
+        }
+    }
+
     /// Ensures that the total length of this column is `len` rows,
     /// padding it with trailing NULLs if necessary
     pub(crate) fn push_nulls_to_len(&mut self, len: usize) {
@@ -203,6 +235,46 @@ impl Column {
         }
     }
 
+    /// Replaces values of a string field for which `is_null` returns true
+    /// with NULL, updating the column's statistics accordingly.
+    ///
+    /// Columns of any other type are left unchanged.
+    pub(crate) fn nullify_strings(&mut self, is_null: impl Fn(&str) -> bool) {
+        if self.influx_type != InfluxColumnType::Field(InfluxFieldType::String) {
+            return;
+        }
+
+        let (data, stats) = match &mut self.data {
+            ColumnData::String(data, stats) => (data, stats),
+            x => unreachable!("expected string got {}", x),
+        };
+
+        let mut valid = BitSet::new();
+        let mut new_data = PackedStringArray::new();
+        let mut new_stats = StatValues::new_empty();
+        let mut nulls = 0;
+
+        for (idx, value) in data.iter().enumerate() {
+            if self.valid.get(idx) && !is_null(value) {
+                valid.append_set(1);
+                new_data.append(value);
+                new_stats.update(value);
+            } else {
+                valid.append_unset(1);
+                new_data.extend(1);
+                nulls += 1;
+            }
+        }
+
+        // The distinct count of the original values no longer holds, and is
+        // left unset as for any other string field
+        new_stats.update_for_nulls(nulls);
+
+        self.valid = valid;
+        *data = new_data;
+        *stats = new_stats;
+    }
+
     /// Returns the number of rows in this column
     pub fn len(&self) -> usize {
         self.valid.len()
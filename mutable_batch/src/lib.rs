@@ -19,7 +19,7 @@
 use std::ops::Range;
 
 use arrow::record_batch::RecordBatch;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use snafu::{OptionExt, ResultExt, Snafu};
 
 use data_types::write_summary::TimestampSummary;
@@ -159,6 +159,16 @@ impl MutableBatch {
         Some(summary)
     }
 
+    /// Returns the write timestamps of this chunk, in row order, if a time
+    /// column exists
+    pub fn timestamps(&self) -> Option<&[i64]> {
+        let time = self.column_names.get(TIME_COLUMN_NAME)?;
+        match &self.columns[*time].data {
+            ColumnData::I64(col_data, _) => Some(col_data),
+            _ => unreachable!(),
+        }
+    }
+
     /// Extend this [`MutableBatch`] with the contents of `other`
     pub fn extend_from(&mut self, other: &Self) -> Result<()> {
         let mut writer = writer::Writer::new(self, other.row_count);
@@ -185,6 +195,42 @@ impl MutableBatch {
         Ok(())
     }
 
+    /// Returns a copy of this [`MutableBatch`] that retains only the first
+    /// occurrence of each row, where two rows are duplicates if they have
+    /// identical values (including nulls) for every column.
+    pub fn dedup_rows(&self) -> Result<Self> {
+        let mut seen = HashSet::with_capacity(self.row_count);
+        let mut ranges: Vec<Range<usize>> = vec![];
+        for row in 0..self.row_count {
+            let key: Vec<_> = self
+                .columns
+                .iter()
+                .map(|column| column.row_value(row))
+                .collect();
+
+            if !seen.insert(key) {
+                continue;
+            }
+
+            match ranges.last_mut() {
+                Some(range) if range.end == row => range.end += 1,
+                _ => ranges.push(row..row + 1),
+            }
+        }
+
+        let mut deduped = Self::new();
+        deduped.extend_from_ranges(self, &ranges)?;
+        Ok(deduped)
+    }
+
+    /// Replaces values of string fields that are equal to any of `sentinels`
+    /// with NULL.
+    pub fn nullify_string_sentinels(&mut self, sentinels: &[String]) {
+        for column in &mut self.columns {
+            column.nullify_strings(|value| sentinels.iter().any(|s| s == value));
+        }
+    }
+
     /// Returns a reference to the specified column
     pub fn column(&self, column: &str) -> Result<&Column> {
         let idx = self
@@ -0,0 +1,51 @@
+use arrow_util::assert_batches_eq;
+use mutable_batch::writer::Writer;
+use mutable_batch::MutableBatch;
+use schema::selection::Selection;
+
+#[test]
+fn test_dedup_rows() {
+    let mut batch = MutableBatch::new();
+    let mut writer = Writer::new(&mut batch, 6);
+
+    writer
+        .write_tag(
+            "tag1",
+            Some(&[0b00111011]),
+            vec!["v1", "v1", "v2", "v1", "v1"].into_iter(),
+        )
+        .unwrap();
+
+    writer
+        .write_f64(
+            "f64",
+            Some(&[0b00111111]),
+            vec![1., 1., 1., 1., 2., 1.].into_iter(),
+        )
+        .unwrap();
+
+    writer
+        .write_time("time", vec![0, 0, 0, 0, 0, 0].into_iter())
+        .unwrap();
+
+    writer.commit();
+
+    let deduped = batch.dedup_rows().unwrap();
+    assert_eq!(deduped.rows(), 4);
+
+    // the tag value is null for the third row, so it is not a duplicate of
+    // the first row
+    assert_batches_eq!(
+        &[
+            "+-----+------+----------------------+",
+            "| f64 | tag1 | time                 |",
+            "+-----+------+----------------------+",
+            "| 1   | v1   | 1970-01-01T00:00:00Z |",
+            "| 1   |      | 1970-01-01T00:00:00Z |",
+            "| 1   | v2   | 1970-01-01T00:00:00Z |",
+            "| 2   | v1   | 1970-01-01T00:00:00Z |",
+            "+-----+------+----------------------+",
+        ],
+        &[deduped.to_arrow(Selection::All).unwrap()]
+    );
+}
@@ -0,0 +1,59 @@
+use arrow_util::assert_batches_eq;
+use data_types::partition_metadata::{StatValues, Statistics};
+use mutable_batch::writer::Writer;
+use mutable_batch::MutableBatch;
+use schema::selection::Selection;
+
+#[test]
+fn test_nullify_string_sentinels() {
+    let mut batch = MutableBatch::new();
+    let mut writer = Writer::new(&mut batch, 5);
+
+    writer
+        .write_string(
+            "status",
+            Some(&[0b00011011]),
+            vec!["ok", "null", "", "failed"].into_iter(),
+        )
+        .unwrap();
+
+    writer
+        .write_tag("host", None, vec!["null", "a", "", "b", "c"].into_iter())
+        .unwrap();
+
+    writer
+        .write_time("time", vec![0, 1, 2, 3, 4].into_iter())
+        .unwrap();
+
+    writer.commit();
+
+    batch.nullify_string_sentinels(&["null".to_string(), "".to_string()]);
+
+    // tags are not string fields, so are left unchanged
+    assert_batches_eq!(
+        &[
+            "+------+--------+--------------------------------+",
+            "| host | status | time                           |",
+            "+------+--------+--------------------------------+",
+            "| null | ok     | 1970-01-01T00:00:00Z           |",
+            "| a    |        | 1970-01-01T00:00:00.000000001Z |",
+            "|      |        | 1970-01-01T00:00:00.000000002Z |",
+            "| b    |        | 1970-01-01T00:00:00.000000003Z |",
+            "| c    | failed | 1970-01-01T00:00:00.000000004Z |",
+            "+------+--------+--------------------------------+",
+        ],
+        &[batch.to_arrow(Selection::All).unwrap()]
+    );
+
+    // the statistics reflect the remaining values
+    assert_eq!(
+        batch.column("status").unwrap().stats(),
+        Statistics::String(StatValues {
+            min: Some("failed".to_string()),
+            max: Some("ok".to_string()),
+            total_count: 5,
+            null_count: 3,
+            distinct_count: None,
+        })
+    );
+}
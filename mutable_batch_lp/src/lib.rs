@@ -11,11 +11,13 @@
     clippy::clone_on_ref_ptr
 )]
 
+use data_types::precision::Precision;
 use hashbrown::HashMap;
-use influxdb_line_protocol::{parse_lines, FieldValue, ParsedLine};
+use influxdb_line_protocol::{parse_lines_with_offsets, FieldValue, ParsedLine};
 use mutable_batch::writer::Writer;
 use mutable_batch::MutableBatch;
-use snafu::{ensure, ResultExt, Snafu};
+use schema::{InfluxColumnType, InfluxFieldType, TIME_COLUMN_NAME};
+use snafu::{ensure, OptionExt, ResultExt, Snafu};
 
 /// Error type for line protocol conversion
 #[derive(Debug, Snafu)]
@@ -25,18 +27,51 @@ pub enum Error {
     LineProtocol {
         source: influxdb_line_protocol::Error,
         line: usize,
+        offset: usize,
     },
 
     #[snafu(display("error writing line {}: {}", line, source))]
     Write {
         source: mutable_batch::writer::Error,
         line: usize,
+        offset: usize,
     },
 
+    #[snafu(display(
+        "error writing line {}: {} '{}' collides with the timestamp column",
+        line,
+        kind,
+        name
+    ))]
+    ReservedKey {
+        kind: &'static str,
+        name: String,
+        line: usize,
+        offset: usize,
+    },
+
+    #[snafu(display("timestamp of line {} overflows when converted to nanoseconds", line))]
+    TimestampOverflow { line: usize, offset: usize },
+
     #[snafu(display("empty write payload"))]
     EmptyPayload,
 }
 
+impl Error {
+    /// Returns the position of the line that caused this error, if any, as
+    /// its 1-based line number and the byte offset at which it starts within
+    /// the payload.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::LineProtocol { line, offset, .. }
+            | Self::Write { line, offset, .. }
+            | Self::ReservedKey { line, offset, .. }
+            | Self::TimestampOverflow { line, offset } => Some((*line, *offset)),
+            Self::EmptyPayload => None,
+        }
+    }
+}
+
 /// Result type for line protocol conversion
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -49,6 +84,51 @@ pub struct PayloadStatistics {
     pub num_lines: usize,
 }
 
+/// A field of a measurement whose values were converted from the type written
+/// to the type pinned for the field by a [`SchemaHint`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldCoercion {
+    /// The measurement of the field
+    pub measurement: String,
+    /// The name of the field
+    pub field: String,
+    /// The type of the values written
+    pub from: InfluxFieldType,
+    /// The type pinned for the field
+    pub to: InfluxFieldType,
+}
+
+/// Pre-declared field types for a set of measurements
+///
+/// Integer values written to a pinned float field are converted to floats,
+/// and any other value not matching the pinned type is rejected. This allows
+/// the first write to a measurement to establish the intended column types
+/// rather than inferring them.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SchemaHint {
+    measurements: HashMap<String, HashMap<String, InfluxFieldType>>,
+}
+
+impl SchemaHint {
+    /// Pins the type of `field` within `measurement` to `field_type`
+    pub fn pin_field(
+        &mut self,
+        measurement: impl Into<String>,
+        field: impl Into<String>,
+        field_type: InfluxFieldType,
+    ) {
+        self.measurements
+            .entry(measurement.into())
+            .or_default()
+            .insert(field.into(), field_type);
+    }
+
+    /// Returns the pinned type of `field` within `measurement`, if any
+    pub fn field_type(&self, measurement: &str, field: &str) -> Option<InfluxFieldType> {
+        self.measurements.get(measurement)?.get(field).copied()
+    }
+}
+
 /// Converts the provided lines of line protocol to a set of [`MutableBatch`]
 /// keyed by measurement name
 pub fn lines_to_batches(lines: &str, default_time: i64) -> Result<HashMap<String, MutableBatch>> {
@@ -61,29 +141,120 @@ pub fn lines_to_batches_stats(
     lines: &str,
     default_time: i64,
 ) -> Result<(HashMap<String, MutableBatch>, PayloadStatistics)> {
+    lines_to_batches_stats_with_hint(lines, default_time, Precision::Nanoseconds, None)
+}
+
+/// Converts the provided lines of line protocol to a set of [`MutableBatch`]
+/// keyed by measurement name, conforming field values to the types pinned by
+/// `hint` (if any), a set of statistics about the converted line protocol and
+/// the fields whose values were converted to their pinned type
+///
+/// Timestamps in `lines` are interpreted with the given `precision`, whereas
+/// `default_time` is always in nanoseconds
+pub fn lines_to_batches_stats_with_hint(
+    lines: &str,
+    default_time: i64,
+    precision: Precision,
+    hint: Option<&SchemaHint>,
+) -> Result<(
+    HashMap<String, MutableBatch>,
+    PayloadStatistics,
+    Vec<FieldCoercion>,
+)> {
     let mut stats = PayloadStatistics::default();
     let mut batches = HashMap::new();
-    for (line_idx, maybe_line) in parse_lines(lines).enumerate() {
-        let line = maybe_line.context(LineProtocolSnafu { line: line_idx + 1 })?;
+    let mut coercions: Vec<FieldCoercion> = vec![];
+    // line numbers are tracked incrementally from the offset of each line
+    let mut line_number = 1;
+    let mut line_number_offset = 0;
+    for (offset, maybe_line) in parse_lines_with_offsets(lines) {
+        line_number += lines.as_bytes()[line_number_offset..offset]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+        line_number_offset = offset;
+
+        let line = maybe_line.context(LineProtocolSnafu {
+            line: line_number,
+            offset,
+        })?;
 
         stats.num_lines += 1;
         stats.num_fields += line.field_set.len();
 
         let measurement = line.series.measurement.as_str();
 
+        let tag_keys = line
+            .series
+            .tag_set
+            .iter()
+            .flatten()
+            .map(|(key, _)| ("tag key", key));
+        let field_keys = line.field_set.iter().map(|(key, _)| ("field key", key));
+        for (kind, key) in tag_keys.chain(field_keys) {
+            ensure!(
+                key.as_str() != TIME_COLUMN_NAME,
+                ReservedKeySnafu {
+                    kind,
+                    name: key.as_str(),
+                    line: line_number,
+                    offset,
+                }
+            );
+        }
+
         let (_, batch) = batches
             .raw_entry_mut()
             .from_key(measurement)
             .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
 
+        let time = match line.timestamp {
+            Some(timestamp) => timestamp
+                .checked_mul(precision.multiplier_nanos())
+                .context(TimestampOverflowSnafu {
+                    line: line_number,
+                    offset,
+                })?,
+            None => default_time,
+        };
+
         // TODO: Reuse writer
         let mut writer = Writer::new(batch, 1);
-        write_line(&mut writer, &line, default_time).context(WriteSnafu { line: line_idx + 1 })?;
+        let field_types = hint.and_then(|hint| hint.measurements.get(measurement));
+        write_line_with_hint(&mut writer, &line, time, field_types).context(WriteSnafu {
+            line: line_number,
+            offset,
+        })?;
         writer.commit();
+
+        // values that do not match their pinned type were converted above
+        let field_types = match field_types {
+            Some(field_types) => field_types,
+            None => continue,
+        };
+        for (field_key, field_value) in &line.field_set {
+            let field = field_key.as_str();
+            let from = field_value_type(field_value);
+            let to = match field_types.get(field) {
+                Some(to) if *to != from => *to,
+                _ => continue,
+            };
+            let known = coercions
+                .iter()
+                .any(|c| c.measurement == measurement && c.field == field && c.from == from);
+            if !known {
+                coercions.push(FieldCoercion {
+                    measurement: measurement.to_string(),
+                    field: field.to_string(),
+                    from,
+                    to,
+                });
+            }
+        }
     }
     ensure!(!batches.is_empty(), EmptyPayloadSnafu);
 
-    Ok((batches, stats))
+    Ok((batches, stats, coercions))
 }
 
 /// Writes the [`ParsedLine`] to the [`MutableBatch`]
@@ -91,37 +262,73 @@ pub fn write_line(
     writer: &mut Writer<'_>,
     line: &ParsedLine<'_>,
     default_time: i64,
+) -> mutable_batch::writer::Result<()> {
+    let time = line.timestamp.unwrap_or(default_time);
+    write_line_with_hint(writer, line, time, None)
+}
+
+/// Writes the [`ParsedLine`] with the nanosecond timestamp `time` to the
+/// [`MutableBatch`], coercing field values to the types in `field_types`
+fn write_line_with_hint(
+    writer: &mut Writer<'_>,
+    line: &ParsedLine<'_>,
+    time: i64,
+    field_types: Option<&HashMap<String, InfluxFieldType>>,
 ) -> mutable_batch::writer::Result<()> {
     for (tag_key, tag_value) in line.series.tag_set.iter().flatten() {
         writer.write_tag(tag_key.as_str(), None, std::iter::once(tag_value.as_str()))?
     }
 
     for (field_key, field_value) in &line.field_set {
-        match field_value {
-            FieldValue::I64(value) => {
+        let pinned = field_types.and_then(|types| types.get(field_key.as_str()).copied());
+        match (pinned, field_value) {
+            // Integers beyond 2^53 lose precision when converted to floats
+            (Some(InfluxFieldType::Float), FieldValue::I64(value)) => {
+                writer.write_f64(field_key.as_str(), None, std::iter::once(*value as f64))?;
+            }
+            (Some(InfluxFieldType::Float), FieldValue::U64(value)) => {
+                writer.write_f64(field_key.as_str(), None, std::iter::once(*value as f64))?;
+            }
+            (Some(pinned), value) if pinned != field_value_type(value) => {
+                return Err(mutable_batch::writer::Error::TypeMismatch {
+                    existing: InfluxColumnType::Field(pinned),
+                    inserted: InfluxColumnType::Field(field_value_type(value)),
+                });
+            }
+            (_, FieldValue::I64(value)) => {
                 writer.write_i64(field_key.as_str(), None, std::iter::once(*value))?;
             }
-            FieldValue::U64(value) => {
+            (_, FieldValue::U64(value)) => {
                 writer.write_u64(field_key.as_str(), None, std::iter::once(*value))?;
             }
-            FieldValue::F64(value) => {
+            (_, FieldValue::F64(value)) => {
                 writer.write_f64(field_key.as_str(), None, std::iter::once(*value))?;
             }
-            FieldValue::String(value) => {
+            (_, FieldValue::String(value)) => {
                 writer.write_string(field_key.as_str(), None, std::iter::once(value.as_str()))?;
             }
-            FieldValue::Boolean(value) => {
+            (_, FieldValue::Boolean(value)) => {
                 writer.write_bool(field_key.as_str(), None, std::iter::once(*value))?;
             }
         }
     }
 
-    let time = line.timestamp.unwrap_or(default_time);
     writer.write_time("time", std::iter::once(time))?;
 
     Ok(())
 }
 
+/// Returns the [`InfluxFieldType`] of a line protocol field value
+pub fn field_value_type(value: &FieldValue<'_>) -> InfluxFieldType {
+    match value {
+        FieldValue::I64(_) => InfluxFieldType::Integer,
+        FieldValue::U64(_) => InfluxFieldType::UInteger,
+        FieldValue::F64(_) => InfluxFieldType::Float,
+        FieldValue::String(_) => InfluxFieldType::String,
+        FieldValue::Boolean(_) => InfluxFieldType::Boolean,
+    }
+}
+
 /// Test helper utilities
 pub mod test_helpers {
     use mutable_batch::MutableBatch;
@@ -180,4 +387,118 @@ mod tests {
             &[batch["mem"].to_arrow(Selection::All).unwrap()]
         );
     }
+
+    #[test]
+    fn test_schema_hint() {
+        let mut hint = SchemaHint::default();
+        hint.pin_field("cpu", "val", InfluxFieldType::Float);
+
+        // An integer written to a pinned float field is coerced
+        let lp = "cpu,tag1=v1 val=2i 0\ncpu,tag1=v2 val=1.5 1\nmem ival=3i 0";
+        let (batches, _, coercions) =
+            lines_to_batches_stats_with_hint(lp, 5, Precision::Nanoseconds, Some(&hint)).unwrap();
+
+        assert_eq!(
+            coercions,
+            vec![FieldCoercion {
+                measurement: "cpu".to_string(),
+                field: "val".to_string(),
+                from: InfluxFieldType::Integer,
+                to: InfluxFieldType::Float,
+            }]
+        );
+
+        assert_batches_eq!(
+            &[
+                "+------+--------------------------------+-----+",
+                "| tag1 | time                           | val |",
+                "+------+--------------------------------+-----+",
+                "| v1   | 1970-01-01T00:00:00Z           | 2   |",
+                "| v2   | 1970-01-01T00:00:00.000000001Z | 1.5 |",
+                "+------+--------------------------------+-----+",
+            ],
+            &[batches["cpu"].to_arrow(Selection::All).unwrap()]
+        );
+
+        // Unpinned measurements are unaffected
+        assert_eq!(
+            batches["mem"].column("ival").unwrap().influx_type(),
+            InfluxColumnType::Field(InfluxFieldType::Integer)
+        );
+
+        // Values that cannot be coerced are rejected
+        let err = lines_to_batches_stats_with_hint(
+            "cpu val=\"foo\" 0",
+            5,
+            Precision::Nanoseconds,
+            Some(&hint),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Write {
+                source: mutable_batch::writer::Error::TypeMismatch { .. },
+                line: 1,
+                offset: 0,
+            }
+        ));
+    }
+
+    #[test]
+    fn test_precision() {
+        let lp = "cpu val=1 2\ncpu val=2";
+        let (batches, _, coercions) =
+            lines_to_batches_stats_with_hint(lp, 5, Precision::Seconds, None).unwrap();
+        assert!(coercions.is_empty());
+
+        // Explicit timestamps are scaled, the default time is not
+        assert_batches_eq!(
+            &[
+                "+--------------------------------+-----+",
+                "| time                           | val |",
+                "+--------------------------------+-----+",
+                "| 1970-01-01T00:00:02Z           | 1   |",
+                "| 1970-01-01T00:00:00.000000005Z | 2   |",
+                "+--------------------------------+-----+",
+            ],
+            &[batches["cpu"].to_arrow(Selection::All).unwrap()]
+        );
+
+        let lp = "cpu val=1 0\ncpu val=2 9223372036854775807";
+        let err =
+            lines_to_batches_stats_with_hint(lp, 5, Precision::Milliseconds, None).unwrap_err();
+        assert!(matches!(err, Error::TimestampOverflow { .. }));
+        assert_eq!(err.position(), Some((2, 12)));
+    }
+
+    #[test]
+    fn test_error_position() {
+        let lp = "cpu val=1 0\n\n# a comment\n  cpu val=2 1\ncpu val 2";
+        let err = lines_to_batches(lp, 5).unwrap_err();
+        assert!(matches!(err, Error::LineProtocol { .. }));
+        assert_eq!(err.position(), Some((5, 39)));
+        assert_eq!(&lp[39..], "cpu val 2");
+
+        let err = lines_to_batches("cpu val=1 0\ncpu val=\"a\" 1", 5).unwrap_err();
+        assert!(matches!(err, Error::Write { .. }));
+        assert_eq!(err.position(), Some((2, 12)));
+
+        assert_eq!(Error::EmptyPayload.position(), None);
+    }
+
+    #[test]
+    fn test_time_key() {
+        let err = lines_to_batches("cpu val=1 0\ncpu,time=a val=2 1", 5).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error writing line 2: tag key 'time' collides with the timestamp column"
+        );
+        assert_eq!(err.position(), Some((2, 12)));
+
+        let err = lines_to_batches("cpu time=1 0", 5).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "error writing line 1: field key 'time' collides with the timestamp column"
+        );
+    }
 }
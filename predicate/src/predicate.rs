@@ -13,6 +13,7 @@ use datafusion::{
     error::DataFusionError,
     logical_plan::{col, lit_timestamp_nano, Column, Expr, Operator},
     optimizer::utils,
+    scalar::ScalarValue,
 };
 use datafusion_util::{make_range_expr, AndExprBuilder};
 use observability_deps::tracing::debug;
@@ -208,6 +209,27 @@ impl Predicate {
         }
     }
 
+    /// Renders the row filters of this predicate, its timestamp range and
+    /// `exprs`, as a SQL `WHERE` clause without the `WHERE` keyword, e.g.
+    /// `time >= 100 AND time < 200 AND env = 'us-west'`.
+    ///
+    /// Restrictions on table names, field columns and the partition key are
+    /// not rendered. Returns an empty string if there are no row filters.
+    pub fn to_sql_string(&self) -> String {
+        let range = self.range.iter().flat_map(|range| {
+            [
+                format!("{} >= {}", TIME_COLUMN_NAME, range.start()),
+                format!("{} < {}", TIME_COLUMN_NAME, range.end()),
+            ]
+        });
+        let exprs = self
+            .exprs
+            .iter()
+            .map(|expr| sql_operand(expr, Operator::And));
+
+        range.chain(exprs).collect::<Vec<_>>().join(" AND ")
+    }
+
     /// Removes the timestamp range from this predicate, if the range
     /// is for the entire min/max valid range.
     ///
@@ -271,6 +293,96 @@ impl fmt::Display for Predicate {
     }
 }
 
+/// Renders `expr` as SQL. Expressions without a SQL rendering fall back to
+/// DataFusion's display format.
+fn sql_string(expr: &Expr) -> String {
+    match expr {
+        Expr::Column(column) => sql_identifier(&column.name),
+        Expr::Literal(value) => sql_literal(value),
+        Expr::BinaryExpr { left, op, right } => format!(
+            "{} {} {}",
+            sql_operand(left, *op),
+            op,
+            sql_operand(right, *op)
+        ),
+        Expr::Not(expr) => format!("NOT ({})", sql_string(expr)),
+        Expr::IsNull(expr) => format!("{} IS NULL", sql_string(expr)),
+        Expr::IsNotNull(expr) => format!("{} IS NOT NULL", sql_string(expr)),
+        Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+        } => format!(
+            "{} {}BETWEEN {} AND {}",
+            sql_string(expr),
+            if *negated { "NOT " } else { "" },
+            sql_string(low),
+            sql_string(high)
+        ),
+        Expr::InList {
+            expr,
+            list,
+            negated,
+        } => format!(
+            "{} {}IN ({})",
+            sql_string(expr),
+            if *negated { "NOT " } else { "" },
+            list.iter().map(sql_string).collect::<Vec<_>>().join(", ")
+        ),
+        other => other.to_string(),
+    }
+}
+
+/// Renders `expr` as an operand of `parent_op`, parenthesized if it is a
+/// binary expression, unless it is a comparison within a conjunction or
+/// disjunction, or a conjunction or disjunction of the same kind as
+/// `parent_op`
+fn sql_operand(expr: &Expr, parent_op: Operator) -> String {
+    let is_logical = |op: Operator| matches!(op, Operator::And | Operator::Or);
+    match expr {
+        Expr::BinaryExpr { op, .. } => {
+            let unambiguous = is_logical(parent_op)
+                && (*op == parent_op
+                    || matches!(
+                        op,
+                        Operator::Eq
+                            | Operator::NotEq
+                            | Operator::Lt
+                            | Operator::LtEq
+                            | Operator::Gt
+                            | Operator::GtEq
+                    ));
+            match unambiguous {
+                true => sql_string(expr),
+                false => format!("({})", sql_string(expr)),
+            }
+        }
+        _ => sql_string(expr),
+    }
+}
+
+/// Double quotes `name` unless it is a plain identifier
+fn sql_identifier(name: &str) -> String {
+    let plain = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    match plain {
+        true => name.to_string(),
+        false => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+/// Single quotes string literals, doubling any embedded quotes
+fn sql_literal(value: &ScalarValue) -> String {
+    match value {
+        ScalarValue::Utf8(Some(s)) | ScalarValue::LargeUtf8(Some(s)) => {
+            format!("'{}'", s.replace('\'', "''"))
+        }
+        value if value.is_null() => "NULL".to_string(),
+        value => value.to_string(),
+    }
+}
+
 #[derive(Debug, Default)]
 /// Structure for building [`Predicate`]s
 ///
@@ -516,6 +628,65 @@ mod tests {
         assert!(p.is_empty());
     }
 
+    #[test]
+    fn test_predicate_to_sql_string() {
+        assert_eq!(Predicate::default().to_sql_string(), "");
+
+        let p = PredicateBuilder::new()
+            .timestamp_range(100, 200)
+            .add_expr(col("env").eq(lit("us-west")))
+            .build();
+        assert_eq!(
+            p.to_sql_string(),
+            "time >= 100 AND time < 200 AND env = 'us-west'"
+        );
+
+        // string literals and identifiers are quoted
+        let p = PredicateBuilder::new()
+            .add_expr(col("city").not_eq(lit("O'Fallon")))
+            .add_expr(col("air temp").gt_eq(lit(70.5)))
+            .build();
+        assert_eq!(
+            p.to_sql_string(),
+            r#"city != 'O''Fallon' AND "air temp" >= 70.5"#
+        );
+
+        // disjunctions are parenthesized within the conjunction
+        let p = PredicateBuilder::new()
+            .add_expr(col("a").lt(lit(1i64)).or(col("b").eq(lit(true))))
+            .add_expr(col("host").in_list(vec![lit("a"), lit("b")], true))
+            .build();
+        assert_eq!(
+            p.to_sql_string(),
+            "(a < 1 OR b = true) AND host NOT IN ('a', 'b')"
+        );
+
+        // nested arithmetic and comparisons are parenthesized
+        let p = PredicateBuilder::new()
+            .add_expr(((col("a") + col("b")) * lit(2i64)).gt(col("c") - lit(1i64)))
+            .add_expr(col("flag").eq(col("x").lt(col("y"))))
+            .build();
+        assert_eq!(
+            p.to_sql_string(),
+            "((a + b) * 2) > (c - 1) AND flag = (x < y)"
+        );
+    }
+
+    #[test]
+    fn test_predicate_to_sql_string_negated_delete() {
+        let delete = PredicateBuilder::new()
+            .timestamp_range(10, 30)
+            .add_expr(col("city").eq(lit("Boston")))
+            .build();
+
+        let mut p = PredicateBuilder::new().timestamp_range(0, 100).build();
+        p.merge_delete_predicates(&[std::sync::Arc::new(delete)]);
+        assert_eq!(
+            p.to_sql_string(),
+            "time >= 0 AND time < 100 AND (time < 10 OR time > 30 OR NOT (city = 'Boston'))"
+        );
+    }
+
     #[test]
     fn test_non_default_predicate_is_not_empty() {
         let p = PredicateBuilder::new().timestamp_range(1, 100).build();
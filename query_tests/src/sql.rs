@@ -138,6 +138,7 @@ async fn sql_select_from_information_schema_tables() {
         "| public        | system             | chunk_columns       | BASE TABLE |",
         "| public        | system             | chunks              | BASE TABLE |",
         "| public        | system             | columns             | BASE TABLE |",
+        "| public        | system             | ingest              | BASE TABLE |",
         "| public        | system             | operations          | BASE TABLE |",
         "| public        | system             | persistence_windows | BASE TABLE |",
         "| public        | system             | queries             | BASE TABLE |",
@@ -1,19 +1,36 @@
 use crate::{
-    column::Statistics,
+    column::{Column, RowIDs, Statistics},
     row_group::{ColumnName, Predicate, RowGroup},
-    schema::{AggregateType, ResultSchema},
-    table::{self, Table},
+    schema::{
+        AggregateType, ColumnNameMatching, ColumnOrder, ColumnType, LogicalDataType, ResultSchema,
+        TimeWindow,
+    },
+    table::{self, ReadAggregateResults, Table},
+    value::OwnedValue,
+};
+use arrow::{
+    array::{Array, BooleanArray, TimestampNanosecondArray},
+    ipc::{reader::StreamReader, writer::StreamWriter},
+    record_batch::RecordBatch,
+    util::display::array_value_to_string,
+};
+use data_types::{
+    chunk_metadata::ChunkColumnSummary,
+    partition_metadata::{InfluxDbType, TableSummary},
+};
+use datafusion::logical_plan::Expr;
+use metric::{
+    Attributes, CumulativeGauge, CumulativeRecorder, DurationHistogram, DurationHistogramOptions,
+    Metric, RecorderCollection,
 };
-use arrow::record_batch::RecordBatch;
-use data_types::{chunk_metadata::ChunkColumnSummary, partition_metadata::TableSummary};
-use metric::{Attributes, CumulativeGauge, CumulativeRecorder, RecorderCollection};
 use observability_deps::tracing::debug;
 use schema::selection::Selection;
-use schema::{builder::Error as SchemaError, Schema};
-use snafu::{ResultExt, Snafu};
+use schema::{builder::Error as SchemaError, Schema, TIME_COLUMN_NAME};
+use snafu::{ensure, OptionExt, ResultExt, Snafu};
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     convert::TryFrom,
+    time::Duration,
 };
 
 #[derive(Debug, Snafu)]
@@ -35,10 +52,45 @@ pub enum Error {
         column_name: String,
         table_name: String,
     },
+
+    #[snafu(display("column '{}' already exists in table '{}'", column_name, table_name))]
+    ColumnAlreadyExists {
+        column_name: String,
+        table_name: String,
+    },
+
+    #[snafu(display("at least one record batch is required to create a chunk"))]
+    NoRecordBatches {},
+
+    #[snafu(display(
+        "record batch {} has a different schema to the first record batch",
+        index
+    ))]
+    RecordBatchSchemaMismatch { index: usize },
+
+    #[snafu(display("error converting chunk to or from Arrow IPC: {}", source))]
+    ArrowIpc { source: arrow::error::ArrowError },
+
+    #[snafu(display("invalid schema in Arrow IPC stream: {}", source))]
+    ArrowIpcSchema { source: schema::Error },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// A read-only view of a [`Chunk`] created by [`Chunk::read_only_fork`].
+///
+/// It shares the immutable row groups of the chunk it was forked from and
+/// exposes the chunk's read methods, but not those that modify it.
+pub struct ReadOnlyChunk(Chunk);
+
+impl std::ops::Deref for ReadOnlyChunk {
+    type Target = Chunk;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 /// A `Chunk` is a horizontal partition of data for a single table.
 pub struct Chunk {
     // All metrics for the chunk.
@@ -46,16 +98,32 @@ pub struct Chunk {
 
     // The table associated with the chunk.
     pub(crate) table: Table,
+
+    // How column names in predicates and projections are resolved.
+    column_name_matching: ColumnNameMatching,
+
+    // The order of explicitly selected columns in read results.
+    column_order: ColumnOrder,
 }
 
 impl Chunk {
     /// Start a new Chunk from the given record batch.
+    ///
+    /// A record batch without any rows produces a chunk with the batch's
+    /// schema but no row groups, as if created via `Chunk::empty`.
     pub fn new(
         table_name: impl Into<String>,
         table_data: RecordBatch,
         mut metrics: ChunkMetrics,
     ) -> Self {
         let table_name = table_name.into();
+        if table_data.num_rows() == 0 {
+            let schema = Schema::try_from(table_data.schema())
+                .expect("Valid time-series schema when creating chunk");
+            return Self::empty(table_name, &schema, metrics)
+                .expect("Valid time-series schema when creating chunk");
+        }
+
         let row_group = record_batch_to_row_group(&table_name, table_data);
         let storage_statistics = row_group.column_storage_statistics();
 
@@ -63,7 +131,103 @@ impl Chunk {
 
         metrics.update_column_storage_statistics(&storage_statistics);
 
-        Self { metrics, table }
+        Self {
+            metrics,
+            table,
+            column_name_matching: ColumnNameMatching::default(),
+            column_order: ColumnOrder::default(),
+        }
+    }
+
+    /// Start a new Chunk with the provided schema but no rows or row groups.
+    ///
+    /// The chunk's schema is available via `read_filter_table_schema`, and
+    /// reads against it return no data until row groups are added. Returns an
+    /// error if the schema contains columns that are not tags, fields or the
+    /// timestamp column.
+    pub fn empty(
+        table_name: impl Into<String>,
+        schema: &Schema,
+        metrics: ChunkMetrics,
+    ) -> Result<Self> {
+        Ok(Self {
+            metrics,
+            table: Table::with_schema(table_name, schema).context(TableSnafu)?,
+            column_name_matching: ColumnNameMatching::default(),
+            column_order: ColumnOrder::default(),
+        })
+    }
+
+    /// Start a new Chunk from the given record batches, each of which becomes
+    /// a row group in the chunk. All batches must have the same schema.
+    pub fn from_batches(
+        table_name: impl Into<String>,
+        batches: Vec<RecordBatch>,
+        mut metrics: ChunkMetrics,
+    ) -> Result<Self> {
+        let first_schema = batches.first().context(NoRecordBatchesSnafu)?.schema();
+        for (index, batch) in batches.iter().enumerate().skip(1) {
+            ensure!(
+                batch.schema() == first_schema,
+                RecordBatchSchemaMismatchSnafu { index }
+            );
+        }
+
+        let table_name = table_name.into();
+        let row_groups = batches
+            .into_iter()
+            .map(|batch| record_batch_to_row_group(&table_name, batch))
+            .collect::<Vec<_>>();
+        let storage_statistics = row_groups
+            .iter()
+            .map(|row_group| row_group.column_storage_statistics())
+            .collect::<Vec<_>>();
+
+        let mut row_groups = row_groups.into_iter();
+        let mut table = Table::with_row_group(
+            table_name,
+            row_groups.next().expect("at least one row group"),
+        );
+        for row_group in row_groups {
+            table.add_row_group(row_group);
+        }
+
+        for statistics in &storage_statistics {
+            metrics.update_column_storage_statistics(statistics);
+        }
+
+        Ok(Self {
+            metrics,
+            table,
+            column_name_matching: ColumnNameMatching::default(),
+            column_order: ColumnOrder::default(),
+        })
+    }
+
+    /// Reconstruct a Chunk from Arrow IPC stream bytes produced by
+    /// [`Chunk::to_arrow_ipc`]. Each record batch in the stream becomes a row
+    /// group in the chunk.
+    ///
+    /// A stream without any rows produces a chunk with the stream's schema
+    /// but no row groups.
+    pub fn from_arrow_ipc(
+        table_name: impl Into<String>,
+        bytes: &[u8],
+        metrics: ChunkMetrics,
+    ) -> Result<Self> {
+        let reader = StreamReader::try_new(bytes).context(ArrowIpcSnafu)?;
+        let schema = reader.schema();
+
+        let batches = reader
+            .filter(|batch| !matches!(batch, Ok(batch) if batch.num_rows() == 0))
+            .collect::<Result<Vec<_>, _>>()
+            .context(ArrowIpcSnafu)?;
+
+        if batches.is_empty() {
+            let schema = Schema::try_from(schema).context(ArrowIpcSchemaSnafu)?;
+            return Self::empty(table_name, &schema, metrics);
+        }
+        Self::from_batches(table_name, batches, metrics)
     }
 
     // Only used in tests and benchmarks
@@ -75,6 +239,8 @@ impl Chunk {
         Self {
             metrics,
             table: Table::with_row_group(table_name, row_group),
+            column_name_matching: ColumnNameMatching::default(),
+            column_order: ColumnOrder::default(),
         }
     }
 
@@ -89,12 +255,26 @@ impl Chunk {
         Self::base_size() + self.table.size()
     }
 
-    /// Return the estimated size for each column in the table.
-    /// Note there may be multiple entries for each column.
+    /// Return the estimated size for each column in the table, summed across
+    /// row groups and ordered by column name.
     pub fn column_sizes(&self) -> Vec<ChunkColumnSummary> {
         self.table.column_sizes()
     }
 
+    /// Returns the estimated size of the `n` largest columns in the table,
+    /// ordered by size descending and then by name. Sizes are summed across
+    /// all row groups so each column appears at most once.
+    pub fn largest_columns(&self, n: usize) -> Vec<ChunkColumnSummary> {
+        let mut columns = self.table.column_sizes();
+        columns.sort_by(|a, b| {
+            b.memory_bytes
+                .cmp(&a.memory_bytes)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        columns.truncate(n);
+        columns
+    }
+
     /// The total estimated size in bytes of this `Chunk` and all contained
     /// data if the data was not compressed but was stored contiguously in
     /// vectors. `include_nulls` allows the caller to factor in NULL values or
@@ -103,6 +283,13 @@ impl Chunk {
         self.table.size_raw(include_nulls)
     }
 
+    /// As `size_raw`, but also includes `parquet_size`, the size in bytes of
+    /// the chunk's persisted parquet file if there is one. The Read Buffer
+    /// knows nothing about object storage so callers must supply it.
+    pub fn size_raw_with_parquet(&self, include_nulls: bool, parquet_size: Option<usize>) -> usize {
+        self.size_raw(include_nulls) + parquet_size.unwrap_or_default()
+    }
+
     /// The total number of rows in all row groups in all tables in this chunk.
     pub fn rows(&self) -> u64 {
         self.table.rows()
@@ -125,11 +312,321 @@ impl Chunk {
             .update_column_storage_statistics(&storage_statistics);
     }
 
+    /// Recalculates the chunk's storage metrics from its current row groups,
+    /// correcting any drift in the incrementally maintained values.
+    pub fn recompute_metrics(&mut self) {
+        self.metrics.reset_column_storage_statistics(
+            self.table.row_groups() as u64,
+            &self.table.column_storage_statistics(),
+        );
+    }
+
+    /// Recalculates the storage metrics attributed to the encoding and
+    /// logical data type of the named column, leaving all other storage
+    /// metrics untouched.
+    ///
+    /// Storage metrics are not attributed by column name, so this corrects
+    /// the contributions of every column sharing an encoding and logical
+    /// data type with the named column. Use `recompute_metrics` to correct
+    /// all of the chunk's storage metrics.
+    pub fn refresh_column_stats(&mut self, name: &str) -> Result<()> {
+        ensure!(
+            self.table.meta().has_column(name),
+            ColumnDoesNotExistSnafu {
+                column_name: name,
+                table_name: self.table.name(),
+            }
+        );
+
+        self.metrics.refresh_column_storage_statistics(
+            &self.table.column_storage_statistics_for(name),
+            &self.table.column_storage_statistics(),
+        );
+        Ok(())
+    }
+
+    /// Rename the table held by the chunk without touching any of its row
+    /// data.
+    ///
+    /// Note: none of the chunk's metrics are currently attributed by table
+    /// name so there is nothing to re-register.
+    pub fn rename_table(&mut self, new_name: impl Into<String>) {
+        self.table.rename(new_name);
+    }
+
+    /// Rename the column `old` to `new` in every row group of the chunk without
+    /// touching any of its row data.
+    ///
+    /// Returns an error if `old` does not exist, `new` already exists, or
+    /// `old` is the timestamp column, which cannot be renamed.
+    pub fn rename_column(&mut self, old: &str, new: &str) -> Result<()> {
+        ensure!(
+            old != TIME_COLUMN_NAME,
+            UnsupportedOperationSnafu {
+                msg: format!("cannot rename the '{}' column", TIME_COLUMN_NAME),
+            }
+        );
+
+        let table_meta = self.table.meta();
+        ensure!(
+            table_meta.has_column(old),
+            ColumnDoesNotExistSnafu {
+                column_name: old,
+                table_name: self.table.name(),
+            }
+        );
+        ensure!(
+            !table_meta.has_column(new),
+            ColumnAlreadyExistsSnafu {
+                column_name: new,
+                table_name: self.table.name(),
+            }
+        );
+        // Release the meta-data so that it can be modified in place.
+        drop(table_meta);
+
+        self.table.rename_column(old, new);
+        Ok(())
+    }
+
+    /// Sets how column names in predicates and selections passed to
+    /// `read_filter` and `validate_predicate` are resolved against the
+    /// chunk's columns. Defaults to [`ColumnNameMatching::Exact`].
+    pub fn set_column_name_matching(&mut self, matching: ColumnNameMatching) {
+        self.column_name_matching = matching;
+    }
+
+    /// Returns how column names are resolved against the chunk's columns.
+    pub fn column_name_matching(&self) -> ColumnNameMatching {
+        self.column_name_matching
+    }
+
+    /// Sets the order of the columns returned by `read_filter` and
+    /// `read_filter_table_schema` when an explicit set of columns is
+    /// selected. Defaults to [`ColumnOrder::Selection`].
+    pub fn set_column_order(&mut self, order: ColumnOrder) {
+        self.column_order = order;
+    }
+
+    /// Returns the order of explicitly selected columns in read results.
+    pub fn column_order(&self) -> ColumnOrder {
+        self.column_order
+    }
+
+    /// Returns a new chunk holding only the rows with a timestamp in the range
+    /// `[start, end)`, leaving this chunk unchanged. If no rows fall within
+    /// the range then the new chunk is empty but retains this chunk's schema.
+    ///
+    /// The new chunk's metrics are not registered with any metric registry.
+    pub fn subset_by_time(&self, start: i64, end: i64) -> Result<Self> {
+        self.filter_to_new(&Predicate::with_time_range(&[], start, end), &[])
+    }
+
+    /// Returns a new chunk holding only the rows that satisfy `predicate` and
+    /// none of the `negated` predicates, leaving this chunk unchanged. If no
+    /// rows survive then the new chunk is empty but retains this chunk's
+    /// schema.
+    ///
+    /// The new chunk's metrics are not registered with any metric registry.
+    pub fn filter_to_new(&self, predicate: &Predicate, negated: &[Predicate]) -> Result<Self> {
+        let row_groups = self
+            .read_filter(predicate.clone(), Selection::All, negated.to_vec())?
+            .map(|rb| record_batch_to_row_group(self.table.name(), rb));
+
+        let mut metrics = ChunkMetrics::new_unregistered();
+        let mut table = self.table.new_empty_like();
+        for row_group in row_groups {
+            metrics.update_column_storage_statistics(&row_group.column_storage_statistics());
+            table.add_row_group(row_group);
+        }
+
+        Ok(Self {
+            metrics,
+            table,
+            column_name_matching: self.column_name_matching,
+            column_order: self.column_order,
+        })
+    }
+
+    /// Returns a read-only view of this chunk that shares its row groups.
+    ///
+    /// The fork's metrics are not registered with any metric registry, so
+    /// creating (and dropping) transient query-time wrappers around a chunk
+    /// does not affect the chunk's reported metrics.
+    pub fn read_only_fork(&self) -> ReadOnlyChunk {
+        ReadOnlyChunk(Self {
+            metrics: ChunkMetrics::new_unregistered(),
+            table: self.table.fork(),
+            column_name_matching: self.column_name_matching,
+            column_order: self.column_order,
+        })
+    }
+
+    /// Consumes the chunk, producing one chunk per row group. Each new chunk
+    /// holds the same table name and has its own unregistered metrics.
+    pub fn split_by_row_group(self) -> Vec<Self> {
+        let column_name_matching = self.column_name_matching;
+        let column_order = self.column_order;
+        self.table
+            .split_by_row_group()
+            .into_iter()
+            .map(|table| {
+                let mut metrics = ChunkMetrics::new_unregistered();
+                metrics.update_column_storage_statistics(&table.column_storage_statistics());
+                Self {
+                    metrics,
+                    table,
+                    column_name_matching,
+                    column_order,
+                }
+            })
+            .collect()
+    }
+
+    /// Removes rows that share the same tag set (the values of `tag_columns`)
+    /// and timestamp, keeping only the last occurrence of each such row.
+    /// Row groups are ordered by insertion, so later writes win; the order of
+    /// the chunks the rows were compacted from is not considered.
+    ///
+    /// Because row groups are immutable, the row groups containing superseded
+    /// rows are rebuilt; all others are left untouched. Returns the number of
+    /// rows removed.
+    pub fn dedup_rows(&mut self, tag_columns: &[&str]) -> Result<u64> {
+        self.validate_tag_columns(tag_columns, "dedup")?;
+
+        // Only the tag set and time are needed to find duplicates. The
+        // batches hold every row of the chunk in row group order, however
+        // they are split.
+        let mut key_columns = tag_columns.to_vec();
+        key_columns.push(TIME_COLUMN_NAME);
+        let keys = self
+            .read_filter(Predicate::default(), Selection::Some(&key_columns), vec![])?
+            .flat_map(|rb| dedup_keys(&rb, tag_columns))
+            .collect::<Vec<_>>();
+
+        // Determine the position of the last occurrence of each (tag set,
+        // time) key.
+        let mut last_seen = HashMap::with_capacity(keys.len());
+        for (row, key) in keys.iter().enumerate() {
+            last_seen.insert(key, row);
+        }
+
+        let removed = (keys.len() - last_seen.len()) as u64;
+        if removed == 0 {
+            return Ok(0);
+        }
+
+        let keep = keys
+            .iter()
+            .enumerate()
+            .map(|(row, key)| Some(last_seen[key] == row))
+            .collect::<BooleanArray>();
+
+        self.table.retain_rows(&keep).context(TableSnafu)?;
+        self.recompute_metrics();
+
+        Ok(removed)
+    }
+
+    /// Adds a tag or field column named `name` holding only NULL values to
+    /// every row group in the chunk, so that the chunk conforms to a schema
+    /// that has gained the column since the chunk was created.
+    ///
+    /// Existing column data is left untouched. Tag columns must have the
+    /// `String` logical data type.
+    pub fn append_null_column(
+        &mut self,
+        name: &str,
+        influxdb_type: InfluxDbType,
+        data_type: LogicalDataType,
+    ) -> Result<()> {
+        let table_meta = self.table.meta();
+        ensure!(
+            !table_meta.has_column(name),
+            ColumnAlreadyExistsSnafu {
+                column_name: name,
+                table_name: self.table.name(),
+            }
+        );
+
+        let column_type = match (&influxdb_type, data_type) {
+            (_, LogicalDataType::Binary) => {
+                return UnsupportedOperationSnafu {
+                    msg: "cannot append a binary column",
+                }
+                .fail()
+            }
+            (InfluxDbType::Tag, LogicalDataType::String) => ColumnType::Tag(name.to_string()),
+            (InfluxDbType::Tag, _) => {
+                return UnsupportedOperationSnafu {
+                    msg: format!("cannot append tag column '{}' of type {}", name, data_type),
+                }
+                .fail()
+            }
+            (InfluxDbType::Field, _) => ColumnType::Field(name.to_string()),
+            (InfluxDbType::Timestamp, _) => {
+                return UnsupportedOperationSnafu {
+                    msg: "cannot append a timestamp column",
+                }
+                .fail()
+            }
+        };
+
+        if self.table.row_groups() == 0 {
+            let mut select_columns = table_meta.schema_for_all_columns();
+            select_columns.push((column_type, data_type));
+            let schema = Schema::try_from(&ResultSchema {
+                select_columns,
+                ..ResultSchema::default()
+            })
+            .context(TableSchemaSnafu)?;
+
+            self.table = Table::with_schema(self.table.name(), &schema).context(TableSnafu)?;
+            return Ok(());
+        }
+
+        self.table.append_null_column(name, column_type, data_type);
+        self.recompute_metrics();
+        Ok(())
+    }
+
+    // Returns an error if any of the provided columns is not a tag column
+    // in the chunk, describing the failed `operation`.
+    fn validate_tag_columns(&self, tag_columns: &[&str], operation: &str) -> Result<()> {
+        let table_meta = self.table.meta();
+        for &column_name in tag_columns {
+            match table_meta.schema_for_column_names(&[column_name]).first() {
+                Some((ColumnType::Tag(_), _)) => {}
+                Some(_) => {
+                    return UnsupportedOperationSnafu {
+                        msg: format!("cannot {} on non-tag column '{}'", operation, column_name),
+                    }
+                    .fail()
+                }
+                None => {
+                    return ColumnDoesNotExistSnafu {
+                        column_name: column_name.to_string(),
+                        table_name: self.table.name().to_string(),
+                    }
+                    .fail()
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Add a record batch of data to to a `Table` in the chunk.
     ///
     /// The data is converted to a `RowGroup` outside of any locking so the
     /// caller does not need to be concerned about the size of the update.
+    ///
+    /// A record batch without any rows is ignored, so that no empty row group
+    /// is added to the chunk or its metrics.
     pub fn upsert_table(&mut self, table_data: RecordBatch) {
+        if table_data.num_rows() == 0 {
+            return;
+        }
+
         let table_name = self.table.name();
 
         let row_group = record_batch_to_row_group(table_name, table_data);
@@ -179,6 +676,13 @@ impl Chunk {
     /// unioned set of rows is then removed from any rows matching the
     /// `predicate` argument.
     ///
+    /// Columns selected more than once are only returned once, in the position
+    /// they were first selected.
+    ///
+    /// Column names in the predicates and selection are resolved according to
+    /// the chunk's [`ColumnNameMatching`], and selected columns are returned
+    /// according to the chunk's [`ColumnOrder`].
+    ///
     pub fn read_filter(
         &self,
         predicate: Predicate,
@@ -187,9 +691,50 @@ impl Chunk {
     ) -> Result<table::ReadFilterResults> {
         debug!(%predicate, ?select_columns, ?negated_predicates, "read_filter called");
         let now = std::time::Instant::now();
+
+        let matching = self.column_name_matching;
+        let predicate = self
+            .table
+            .resolve_predicate(predicate, matching)
+            .context(TableSnafu)?;
+        let negated_predicates = negated_predicates
+            .into_iter()
+            .map(|pred| self.table.resolve_predicate(pred, matching))
+            .collect::<Result<Vec<_>, _>>()
+            .context(TableSnafu)?;
+        let resolved_columns = match (&select_columns, matching) {
+            (Selection::Some(names), ColumnNameMatching::CaseInsensitive) => Some(
+                self.table
+                    .resolve_column_names(names, matching)
+                    .context(TableSnafu)?,
+            ),
+            (Selection::Some(names), _) if self.column_order == ColumnOrder::Ingestion => {
+                Some(names.iter().map(|&name| name.to_owned()).collect())
+            }
+            _ => None,
+        };
+        let resolved_columns = match self.column_order {
+            ColumnOrder::Selection => resolved_columns,
+            ColumnOrder::Ingestion => resolved_columns.map(|names| {
+                let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+                self.table.order_column_names(&names)
+            }),
+        };
+        let resolved_names = resolved_columns
+            .as_ref()
+            .map(|names| names.iter().map(String::as_str).collect::<Vec<_>>());
+        let select_columns = match &resolved_names {
+            Some(names) => Selection::Some(names),
+            None => select_columns,
+        };
+
         let result = self
             .table
             .read_filter(&select_columns, &predicate, negated_predicates.as_slice())
+            .map(|results| match &self.metrics.column_decode_duration {
+                Some(decode_metrics) => results.with_decode_metrics(decode_metrics.clone()),
+                None => results,
+            })
             .context(TableSnafu);
 
         let row_groups = result
@@ -200,21 +745,175 @@ impl Chunk {
         result
     }
 
+    /// Like [`Chunk::read_filter`], but skips the first `offset` matching rows
+    /// and returns at most `limit` rows, if set.
+    ///
+    /// Row groups are only read until the limit has been reached, and the
+    /// batches at either boundary are sliced so that exactly the requested
+    /// rows are returned.
+    pub fn read_filter_limited(
+        &self,
+        predicate: Predicate,
+        select_columns: Selection<'_>,
+        negated_predicates: Vec<Predicate>,
+        limit: Option<u64>,
+        offset: u64,
+    ) -> Result<table::ReadFilterResults> {
+        self.read_filter(predicate, select_columns, negated_predicates)
+            .map(|results| results.with_limit(limit, offset))
+    }
+
+    /// Like [`Chunk::read_filter`], but with the predicate given as a
+    /// DataFusion expression, which is lowered using
+    /// [`Predicate::try_from_expr`].
+    ///
+    /// An error is returned if the expression cannot be lowered into a
+    /// read buffer predicate.
+    pub fn read_filter_expr(
+        &self,
+        expr: &Expr,
+        select_columns: Selection<'_>,
+        negated_predicates: Vec<Predicate>,
+    ) -> Result<table::ReadFilterResults> {
+        let predicate =
+            Predicate::try_from_expr(expr).map_err(|msg| Error::UnsupportedOperation { msg })?;
+        self.read_filter(predicate, select_columns, negated_predicates)
+    }
+
+    /// Returns an iterator over the values of the column `name` for the rows
+    /// satisfying the predicate, without building a `RecordBatch`.
+    ///
+    /// Values are yielded in row order one row group at a time, and only the
+    /// named column is decoded. The column and predicate are resolved using
+    /// the chunk's [`ColumnNameMatching`].
+    pub fn column_iter(
+        &self,
+        name: &str,
+        predicate: &Predicate,
+    ) -> Result<impl Iterator<Item = OwnedValue>> {
+        let matching = self.column_name_matching;
+        let column_name = self
+            .table
+            .resolve_column_names(&[name], matching)
+            .context(TableSnafu)?
+            .remove(0);
+        ensure!(
+            self.table.meta().has_column(&column_name),
+            ColumnDoesNotExistSnafu {
+                column_name,
+                table_name: self.table.name(),
+            }
+        );
+
+        let predicate = self
+            .table
+            .resolve_predicate(predicate.clone(), matching)
+            .context(TableSnafu)?;
+        self.table
+            .column_iter(&column_name, &predicate)
+            .context(TableSnafu)
+    }
+
+    /// Returns a pseudo-random sample of roughly `fraction` of the rows
+    /// satisfying the predicate and not removed by any of the negated
+    /// predicates (deletes), with the selected columns materialised as a
+    /// single `RecordBatch`.
+    ///
+    /// Rows are sampled by row id before their values are decoded, so
+    /// discarded rows are never materialised. The same `seed` always selects
+    /// the same rows from the same chunk. `fraction` must be within `[0, 1]`.
+    pub fn read_sample(
+        &self,
+        predicate: Predicate,
+        select_columns: Selection<'_>,
+        negated_predicates: Vec<Predicate>,
+        fraction: f64,
+        seed: u64,
+    ) -> Result<RecordBatch> {
+        let matching = self.column_name_matching;
+        let predicate = self
+            .table
+            .resolve_predicate(predicate, matching)
+            .context(TableSnafu)?;
+        let negated_predicates = negated_predicates
+            .into_iter()
+            .map(|pred| self.table.resolve_predicate(pred, matching))
+            .collect::<Result<Vec<_>, _>>()
+            .context(TableSnafu)?;
+        let resolved_columns = match (&select_columns, matching) {
+            (Selection::Some(names), ColumnNameMatching::CaseInsensitive) => Some(
+                self.table
+                    .resolve_column_names(names, matching)
+                    .context(TableSnafu)?,
+            ),
+            _ => None,
+        };
+        let resolved_names = resolved_columns
+            .as_ref()
+            .map(|names| names.iter().map(String::as_str).collect::<Vec<_>>());
+        let select_columns = match &resolved_names {
+            Some(names) => Selection::Some(names),
+            None => select_columns,
+        };
+
+        self.table
+            .read_sample(
+                &select_columns,
+                &predicate,
+                &negated_predicates,
+                fraction,
+                seed,
+            )
+            .context(TableSnafu)
+    }
+
     /// Returns an iterable collection of data in group columns and aggregate
     /// columns, optionally filtered by the provided predicate. Results are
     /// merged across all row groups.
     ///
+    /// An empty selection of group columns produces a single row of global
+    /// aggregates.
+    ///
+    /// If `time_window` is provided, rows are additionally grouped into the
+    /// half-open time windows it describes. The results then form a single
+    /// `RecordBatch` with a leading `time` column holding the start of each
+    /// window. Windows without any matching rows are omitted.
+    ///
     /// Note: `read_aggregate` currently only supports grouping on "tag"
     /// columns.
-    pub(crate) fn read_aggregate(
+    pub fn read_aggregate(
         &self,
         predicate: Predicate,
         group_columns: &Selection<'_>,
         aggregates: &[(ColumnName<'_>, AggregateType)],
-    ) -> Result<table::ReadAggregateResults> {
-        self.table
-            .read_aggregate(predicate, group_columns, aggregates)
-            .context(TableSnafu)
+        time_window: Option<TimeWindow>,
+    ) -> Result<ReadAggregateResults> {
+        match time_window {
+            Some(window) => {
+                self.table
+                    .read_window_aggregate(predicate, window, group_columns, aggregates)
+            }
+            None => self
+                .table
+                .read_aggregate(predicate, group_columns, aggregates),
+        }
+        .context(TableSnafu)
+    }
+
+    /// Returns the ids of the rows in the chunk satisfying the predicate,
+    /// with one entry per row group in the order the row groups were added.
+    ///
+    /// The returned row ids are local to their row group: the same id in two
+    /// entries refers to two different rows. Callers may intersect or union
+    /// entries for the same row group (for example across several predicate
+    /// evaluations) before materialising rows. Delete predicates are not
+    /// applied.
+    pub fn row_ids_matching(&self, predicate: &Predicate) -> Result<Vec<RowIDs>> {
+        let predicate = self
+            .table
+            .resolve_predicate(predicate.clone(), self.column_name_matching)
+            .context(TableSnafu)?;
+        self.table.row_ids_matching(&predicate).context(TableSnafu)
     }
 
     //
@@ -224,10 +923,38 @@ impl Chunk {
     /// Validates if the predicate can be applied to the table based on the
     /// schema and the predicate's expressions. Returns an error if the
     /// predicate cannot be applied.
+    ///
+    /// Column names in the returned predicate are resolved according to the
+    /// chunk's [`ColumnNameMatching`].
     pub fn validate_predicate(&self, predicate: Predicate) -> Result<Predicate, Error> {
+        let predicate = self
+            .table
+            .resolve_predicate(predicate, self.column_name_matching)
+            .context(TableSnafu)?;
         self.table.validate_predicate(predicate).context(TableSnafu)
     }
 
+    /// As `validate_predicate`, but validates every expression in the
+    /// predicate and returns an error for each one that cannot be applied,
+    /// rather than stopping at the first. Useful for reporting all problems
+    /// with a predicate at once; `validate_predicate` should be preferred
+    /// when only the success or failure of validation is needed.
+    pub fn validate_predicate_all(&self, predicate: Predicate) -> Result<(), Vec<Error>> {
+        let predicate = self
+            .table
+            .resolve_predicate(predicate, self.column_name_matching)
+            .context(TableSnafu)
+            .map_err(|e| vec![e])?;
+        self.table
+            .validate_predicate_all(&predicate)
+            .map_err(|errors| {
+                errors
+                    .into_iter()
+                    .map(|source| Error::TableError { source })
+                    .collect()
+            })
+    }
+
     /// Determines if one of more rows in the provided table could possibly
     /// match the provided predicate.
     ///
@@ -247,9 +974,24 @@ impl Chunk {
         self.table.table_summary()
     }
 
+    /// Returns the names of the columns in the chunk that have no non-null
+    /// values, in lexicographical order.
+    ///
+    /// This is determined from the column statistics, without scanning any
+    /// column data. Every column of a chunk without rows is reported.
+    pub fn all_null_columns(&self) -> Vec<String> {
+        self.table_summary()
+            .columns
+            .into_iter()
+            .filter(|column| column.stats.null_count() == column.stats.total_count())
+            .map(|column| column.name)
+            .collect()
+    }
+
     /// Returns a schema object for a `read_filter` operation using the provided
     /// column selection. An error is returned if the specified columns do not
-    /// exist.
+    /// exist. As with `read_filter`, duplicate column names are ignored and
+    /// selected columns are ordered according to the chunk's [`ColumnOrder`].
     pub fn read_filter_table_schema(&self, columns: Selection<'_>) -> Result<Schema> {
         // Validate columns exist in table.
         let table_meta = self.table.meta();
@@ -267,15 +1009,49 @@ impl Chunk {
 
         // Build a table schema
         Schema::try_from(&ResultSchema {
-            select_columns: match columns {
-                Selection::All => table_meta.schema_for_all_columns(),
-                Selection::Some(column_names) => table_meta.schema_for_column_names(column_names),
+            select_columns: match (columns, self.column_order) {
+                (Selection::All, _) => table_meta.schema_for_all_columns(),
+                (Selection::Some(column_names), ColumnOrder::Selection) => {
+                    table_meta.schema_for_column_names(column_names)
+                }
+                (Selection::Some(column_names), ColumnOrder::Ingestion) => {
+                    let ordered = self.table.order_column_names(column_names);
+                    let ordered = ordered.iter().map(String::as_str).collect::<Vec<_>>();
+                    table_meta.schema_for_column_names(&ordered)
+                }
             },
             ..ResultSchema::default()
         })
         .context(TableSchemaSnafu)
     }
 
+    /// Returns the Arrow schema for a `read_filter` operation using the
+    /// provided column selection. Tag columns are reported with their
+    /// dictionary type. An error is returned if the specified columns do not
+    /// exist.
+    pub fn arrow_schema(&self, columns: Selection<'_>) -> Result<arrow::datatypes::SchemaRef> {
+        self.read_filter_table_schema(columns)
+            .map(|schema| schema.as_arrow())
+    }
+
+    /// Materialises the selected columns of the chunk as an Arrow IPC stream,
+    /// with one record batch per row group. The chunk can be rebuilt from the
+    /// bytes with [`Chunk::from_arrow_ipc`].
+    pub fn to_arrow_ipc(&self, columns: Selection<'_>) -> Result<Vec<u8>> {
+        let schema = self.arrow_schema(columns)?;
+        let batches = self.read_filter(Predicate::default(), columns, vec![])?;
+
+        let mut bytes = vec![];
+        {
+            let mut writer = StreamWriter::try_new(&mut bytes, &schema).context(ArrowIpcSnafu)?;
+            for batch in batches {
+                writer.write(&batch).context(ArrowIpcSnafu)?;
+            }
+            writer.finish().context(ArrowIpcSnafu)?;
+        }
+        Ok(bytes)
+    }
+
     /// Determines if at least one row in the Chunk satisfies the provided
     /// predicate. `satisfies_predicate` will return true if it is guaranteed
     /// that at least one row in the Chunk will satisfy the predicate.
@@ -283,11 +1059,35 @@ impl Chunk {
         self.table.satisfies_predicate(predicate)
     }
 
+    /// Determines if at least one row in the chunk has `value` in `column`.
+    ///
+    /// For tag and other string columns a value missing from the column
+    /// dictionaries is rejected without scanning rows. A dictionary entry is
+    /// confirmed from its row ids for RLE-encoded columns, whereas plain
+    /// dictionary-encoded (high cardinality) columns scan their encoded ids as
+    /// their dictionary may hold entries no row refers to. Other columns fall
+    /// back to scanning for `value` parsed as the column's type.
+    pub fn contains_value(&self, column: ColumnName<'_>, value: &str) -> Result<bool> {
+        ensure!(
+            self.table.meta().has_column(column),
+            ColumnDoesNotExistSnafu {
+                column_name: column,
+                table_name: self.table.name(),
+            }
+        );
+
+        self.table.contains_value(column, value).context(TableSnafu)
+    }
+
     /// Returns the distinct set of column names that contain data matching the
     /// provided predicate, which may be empty.
     ///
     /// Results can be further limited to a specific selection of columns.
     ///
+    /// By default columns whose values are NULL on every matching row are
+    /// omitted. If `include_null_only` is set, every column present in a row
+    /// group with matching rows is returned, regardless of its values.
+    ///
     /// `dst` is a buffer that will be populated with results. `column_names` is
     /// smart enough to short-circuit processing on row groups when it
     /// determines that all the columns in the row group are already contained
@@ -298,14 +1098,58 @@ impl Chunk {
         predicate: Predicate,
         negated_predicates: Vec<Predicate>,
         only_columns: Selection<'_>,
+        include_null_only: bool,
         dst: BTreeSet<String>,
     ) -> Result<BTreeSet<String>> {
         self.table
-            .column_names(&predicate, &negated_predicates, only_columns, dst)
+            .column_names(
+                &predicate,
+                &negated_predicates,
+                only_columns,
+                include_null_only,
+                dst,
+            )
             .context(TableSnafu)
     }
 
-    /// Returns the distinct set of column values for each provided column,
+    /// Returns the distinct set of tag column names that contain data matching
+    /// the provided predicate. Field and timestamp columns are never returned.
+    ///
+    /// This is `column_names` restricted to the chunk's tag columns, and
+    /// answers queries such as InfluxQL's `SHOW TAG KEYS`.
+    pub fn tag_keys(
+        &self,
+        predicate: &Predicate,
+        negated_predicates: &[Predicate],
+    ) -> Result<BTreeSet<String>> {
+        let tag_columns = self
+            .table
+            .meta()
+            .schema_for_all_columns()
+            .into_iter()
+            .filter_map(|(typ, _)| match typ {
+                ColumnType::Tag(name) => Some(name),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if tag_columns.is_empty() {
+            return Ok(BTreeSet::new());
+        }
+
+        let tag_columns = tag_columns.iter().map(String::as_str).collect::<Vec<_>>();
+        self.table
+            .column_names(
+                predicate,
+                negated_predicates,
+                Selection::Some(&tag_columns),
+                false,
+                BTreeSet::new(),
+            )
+            .context(TableSnafu)
+    }
+
+    /// Returns the distinct set of column values for each provided column,
     /// where each returned value lives in a row matching the provided
     /// predicate.
     ///
@@ -333,9 +1177,83 @@ impl Chunk {
         };
 
         self.table
-            .column_values(&predicate, columns, dst)
+            .column_values(&predicate, columns, dst, None)
+            .map(|result| result.values)
+            .context(TableSnafu)
+    }
+
+    /// Like [`Chunk::column_values`], but returns at most `max_values`
+    /// distinct values for each column. Columns with more distinct values are
+    /// reported as truncated in the result, and row groups are only read
+    /// until every column present in the table has been truncated.
+    pub fn column_values_limited(
+        &self,
+        predicate: Predicate,
+        columns: Selection<'_>,
+        max_values: usize,
+    ) -> Result<table::ColumnValuesResult> {
+        let columns = match columns {
+            Selection::All => {
+                return UnsupportedOperationSnafu {
+                    msg: "column_values does not support All columns".to_owned(),
+                }
+                .fail();
+            }
+            Selection::Some(columns) => columns,
+        };
+
+        self.table
+            .column_values(&predicate, columns, BTreeMap::new(), Some(max_values))
             .context(TableSnafu)
     }
+
+    /// Estimates the number of series in the chunk, i.e., the number of
+    /// distinct combinations of values in the provided tag columns.
+    ///
+    /// The estimate is the product of the number of distinct values in each
+    /// column, with NULL counted as a value, capped at the number of rows in
+    /// the chunk. Only the distinct values of each column are materialised,
+    /// never the combinations themselves.
+    ///
+    /// The estimate is never lower than the true series count. It is exact
+    /// for a single tag column, or when every combination of values is
+    /// present. The more correlated the columns (for example, `host`
+    /// determining `region`) the more the true count is overestimated: by at
+    /// most a factor of the product of the distinct counts of all but the
+    /// column with the most distinct values.
+    pub fn estimate_series_count(&self, tag_columns: &[&str]) -> Result<u64> {
+        self.validate_tag_columns(tag_columns, "estimate series count")?;
+
+        let rows = self.rows();
+        if rows == 0 {
+            return Ok(0);
+        }
+
+        // duplicate columns don't change the number of series
+        let columns = tag_columns
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let values = self.column_values(
+            Predicate::default(),
+            Selection::Some(&columns),
+            BTreeMap::new(),
+        )?;
+
+        let estimate = columns.iter().fold(1_u64, |estimate, &column_name| {
+            let distinct = values.get(column_name).map_or(0, |v| v.len() as u64);
+            let has_nulls = self
+                .table
+                .column_storage_statistics_for(column_name)
+                .iter()
+                .any(|stats| stats.nulls > 0);
+            estimate.saturating_mul(distinct + has_nulls as u64)
+        });
+
+        Ok(estimate.min(rows))
+    }
 }
 
 fn record_batch_to_row_group(table_name: &str, rb: RecordBatch) -> RowGroup {
@@ -346,6 +1264,32 @@ fn record_batch_to_row_group(table_name: &str, rb: RecordBatch) -> RowGroup {
     row_group
 }
 
+// Produces a (tag set, time) key for each row in `rb`.
+fn dedup_keys(rb: &RecordBatch, tag_columns: &[&str]) -> Vec<(Vec<Option<String>>, i64)> {
+    let tag_arrays = tag_columns
+        .iter()
+        .map(|&name| rb.column(rb.schema().index_of(name).unwrap()))
+        .collect::<Vec<_>>();
+    let time_array = rb.column(rb.schema().index_of(TIME_COLUMN_NAME).unwrap());
+    let time_array = time_array
+        .as_any()
+        .downcast_ref::<TimestampNanosecondArray>()
+        .expect("time column is a nanosecond timestamp");
+
+    (0..rb.num_rows())
+        .map(|row| {
+            let tag_set = tag_arrays
+                .iter()
+                .map(|arr| {
+                    (!arr.is_null(row))
+                        .then(|| array_value_to_string(arr, row).expect("tag value is a string"))
+                })
+                .collect::<Vec<_>>();
+            (tag_set, time_array.value(row))
+        })
+        .collect()
+}
+
 impl std::fmt::Debug for Chunk {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Chunk: rows: {:?}", self.rows())
@@ -383,6 +1327,11 @@ pub struct ChunkMetrics {
     /// columns, further segmented by nullness. It is a building block for
     /// tracking a measure of overall compression.
     column_raw_bytes_total: RecorderCollection<CumulativeGauge>,
+
+    /// This metric tracks the time taken to decode read buffer columns when
+    /// materialising `read_filter` results. Together with the storage metrics
+    /// above it describes the read cost of each encoding.
+    column_decode_duration: Option<ColumnDecodeMetrics>,
 }
 
 impl ChunkMetrics {
@@ -395,7 +1344,7 @@ impl ChunkMetrics {
             row_groups_total: registry.register_metric::<CumulativeGauge>(
                 "read_buffer_row_group_total",
                 "The number of row groups within the Read Buffer",
-            ).recorder(base_attributes),
+            ).recorder(base_attributes.clone()),
             columns_total: RecorderCollection::new(registry.register_metric(
                 "read_buffer_column_total",
                 "The number of columns within the Read Buffer",
@@ -416,6 +1365,24 @@ impl ChunkMetrics {
                 "read_buffer_column_raw_bytes",
                 "The number of bytes used by all columns if they were uncompressed in the Read Buffer",
             )),
+            column_decode_duration: Some(ColumnDecodeMetrics {
+                base_attributes,
+                duration: registry.register_metric_with_options(
+                    "read_buffer_column_decode_duration",
+                    "The time taken to decode column data in the Read Buffer during read_filter",
+                    || {
+                        DurationHistogramOptions::new([
+                            Duration::from_micros(10),
+                            Duration::from_micros(100),
+                            Duration::from_millis(1),
+                            Duration::from_millis(10),
+                            Duration::from_millis(100),
+                            Duration::from_secs(1),
+                            metric::DURATION_MAX,
+                        ])
+                    },
+                ),
+            }),
         }
     }
 
@@ -432,6 +1399,64 @@ impl ChunkMetrics {
             column_allocated_bytes_total: RecorderCollection::new_unregistered(),
             column_required_bytes_total: RecorderCollection::new_unregistered(),
             column_raw_bytes_total: RecorderCollection::new_unregistered(),
+            column_decode_duration: None,
+        }
+    }
+
+    // Replaces all column storage statistics with those of `row_groups` row
+    // groups having the provided `statistics`.
+    fn reset_column_storage_statistics(&mut self, row_groups: u64, statistics: &[Statistics]) {
+        // dropping the cumulative recorders removes their contribution
+        self.columns_total.clear();
+        self.column_values_total.clear();
+        self.column_allocated_bytes_total.clear();
+        self.column_required_bytes_total.clear();
+        self.column_raw_bytes_total.clear();
+
+        self.update_column_storage_statistics(statistics);
+        self.row_groups_total.set(row_groups);
+    }
+
+    // Replaces the column storage statistics recorded for the encodings and
+    // logical data types of `column_statistics` with those recomputed from
+    // `statistics`, which must describe every column held by the chunk.
+    // Statistics for other encodings and logical data types are unchanged.
+    fn refresh_column_storage_statistics(
+        &mut self,
+        column_statistics: &[Statistics],
+        statistics: &[Statistics],
+    ) {
+        let refreshed = column_statistics
+            .iter()
+            .map(|stat| (stat.enc_type.clone(), stat.log_data_type))
+            .collect::<BTreeSet<_>>();
+
+        for (enc_type, log_data_type) in &refreshed {
+            let mut attributes = self.base_attributes.clone();
+            attributes.insert("encoding", enc_type.clone());
+            attributes.insert("log_data_type", *log_data_type);
+
+            self.columns_total.recorder(attributes.clone()).set(0);
+            self.column_allocated_bytes_total
+                .recorder(attributes.clone())
+                .set(0);
+            self.column_required_bytes_total
+                .recorder(attributes.clone())
+                .set(0);
+
+            for null in ["true", "false"] {
+                attributes.insert("null", null);
+                self.column_raw_bytes_total
+                    .recorder(attributes.clone())
+                    .set(0);
+                self.column_values_total.recorder(attributes.clone()).set(0);
+            }
+        }
+
+        for stat in statistics {
+            if refreshed.contains(&(stat.enc_type.clone(), stat.log_data_type)) {
+                self.add_column_storage_statistics(stat);
+            }
         }
     }
 
@@ -441,47 +1466,72 @@ impl ChunkMetrics {
         self.row_groups_total.inc(1);
 
         for stat in statistics {
-            let mut attributes = self.base_attributes.clone();
-            attributes.insert("encoding", stat.enc_type.clone());
-            attributes.insert("log_data_type", stat.log_data_type);
+            self.add_column_storage_statistics(stat);
+        }
+    }
 
-            // update number of columns
-            self.columns_total.recorder(attributes.clone()).inc(1);
+    // Adds the storage statistics of a single column to the metrics.
+    fn add_column_storage_statistics(&mut self, stat: &Statistics) {
+        let mut attributes = self.base_attributes.clone();
+        attributes.insert("encoding", stat.enc_type.clone());
+        attributes.insert("log_data_type", stat.log_data_type);
 
-            // update bytes allocated associated with columns
-            self.column_allocated_bytes_total
-                .recorder(attributes.clone())
-                .inc(stat.allocated_bytes as u64);
+        // update number of columns
+        self.columns_total.recorder(attributes.clone()).inc(1);
 
-            // update bytes in use but excluded unused
-            self.column_required_bytes_total
-                .recorder(attributes.clone())
-                .inc(stat.required_bytes as u64);
+        // update bytes allocated associated with columns
+        self.column_allocated_bytes_total
+            .recorder(attributes.clone())
+            .inc(stat.allocated_bytes as u64);
 
-            attributes.insert("null", "true");
+        // update bytes in use but excluded unused
+        self.column_required_bytes_total
+            .recorder(attributes.clone())
+            .inc(stat.required_bytes as u64);
 
-            // update raw estimated bytes of NULL values
-            self.column_raw_bytes_total
-                .recorder(attributes.clone())
-                .inc((stat.raw_bytes - stat.raw_bytes_no_null) as u64);
+        attributes.insert("null", "true");
 
-            // update number of NULL values
-            self.column_values_total
-                .recorder(attributes.clone())
-                .inc(stat.nulls as u64);
+        // update raw estimated bytes of NULL values
+        self.column_raw_bytes_total
+            .recorder(attributes.clone())
+            .inc((stat.raw_bytes - stat.raw_bytes_no_null) as u64);
 
-            attributes.insert("null", "false");
+        // update number of NULL values
+        self.column_values_total
+            .recorder(attributes.clone())
+            .inc(stat.nulls as u64);
 
-            // update raw estimated bytes of non-NULL values
-            self.column_raw_bytes_total
-                .recorder(attributes.clone())
-                .inc(stat.raw_bytes_no_null as u64);
+        attributes.insert("null", "false");
 
-            // update number of non-NULL values
-            self.column_values_total
-                .recorder(attributes)
-                .inc((stat.values - stat.nulls) as u64);
-        }
+        // update raw estimated bytes of non-NULL values
+        self.column_raw_bytes_total
+            .recorder(attributes.clone())
+            .inc(stat.raw_bytes_no_null as u64);
+
+        // update number of non-NULL values
+        self.column_values_total
+            .recorder(attributes)
+            .inc((stat.values - stat.nulls) as u64);
+    }
+}
+
+/// Records the time taken to decode column data when materialising
+/// `read_filter` results, attributed by column encoding and logical data-type
+/// in the same way as the column storage metrics in `ChunkMetrics`.
+#[derive(Debug, Clone)]
+pub(crate) struct ColumnDecodeMetrics {
+    base_attributes: Attributes,
+    duration: Metric<DurationHistogram>,
+}
+
+impl ColumnDecodeMetrics {
+    pub(crate) fn record(&self, column: &Column, elapsed: Duration) {
+        let (encoding, log_data_type) = column.encoding_attributes();
+        let mut attributes = self.base_attributes.clone();
+        attributes.insert("encoding", encoding);
+        attributes.insert("log_data_type", log_data_type);
+
+        self.duration.recorder(attributes).record(elapsed);
     }
 }
 
@@ -489,8 +1539,8 @@ impl ChunkMetrics {
 mod test {
     use super::*;
     use crate::{
-        row_group::{ColumnType, RowGroup},
-        value::Values,
+        row_group::{ColumnType, Literal, RowGroup},
+        value::{Scalar, Values},
         BinaryExpr,
     };
     use arrow::{
@@ -499,12 +1549,13 @@ mod test {
             StringArray, TimestampNanosecondArray, UInt64Array,
         },
         datatypes::{
-            DataType::{Boolean, Float64, Int64, UInt64, Utf8},
-            Int32Type,
+            DataType::{self, Boolean, Float64, Int64, UInt64, Utf8},
+            Int32Type, TimeUnit,
         },
     };
     use data_types::partition_metadata::{ColumnSummary, InfluxDbType, StatValues, Statistics};
     use metric::{MetricKind, Observation, ObservationSet, RawReporter};
+    use regex::Regex;
     use schema::builder::SchemaBuilder;
     use std::iter::FromIterator;
     use std::{num::NonZeroU64, sync::Arc};
@@ -701,27 +1752,10 @@ mod test {
         }
     }
 
-    #[test]
-    fn add_remove_tables() {
-        let registry = metric::Registry::new();
-
-        let mut chunk = ChunkBuilder::default()
-            .metrics(ChunkMetrics::new(&registry, "mydb"))
-            .build();
-
-        assert_eq!(chunk.rows(), 3);
-        assert_eq!(chunk.row_groups(), 1);
-        assert!(chunk.size() > 0);
-
-        // Add a row group to the same table in the Chunk.
-        let last_chunk_size = chunk.size();
-        chunk.upsert_table(gen_recordbatch());
-
-        assert_eq!(chunk.rows(), 6);
-        assert_eq!(chunk.row_groups(), 2);
-        assert!(chunk.size() > last_chunk_size);
-
-        let expected_observations = vec![
+    // The metric observations of the chunk built by `add_remove_tables`, which
+    // holds two row groups generated by `gen_recordbatch`.
+    fn two_row_group_observations() -> Vec<ObservationSet> {
+        vec![
             ObservationSet {
                 metric_name: "read_buffer_column_allocated_bytes",
                 description: "The number of bytes used by all data in the Read Buffer including allocated by unused buffers",
@@ -734,6 +1768,12 @@ mod test {
                     (Attributes::from(&[("db_name", "mydb"), ("encoding", "RLE"), ("log_data_type", "string")]), Observation::U64Gauge(784)),
                 ]
             },
+            ObservationSet {
+                metric_name: "read_buffer_column_decode_duration",
+                description: "The time taken to decode column data in the Read Buffer during read_filter",
+                kind: MetricKind::DurationHistogram,
+                observations: vec![],
+            },
             ObservationSet {
                 metric_name: "read_buffer_column_raw_bytes",
                 description: "The number of bytes used by all columns if they were uncompressed in the Read Buffer",
@@ -800,8 +1840,334 @@ mod test {
                     (Attributes::from(&[("db_name", "mydb")]), Observation::U64Gauge(2)),
                 ]
             },
+        ]
+    }
+
+    #[test]
+    fn zero_row_batches() {
+        let registry = metric::Registry::new();
+        let empty_batch = gen_recordbatch().slice(0, 0);
+
+        let row_groups_total = |registry: &metric::Registry| {
+            let mut reporter = RawReporter::default();
+            registry.report(&mut reporter);
+            reporter
+                .metric("read_buffer_row_group_total")
+                .unwrap()
+                .observations
+                .clone()
+        };
+
+        // a chunk created from an empty batch has no row groups
+        let mut chunk = Chunk::new(
+            "a_table",
+            empty_batch.clone(),
+            ChunkMetrics::new(&registry, "mydb"),
+        );
+        assert_eq!(chunk.rows(), 0);
+        assert_eq!(chunk.row_groups(), 0);
+        let schema = chunk.read_filter_table_schema(Selection::All).unwrap();
+        let names = schema
+            .iter()
+            .map(|(_, f)| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["region", "counter", "active", "time", "sketchy_sensor"]
+        );
+        assert_eq!(
+            row_groups_total(&registry),
+            vec![(
+                Attributes::from(&[("db_name", "mydb")]),
+                Observation::U64Gauge(0)
+            )]
+        );
+
+        chunk.upsert_table(gen_recordbatch());
+        assert_eq!(chunk.rows(), 3);
+        assert_eq!(chunk.row_groups(), 1);
+
+        // upserting an empty batch is a no-op
+        chunk.upsert_table(empty_batch);
+        assert_eq!(chunk.rows(), 3);
+        assert_eq!(chunk.row_groups(), 1);
+        assert_eq!(
+            row_groups_total(&registry),
+            vec![(
+                Attributes::from(&[("db_name", "mydb")]),
+                Observation::U64Gauge(1)
+            )]
+        );
+    }
+
+    #[test]
+    fn largest_columns() {
+        let mut chunk = Chunk::new(
+            "a_table",
+            gen_recordbatch(),
+            ChunkMetrics::new_unregistered(),
+        );
+        let single = chunk.column_sizes();
+
+        chunk.upsert_table(gen_recordbatch());
+        assert_eq!(chunk.row_groups(), 2);
+
+        let largest = chunk.largest_columns(usize::MAX);
+        assert_eq!(largest.len(), 5);
+
+        // sizes are aggregated across the identical row groups
+        for column in &largest {
+            let single = single.iter().find(|c| c.name == column.name).unwrap();
+            assert_eq!(column.memory_bytes, 2 * single.memory_bytes);
+        }
+
+        // ordered by size descending, then by name
+        assert!(largest.windows(2).all(|w| {
+            w[0].memory_bytes > w[1].memory_bytes
+                || (w[0].memory_bytes == w[1].memory_bytes && w[0].name < w[1].name)
+        }));
+
+        assert_eq!(chunk.largest_columns(2), largest[..2].to_vec());
+        assert!(chunk.largest_columns(0).is_empty());
+    }
+
+    #[test]
+    fn column_iter() {
+        let mut chunk = ChunkBuilder::default().build();
+        chunk.upsert_table(gen_recordbatch());
+
+        let predicate = Predicate::with_time_range(&[], 5000, 20_000_000);
+        let values = chunk
+            .column_iter("time", &predicate)
+            .unwrap()
+            .collect::<Vec<_>>();
+        let expected = [11111111, 222222, 11111111, 222222]
+            .iter()
+            .map(|&t| OwnedValue::Scalar(Scalar::I64(t)))
+            .collect::<Vec<_>>();
+        assert_eq!(values, expected);
+
+        // only the rows satisfying every expression are yielded
+        let predicate =
+            Predicate::with_time_range(&[BinaryExpr::from(("region", "=", "east"))], 0, 20_000_000);
+        let values = chunk
+            .column_iter("sketchy_sensor", &predicate)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                OwnedValue::Scalar(Scalar::F64(12.0)),
+                OwnedValue::Scalar(Scalar::F64(12.0))
+            ]
+        );
+
+        let values = chunk
+            .column_iter("region", &Predicate::with_time_range(&[], 0, 5000))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                OwnedValue::String("east".to_string()),
+                OwnedValue::String("east".to_string())
+            ]
+        );
+
+        assert!(matches!(
+            chunk.column_iter("unknown", &Predicate::default()),
+            Err(Error::ColumnDoesNotExist { .. })
+        ));
+    }
+
+    #[test]
+    fn append_null_column() {
+        let registry = metric::Registry::new();
+        let mut chunk = ChunkBuilder::default()
+            .metrics(ChunkMetrics::new(&registry, "mydb"))
+            .build();
+        chunk.upsert_table(gen_recordbatch());
+        let fork = chunk.read_only_fork();
+
+        chunk
+            .append_null_column("new_field", InfluxDbType::Field, LogicalDataType::Integer)
+            .unwrap();
+        chunk
+            .append_null_column("new_tag", InfluxDbType::Tag, LogicalDataType::String)
+            .unwrap();
+        assert_eq!(chunk.row_groups(), 2);
+        assert_eq!(chunk.rows(), 6);
+
+        let batches = chunk
+            .read_filter(Predicate::default(), Selection::All, vec![])
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(batches.len(), 2);
+        for rb in &batches {
+            assert_eq!(rb.num_columns(), 7);
+            for name in ["new_field", "new_tag"] {
+                let column = rb.column(rb.schema().index_of(name).unwrap());
+                assert_eq!(column.len(), 3);
+                assert_eq!(column.null_count(), 3);
+            }
+        }
+        let mut all_null_columns = chunk.all_null_columns();
+        all_null_columns.sort();
+        assert_eq!(all_null_columns, vec!["new_field", "new_tag"]);
+
+        // row groups shared with a fork are copied, leaving the fork as is
+        assert!(fork.all_null_columns().is_empty());
+
+        // the storage metrics account for the new columns in every row group
+        let mut reporter = RawReporter::default();
+        registry.report(&mut reporter);
+        let columns_total = reporter
+            .observations()
+            .iter()
+            .find(|set| set.metric_name == "read_buffer_column_total")
+            .unwrap()
+            .observations
+            .iter()
+            .map(|(_, observation)| match observation {
+                Observation::U64Gauge(v) => *v,
+                _ => unreachable!(),
+            })
+            .sum::<u64>();
+        assert_eq!(columns_total, 14);
+
+        // existing columns and timestamps cannot be appended
+        assert!(matches!(
+            chunk.append_null_column("region", InfluxDbType::Tag, LogicalDataType::String),
+            Err(Error::ColumnAlreadyExists { .. })
+        ));
+        assert!(matches!(
+            chunk.append_null_column("other", InfluxDbType::Tag, LogicalDataType::Float),
+            Err(Error::UnsupportedOperation { .. })
+        ));
+        assert!(matches!(
+            chunk.append_null_column("other", InfluxDbType::Timestamp, LogicalDataType::Integer),
+            Err(Error::UnsupportedOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn read_filter_expr() {
+        use datafusion::logical_plan::{col, lit};
+
+        let mut chunk = ChunkBuilder::default().build();
+        chunk.upsert_table(gen_recordbatch());
+
+        let cases = vec![
+            (
+                col("region").eq(lit("west")),
+                Predicate::new(vec![BinaryExpr::from(("region", "=", "west"))]),
+            ),
+            (
+                lit(3_000_i64)
+                    .lt(col("time"))
+                    .and(col("counter").gt_eq(lit(3.3))),
+                Predicate::new(vec![
+                    BinaryExpr::from(("time", ">", 3_000_i64)),
+                    BinaryExpr::from(("counter", ">=", 3.3)),
+                ]),
+            ),
         ];
 
+        for (expr, predicate) in cases {
+            let got = chunk
+                .read_filter_expr(&expr, Selection::All, vec![])
+                .unwrap()
+                .collect::<Vec<_>>();
+            let exp = chunk
+                .read_filter(predicate, Selection::All, vec![])
+                .unwrap()
+                .collect::<Vec<_>>();
+            assert!(got.iter().map(|rb| rb.num_rows()).sum::<usize>() > 0);
+            assert_eq!(
+                arrow_util::display::pretty_format_batches(&got).unwrap(),
+                arrow_util::display::pretty_format_batches(&exp).unwrap()
+            );
+        }
+
+        let expr = col("region")
+            .eq(lit("west"))
+            .or(col("region").eq(lit("east")));
+        assert!(matches!(
+            chunk.read_filter_expr(&expr, Selection::All, vec![]),
+            Err(Error::UnsupportedOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn rename_column() {
+        let mut chunk = ChunkBuilder::default().build();
+        chunk.upsert_table(gen_recordbatch());
+        chunk.upsert_table(gen_recordbatch());
+
+        // row groups shared with a fork are copied, leaving the fork as is
+        let fork = chunk.read_only_fork();
+        chunk.rename_column("counter", "count").unwrap();
+        assert!(fork
+            .read_filter_table_schema(Selection::Some(&["counter"]))
+            .is_ok());
+        assert!(fork
+            .read_filter_table_schema(Selection::Some(&["count"]))
+            .is_err());
+
+        let batches = chunk
+            .read_filter(Predicate::default(), Selection::All, vec![])
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(batches.len(), 2);
+        for rb in &batches {
+            assert_eq!(rb.num_columns(), 5);
+            assert!(rb.schema().index_of("counter").is_err());
+            assert_rb_column_equals(rb, "count", &Values::F64(vec![1.2, 3.3, 45.3]));
+        }
+
+        assert!(matches!(
+            chunk.read_filter_table_schema(Selection::Some(&["counter"])),
+            Err(Error::ColumnDoesNotExist { .. })
+        ));
+
+        // renaming columns that don't exist, to columns that do, or the
+        // timestamp column is not possible
+        assert!(matches!(
+            chunk.rename_column("counter", "other"),
+            Err(Error::ColumnDoesNotExist { .. })
+        ));
+        assert!(matches!(
+            chunk.rename_column("count", "region"),
+            Err(Error::ColumnAlreadyExists { .. })
+        ));
+        assert!(matches!(
+            chunk.rename_column("time", "timestamp"),
+            Err(Error::UnsupportedOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn add_remove_tables() {
+        let registry = metric::Registry::new();
+
+        let mut chunk = ChunkBuilder::default()
+            .metrics(ChunkMetrics::new(&registry, "mydb"))
+            .build();
+
+        assert_eq!(chunk.rows(), 3);
+        assert_eq!(chunk.row_groups(), 1);
+        assert!(chunk.size() > 0);
+
+        // Add a row group to the same table in the Chunk.
+        let last_chunk_size = chunk.size();
+        chunk.upsert_table(gen_recordbatch());
+
+        assert_eq!(chunk.rows(), 6);
+        assert_eq!(chunk.row_groups(), 2);
+        assert!(chunk.size() > last_chunk_size);
+
+        let expected_observations = two_row_group_observations();
+
         let mut reporter = RawReporter::default();
         registry.report(&mut reporter);
         assert_eq!(&expected_observations, reporter.observations());
@@ -829,7 +2195,395 @@ mod test {
     }
 
     #[test]
-    fn read_filter_table_schema() {
+    fn read_only_fork() {
+        let registry = metric::Registry::new();
+
+        let mut chunk = ChunkBuilder::default()
+            .metrics(ChunkMetrics::new(&registry, "mydb"))
+            .build();
+        chunk.upsert_table(gen_recordbatch());
+
+        let fork = chunk.read_only_fork();
+        assert_eq!(fork.rows(), 6);
+        assert_eq!(fork.row_groups(), 2);
+        assert_eq!(fork.size(), chunk.size());
+
+        let mut reporter = RawReporter::default();
+        registry.report(&mut reporter);
+        assert_eq!(&two_row_group_observations(), reporter.observations());
+
+        // dropping the fork leaves the chunk's metrics untouched
+        std::mem::drop(fork);
+        let mut reporter = RawReporter::default();
+        registry.report(&mut reporter);
+        assert_eq!(&two_row_group_observations(), reporter.observations());
+
+        // row groups added to the chunk after forking are not visible to the
+        // fork
+        let fork = chunk.read_only_fork();
+        chunk.upsert_table(gen_recordbatch());
+        assert_eq!(fork.row_groups(), 2);
+        assert_eq!(chunk.row_groups(), 3);
+    }
+
+    #[test]
+    fn recompute_metrics() {
+        let registry = metric::Registry::new();
+
+        let mut chunk = ChunkBuilder::default()
+            .metrics(ChunkMetrics::new(&registry, "mydb"))
+            .build();
+        chunk.upsert_table(gen_recordbatch());
+
+        // skew the metrics by double counting a row group and adding a
+        // column that doesn't exist
+        let statistics = chunk.table.column_storage_statistics();
+        chunk
+            .metrics
+            .update_column_storage_statistics(&statistics[..statistics.len() / 2]);
+        let mut attributes = chunk.metrics.base_attributes.clone();
+        attributes.insert("encoding", "BOGUS");
+        attributes.insert("log_data_type", "i64");
+        chunk.metrics.columns_total.recorder(attributes).inc(3);
+
+        let mut reporter = RawReporter::default();
+        registry.report(&mut reporter);
+        assert_ne!(&two_row_group_observations(), reporter.observations());
+
+        chunk.recompute_metrics();
+
+        let mut expected_observations = two_row_group_observations();
+        let columns_total = expected_observations
+            .iter_mut()
+            .find(|set| set.metric_name == "read_buffer_column_total")
+            .unwrap();
+        columns_total.observations.insert(
+            0,
+            (
+                Attributes::from(&[
+                    ("db_name", "mydb"),
+                    ("encoding", "BOGUS"),
+                    ("log_data_type", "i64"),
+                ]),
+                Observation::U64Gauge(0),
+            ),
+        );
+
+        let mut reporter = RawReporter::default();
+        registry.report(&mut reporter);
+        assert_eq!(&expected_observations, reporter.observations());
+    }
+
+    #[test]
+    fn refresh_column_stats() {
+        let registry = metric::Registry::new();
+
+        let mut chunk = ChunkBuilder::default()
+            .metrics(ChunkMetrics::new(&registry, "mydb"))
+            .build();
+        chunk.upsert_table(gen_recordbatch());
+
+        // skew the column counts for the "region" (RLE) and "counter"
+        // (FIXED) columns.
+        for (encoding, log_data_type) in [("RLE", "string"), ("FIXED", "f64")] {
+            let mut attributes = chunk.metrics.base_attributes.clone();
+            attributes.insert("encoding", encoding);
+            attributes.insert("log_data_type", log_data_type);
+            chunk.metrics.columns_total.recorder(attributes).inc(5);
+        }
+
+        let columns_total = |registry: &metric::Registry| {
+            let mut reporter = RawReporter::default();
+            registry.report(&mut reporter);
+            reporter
+                .metric("read_buffer_column_total")
+                .unwrap()
+                .observations
+                .clone()
+        };
+        let observation = |encoding, log_data_type, value| {
+            (
+                Attributes::from(&[
+                    ("db_name", "mydb"),
+                    ("encoding", encoding),
+                    ("log_data_type", log_data_type),
+                ]),
+                Observation::U64Gauge(value),
+            )
+        };
+
+        chunk.refresh_column_stats("region").unwrap();
+        assert_eq!(
+            columns_total(&registry),
+            vec![
+                observation("BT_U32-FIXED", "i64", 2),
+                observation("FBT_U8-FIXEDN", "f64", 2),
+                observation("FIXED", "f64", 7), // not refreshed
+                observation("FIXEDN", "bool", 2),
+                observation("RLE", "string", 2),
+            ]
+        );
+
+        chunk.refresh_column_stats("counter").unwrap();
+        let mut reporter = RawReporter::default();
+        registry.report(&mut reporter);
+        assert_eq!(&two_row_group_observations(), reporter.observations());
+
+        assert!(matches!(
+            chunk.refresh_column_stats("not_a_column"),
+            Err(Error::ColumnDoesNotExist { .. })
+        ));
+    }
+
+    #[test]
+    fn from_batches() {
+        let registry = metric::Registry::new();
+
+        let chunk = Chunk::from_batches(
+            "a_table",
+            vec![gen_recordbatch(), gen_recordbatch(), gen_recordbatch()],
+            ChunkMetrics::new(&registry, "mydb"),
+        )
+        .unwrap();
+        assert_eq!(chunk.rows(), 9);
+        assert_eq!(chunk.row_groups(), 3);
+        assert_eq!(chunk.table_summary().name, "a_table");
+
+        let mut reporter = RawReporter::default();
+        registry.report(&mut reporter);
+        assert_eq!(
+            reporter
+                .metric("read_buffer_row_group_total")
+                .unwrap()
+                .observations,
+            vec![(
+                Attributes::from(&[("db_name", "mydb")]),
+                Observation::U64Gauge(3)
+            )]
+        );
+
+        assert!(matches!(
+            Chunk::from_batches("a_table", vec![], ChunkMetrics::new_unregistered()),
+            Err(Error::NoRecordBatches {})
+        ));
+
+        let other = RecordBatch::try_new(
+            SchemaBuilder::new().timestamp().build().unwrap().into(),
+            vec![Arc::new(TimestampNanosecondArray::from_vec(vec![1], None))],
+        )
+        .unwrap();
+        assert!(matches!(
+            Chunk::from_batches(
+                "a_table",
+                vec![gen_recordbatch(), gen_recordbatch(), other],
+                ChunkMetrics::new_unregistered()
+            ),
+            Err(Error::RecordBatchSchemaMismatch { index: 2 })
+        ));
+    }
+
+    #[test]
+    fn arrow_ipc_round_trip() {
+        let chunk = Chunk::from_batches(
+            "a_table",
+            vec![gen_recordbatch(), gen_recordbatch()],
+            ChunkMetrics::new_unregistered(),
+        )
+        .unwrap();
+
+        let read_all = |chunk: &Chunk, columns: Selection<'_>| {
+            let batches = chunk
+                .read_filter(Predicate::default(), columns, vec![])
+                .unwrap()
+                .collect::<Vec<_>>();
+            arrow_util::display::pretty_format_batches(&batches).unwrap()
+        };
+
+        let bytes = chunk.to_arrow_ipc(Selection::All).unwrap();
+        let decoded =
+            Chunk::from_arrow_ipc("a_table", &bytes, ChunkMetrics::new_unregistered()).unwrap();
+        assert_eq!(decoded.rows(), 6);
+        assert_eq!(decoded.row_groups(), 2);
+        assert_eq!(
+            read_all(&decoded, Selection::All),
+            read_all(&chunk, Selection::All)
+        );
+
+        // only the selected columns are serialised
+        let columns = Selection::Some(&["region", "time"]);
+        let bytes = chunk.to_arrow_ipc(columns).unwrap();
+        let decoded =
+            Chunk::from_arrow_ipc("a_table", &bytes, ChunkMetrics::new_unregistered()).unwrap();
+        assert_eq!(
+            read_all(&decoded, Selection::All),
+            read_all(&chunk, columns)
+        );
+
+        // a chunk without rows keeps its schema
+        let empty = Chunk::new(
+            "a_table",
+            gen_recordbatch().slice(0, 0),
+            ChunkMetrics::new_unregistered(),
+        );
+        let bytes = empty.to_arrow_ipc(Selection::All).unwrap();
+        let decoded =
+            Chunk::from_arrow_ipc("a_table", &bytes, ChunkMetrics::new_unregistered()).unwrap();
+        assert_eq!(decoded.row_groups(), 0);
+        assert_eq!(
+            decoded.read_filter_table_schema(Selection::All).unwrap(),
+            empty.read_filter_table_schema(Selection::All).unwrap()
+        );
+
+        assert!(matches!(
+            Chunk::from_arrow_ipc("a_table", b"not ipc", ChunkMetrics::new_unregistered()),
+            Err(Error::ArrowIpc { .. })
+        ));
+    }
+
+    #[test]
+    fn column_decode_duration() {
+        let registry = metric::Registry::new();
+
+        let mut chunk = ChunkBuilder::default()
+            .metrics(ChunkMetrics::new(&registry, "mydb"))
+            .build();
+        chunk.upsert_table(gen_recordbatch());
+
+        // nothing decoded until results are read
+        let results = chunk
+            .read_filter(Predicate::default(), Selection::All, vec![])
+            .unwrap();
+        let mut reporter = RawReporter::default();
+        registry.report(&mut reporter);
+        assert!(reporter
+            .metric("read_buffer_column_decode_duration")
+            .unwrap()
+            .observations
+            .is_empty());
+
+        // every column in both row groups is decoded
+        assert_eq!(results.count(), 2);
+
+        // only the tag column is decoded
+        let results = chunk
+            .read_filter(Predicate::default(), Selection::Some(&["region"]), vec![])
+            .unwrap();
+        assert_eq!(results.count(), 2);
+
+        let mut reporter = RawReporter::default();
+        registry.report(&mut reporter);
+        let samples = reporter
+            .metric("read_buffer_column_decode_duration")
+            .unwrap()
+            .observations
+            .iter()
+            .map(|(attributes, observation)| match observation {
+                Observation::DurationHistogram(histogram) => (
+                    attributes.clone(),
+                    histogram.buckets.iter().map(|bucket| bucket.count).sum(),
+                ),
+                v => panic!("unexpected observation {:?}", v),
+            })
+            .collect::<Vec<(Attributes, u64)>>();
+
+        assert_eq!(
+            samples,
+            vec![
+                (
+                    Attributes::from(&[
+                        ("db_name", "mydb"),
+                        ("encoding", "BT_U32-FIXED"),
+                        ("log_data_type", "i64")
+                    ]),
+                    2
+                ),
+                (
+                    Attributes::from(&[
+                        ("db_name", "mydb"),
+                        ("encoding", "FBT_U8-FIXEDN"),
+                        ("log_data_type", "f64")
+                    ]),
+                    2
+                ),
+                (
+                    Attributes::from(&[
+                        ("db_name", "mydb"),
+                        ("encoding", "FIXED"),
+                        ("log_data_type", "f64")
+                    ]),
+                    2
+                ),
+                (
+                    Attributes::from(&[
+                        ("db_name", "mydb"),
+                        ("encoding", "FIXEDN"),
+                        ("log_data_type", "bool")
+                    ]),
+                    2
+                ),
+                (
+                    Attributes::from(&[
+                        ("db_name", "mydb"),
+                        ("encoding", "RLE"),
+                        ("log_data_type", "string")
+                    ]),
+                    4
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn size_raw_with_parquet() {
+        let chunk = ChunkBuilder::default().build();
+
+        let size_raw = chunk.size_raw(true);
+        assert!(size_raw > 0);
+        assert_eq!(chunk.size_raw_with_parquet(true, None), size_raw);
+        assert_eq!(
+            chunk.size_raw_with_parquet(true, Some(1024)),
+            size_raw + 1024
+        );
+        assert_eq!(
+            chunk.size_raw_with_parquet(false, Some(1024)),
+            chunk.size_raw(false) + 1024
+        );
+    }
+
+    #[test]
+    fn low_cardinality_string_field_encoding() {
+        let schema = SchemaBuilder::new()
+            .non_null_field("level", Utf8)
+            .timestamp()
+            .build()
+            .unwrap();
+
+        let levels = ["info", "warn", "error"];
+        let data: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(
+                (0..1000).map(|i| levels[i / 400]).collect::<Vec<_>>(),
+            )),
+            Arc::new(TimestampNanosecondArray::from_vec(
+                (0..1000).collect::<Vec<_>>(),
+                None,
+            )),
+        ];
+        let rb = RecordBatch::try_new(schema.into(), data).unwrap();
+        let chunk = ChunkBuilder::default().record_batch(rb).build();
+
+        let stats = chunk
+            .table
+            .column_storage_statistics()
+            .into_iter()
+            .filter(|stat| stat.log_data_type == "string")
+            .collect::<Vec<_>>();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].enc_type, "RLE");
+        assert!(stats[0].allocated_bytes < stats[0].raw_bytes);
+    }
+
+    #[test]
+    fn read_filter_table_schema() {
         let chunk = ChunkBuilder::default().build();
         let schema = chunk.read_filter_table_schema(Selection::All).unwrap();
 
@@ -864,6 +2618,161 @@ mod test {
         ));
     }
 
+    #[test]
+    fn read_filter_column_order() {
+        let mut chunk = ChunkBuilder::default().build();
+        let columns = Selection::Some(&["sketchy_sensor", "time", "region"]);
+
+        let field_names = |chunk: &Chunk| {
+            let schema_names = chunk
+                .read_filter_table_schema(columns)
+                .unwrap()
+                .iter()
+                .map(|(_, field)| field.name().clone())
+                .collect::<Vec<_>>();
+
+            let batches = chunk
+                .read_filter(Predicate::default(), columns, vec![])
+                .unwrap()
+                .collect::<Vec<_>>();
+            for batch in &batches {
+                let batch_names = batch
+                    .schema()
+                    .fields()
+                    .iter()
+                    .map(|f| f.name().clone())
+                    .collect::<Vec<_>>();
+                assert_eq!(batch_names, schema_names);
+            }
+            schema_names
+        };
+
+        // selected columns are returned in the order they were selected by
+        // default
+        assert_eq!(chunk.column_order(), ColumnOrder::Selection);
+        assert_eq!(
+            field_names(&chunk),
+            vec!["sketchy_sensor", "time", "region"]
+        );
+
+        // the chunk was built from columns region, counter, active, time and
+        // sketchy_sensor
+        chunk.set_column_order(ColumnOrder::Ingestion);
+        assert_eq!(
+            field_names(&chunk),
+            vec!["region", "time", "sketchy_sensor"]
+        );
+
+        // selecting all columns is unaffected
+        let schema = chunk.read_filter_table_schema(Selection::All).unwrap();
+        let names = schema
+            .iter()
+            .map(|(_, field)| field.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["region", "counter", "active", "time", "sketchy_sensor"]
+        );
+    }
+
+    #[test]
+    fn empty_chunk() {
+        let schema = SchemaBuilder::new()
+            .tag("region")
+            .field("counter", Float64)
+            .field("active", Boolean)
+            .timestamp()
+            .build()
+            .unwrap();
+        let chunk = Chunk::empty("a_table", &schema, ChunkMetrics::new_unregistered()).unwrap();
+
+        assert_eq!(chunk.rows(), 0);
+        assert_eq!(chunk.row_groups(), 0);
+        assert_eq!(
+            chunk.read_filter_table_schema(Selection::All).unwrap(),
+            schema
+        );
+
+        let results = chunk
+            .read_filter(Predicate::default(), Selection::All, vec![])
+            .unwrap();
+        assert!(results.is_empty());
+        assert_eq!(results.count(), 0);
+
+        let results = chunk
+            .read_filter(
+                Predicate::new(vec![BinaryExpr::from(("region", "=", "west"))]),
+                Selection::Some(&["counter"]),
+                vec![],
+            )
+            .unwrap();
+        assert_eq!(results.count(), 0);
+
+        // columns must be tags, fields or the timestamp
+        let schema = SchemaBuilder::new()
+            .tag("region")
+            .field("day", DataType::Date32)
+            .timestamp()
+            .build()
+            .unwrap();
+        let err = Chunk::empty("a_table", &schema, ChunkMetrics::new_unregistered()).unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                Error::TableError {
+                    source: table::Error::UnsupportedColumnType { column_name }
+                } if column_name == "day"
+            ),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn arrow_schema() {
+        let chunk = ChunkBuilder::default().build();
+        let schema = chunk.arrow_schema(Selection::All).unwrap();
+
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().as_str(), f.data_type().clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![
+                (
+                    "region",
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(Utf8))
+                ),
+                ("counter", Float64),
+                ("active", Boolean),
+                (
+                    TIME_COLUMN_NAME,
+                    DataType::Timestamp(TimeUnit::Nanosecond, None)
+                ),
+                ("sketchy_sensor", Float64),
+            ]
+        );
+
+        let schema = chunk
+            .arrow_schema(Selection::Some(&["counter", "region"]))
+            .unwrap();
+        assert_eq!(
+            schema
+                .fields()
+                .iter()
+                .map(|f| f.name().as_str())
+                .collect::<Vec<_>>(),
+            vec!["counter", "region"]
+        );
+
+        assert!(matches!(
+            chunk.arrow_schema(Selection::Some(&["random column name"])),
+            Err(Error::ColumnDoesNotExist { .. })
+        ));
+    }
+
     #[test]
     fn table_summaries() {
         use std::iter::repeat;
@@ -1023,12 +2932,17 @@ mod test {
             "expected:\n{:#?}\n\nactual:{:#?}\n\n",
             expected_column_summaries, column_summaries
         );
+
+        // `host` contains a NULL but is not entirely NULL
+        assert_eq!(
+            chunk.all_null_columns(),
+            vec!["zbool", "zf64", "zi64", "zstr", "zu64"]
+        );
     }
 
     fn read_filter_setup() -> Chunk {
-        let mut chunk: Option<Chunk> = None;
-
         // Add a bunch of row groups to a single table in a single chunk
+        let mut batches = vec![];
         for &i in &[100, 200, 300] {
             let schema = SchemaBuilder::new()
                 .non_null_tag("env")
@@ -1068,93 +2982,1004 @@ mod test {
                 )),
             ];
 
-            // Add a record batch to a single partition
-            let rb = RecordBatch::try_new(schema.into(), data).unwrap();
+            batches.push(RecordBatch::try_new(schema.into(), data).unwrap());
+        }
+
+        Chunk::from_batches("Coolverine", batches, ChunkMetrics::new_unregistered()).unwrap()
+    }
+
+    #[test]
+    fn read_filter() {
+        // Chunk should be initialized now.
+        let chunk = read_filter_setup();
+
+        // Build the operation equivalent to the following query:
+        //
+        //   SELECT * FROM "table_1"
+        //   WHERE "env" = 'us-west' AND
+        //   "time" >= 100 AND  "time" < 205
+        //
+        let predicate =
+            Predicate::with_time_range(&[BinaryExpr::from(("env", "=", "us-west"))], 100, 205); // filter on time
+
+        let mut itr = chunk
+            .read_filter(predicate, Selection::All, vec![])
+            .unwrap();
+
+        let exp_env_values = Values::Dictionary(vec![0], vec![Some("us-west")]);
+        let exp_region_values = Values::Dictionary(vec![0], vec![Some("west")]);
+        let exp_counter_values = Values::F64(vec![1.2]);
+        let exp_sketchy_sensor_values = Values::I64N(vec![None]);
+        let exp_active_values = Values::Bool(vec![Some(true)]);
+        let exp_msg_values = Values::String(vec![Some("message a")]);
+
+        let first_row_group = itr.next().unwrap();
+        assert_rb_column_equals(&first_row_group, "env", &exp_env_values);
+        assert_rb_column_equals(&first_row_group, "region", &exp_region_values);
+        assert_rb_column_equals(&first_row_group, "counter", &exp_counter_values);
+        assert_rb_column_equals(
+            &first_row_group,
+            "sketchy_sensor",
+            &exp_sketchy_sensor_values,
+        );
+        assert_rb_column_equals(&first_row_group, "active", &exp_active_values);
+        assert_rb_column_equals(&first_row_group, "msg", &exp_msg_values);
+        assert_rb_column_equals(&first_row_group, "all_null", &Values::String(vec![None]));
+        assert_rb_column_equals(&first_row_group, "time", &Values::I64(vec![100])); // first row from first record batch
+
+        let second_row_group = itr.next().unwrap();
+        assert_rb_column_equals(&second_row_group, "env", &exp_env_values);
+        assert_rb_column_equals(&second_row_group, "region", &exp_region_values);
+        assert_rb_column_equals(&second_row_group, "counter", &exp_counter_values);
+        assert_rb_column_equals(
+            &first_row_group,
+            "sketchy_sensor",
+            &exp_sketchy_sensor_values,
+        );
+        assert_rb_column_equals(&first_row_group, "active", &exp_active_values);
+        assert_rb_column_equals(&first_row_group, "all_null", &Values::String(vec![None]));
+        assert_rb_column_equals(&second_row_group, "time", &Values::I64(vec![200])); // first row from second record batch
+
+        // No rows returned when filtering on all_null column
+        let predicate = Predicate::new(vec![BinaryExpr::from(("all_null", "!=", "a string"))]);
+        let mut itr = chunk
+            .read_filter(predicate, Selection::All, vec![])
+            .unwrap();
+        assert!(itr.next().is_none());
+
+        // Error when predicate is invalid
+        let predicate =
+            Predicate::with_time_range(&[BinaryExpr::from(("env", "=", 22.3))], 100, 205);
+        assert!(chunk
+            .read_filter(predicate, Selection::All, vec![])
+            .is_err());
+
+        // No more data
+        assert!(itr.next().is_none());
+    }
+
+    #[test]
+    fn row_ids_matching() {
+        let chunk = read_filter_setup();
+
+        // Same predicate as in the `read_filter` test above, which emits the
+        // first row of the first and second row groups.
+        let predicate =
+            Predicate::with_time_range(&[BinaryExpr::from(("env", "=", "us-west"))], 100, 205);
+
+        let row_ids = chunk.row_ids_matching(&predicate).unwrap();
+        let row_ids = row_ids.iter().map(RowIDs::to_vec).collect::<Vec<_>>();
+        assert_eq!(row_ids, vec![vec![0], vec![0], vec![]]);
+
+        let times = chunk
+            .read_filter(predicate, Selection::Some(&["time"]), vec![])
+            .unwrap()
+            .map(|rb| {
+                rb.column(0)
+                    .as_any()
+                    .downcast_ref::<TimestampNanosecondArray>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        // each row group was built with the times `[i, 2 * i, 3 * i]`
+        let expected_times = row_ids
+            .iter()
+            .zip([100, 200, 300])
+            .filter(|(ids, _)| !ids.is_empty())
+            .map(|(ids, i)| {
+                ids.iter()
+                    .map(|&id| (id as i64 + 1) * i)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(times, expected_times);
+
+        // An empty predicate matches every row in every row group.
+        let row_ids = chunk.row_ids_matching(&Predicate::default()).unwrap();
+        assert_eq!(
+            row_ids.iter().map(RowIDs::to_vec).collect::<Vec<_>>(),
+            vec![vec![0, 1, 2]; 3]
+        );
+
+        // Error when predicate is invalid
+        let predicate = Predicate::new(vec![BinaryExpr::from(("env", "=", 22.3))]);
+        assert!(chunk.row_ids_matching(&predicate).is_err());
+    }
+
+    #[test]
+    fn read_filter_duplicate_columns() {
+        let chunk = read_filter_setup();
+        let columns = Selection::Some(&["region", "time", "region", "counter", "time"]);
+
+        let schema = chunk.read_filter_table_schema(columns).unwrap();
+        let names = schema
+            .iter()
+            .map(|(_, f)| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["region", "time", "counter"]);
+
+        let batches = chunk
+            .read_filter(Predicate::default(), columns, vec![])
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(batches.len(), 3);
+        for rb in batches {
+            let names = rb
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect::<Vec<_>>();
+            assert_eq!(names, vec!["region", "time", "counter"]);
+        }
+    }
+
+    #[test]
+    fn validate_predicate_all() {
+        let chunk = read_filter_setup();
+
+        let predicate = Predicate::with_time_range(
+            &[
+                BinaryExpr::from(("env", "=", 22.3)),
+                BinaryExpr::from(("region", "=", "west")),
+                BinaryExpr::from(("not_a_column", "=", "foo")),
+            ],
+            100,
+            205,
+        );
+
+        // the single error version only reports the first invalid expression
+        assert!(chunk.validate_predicate(predicate.clone()).is_err());
+
+        let errors = chunk.validate_predicate_all(predicate).unwrap_err();
+        let column_names = errors
+            .iter()
+            .map(|e| match e {
+                Error::TableError {
+                    source: table::Error::UnsupportedColumnOperation { column_name, .. },
+                } => column_name.as_str(),
+                e => panic!("unexpected error {}", e),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(column_names, vec!["env", "not_a_column"]);
+
+        let predicate = Predicate::new(vec![BinaryExpr::from(("region", "=", "west"))]);
+        assert!(chunk.validate_predicate_all(predicate).is_ok());
+    }
+
+    #[test]
+    fn read_filter_regex() {
+        let chunk = read_filter_setup();
+
+        let read_times = |exprs: Vec<BinaryExpr>| {
+            chunk
+                .read_filter(Predicate::new(exprs), Selection::Some(&["time"]), vec![])
+                .unwrap()
+                .map(|rb| {
+                    rb.column(0)
+                        .as_any()
+                        .downcast_ref::<TimestampNanosecondArray>()
+                        .unwrap()
+                        .values()
+                        .to_vec()
+                })
+                .collect::<Vec<_>>()
+        };
+        let regex = |pattern: &str| Regex::new(pattern).unwrap();
+
+        // region: ["west", "west", "east"] in each row group
+        let times = read_times(vec![BinaryExpr::regex_match("region", regex("^we"))]);
+        assert_eq!(times, vec![vec![100, 200], vec![200, 400], vec![300, 600]]);
+
+        let times = read_times(vec![BinaryExpr::regex_not_match("region", regex("^we"))]);
+        assert_eq!(times, vec![vec![300], vec![600], vec![900]]);
+
+        // env: ["us-west", "us-east", "us-west"] in each row group
+        let times = read_times(vec![
+            BinaryExpr::regex_match("env", regex("^us-")),
+            BinaryExpr::regex_match("region", regex("east|west")),
+            BinaryExpr::regex_not_match("env", regex("west$")),
+        ]);
+        assert_eq!(times, vec![vec![200], vec![400], vec![600]]);
+
+        // NULL values satisfy neither operator.
+        let times = read_times(vec![BinaryExpr::regex_not_match("msg", regex("b$"))]);
+        assert_eq!(times, vec![vec![100], vec![200], vec![300]]);
+        assert!(read_times(vec![BinaryExpr::regex_not_match("all_null", regex("a"))]).is_empty());
+
+        assert!(
+            chunk.could_pass_predicate(Predicate::new(vec![BinaryExpr::regex_match(
+                "region",
+                regex("^we")
+            )]))
+        );
+        assert!(
+            !chunk.could_pass_predicate(Predicate::new(vec![BinaryExpr::regex_match(
+                "all_null",
+                regex(".*")
+            )]))
+        );
+
+        // regexes can only be matched against string columns.
+        for column in ["counter", "active", "time"] {
+            let predicate = Predicate::new(vec![BinaryExpr::regex_match(column, regex("1"))]);
+            assert!(
+                chunk.validate_predicate(predicate.clone()).is_err(),
+                "{}",
+                column
+            );
+            assert!(!chunk.could_pass_predicate(predicate));
+        }
+
+        // a regex operator requires a regex literal.
+        let predicate = Predicate::new(vec![BinaryExpr::from(("region", "=~", "west"))]);
+        assert!(chunk.validate_predicate(predicate).is_err());
+    }
+
+    #[test]
+    fn read_filter_in_list() {
+        let chunk = read_filter_setup();
+
+        let read_times = |exprs: Vec<BinaryExpr>| {
+            chunk
+                .read_filter(Predicate::new(exprs), Selection::Some(&["time"]), vec![])
+                .unwrap()
+                .map(|rb| {
+                    rb.column(0)
+                        .as_any()
+                        .downcast_ref::<TimestampNanosecondArray>()
+                        .unwrap()
+                        .values()
+                        .to_vec()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // region is ["west", "west", "east"] in each row group
+        let times = read_times(vec![BinaryExpr::from((
+            "region",
+            "IN",
+            vec!["east", "north"],
+        ))]);
+        assert_eq!(times, vec![vec![300], vec![600], vec![900]]);
+
+        // counter is [1.2, 300.3, 4500.3] in each row group
+        let times = read_times(vec![
+            BinaryExpr::from(("env", "IN", vec!["us-east", "us-west"])),
+            BinaryExpr::from(("counter", "IN", vec![1.2, 4500.3, 99.0])),
+        ]);
+        assert_eq!(times, vec![vec![100, 300], vec![200, 600], vec![300, 900]]);
+
+        let times = read_times(vec![BinaryExpr::from(("time", "IN", vec![200_i64, 600]))]);
+        assert_eq!(times, vec![vec![200], vec![200, 600], vec![600]]);
+
+        let empty: Vec<&str> = vec![];
+        assert!(read_times(vec![BinaryExpr::from(("region", "IN", empty))]).is_empty());
+
+        // msg is ["message a", "message b", NULL] and sketchy_sensor is
+        // [NULL, 33, 44] in each row group.
+        let names = chunk
+            .column_names(
+                Predicate::new(vec![BinaryExpr::from(("msg", "IN", vec!["message a"]))]),
+                vec![],
+                Selection::Some(&["msg", "sketchy_sensor", "all_null"]),
+                false,
+                BTreeSet::new(),
+            )
+            .unwrap();
+        assert_eq!(names, to_set(&["msg"]));
+
+        let values = chunk
+            .column_values(
+                Predicate::new(vec![BinaryExpr::from(("counter", "IN", vec![300.3]))]),
+                Selection::Some(&["region", "env"]),
+                BTreeMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            values,
+            to_map(vec![("region", &["west"]), ("env", &["us-east"])])
+        );
+
+        // every value in the list must have the column's type
+        for expr in [
+            BinaryExpr::from(("region", "IN", vec![1_i64])),
+            BinaryExpr::from(("counter", "IN", vec!["a"])),
+            BinaryExpr::in_list(
+                "region",
+                vec![Literal::String("west".to_owned()), Literal::Integer(1)],
+            ),
+            BinaryExpr::from(("region", "IN", "west")),
+            BinaryExpr::from(("region", "=", vec!["west"])),
+        ] {
+            let predicate = Predicate::new(vec![expr]);
+            assert!(
+                chunk.validate_predicate(predicate.clone()).is_err(),
+                "{}",
+                predicate
+            );
+        }
+    }
+
+    #[test]
+    fn read_filter_case_insensitive_columns() {
+        let mut chunk = read_filter_setup();
+        let predicate =
+            Predicate::with_time_range(&[BinaryExpr::from(("ENV", "=", "us-west"))], 100, 205);
+        let columns = Selection::Some(&["Region", "TIME"]);
+
+        // column names must match exactly by default
+        assert!(chunk.validate_predicate(predicate.clone()).is_err());
+
+        chunk.set_column_name_matching(ColumnNameMatching::CaseInsensitive);
+        let validated = chunk.validate_predicate(predicate.clone()).unwrap();
+        assert_eq!(validated.iter().last().unwrap().column(), "env");
+
+        let batches = chunk
+            .read_filter(predicate, columns, vec![])
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(batches.len(), 2);
+        let names = batches[0]
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["region", "time"]);
+        assert_rb_column_equals(&batches[0], "time", &Values::I64(vec![100]));
+        assert_rb_column_equals(&batches[1], "time", &Values::I64(vec![200]));
+    }
+
+    #[test]
+    fn read_filter_ambiguous_column_name() {
+        let schema = SchemaBuilder::new()
+            .non_null_tag("host")
+            .non_null_tag("HOST")
+            .timestamp()
+            .build()
+            .unwrap();
+        let data: Vec<ArrayRef> = vec![
+            Arc::new(
+                vec!["a", "b"]
+                    .into_iter()
+                    .collect::<DictionaryArray<Int32Type>>(),
+            ),
+            Arc::new(
+                vec!["c", "d"]
+                    .into_iter()
+                    .collect::<DictionaryArray<Int32Type>>(),
+            ),
+            Arc::new(TimestampNanosecondArray::from_vec(vec![1, 2], None)),
+        ];
+        let rb = RecordBatch::try_new(schema.into(), data).unwrap();
+        let mut chunk = Chunk::new("a_table", rb, ChunkMetrics::new_unregistered());
+        chunk.set_column_name_matching(ColumnNameMatching::CaseInsensitive);
+
+        // an exact match is never ambiguous
+        let predicate = Predicate::new(vec![BinaryExpr::from(("HOST", "=", "c"))]);
+        assert!(chunk.validate_predicate(predicate).is_ok());
+
+        let predicate = Predicate::new(vec![BinaryExpr::from(("Host", "=", "c"))]);
+        assert_eq!(
+            chunk.validate_predicate(predicate.clone()).unwrap_err().to_string(),
+            "error processing table: column name \"Host\" is ambiguous, it matches columns HOST, host"
+        );
+        assert!(chunk
+            .read_filter(predicate, Selection::All, vec![])
+            .is_err());
+
+        let err = chunk
+            .read_filter(Predicate::default(), Selection::Some(&["Host"]), vec![])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::TableError {
+                source: table::Error::AmbiguousColumnName { .. }
+            }
+        ));
+    }
+
+    #[test]
+    fn contains_value() {
+        let chunk = read_filter_setup();
+
+        assert!(chunk.contains_value("env", "us-east").unwrap());
+        assert!(!chunk.contains_value("env", "eu-central").unwrap());
+        assert!(chunk.contains_value("msg", "message b").unwrap());
+        assert!(!chunk.contains_value("all_null", "").unwrap());
+
+        // non-dictionary columns are scanned
+        assert!(chunk.contains_value("sketchy_sensor", "33").unwrap());
+        assert!(!chunk.contains_value("sketchy_sensor", "34").unwrap());
+        assert!(!chunk
+            .contains_value("sketchy_sensor", "not a number")
+            .unwrap());
+        assert!(chunk.contains_value("active", "false").unwrap());
+
+        assert!(matches!(
+            chunk.contains_value("region_id", "west"),
+            Err(Error::ColumnDoesNotExist { .. })
+        ));
+    }
+
+    #[test]
+    fn split_by_row_group() {
+        let chunk = read_filter_setup();
+        assert_eq!(chunk.row_groups(), 3);
+        let rows = chunk.rows();
+
+        let chunks = chunk.split_by_row_group();
+        assert_eq!(chunks.len(), 3);
+        for (chunk, &first_time) in chunks.iter().zip(&[100, 200, 300]) {
+            assert_eq!(chunk.row_groups(), 1);
+            assert_eq!(chunk.rows(), 3);
+            assert_eq!(chunk.table_summary().name, "Coolverine");
+
+            let mut itr = chunk
+                .read_filter(Predicate::default(), Selection::Some(&["time"]), vec![])
+                .unwrap();
+            let rb = itr.next().unwrap();
+            assert_rb_column_equals(
+                &rb,
+                "time",
+                &Values::I64(vec![first_time, 2 * first_time, 3 * first_time]),
+            );
+            assert!(itr.next().is_none());
+        }
+        assert_eq!(chunks.iter().map(|c| c.rows()).sum::<u64>(), rows);
+    }
+
+    #[test]
+    fn rename_table() {
+        let mut chunk = read_filter_setup();
+        let rows = chunk.rows();
+        let row_groups = chunk.row_groups();
+
+        chunk.rename_table("Wolverine");
+        assert_eq!(chunk.table_summary().name, "Wolverine");
+        assert_eq!(chunk.rows(), rows);
+        assert_eq!(chunk.row_groups(), row_groups);
+
+        let predicate =
+            Predicate::with_time_range(&[BinaryExpr::from(("env", "=", "us-west"))], 100, 205);
+        let mut itr = chunk
+            .read_filter(predicate, Selection::All, vec![])
+            .unwrap();
+
+        let first_row_group = itr.next().unwrap();
+        assert_rb_column_equals(&first_row_group, "time", &Values::I64(vec![100]));
+        let second_row_group = itr.next().unwrap();
+        assert_rb_column_equals(&second_row_group, "time", &Values::I64(vec![200]));
+        assert!(itr.next().is_none());
+    }
+
+    #[test]
+    fn read_sample() {
+        let rows = 10_000;
+        let batch = |offset: i64| {
+            let schema = SchemaBuilder::new()
+                .non_null_tag("env")
+                .timestamp()
+                .build()
+                .unwrap();
+            let data: Vec<ArrayRef> = vec![
+                Arc::new(
+                    (0..rows)
+                        .map(|i| if i % 2 == 0 { "prod" } else { "dev" })
+                        .collect::<DictionaryArray<Int32Type>>(),
+                ),
+                Arc::new(TimestampNanosecondArray::from_vec(
+                    (offset..offset + rows).collect(),
+                    None,
+                )),
+            ];
+            RecordBatch::try_new(schema.into(), data).unwrap()
+        };
+        let chunk = Chunk::from_batches(
+            "a_table",
+            vec![batch(0), batch(rows)],
+            ChunkMetrics::new_unregistered(),
+        )
+        .unwrap();
+
+        // 10,000 rows match across the two row groups
+        let predicate = Predicate::new(vec![BinaryExpr::from(("env", "=", "prod"))]);
+        let sample = |fraction, seed| {
+            chunk
+                .read_sample(
+                    predicate.clone(),
+                    Selection::Some(&["time"]),
+                    vec![],
+                    fraction,
+                    seed,
+                )
+                .unwrap()
+        };
+
+        let sampled = sample(0.1, 42);
+        assert_eq!(sampled.num_columns(), 1);
+        assert!(
+            (800..=1_200).contains(&sampled.num_rows()),
+            "sampled {} rows",
+            sampled.num_rows()
+        );
+
+        // only rows satisfying the predicate are sampled
+        let times = sampled
+            .column(0)
+            .as_any()
+            .downcast_ref::<TimestampNanosecondArray>()
+            .unwrap();
+        assert!(times.values().iter().all(|time| time % 2 == 0));
+
+        // the sample is reproducible for a seed
+        let format = |batch| arrow_util::display::pretty_format_batches(&[batch]).unwrap();
+        assert_eq!(format(sample(0.1, 42)), format(sampled.clone()));
+        assert_ne!(format(sample(0.1, 43)), format(sampled));
+
+        assert_eq!(sample(1.0, 42).num_rows(), 10_000);
+        assert_eq!(sample(0.0, 42).num_rows(), 0);
+
+        // rows removed by a delete predicate are never sampled
+        let deleted = chunk
+            .read_sample(
+                predicate.clone(),
+                Selection::Some(&["time"]),
+                vec![Predicate::new(vec![BinaryExpr::from(("time", ">=", rows))])],
+                1.0,
+                42,
+            )
+            .unwrap();
+        assert_eq!(deleted.num_rows(), 5_000);
+
+        assert!(matches!(
+            chunk.read_sample(Predicate::default(), Selection::All, vec![], 1.5, 42),
+            Err(Error::TableError {
+                source: table::Error::InvalidSampleFraction { .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn read_filter_limited() {
+        let chunk = read_filter_setup();
+        let times_matching = |predicate: Predicate, limit: Option<u64>, offset: u64| {
+            chunk
+                .read_filter_limited(predicate, Selection::Some(&["time"]), vec![], limit, offset)
+                .unwrap()
+                .map(|rb| {
+                    let column = rb.column(rb.schema().index_of("time").unwrap());
+                    let arr = column
+                        .as_any()
+                        .downcast_ref::<TimestampNanosecondArray>()
+                        .unwrap();
+                    arr.values().to_vec()
+                })
+                .collect::<Vec<_>>()
+        };
+        let times = |limit, offset| times_matching(Predicate::default(), limit, offset);
+
+        // Rows have timestamps [100, 200, 300], [200, 400, 600] and
+        // [300, 600, 900] in each of the three row groups.
+        assert_eq!(
+            times(None, 0),
+            vec![
+                vec![100, 200, 300],
+                vec![200, 400, 600],
+                vec![300, 600, 900]
+            ]
+        );
+        assert_eq!(times(Some(4), 2), vec![vec![300], vec![200, 400, 600]]);
+        assert_eq!(times(Some(2), 0), vec![vec![100, 200]]);
+        assert_eq!(times(None, 7), vec![vec![600, 900]]);
+        assert_eq!(
+            times(Some(100), 3),
+            vec![vec![200, 400, 600], vec![300, 600, 900]]
+        );
+        assert!(times(Some(0), 0).is_empty());
+        assert!(times(None, 9).is_empty());
+
+        // only rows satisfying the predicate count towards the offset.
+        let predicate = Predicate::new(vec![BinaryExpr::from(("time", ">", 250_i64))]);
+        assert_eq!(
+            times_matching(predicate.clone(), Some(2), 2),
+            vec![vec![600], vec![300]]
+        );
+        assert_eq!(
+            times_matching(predicate, None, 3),
+            vec![vec![300, 600, 900]]
+        );
+    }
+
+    #[test]
+    fn read_window_aggregate() {
+        let chunk = read_filter_setup();
+
+        let rb = chunk
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&["env"]),
+                &[("counter", AggregateType::Sum)],
+                Some(TimeWindow::new(100, 0)),
+            )
+            .unwrap()
+            .into_record_batch()
+            .unwrap();
+
+        // the windows starting at 500, 700 and 800 contain no rows.
+        arrow_util::assert_batches_eq!(
+            &[
+                "+--------------------------------+---------+-------------+",
+                "| time                           | env     | counter_sum |",
+                "+--------------------------------+---------+-------------+",
+                "| 1970-01-01T00:00:00.000000100Z | us-west | 1.2         |",
+                "| 1970-01-01T00:00:00.000000200Z | us-east | 300.3       |",
+                "| 1970-01-01T00:00:00.000000200Z | us-west | 1.2         |",
+                "| 1970-01-01T00:00:00.000000300Z | us-west | 4501.5      |",
+                "| 1970-01-01T00:00:00.000000400Z | us-east | 300.3       |",
+                "| 1970-01-01T00:00:00.000000600Z | us-east | 300.3       |",
+                "| 1970-01-01T00:00:00.000000600Z | us-west | 4500.3      |",
+                "| 1970-01-01T00:00:00.000000900Z | us-west | 4500.3      |",
+                "+--------------------------------+---------+-------------+",
+            ],
+            &[rb]
+        );
+
+        // windows are half-open and aligned to the offset.
+        let rb = chunk
+            .read_aggregate(
+                Predicate::new(vec![BinaryExpr::from(("env", "=", "us-west"))]),
+                &Selection::Some(&[]),
+                &[("counter", AggregateType::Count)],
+                Some(TimeWindow::new(500, 100)),
+            )
+            .unwrap()
+            .into_record_batch()
+            .unwrap();
+
+        arrow_util::assert_batches_eq!(
+            &[
+                "+--------------------------------+---------------+",
+                "| time                           | counter_count |",
+                "+--------------------------------+---------------+",
+                "| 1970-01-01T00:00:00.000000100Z | 4             |",
+                "| 1970-01-01T00:00:00.000000600Z | 2             |",
+                "+--------------------------------+---------------+",
+            ],
+            &[rb]
+        );
+
+        let err = chunk
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&["env"]),
+                &[("counter", AggregateType::Sum)],
+                Some(TimeWindow::new(0, 0)),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::TableError {
+                source: table::Error::InvalidTimeWindow { every_ns: 0 }
+            }
+        ));
+    }
+
+    #[test]
+    fn read_aggregate_global() {
+        let chunk = read_filter_setup();
+
+        // Build the operation equivalent to the following query:
+        //
+        //   SELECT SUM("sketchy_sensor"), COUNT("counter") FROM "table_1"
+        //   WHERE "env" = 'us-west'
+        //
+        let predicate = Predicate::new(vec![BinaryExpr::from(("env", "=", "us-west"))]);
+        let mut itr = chunk
+            .read_aggregate(
+                predicate,
+                &Selection::Some(&[]),
+                &[
+                    ("sketchy_sensor", AggregateType::Sum),
+                    ("counter", AggregateType::Count),
+                ],
+                None,
+            )
+            .unwrap();
+
+        let rb = itr.next().unwrap().unwrap();
+        assert_eq!(rb.num_rows(), 1);
+        assert_rb_column_equals(&rb, "sketchy_sensor_sum", &Values::I64(vec![132]));
+        assert_rb_column_equals(&rb, "counter_count", &Values::U64(vec![6]));
+        assert!(itr.next().is_none());
+    }
+
+    #[test]
+    fn read_aggregate_percentile_stddev() {
+        let chunk = read_filter_setup();
+        let f64_column = |rb: &RecordBatch, name: &str| {
+            let column = rb.column(rb.schema().index_of(name).unwrap());
+            let arr = column.as_any().downcast_ref::<Float64Array>().unwrap();
+            arr.iter().collect::<Vec<_>>()
+        };
+        let assert_approx_eq = |got: Option<f64>, exp: f64| {
+            let got = got.unwrap();
+            assert!((got - exp).abs() < 1e-9, "got {}, expected {}", got, exp);
+        };
+
+        // Each of the three row groups has "counter" values [1.2, 300.3,
+        // 4500.3] and "sketchy_sensor" values [NULL, 33, 44].
+        let mut itr = chunk
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&[]),
+                &[
+                    ("counter", AggregateType::Percentile(0.0)),
+                    ("counter", AggregateType::Percentile(0.5)),
+                    ("counter", AggregateType::Percentile(0.95)),
+                    ("sketchy_sensor", AggregateType::StdDev),
+                ],
+                None,
+            )
+            .unwrap();
+
+        let rb = itr.next().unwrap().unwrap();
+        assert_eq!(rb.num_rows(), 1);
+        assert_eq!(f64_column(&rb, "counter_percentile_0"), vec![Some(1.2)]);
+        assert_eq!(f64_column(&rb, "counter_percentile_0.5"), vec![Some(300.3)]);
+        assert_eq!(
+            f64_column(&rb, "counter_percentile_0.95"),
+            vec![Some(4500.3)]
+        );
+        // sample standard deviation of [33, 44, 33, 44, 33, 44]
+        assert_approx_eq(f64_column(&rb, "sketchy_sensor_stddev")[0], 36.3_f64.sqrt());
+        assert!(itr.next().is_none());
+
+        let mut itr = chunk
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&["env"]),
+                &[
+                    ("counter", AggregateType::Percentile(0.5)),
+                    ("counter", AggregateType::StdDev),
+                    ("sketchy_sensor", AggregateType::StdDev),
+                ],
+                None,
+            )
+            .unwrap();
+
+        let rb = itr.next().unwrap().unwrap();
+        assert_rb_column_equals(
+            &rb,
+            "env",
+            &Values::Dictionary(vec![0, 1], vec![Some("us-east"), Some("us-west")]),
+        );
+        assert_eq!(
+            f64_column(&rb, "counter_percentile_0.5"),
+            vec![Some(300.3), Some(1.2)]
+        );
+        let stddevs = f64_column(&rb, "counter_stddev");
+        assert_approx_eq(stddevs[0], 0.0);
+        // [1.2, 4500.3] repeated three times
+        assert_approx_eq(stddevs[1], 2249.55 * 1.2_f64.sqrt());
+        // "us-east" and "us-west" have three identical non-null values each
+        assert_eq!(
+            f64_column(&rb, "sketchy_sensor_stddev"),
+            vec![Some(0.0), Some(0.0)]
+        );
+
+        // quantiles must be within [0, 1]
+        let err = chunk
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&[]),
+                &[("counter", AggregateType::Percentile(1.5))],
+                None,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::TableError {
+                source: table::Error::InvalidPercentile { .. }
+            }
+        ));
 
-            // First time through the loop, create a new Chunk. Other times, upsert into the chunk.
-            match chunk {
-                Some(ref mut c) => c.upsert_table(rb),
-                None => {
-                    chunk = Some(
-                        ChunkBuilder::default()
-                            .name("Coolverine")
-                            .record_batch(rb)
-                            .build(),
-                    );
-                }
+        // only numerical columns are supported
+        let err = chunk
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&[]),
+                &[("msg", AggregateType::StdDev)],
+                None,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::TableError {
+                source: table::Error::UnsupportedColumnOperation { .. }
             }
-        }
-        chunk.unwrap()
+        ));
     }
 
     #[test]
-    fn read_filter() {
-        // Chunk should be initialized now.
+    fn subset_by_time() {
         let chunk = read_filter_setup();
 
-        // Build the operation equivalent to the following query:
-        //
-        //   SELECT * FROM "table_1"
-        //   WHERE "env" = 'us-west' AND
-        //   "time" >= 100 AND  "time" < 205
-        //
-        let predicate =
-            Predicate::with_time_range(&[BinaryExpr::from(("env", "=", "us-west"))], 100, 205); // filter on time
+        // Rows have timestamps [100, 200, 300], [200, 400, 600] and
+        // [300, 600, 900] in each of the three row groups.
+        let subset = chunk.subset_by_time(200, 600).unwrap();
+        assert_eq!(subset.rows(), 5);
+        assert_eq!(subset.row_groups(), 3);
+        assert_eq!(subset.table_summary().name, "Coolverine");
 
-        let mut itr = chunk
-            .read_filter(predicate, Selection::All, vec![])
+        // the source chunk is unchanged
+        assert_eq!(chunk.rows(), 9);
+
+        let mut itr = subset
+            .read_filter(Predicate::default(), Selection::Some(&["time"]), vec![])
             .unwrap();
+        assert_rb_column_equals(&itr.next().unwrap(), "time", &Values::I64(vec![200, 300]));
+        assert_rb_column_equals(&itr.next().unwrap(), "time", &Values::I64(vec![200, 400]));
+        assert_rb_column_equals(&itr.next().unwrap(), "time", &Values::I64(vec![300]));
+        assert!(itr.next().is_none());
 
-        let exp_env_values = Values::Dictionary(vec![0], vec![Some("us-west")]);
-        let exp_region_values = Values::Dictionary(vec![0], vec![Some("west")]);
-        let exp_counter_values = Values::F64(vec![1.2]);
-        let exp_sketchy_sensor_values = Values::I64N(vec![None]);
-        let exp_active_values = Values::Bool(vec![Some(true)]);
-        let exp_msg_values = Values::String(vec![Some("message a")]);
+        // An empty window produces an empty chunk
+        let empty = chunk.subset_by_time(1000, 2000).unwrap();
+        assert_eq!(empty.rows(), 0);
+        assert_eq!(empty.row_groups(), 0);
+        assert_eq!(
+            empty.read_filter_table_schema(Selection::All).unwrap(),
+            chunk.read_filter_table_schema(Selection::All).unwrap()
+        );
+        assert!(empty
+            .read_filter(Predicate::default(), Selection::All, vec![])
+            .unwrap()
+            .next()
+            .is_none());
+    }
 
-        let first_row_group = itr.next().unwrap();
-        assert_rb_column_equals(&first_row_group, "env", &exp_env_values);
-        assert_rb_column_equals(&first_row_group, "region", &exp_region_values);
-        assert_rb_column_equals(&first_row_group, "counter", &exp_counter_values);
-        assert_rb_column_equals(
-            &first_row_group,
-            "sketchy_sensor",
-            &exp_sketchy_sensor_values,
+    #[test]
+    fn filter_to_new() {
+        let chunk = read_filter_setup();
+
+        let pretty = |batches: table::ReadFilterResults| {
+            arrow_util::display::pretty_format_batches(&batches.collect::<Vec<_>>()).unwrap()
+        };
+
+        // env is ["us-west", "us-east", "us-west"] and region is
+        // ["west", "west", "east"] in each row group.
+        let predicate =
+            Predicate::with_time_range(&[BinaryExpr::from(("env", "=", "us-west"))], 100, 700);
+        let negated = vec![Predicate::new(vec![BinaryExpr::from((
+            "region", "=", "east",
+        ))])];
+
+        let filtered = chunk.filter_to_new(&predicate, &negated).unwrap();
+        assert_eq!(filtered.rows(), 3);
+        assert_eq!(filtered.table_summary().name, "Coolverine");
+        assert_eq!(
+            pretty(
+                filtered
+                    .read_filter(Predicate::default(), Selection::All, vec![])
+                    .unwrap()
+            ),
+            pretty(
+                chunk
+                    .read_filter(predicate, Selection::All, negated)
+                    .unwrap()
+            )
         );
-        assert_rb_column_equals(&first_row_group, "active", &exp_active_values);
-        assert_rb_column_equals(&first_row_group, "msg", &exp_msg_values);
-        assert_rb_column_equals(&first_row_group, "all_null", &Values::String(vec![None]));
-        assert_rb_column_equals(&first_row_group, "time", &Values::I64(vec![100])); // first row from first record batch
 
-        let second_row_group = itr.next().unwrap();
-        assert_rb_column_equals(&second_row_group, "env", &exp_env_values);
-        assert_rb_column_equals(&second_row_group, "region", &exp_region_values);
-        assert_rb_column_equals(&second_row_group, "counter", &exp_counter_values);
-        assert_rb_column_equals(
-            &first_row_group,
-            "sketchy_sensor",
-            &exp_sketchy_sensor_values,
+        // the source chunk is unchanged
+        assert_eq!(chunk.rows(), 9);
+
+        // no surviving rows produces an empty chunk
+        let predicate = Predicate::new(vec![BinaryExpr::from(("env", "=", "eu-central"))]);
+        let empty = chunk.filter_to_new(&predicate, &[]).unwrap();
+        assert_eq!(empty.rows(), 0);
+        assert_eq!(empty.row_groups(), 0);
+        assert_eq!(
+            empty.read_filter_table_schema(Selection::All).unwrap(),
+            chunk.read_filter_table_schema(Selection::All).unwrap()
         );
-        assert_rb_column_equals(&first_row_group, "active", &exp_active_values);
-        assert_rb_column_equals(&first_row_group, "all_null", &Values::String(vec![None]));
-        assert_rb_column_equals(&second_row_group, "time", &Values::I64(vec![200])); // first row from second record batch
 
-        // No rows returned when filtering on all_null column
-        let predicate = Predicate::new(vec![BinaryExpr::from(("all_null", "!=", "a string"))]);
+        // an invalid predicate is an error
+        let predicate = Predicate::new(vec![BinaryExpr::from(("env", "=", 22.3))]);
+        assert!(chunk.filter_to_new(&predicate, &[]).is_err());
+    }
+
+    #[test]
+    fn dedup_rows() {
+        let gen_rb = |regions: Vec<&str>, counters: Vec<f64>, times: Vec<i64>| {
+            let schema = SchemaBuilder::new()
+                .non_null_tag("region")
+                .non_null_field("counter", Float64)
+                .timestamp()
+                .build()
+                .unwrap();
+
+            let data: Vec<ArrayRef> = vec![
+                Arc::new(regions.into_iter().collect::<DictionaryArray<Int32Type>>()),
+                Arc::new(Float64Array::from(counters)),
+                Arc::new(TimestampNanosecondArray::from_vec(times, None)),
+            ];
+            RecordBatch::try_new(schema.into(), data).unwrap()
+        };
+
+        let mut chunk = ChunkBuilder::default()
+            .record_batch(gen_rb(vec!["west", "east"], vec![1.0, 2.0], vec![10, 10]))
+            .build();
+        chunk.upsert_table(gen_rb(vec!["west", "north"], vec![3.0, 4.0], vec![10, 20]));
+        assert_eq!(chunk.rows(), 4);
+
+        // the first "west" row at time 10 is superseded by the second.
+        assert_eq!(chunk.dedup_rows(&["region"]).unwrap(), 1);
+        assert_eq!(chunk.rows(), 3);
+        assert_eq!(chunk.row_groups(), 2);
+
+        let predicate = Predicate::new(vec![BinaryExpr::from(("region", "=", "west"))]);
         let mut itr = chunk
-            .read_filter(predicate, Selection::All, vec![])
+            .read_filter(predicate, Selection::Some(&["counter"]), vec![])
             .unwrap();
+        assert_rb_column_equals(&itr.next().unwrap(), "counter", &Values::F64(vec![3.0]));
         assert!(itr.next().is_none());
 
-        // Error when predicate is invalid
-        let predicate =
-            Predicate::with_time_range(&[BinaryExpr::from(("env", "=", 22.3))], 100, 205);
-        assert!(chunk
-            .read_filter(predicate, Selection::All, vec![])
-            .is_err());
+        // nothing left to remove
+        assert_eq!(chunk.dedup_rows(&["region"]).unwrap(), 0);
 
-        // No more data
-        assert!(itr.next().is_none());
+        // every row of the second row group is superseded by the third, so
+        // the second is dropped while the others keep their rows.
+        chunk.upsert_table(gen_rb(vec!["north", "west"], vec![5.0, 6.0], vec![20, 10]));
+        assert_eq!(chunk.dedup_rows(&["region"]).unwrap(), 2);
+        assert_eq!(chunk.rows(), 3);
+        assert_eq!(chunk.row_groups(), 2);
+
+        let itr = chunk
+            .read_filter(Predicate::default(), Selection::Some(&["counter"]), vec![])
+            .unwrap();
+        let counters = itr
+            .flat_map(|rb| {
+                let counters = rb
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Float64Array>()
+                    .unwrap();
+                counters.values().to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(counters, vec![2.0, 5.0, 6.0]);
+
+        assert!(matches!(
+            chunk.dedup_rows(&["zone"]),
+            Err(Error::ColumnDoesNotExist { .. })
+        ));
+        assert!(matches!(
+            chunk.dedup_rows(&["counter"]),
+            Err(Error::UnsupportedOperation { .. })
+        ));
     }
 
     #[test]
@@ -1329,6 +4154,7 @@ mod test {
                 Predicate::default(),
                 vec![],
                 Selection::All,
+                false,
                 BTreeSet::new(),
             )
             .unwrap();
@@ -1344,6 +4170,7 @@ mod test {
                 Predicate::new(vec![BinaryExpr::from(("time", "=", 222222_i64))]),
                 vec![],
                 Selection::All,
+                false,
                 BTreeSet::new(),
             )
             .unwrap();
@@ -1352,18 +4179,104 @@ mod test {
         // only matching row.
         assert_eq!(result, to_set(&["counter", "region", "time"]));
 
+        // unless columns with only NULL values are asked for
+        let result = chunk
+            .column_names(
+                Predicate::new(vec![BinaryExpr::from(("time", "=", 222222_i64))]),
+                vec![],
+                Selection::All,
+                true,
+                BTreeSet::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            to_set(&["counter", "region", "sketchy_sensor", "time"])
+        );
+
+        // no columns are returned when no rows match
+        let result = chunk
+            .column_names(
+                Predicate::new(vec![BinaryExpr::from(("time", "=", 1_i64))]),
+                vec![],
+                Selection::All,
+                true,
+                BTreeSet::new(),
+            )
+            .unwrap();
+        assert!(result.is_empty());
+
         // Error when invalid predicate provided.
         assert!(matches!(
             chunk.column_names(
                 Predicate::new(vec![BinaryExpr::from(("time", "=", "not a number"))]),
                 vec![],
                 Selection::Some(&["region", "env"]),
+                false,
                 BTreeSet::new()
             ),
             Err(Error::TableError { .. })
         ));
     }
 
+    #[test]
+    fn tag_keys() {
+        let schema = SchemaBuilder::new()
+            .non_null_tag("region")
+            .tag("env")
+            .non_null_field("counter", Float64)
+            .timestamp()
+            .build()
+            .unwrap()
+            .into();
+
+        let data: Vec<ArrayRef> = vec![
+            Arc::new(
+                vec!["west", "west", "east"]
+                    .into_iter()
+                    .collect::<DictionaryArray<Int32Type>>(),
+            ),
+            Arc::new(
+                vec![Some("prod"), None, None]
+                    .into_iter()
+                    .collect::<DictionaryArray<Int32Type>>(),
+            ),
+            Arc::new(Float64Array::from(vec![1.2, 3.3, 45.3])),
+            Arc::new(TimestampNanosecondArray::from_vec(
+                vec![11111111, 222222, 3333],
+                None,
+            )),
+        ];
+
+        let rb = RecordBatch::try_new(schema, data).unwrap();
+        let chunk = ChunkBuilder::default()
+            .name("Utopia")
+            .record_batch(rb)
+            .build();
+
+        // only tag columns are returned
+        let result = chunk.tag_keys(&Predicate::default(), &[]).unwrap();
+        assert_eq!(result, to_set(&["env", "region"]));
+
+        // env has a NULL value for the only matching row.
+        let result = chunk
+            .tag_keys(
+                &Predicate::new(vec![BinaryExpr::from(("time", "=", 222222_i64))]),
+                &[],
+            )
+            .unwrap();
+        assert_eq!(result, to_set(&["region"]));
+
+        // no rows match
+        let result = chunk
+            .tag_keys(
+                &Predicate::new(vec![BinaryExpr::from(("region", "=", "north"))]),
+                &[],
+            )
+            .unwrap();
+        assert!(result.is_empty());
+    }
+
     #[test]
     fn column_names_with_deletes() {
         let schema = SchemaBuilder::new()
@@ -1401,6 +4314,7 @@ mod test {
                 Predicate::default(),
                 vec![Predicate::default()], // all rows deleted
                 Selection::All,
+                false,
                 BTreeSet::new(),
             )
             .unwrap();
@@ -1413,6 +4327,7 @@ mod test {
                     "region", "!=", "west",
                 ))])], // all rows deleted
                 Selection::All,
+                false,
                 BTreeSet::new(),
             )
             .unwrap();
@@ -1430,6 +4345,7 @@ mod test {
                     10.0,
                 ))])], // deletes all rows with non-null sketchy sensor values
                 Selection::All,
+                false,
                 BTreeSet::new(),
             )
             .unwrap();
@@ -1450,6 +4366,56 @@ mod test {
             .collect::<BTreeMap<_, _>>()
     }
 
+    #[test]
+    fn estimate_series_count() {
+        let chunk = read_filter_setup();
+
+        // determine the true number of series by materialising every tag set
+        let true_series_count = |tag_columns: &[&str]| {
+            chunk
+                .read_filter(Predicate::default(), Selection::Some(tag_columns), vec![])
+                .unwrap()
+                .flat_map(|rb| {
+                    (0..rb.num_rows())
+                        .map(|row| {
+                            rb.columns()
+                                .iter()
+                                .map(|arr| array_value_to_string(arr, row).unwrap())
+                                .collect::<Vec<_>>()
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<BTreeSet<_>>()
+                .len() as u64
+        };
+
+        // exact for a single column
+        assert_eq!(true_series_count(&["env"]), 2);
+        assert_eq!(chunk.estimate_series_count(&["env"]).unwrap(), 2);
+
+        // the tag set (us-east, east) is never written, so the estimate of
+        // every combination of the two values of each column is one too many.
+        assert_eq!(true_series_count(&["env", "region"]), 3);
+        assert_eq!(chunk.estimate_series_count(&["env", "region"]).unwrap(), 4);
+        assert_eq!(
+            chunk
+                .estimate_series_count(&["region", "env", "env"])
+                .unwrap(),
+            4
+        );
+
+        assert_eq!(chunk.estimate_series_count(&[]).unwrap(), 1);
+
+        assert!(matches!(
+            chunk.estimate_series_count(&["counter"]),
+            Err(Error::UnsupportedOperation { .. })
+        ));
+        assert!(matches!(
+            chunk.estimate_series_count(&["not_a_column"]),
+            Err(Error::ColumnDoesNotExist { .. })
+        ));
+    }
+
     #[test]
     fn column_values() {
         let schema = SchemaBuilder::new()
@@ -1535,5 +4501,126 @@ mod test {
             ),
             Err(Error::TableError { .. })
         ));
+
+        // Duplicate and missing columns are ignored, and the unsupported
+        // column is the one reported.
+        match chunk.column_values(
+            Predicate::default(),
+            Selection::Some(&["region", "region", "missing", "time"]),
+            BTreeMap::new(),
+        ) {
+            Err(Error::TableError {
+                source: table::Error::UnsupportedColumnOperation { column_name, .. },
+            }) => assert_eq!(column_name, "time"),
+            r => panic!("unexpected result {:?}", r),
+        }
+
+        let result = chunk
+            .column_values(
+                Predicate::default(),
+                Selection::Some(&["missing", "env", "region", "env"]),
+                BTreeMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            to_map(vec![
+                ("region", &["north", "south", "east"]),
+                ("env", &["prod", "stag"])
+            ])
+        );
+    }
+
+    #[test]
+    fn column_values_limited() {
+        let schema: Arc<arrow::datatypes::Schema> = SchemaBuilder::new()
+            .non_null_tag("region")
+            .non_null_tag("env")
+            .timestamp()
+            .build()
+            .unwrap()
+            .into();
+
+        let batch = |regions: Vec<&str>, envs: Vec<&str>, times: Vec<i64>| {
+            let data: Vec<ArrayRef> = vec![
+                Arc::new(regions.into_iter().collect::<DictionaryArray<Int32Type>>()),
+                Arc::new(envs.into_iter().collect::<DictionaryArray<Int32Type>>()),
+                Arc::new(TimestampNanosecondArray::from_vec(times, None)),
+            ];
+            RecordBatch::try_new(Arc::clone(&schema), data).unwrap()
+        };
+
+        // each batch becomes a row group
+        let chunk = Chunk::from_batches(
+            "my_table",
+            vec![
+                batch(vec!["north", "south"], vec!["prod", "prod"], vec![1, 2]),
+                batch(vec!["east", "west"], vec!["prod", "stag"], vec![3, 4]),
+            ],
+            ChunkMetrics::new_unregistered(),
+        )
+        .unwrap();
+        let columns = Selection::Some(&["region", "env"]);
+
+        let result = chunk
+            .column_values_limited(Predicate::default(), columns, 2)
+            .unwrap();
+        assert_eq!(
+            result.values,
+            to_map(vec![
+                ("region", &["north", "south"]),
+                ("env", &["prod", "stag"])
+            ])
+        );
+        assert!(result.is_truncated("region"));
+        assert!(!result.is_truncated("env"));
+
+        let result = chunk
+            .column_values_limited(Predicate::default(), columns, 3)
+            .unwrap();
+        assert_eq!(
+            result.values,
+            to_map(vec![
+                ("region", &["east", "north", "south"]),
+                ("env", &["prod", "stag"])
+            ])
+        );
+        assert!(result.is_truncated("region"));
+
+        // a limit of exactly the number of distinct values is not truncated
+        let result = chunk
+            .column_values_limited(Predicate::default(), columns, 4)
+            .unwrap();
+        assert_eq!(
+            result.values,
+            chunk
+                .column_values(Predicate::default(), columns, BTreeMap::new())
+                .unwrap()
+        );
+        assert!(result.truncated.is_empty());
+
+        // the limit applies to the values of rows matching the predicate
+        let result = chunk
+            .column_values_limited(
+                Predicate::new(vec![BinaryExpr::from(("time", ">=", 3_i64))]),
+                columns,
+                1,
+            )
+            .unwrap();
+        assert_eq!(
+            result.values,
+            to_map(vec![("region", &["east"]), ("env", &["prod"])])
+        );
+        assert_eq!(
+            result.truncated,
+            vec!["env".to_owned(), "region".to_owned()]
+                .into_iter()
+                .collect::<BTreeSet<_>>()
+        );
+
+        assert!(matches!(
+            chunk.column_values_limited(Predicate::default(), Selection::All, 2),
+            Err(Error::UnsupportedOperation { .. })
+        ));
     }
 }
@@ -11,6 +11,7 @@ use croaring::Bitmap;
 use either::Either;
 
 use arrow::array::Array;
+use regex::Regex;
 
 use crate::schema::LogicalDataType;
 use crate::value::{EncodedValues, OwnedValue, Scalar, Value, Values};
@@ -204,6 +205,19 @@ impl Column {
         }
     }
 
+    /// The encoding name and logical data-type of the column, as used to
+    /// attribute column metrics.
+    pub(crate) fn encoding_attributes(&self) -> (Cow<'static, str>, &'static str) {
+        match &self {
+            Self::String(_, data) => (data.name().into(), "string"),
+            Self::Float(_, data) => (data.name().into(), data.logical_datatype()),
+            Self::Integer(_, data) => (data.name().into(), data.logical_datatype()),
+            Self::Unsigned(_, data) => (data.name().into(), data.logical_datatype()),
+            Self::Bool(_, data) => (data.name().into(), "bool"),
+            Self::ByteArray(_, data) => (data.name().into(), "string"),
+        }
+    }
+
     pub fn properties(&self) -> &ColumnProperties {
         match &self {
             Self::String(meta, _) => &meta.properties,
@@ -340,6 +354,19 @@ impl Column {
         }
     }
 
+    // Calls `f` with each distinct value found at the logical row ids,
+    // stopping as soon as `f` returns `false`.
+    pub fn visit_distinct_values<'a>(
+        &'a self,
+        row_ids: impl Iterator<Item = u32>,
+        f: impl FnMut(Option<&'a str>) -> bool,
+    ) {
+        match &self {
+            Self::String(_, data) => data.visit_distinct_values(row_ids, f),
+            _ => unimplemented!("distinct values is only implemented for String columns"),
+        }
+    }
+
     //
     // Methods for getting encoded (compressed) values.
     //
@@ -421,6 +448,79 @@ impl Column {
         RowIDsOption::Some(row_ids)
     }
 
+    /// Determine the set of row ids whose values match (`=~`) or do not match
+    /// (`!~`) the provided regular expression. NULL values satisfy neither
+    /// operator.
+    ///
+    /// Regex predicates are only supported on string columns.
+    pub fn row_ids_filter_regex(
+        &self,
+        op: &cmp::Operator,
+        regex: &Regex,
+        dst: RowIDs,
+    ) -> RowIDsOption {
+        let negated = match op {
+            cmp::Operator::RegexMatch => false,
+            cmp::Operator::RegexNotMatch => true,
+            op => panic!("{:?} is not a regex operator", op),
+        };
+
+        let row_ids = match &self {
+            Self::String(meta, data) => {
+                // a column of only NULL values can't satisfy the predicate.
+                if meta.range.is_none() {
+                    return RowIDsOption::None(dst);
+                }
+                data.row_ids_filter_regex(regex, negated, dst)
+            }
+            _ => panic!("regex predicates are only supported on string columns"),
+        };
+
+        if row_ids.is_empty() {
+            return RowIDsOption::None(row_ids);
+        }
+        RowIDsOption::Some(row_ids)
+    }
+
+    /// Determine the set of row ids whose value is equal to any of `values`.
+    ///
+    /// String columns translate `values` into their encoded representation
+    /// once and then scan the encoded values. Other columns union the rows
+    /// equal to each value.
+    pub fn row_ids_filter_in(&self, values: &[Value<'_>], mut dst: RowIDs) -> RowIDsOption {
+        // values outside of the column's range can't be present.
+        let values = values
+            .iter()
+            .filter(|value| self.might_contain_value(value))
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return RowIDsOption::None(dst);
+        }
+
+        let row_ids = match &self {
+            Self::String(_, data) => {
+                let values = values.iter().map(|value| value.str()).collect::<Vec<_>>();
+                data.row_ids_filter_in(&values, dst)
+            }
+            _ => {
+                dst.clear();
+                for value in values {
+                    match self.row_ids_filter(&cmp::Operator::Equal, value, RowIDs::new_bitmap()) {
+                        RowIDsOption::None(_) => {}
+                        RowIDsOption::Some(row_ids) => dst.union(&row_ids),
+                        RowIDsOption::All(_) => return RowIDsOption::All(dst),
+                    }
+                }
+                dst
+            }
+        };
+
+        if row_ids.is_empty() {
+            return RowIDsOption::None(row_ids);
+        }
+        RowIDsOption::Some(row_ids)
+    }
+
     /// Determine the set of row ids that satisfy both of the predicates.
     ///
     /// Note: this method is a special case for common range-based predicates
@@ -508,6 +608,12 @@ impl Column {
                     return PredicateMatch::All;
                 }
             }
+
+            // The column range says nothing about which values match a regex,
+            // and a single value can't describe an IN list.
+            cmp::Operator::RegexMatch | cmp::Operator::RegexNotMatch | cmp::Operator::In => {
+                return PredicateMatch::SomeMaybe;
+            }
         }
 
         if self.predicate_matches_no_values(op, value) {
@@ -688,8 +794,10 @@ impl Column {
     // Methods for aggregating
     //
 
-    /// The summation of all non-null values located at the provided rows.
-    pub fn sum(&self, row_ids: &[u32]) -> Scalar {
+    /// The summation of all non-null values located at the provided rows, and
+    /// whether that summation overflowed. Integer sums that overflow saturate
+    /// at the bounds of the column's logical type.
+    pub fn sum(&self, row_ids: &[u32]) -> (Scalar, bool) {
         assert!(row_ids.len() as u32 <= self.num_rows());
 
         match &self {
@@ -714,6 +822,23 @@ impl Column {
         }
     }
 
+    /// The non-null values located at the provided rows as runs of
+    /// `(value, run_length)`, converted to `f64`. Encodings storing runs of
+    /// identical values produce a tuple per run rather than per row.
+    pub fn value_runs(&self, row_ids: &[u32]) -> Vec<(f64, u32)> {
+        assert!(row_ids.len() as u32 <= self.num_rows());
+        if row_ids.is_empty() {
+            return vec![];
+        }
+
+        match &self {
+            Self::Float(_, data) => data.value_runs(row_ids),
+            Self::Integer(_, data) => data.value_runs_f64(row_ids),
+            Self::Unsigned(_, data) => data.value_runs_f64(row_ids),
+            _ => panic!("cannot produce numerical values for non-numerical column type"),
+        }
+    }
+
     //
     // Methods for inspecting
     //
@@ -755,6 +880,16 @@ impl Column {
         }
     }
 
+    /// Determines if the column contains the string `value` on at least one
+    /// row, using the column's dictionary. Returns `None` if this is not a
+    /// string column.
+    pub fn contains_string_value(&self, value: &str) -> Option<bool> {
+        match self {
+            Self::String(_, data) => Some(data.contains_value(value)),
+            _ => None,
+        }
+    }
+
     /// Determines if the column contains any string values that are not present
     /// in the provided `values` argument.
     pub fn has_other_non_null_string_values(&self, values: &BTreeSet<String>) -> bool {
@@ -824,6 +959,10 @@ impl<T: PartialOrd + std::fmt::Debug> MetaData<T> {
                 cmp::Operator::LT => range.1 < u,
                 // all values in column <= v
                 cmp::Operator::LTE => range.1 <= u,
+                // the range can't be compared to a regex or IN list
+                cmp::Operator::RegexMatch | cmp::Operator::RegexNotMatch | cmp::Operator::In => {
+                    false
+                }
             },
             None => false, // only null values in column.
         }
@@ -850,6 +989,10 @@ impl<T: PartialOrd + std::fmt::Debug> MetaData<T> {
                 cmp::Operator::LT => range.0 >= u,
                 // min value in column is `> v` so no values can be `<= v`
                 cmp::Operator::LTE => range.0 > u,
+                // the range can't be compared to a regex or IN list
+                cmp::Operator::RegexMatch | cmp::Operator::RegexNotMatch | cmp::Operator::In => {
+                    false
+                }
             },
             None => true, // only null values in column so no values satisfy `v`
         }
@@ -2384,17 +2527,25 @@ mod test {
     fn sum() {
         let input = &[100_i64, 200, 300, 2, 200, 22, 30];
         let col = Column::from(&input[..]);
-        assert_eq!(col.sum(&[0, 1, 3][..]), Scalar::I64(302));
-        assert_eq!(col.sum(&[0, 1, 2][..]), Scalar::I64(600));
+        assert_eq!(col.sum(&[0, 1, 3][..]), (Scalar::I64(302), false));
+        assert_eq!(col.sum(&[0, 1, 2][..]), (Scalar::I64(600), false));
 
         let input = &[10.2_f64, -2.43, 200.2];
         let col = Column::from(&input[..]);
-        assert_eq!(col.sum(&[0, 1, 2][..]), Scalar::F64(207.97));
+        assert_eq!(col.sum(&[0, 1, 2][..]), (Scalar::F64(207.97), false));
 
         let input = vec![None, Some(200), None];
         let arr = Int64Array::from(input);
         let col = Column::from(arr);
-        assert_eq!(col.sum(&[0, 1, 2][..]), Scalar::I64(200));
+        assert_eq!(col.sum(&[0, 1, 2][..]), (Scalar::I64(200), false));
+
+        let input = &[i64::MAX, 1, 2];
+        let col = Column::from(&input[..]);
+        assert_eq!(col.sum(&[0, 1, 2][..]), (Scalar::I64(i64::MAX), true));
+
+        let input = &[u64::MAX, 1];
+        let col = Column::from(&input[..]);
+        assert_eq!(col.sum(&[0, 1][..]), (Scalar::U64(u64::MAX), true));
     }
 
     #[test]
@@ -10,6 +10,9 @@ pub enum Operator {
     GTE,
     LT,
     LTE,
+    RegexMatch,
+    RegexNotMatch,
+    In,
 }
 
 impl Display for Operator {
@@ -24,6 +27,9 @@ impl Display for Operator {
                 Self::GTE => ">=",
                 Self::LT => "<",
                 Self::LTE => "<=",
+                Self::RegexMatch => "=~",
+                Self::RegexNotMatch => "!~",
+                Self::In => "IN",
             }
         )
     }
@@ -40,6 +46,9 @@ impl TryFrom<&str> for Operator {
             ">=" => Ok(Self::GTE),
             "<" => Ok(Self::LT),
             "<=" => Ok(Self::LTE),
+            "=~" => Ok(Self::RegexMatch),
+            "!~" => Ok(Self::RegexNotMatch),
+            "IN" | "in" => Ok(Self::In),
             v => Err(format!("unknown operator {:?}", v)),
         }
     }
@@ -12,6 +12,46 @@ pub use fixed::Fixed;
 pub use fixed_null::FixedNull;
 pub use rle::RLE;
 
+/// Addition used when summing logical values, which detects overflow rather
+/// than wrapping or panicking.
+pub trait SumAdd: Sized {
+    /// Adds `other` to `self`, returning `true` if the addition overflowed. On
+    /// overflow integer values saturate at the bounds of their type.
+    fn sum_add(&mut self, other: Self) -> bool;
+}
+
+macro_rules! impl_integer_sum_add {
+    ($($t:ty),*) => {
+        $(
+            impl SumAdd for $t {
+                fn sum_add(&mut self, other: Self) -> bool {
+                    match self.checked_add(other) {
+                        Some(v) => {
+                            *self = v;
+                            false
+                        }
+                        None => {
+                            *self = self.saturating_add(other);
+                            true
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_sum_add!(i64, u64);
+
+impl SumAdd for f64 {
+    // Float sums can't overflow; a non-finite result is left for the caller to
+    // detect.
+    fn sum_add(&mut self, other: Self) -> bool {
+        *self += other;
+        false
+    }
+}
+
 /// `ScalarEncoding` describes the behaviour of a columnar encoding for scalar
 /// values.
 pub trait ScalarEncoding<L>: Debug + Display + Send + Sync {
@@ -85,9 +125,23 @@ pub trait ScalarEncoding<L>: Debug + Display + Send + Sync {
     /// offsets.
     fn max(&self, row_ids: &[u32]) -> Option<L>;
 
-    /// The sum of all non-null values located at the provided ordinal offsets.
-    fn sum(&self, row_ids: &[u32]) -> Option<L>;
+    /// The sum of all non-null values located at the provided ordinal offsets,
+    /// and whether that sum overflowed. Integer sums that overflow saturate at
+    /// the bounds of `L` rather than wrapping.
+    fn sum(&self, row_ids: &[u32]) -> Option<(L, bool)>;
 
     /// The count of non-null values located at the provided ordinal offsets.
     fn count(&self, row_ids: &[u32]) -> u32;
+
+    /// The non-null values located at the provided ordinal offsets, as runs
+    /// of `(value, run_length)` in row order.
+    ///
+    /// By default each value is materialised as a run of one; encodings that
+    /// store runs of identical values can avoid that.
+    fn value_runs(&self, row_ids: &[u32]) -> Vec<(L, u32)> {
+        match self.values(row_ids) {
+            Either::Left(values) => values.into_iter().map(|v| (v, 1)).collect(),
+            Either::Right(values) => values.into_iter().flatten().map(|v| (v, 1)).collect(),
+        }
+    }
 }
@@ -15,7 +15,7 @@ use std::mem::size_of;
 use std::ops::AddAssign;
 
 use super::transcoders::Transcoder;
-use super::ScalarEncoding;
+use super::{ScalarEncoding, SumAdd};
 use crate::column::{cmp, RowIDs};
 
 pub const ENCODING_NAME: &str = "FIXED";
@@ -242,7 +242,7 @@ where
 impl<P, L, T> ScalarEncoding<L> for Fixed<P, L, T>
 where
     P: Copy + Debug + PartialOrd + Send + Sync,
-    L: AddAssign + Debug + Default + Send + Sync,
+    L: AddAssign + SumAdd + Debug + Default + Send + Sync,
     T: Transcoder<P, L> + Send + Sync,
 {
     fn name(&self) -> &'static str {
@@ -325,26 +325,24 @@ where
         row_ids.len() as u32
     }
 
-    fn sum(&self, row_ids: &[u32]) -> Option<L>
-    where
-        L: AddAssign,
-    {
+    fn sum(&self, row_ids: &[u32]) -> Option<(L, bool)> {
         let mut result = L::default(); // Add up logical types not physical
+        let mut overflowed = false;
 
         // TODO(edd): There may be a faster unsafe way to do this.
         for chunks in row_ids.chunks_exact(4) {
-            result += self.transcoder.decode(self.values[chunks[3] as usize]);
-            result += self.transcoder.decode(self.values[chunks[2] as usize]);
-            result += self.transcoder.decode(self.values[chunks[1] as usize]);
-            result += self.transcoder.decode(self.values[chunks[0] as usize]);
+            overflowed |= result.sum_add(self.transcoder.decode(self.values[chunks[3] as usize]));
+            overflowed |= result.sum_add(self.transcoder.decode(self.values[chunks[2] as usize]));
+            overflowed |= result.sum_add(self.transcoder.decode(self.values[chunks[1] as usize]));
+            overflowed |= result.sum_add(self.transcoder.decode(self.values[chunks[0] as usize]));
         }
 
         let rem = row_ids.len() % 4;
         for &i in &row_ids[row_ids.len() - rem..row_ids.len()] {
-            result += self.transcoder.decode(self.values[i as usize]);
+            overflowed |= result.sum_add(self.transcoder.decode(self.values[i as usize]));
         }
 
-        Some(result)
+        Some((result, overflowed))
     }
 
     fn min(&self, row_ids: &[u32]) -> Option<L> {
@@ -509,9 +507,13 @@ mod test {
     fn sum() {
         let (v, transcoder) = new_encoding((0..10).collect::<Vec<_>>());
 
-        assert_eq!(v.sum(&[3, 5, 6, 7]), Some(21));
+        assert_eq!(v.sum(&[3, 5, 6, 7]), Some((21, false)));
         assert_eq!(transcoder.decodings(), 4);
-        assert_eq!(v.sum(&[1, 2, 4, 7, 9]), Some(23));
+        assert_eq!(v.sum(&[1, 2, 4, 7, 9]), Some((23, false)));
+
+        let (v, _) = new_encoding(vec![i64::MAX - 1, 1, 1, -1]);
+        assert_eq!(v.sum(&[0, 1]), Some((i64::MAX, false)));
+        assert_eq!(v.sum(&[0, 1, 2]), Some((i64::MAX, true)));
     }
 
     #[test]
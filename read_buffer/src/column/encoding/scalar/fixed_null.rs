@@ -19,7 +19,7 @@ use arrow::{
 };
 
 use super::transcoders::Transcoder;
-use super::ScalarEncoding;
+use super::{ScalarEncoding, SumAdd};
 use crate::column::{cmp, RowIDs};
 
 pub const ENCODING_NAME: &str = "FIXEDN";
@@ -270,7 +270,7 @@ where
 impl<P, L, T> ScalarEncoding<L> for FixedNull<P, L, T>
 where
     P: ArrowNumericType + Debug + Send + Sync,
-    L: Add<Output = L> + Debug + Default + Send + Sync,
+    L: Add<Output = L> + SumAdd + Debug + Default + Send + Sync,
     T: Transcoder<P::Native, L> + Send + Sync,
 {
     /// The name of this encoding.
@@ -375,23 +375,28 @@ where
     /// aggregations methods but they're currently significantly slower than
     /// this implementation (about 85% in the `sum` case). I will revisit/ work
     /// on them them in the future.
-    fn sum(&self, row_ids: &[u32]) -> Option<L> {
+    fn sum(&self, row_ids: &[u32]) -> Option<(L, bool)> {
         let mut result = L::default();
+        let mut overflowed = false;
 
         if self.arr.null_count() == 0 {
             for chunks in row_ids.chunks_exact(4) {
-                result = result + self.transcoder.decode(self.arr.value(chunks[3] as usize));
-                result = result + self.transcoder.decode(self.arr.value(chunks[2] as usize));
-                result = result + self.transcoder.decode(self.arr.value(chunks[1] as usize));
-                result = result + self.transcoder.decode(self.arr.value(chunks[0] as usize));
+                overflowed |=
+                    result.sum_add(self.transcoder.decode(self.arr.value(chunks[3] as usize)));
+                overflowed |=
+                    result.sum_add(self.transcoder.decode(self.arr.value(chunks[2] as usize)));
+                overflowed |=
+                    result.sum_add(self.transcoder.decode(self.arr.value(chunks[1] as usize)));
+                overflowed |=
+                    result.sum_add(self.transcoder.decode(self.arr.value(chunks[0] as usize)));
             }
 
             let rem = row_ids.len() % 4;
             for &i in &row_ids[row_ids.len() - rem..row_ids.len()] {
-                result = result + self.transcoder.decode(self.arr.value(i as usize));
+                overflowed |= result.sum_add(self.transcoder.decode(self.arr.value(i as usize)));
             }
 
-            return Some(result);
+            return Some((result, overflowed));
         }
 
         let mut is_none = true;
@@ -400,13 +405,13 @@ where
                 continue;
             }
             is_none = false;
-            result = result + self.transcoder.decode(self.arr.value(i as usize));
+            overflowed |= result.sum_add(self.transcoder.decode(self.arr.value(i as usize)));
         }
 
         if is_none {
             return None;
         }
-        Some(result)
+        Some((result, overflowed))
     }
 
     fn min(&self, row_ids: &[u32]) -> Option<L> {
@@ -619,9 +624,13 @@ mod test {
     fn sum() {
         let (v, transcoder) = new_encoding((0..10).map(Option::Some).collect::<Vec<_>>());
 
-        assert_eq!(v.sum(&[3, 5, 6, 7]), Some(21));
+        assert_eq!(v.sum(&[3, 5, 6, 7]), Some((21, false)));
         assert_eq!(transcoder.decodings(), 4);
-        assert_eq!(v.sum(&[1, 2, 4, 7, 9]), Some(23));
+        assert_eq!(v.sum(&[1, 2, 4, 7, 9]), Some((23, false)));
+
+        let (v, _) = new_encoding(vec![Some(i64::MIN + 1), None, Some(-1), Some(-1)]);
+        assert_eq!(v.sum(&[0, 1, 2]), Some((i64::MIN, false)));
+        assert_eq!(v.sum(&[0, 2, 3]), Some((i64::MIN, true)));
     }
 
     #[test]
@@ -606,11 +606,62 @@ where
         self.num_rows() - self.null_count() > 0
     }
 
+    /// Returns one `(value, run_length)` tuple per encoded run covering any of
+    /// the provided row IDs, without decoding a value for every row.
+    fn value_runs(&self, row_ids: &[u32]) -> Vec<(L, u32)> {
+        assert!(!row_ids.is_empty(), "no row IDs provided");
+        // Ensure row ids ordered
+        debug_assert!(self.check_row_ids_ordered(row_ids));
+
+        let mut dst: Vec<(L, u32)> = vec![];
+
+        let mut curr_logical_row_id = 0;
+        let (mut curr_entry_rl, mut curr_value) = self.run_lengths[0];
+        // whether the last tuple in `dst` belongs to the current entry
+        let mut curr_entry_pushed = false;
+
+        let mut i = 1;
+        for &row_id in row_ids {
+            assert!(
+                row_id < self.num_rows(),
+                "row_id {:?} beyond max row {:?}",
+                row_id,
+                self.num_rows() - 1
+            );
+
+            while curr_logical_row_id + curr_entry_rl <= row_id {
+                // this encoded entry does not cover the row we need.
+                // move on to next entry
+                curr_logical_row_id += curr_entry_rl;
+                curr_entry_rl = self.run_lengths[i].0;
+                curr_value = self.run_lengths[i].1;
+                curr_entry_pushed = false;
+
+                i += 1;
+            }
+
+            // this encoded entry covers the row_id we want.
+            if let Some(v) = curr_value {
+                if curr_entry_pushed {
+                    dst.last_mut().unwrap().1 += 1;
+                } else {
+                    dst.push((self.transcoder.decode(v), 1));
+                    curr_entry_pushed = true;
+                }
+            }
+
+            curr_logical_row_id += 1; // move forwards a logical row
+            curr_entry_rl -= 1;
+        }
+
+        dst
+    }
+
     fn count(&self, _row_ids: &[u32]) -> u32 {
         todo!()
     }
 
-    fn sum(&self, _row_ids: &[u32]) -> Option<L> {
+    fn sum(&self, _row_ids: &[u32]) -> Option<(L, bool)> {
         todo!()
     }
 
@@ -875,6 +926,24 @@ mod test {
         assert_eq!(enc.values(&[2, 5]).unwrap_right(), vec![Some(45), Some(90)]);
     }
 
+    #[test]
+    fn value_runs() {
+        let (mut enc, transcoder) = new_encoding(vec![]);
+        enc.push_none();
+        enc.push_additional(Some(45), 3);
+        enc.push_additional(Some(90), 2);
+        enc.push(21);
+
+        assert_eq!(
+            enc.value_runs(&[0, 1, 2, 3, 4, 5, 6]),
+            vec![(45, 3), (90, 2), (21, 1)]
+        );
+        assert_eq!(transcoder.decodings(), 3); // one decoding per run
+
+        assert_eq!(enc.value_runs(&[0, 2, 4, 5]), vec![(45, 1), (90, 2)]);
+        assert_eq!(enc.value_runs(&[0]), vec![]);
+    }
+
     #[test]
     fn all_values() {
         let (mut enc, transcoder) = new_encoding(vec![]);
@@ -155,6 +155,9 @@ macro_rules! make_float_trimmer {
                         // domain [23.2, 24.0).
                         Some((v.floor() as $type, Operator::GT))
                     }
+                    Operator::RegexMatch | Operator::RegexNotMatch | Operator::In => {
+                        unreachable!("operator {} can't be encoded", op)
+                    }
                 }
             }
 
@@ -4,6 +4,7 @@ pub mod rle;
 use std::collections::BTreeSet;
 
 use either::Either;
+use regex::Regex;
 
 // This makes the encoding types available under the dictionary module.
 pub use dictionary::Dictionary;
@@ -89,6 +90,25 @@ impl Encoding {
         }
     }
 
+    /// Populates the provided destination container with the row ids of all
+    /// values equal to any of `values`.
+    pub fn row_ids_filter_in(&self, values: &[&str], dst: RowIDs) -> RowIDs {
+        match self {
+            Self::RLE(enc) => enc.row_ids_filter_in(values, dst),
+            Self::Plain(enc) => enc.row_ids_filter_in(values, dst),
+        }
+    }
+
+    /// Populates the provided destination container with the row ids of all
+    /// non-null values that match `regex`, or that don't match it when
+    /// `negated` is true.
+    pub fn row_ids_filter_regex(&self, regex: &Regex, negated: bool, dst: RowIDs) -> RowIDs {
+        match self {
+            Self::RLE(enc) => enc.row_ids_filter_regex(regex, negated, dst),
+            Self::Plain(enc) => enc.row_ids_filter_regex(regex, negated, dst),
+        }
+    }
+
     /// Populates the provided destination container with the row ids for rows
     /// that null.
     fn row_ids_null(&self, dst: RowIDs) -> RowIDs {
@@ -457,6 +477,73 @@ mod test {
         assert_eq!(ids, RowIDs::Vector(vec![3, 10, 11]), "{}", name);
     }
 
+    #[test]
+    fn row_ids_filter_in() {
+        let encodings = vec![
+            Encoding::RLE(RLE::default()),
+            Encoding::Plain(Dictionary::default()),
+        ];
+
+        for enc in encodings {
+            _row_ids_filter_in(enc);
+        }
+    }
+
+    fn _row_ids_filter_in(mut enc: Encoding) {
+        let name = enc.debug_name();
+        enc.push_additional(Some("east".to_string()), 2); // 0, 1
+        enc.push_none(); // 2
+        enc.push_additional(Some("north".to_string()), 1); // 3
+        enc.push_additional(Some("east".to_string()), 1); // 4
+        enc.push_additional(Some("west".to_string()), 2); // 5, 6
+
+        let ids = enc.row_ids_filter_in(&["west", "east"], RowIDs::Vector(vec![]));
+        assert_eq!(ids, RowIDs::Vector(vec![0, 1, 4, 5, 6]), "{}", name);
+
+        // values not in the column are ignored
+        let ids = enc.row_ids_filter_in(&["south", "north", "north"], RowIDs::Vector(vec![]));
+        assert_eq!(ids, RowIDs::Vector(vec![3]), "{}", name);
+
+        let ids = enc.row_ids_filter_in(&["south"], RowIDs::Vector(vec![]));
+        assert!(ids.is_empty(), "{}", name);
+
+        let ids = enc.row_ids_filter_in(&[], RowIDs::Vector(vec![]));
+        assert!(ids.is_empty(), "{}", name);
+    }
+
+    #[test]
+    fn row_ids_filter_regex() {
+        let encodings = vec![
+            Encoding::RLE(RLE::default()),
+            Encoding::Plain(Dictionary::default()),
+        ];
+
+        for enc in encodings {
+            _row_ids_filter_regex(enc);
+        }
+    }
+
+    fn _row_ids_filter_regex(mut enc: Encoding) {
+        let name = enc.debug_name();
+        enc.push_additional(Some("eu-west".to_string()), 2); // 0, 1
+        enc.push_none(); // 2
+        enc.push_additional(Some("us-east".to_string()), 2); // 3, 4
+        enc.push_additional(Some("eu-west".to_string()), 1); // 5
+        enc.push_additional(Some("us-west".to_string()), 1); // 6
+
+        let regex = Regex::new("^us-").unwrap();
+        let ids = enc.row_ids_filter_regex(&regex, false, RowIDs::Vector(vec![]));
+        assert_eq!(ids, RowIDs::Vector(vec![3, 4, 6]), "{}", name);
+
+        // the NULL value doesn't satisfy the negated regex either.
+        let ids = enc.row_ids_filter_regex(&regex, true, RowIDs::Vector(vec![]));
+        assert_eq!(ids, RowIDs::Vector(vec![0, 1, 5]), "{}", name);
+
+        let regex = Regex::new("north").unwrap();
+        let ids = enc.row_ids_filter_regex(&regex, false, RowIDs::Vector(vec![]));
+        assert!(ids.is_empty(), "{}", name);
+    }
+
     #[test]
     fn row_ids_filter_equal_no_null() {
         let encodings = vec![
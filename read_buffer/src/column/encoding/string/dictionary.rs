@@ -12,6 +12,7 @@ use std::convert::From;
 use std::mem::size_of;
 
 use arrow::array::{Array, StringArray};
+use regex::Regex;
 
 use super::NULL_ID;
 use crate::column::{cmp, RowIDs};
@@ -232,7 +233,68 @@ impl Dictionary {
             cmp::Operator::LT | cmp::Operator::LTE | cmp::Operator::GT | cmp::Operator::GTE => {
                 self.row_ids_cmp(value, op, dst)
             }
+            cmp::Operator::RegexMatch | cmp::Operator::RegexNotMatch => {
+                unreachable!("regex operators are applied via row_ids_filter_regex")
+            }
+            cmp::Operator::In => unreachable!("IN lists are applied via row_ids_filter_in"),
+        }
+    }
+
+    /// Populates the provided destination container with the row ids of all
+    /// values equal to any of `values`.
+    pub fn row_ids_filter_in(&self, values: &[&str], mut dst: RowIDs) -> RowIDs {
+        dst.clear();
+
+        // translate the values into the encoded ids present in the column,
+        // which are then all that need comparing against each row.
+        let mut matching = vec![false; self.entries.len()];
+        let mut found = false;
+        for value in values {
+            if let Ok(encoded_id) = self.encoded_id(Some(value)) {
+                matching[encoded_id as usize] = true;
+                found = true;
+            }
+        }
+        if !found {
+            return dst;
+        }
+
+        for (i, encoded_id) in self.encoded_data.iter().enumerate() {
+            if matching[*encoded_id as usize] {
+                dst.add(i as u32);
+            }
         }
+
+        dst
+    }
+
+    /// Populates the provided destination container with the row ids of all
+    /// non-null values that match `regex`, or that don't match it when
+    /// `negated` is true.
+    ///
+    /// The regex is evaluated once for each distinct value in the dictionary.
+    pub fn row_ids_filter_regex(&self, regex: &Regex, negated: bool, mut dst: RowIDs) -> RowIDs {
+        dst.clear();
+
+        // whether the logical value for each encoded id satisfies the
+        // predicate. The NULL entry never does.
+        let matching = self
+            .entries
+            .iter()
+            .map(|entry| {
+                entry
+                    .as_ref()
+                    .map_or(false, |v| regex.is_match(v) != negated)
+            })
+            .collect::<Vec<_>>();
+
+        for (i, encoded_id) in self.encoded_data.iter().enumerate() {
+            if matching[*encoded_id as usize] {
+                dst.add(i as u32);
+            }
+        }
+
+        dst
     }
 
     // Finds row ids based on = or != operator.
@@ -678,25 +740,42 @@ impl Dictionary {
         row_ids: impl Iterator<Item = u32>,
         mut dst: BTreeSet<Option<&'a str>>,
     ) -> BTreeSet<Option<&'a str>> {
+        dst.clear();
+        self.visit_distinct_values(row_ids, |value| {
+            dst.insert(value);
+            true
+        });
+        dst
+    }
+
+    /// Calls `f` with each distinct value encoded at the provided row ids, in
+    /// the order they are first found. Stops as soon as `f` returns `false`.
+    ///
+    /// It is the caller's responsibility to ensure row ids are a monotonically
+    /// increasing set.
+    pub fn visit_distinct_values<'a>(
+        &'a self,
+        row_ids: impl Iterator<Item = u32>,
+        mut f: impl FnMut(Option<&'a str>) -> bool,
+    ) {
         // TODO(edd): Perf... We can improve on this if we know the column is
         // totally ordered.
-        dst.clear();
+        let mut seen = vec![false; self.entries.len()];
+        let mut found = 0;
 
         for row_id in row_ids {
-            let encoded_id = self.encoded_data[row_id as usize];
-
-            let value = self.entries[encoded_id as usize].as_deref();
-            if !dst.contains(&value) {
-                dst.insert(value);
+            let encoded_id = self.encoded_data[row_id as usize] as usize;
+            if seen[encoded_id] {
+                continue;
             }
 
-            if dst.len() as u32 == self.cardinality() {
-                // no more distinct values to find.
-                return dst;
+            seen[encoded_id] = true;
+            found += 1;
+            if !f(self.entries[encoded_id].as_deref()) || found == self.cardinality() {
+                // stopped, or no more distinct values to find.
+                return;
             }
         }
-
-        dst
     }
 
     //
@@ -760,6 +839,18 @@ impl Dictionary {
         })
     }
 
+    /// Determines if the column contains `value` on at least one row.
+    ///
+    /// Values absent from the dictionary are rejected without touching the
+    /// encoded data. Because the dictionary may hold entries no row refers to,
+    /// a present entry is confirmed by scanning the encoded ids.
+    pub fn contains_value(&self, value: &str) -> bool {
+        match self.encoded_id(Some(value)) {
+            Ok(id) => self.encoded_data.contains(&id),
+            Err(_) => false,
+        }
+    }
+
     /// Determines if the column contains at least one non-null value.
     pub fn has_any_non_null_value(&self) -> bool {
         if !self.contains_null() {
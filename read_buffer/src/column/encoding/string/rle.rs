@@ -4,6 +4,7 @@ use std::iter;
 use std::mem::size_of;
 
 use arrow::array::{Array, StringArray};
+use regex::Regex;
 
 use super::NULL_ID;
 use crate::column::{cmp, RowIDs};
@@ -301,7 +302,46 @@ impl RLE {
             cmp::Operator::LT | cmp::Operator::LTE | cmp::Operator::GT | cmp::Operator::GTE => {
                 self.row_ids_cmp(value, op, dst)
             }
+            cmp::Operator::RegexMatch | cmp::Operator::RegexNotMatch => {
+                unreachable!("regex operators are applied via row_ids_filter_regex")
+            }
+            cmp::Operator::In => unreachable!("IN lists are applied via row_ids_filter_in"),
+        }
+    }
+
+    /// Populates the provided destination container with the row ids of all
+    /// values equal to any of `values`.
+    pub fn row_ids_filter_in(&self, values: &[&str], mut dst: RowIDs) -> RowIDs {
+        dst.clear();
+
+        // The row ids for each encoded id are already known so the values only
+        // need translating into encoded ids.
+        for value in values {
+            if let Some(encoded_id) = self.lookup_entry(value) {
+                dst.union(&self.index_row_ids[encoded_id as usize]);
+            }
+        }
+
+        dst
+    }
+
+    /// Populates the provided destination container with the row ids of all
+    /// non-null values that match `regex`, or that don't match it when
+    /// `negated` is true.
+    ///
+    /// The regex is evaluated once for each distinct value in the dictionary.
+    pub fn row_ids_filter_regex(&self, regex: &Regex, negated: bool, mut dst: RowIDs) -> RowIDs {
+        dst.clear();
+
+        // The row ids for each distinct value are already known so the matching
+        // sets just need combining. Skip the first entry representing NULL.
+        for (entry, row_ids) in self.index_entries.iter().zip(&self.index_row_ids).skip(1) {
+            if regex.is_match(entry) != negated {
+                dst.union(row_ids);
+            }
         }
+
+        dst
     }
 
     // Finds row ids based on = or != operator.
@@ -708,9 +748,28 @@ impl RLE {
         row_ids: impl Iterator<Item = u32>,
         mut dst: BTreeSet<Option<&'a str>>,
     ) -> BTreeSet<Option<&'a str>> {
+        dst.clear();
+        self.visit_distinct_values(row_ids, |value| {
+            dst.insert(value);
+            true
+        });
+
+        assert!(dst.len() <= self.index_entries.len());
+        dst
+    }
+
+    /// Calls `f` with each distinct value encoded at the provided row ids, in
+    /// the order they are first found. Stops as soon as `f` returns `false`.
+    ///
+    /// It is the caller's responsibility to ensure row ids are a monotonically
+    /// increasing set.
+    pub fn visit_distinct_values<'a>(
+        &'a self,
+        row_ids: impl Iterator<Item = u32>,
+        mut f: impl FnMut(Option<&'a str>) -> bool,
+    ) {
         // TODO(edd): Perf... We can improve on this if we know the column is
         // totally ordered.
-        dst.clear();
 
         // Used to mark off when a decoded value has been added to the result
         // set. TODO(perf) - this might benefit from being pooled somehow.
@@ -742,13 +801,16 @@ impl RLE {
 
             // encoded value not already in result set.
             if !encoded_values[curr_entry_id as usize] {
-                match curr_entry_id {
-                    NULL_ID => dst.insert(None),
-                    _ => dst.insert(Some(&self.index_entries[curr_entry_id as usize])),
+                let value = match curr_entry_id {
+                    NULL_ID => None,
+                    _ => Some(self.index_entries[curr_entry_id as usize].as_str()),
                 };
 
                 encoded_values[curr_entry_id as usize] = true;
                 found += 1;
+                if !f(value) {
+                    break 'by_row;
+                }
             }
 
             if found == encoded_values.len() {
@@ -759,9 +821,6 @@ impl RLE {
             curr_logical_row_id += 1;
             curr_entry_rl -= 1;
         }
-
-        assert!(dst.len() <= self.index_entries.len());
-        dst
     }
 
     //
@@ -840,6 +899,13 @@ impl RLE {
         self.keys().any(|entry| !values.contains(entry))
     }
 
+    /// Determines if the column contains `value` on at least one row. This
+    /// only consults the dictionary and the row ids of the matching entry.
+    pub fn contains_value(&self, value: &str) -> bool {
+        self.lookup_entry(value)
+            .map_or(false, |id| !self.index_row_ids[id as usize].is_empty())
+    }
+
     /// Determines if the column contains at least one non-null value.
     pub fn has_any_non_null_value(&self) -> bool {
         if !self.contains_null() {
@@ -998,6 +1064,22 @@ mod test {
         assert_eq!(drle.index_row_ids.len(), 3)
     }
 
+    #[test]
+    fn contains_value() {
+        let mut dictionary = BTreeSet::new();
+        dictionary.insert("east".to_string());
+        dictionary.insert("west".to_string());
+
+        let mut enc = RLE::with_dictionary(dictionary);
+        enc.push_additional(Some("west".to_string()), 2);
+        enc.push_none();
+
+        assert!(enc.contains_value("west"));
+        // in the dictionary but not on any row
+        assert!(!enc.contains_value("east"));
+        assert!(!enc.contains_value("north"));
+    }
+
     #[test]
     fn size() {
         let mut enc = RLE::default();
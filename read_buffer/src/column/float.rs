@@ -174,11 +174,13 @@ impl FloatEncoding {
         }
     }
 
-    pub fn sum(&self, row_ids: &[u32]) -> Scalar {
+    /// The sum of all non-null values at the provided rows, and whether that
+    /// sum overflowed.
+    pub fn sum(&self, row_ids: &[u32]) -> (Scalar, bool) {
         match self {
             Self::F64(enc, _) => match enc.sum(row_ids) {
-                Some(sum) => Scalar::F64(sum),
-                None => Scalar::Null,
+                Some((sum, overflowed)) => (Scalar::F64(sum), overflowed),
+                None => (Scalar::Null, false),
             },
         }
     }
@@ -189,6 +191,14 @@ impl FloatEncoding {
         }
     }
 
+    /// The non-null values at the provided rows as `(value, run_length)`
+    /// tuples.
+    pub fn value_runs(&self, row_ids: &[u32]) -> Vec<(f64, u32)> {
+        match self {
+            Self::F64(enc, _) => enc.value_runs(row_ids),
+        }
+    }
+
     /// The name of this encoding.
     pub fn name(&self) -> String {
         match self {
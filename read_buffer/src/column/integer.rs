@@ -205,15 +205,18 @@ impl IntegerEncoding {
         }
     }
 
-    pub fn sum(&self, row_ids: &[u32]) -> Scalar {
+    /// The sum of all non-null values at the provided rows, and whether that
+    /// sum overflowed. Sums that overflow saturate at the bounds of the
+    /// logical type.
+    pub fn sum(&self, row_ids: &[u32]) -> (Scalar, bool) {
         match &self {
             Self::I64(enc, _) => match enc.sum(row_ids) {
-                Some(v) => Scalar::I64(v),
-                None => Scalar::Null,
+                Some((v, overflowed)) => (Scalar::I64(v), overflowed),
+                None => (Scalar::Null, false),
             },
             Self::U64(enc, _) => match enc.sum(row_ids) {
-                Some(v) => Scalar::U64(v),
-                None => Scalar::Null,
+                Some((v, overflowed)) => (Scalar::U64(v), overflowed),
+                None => (Scalar::Null, false),
             },
         }
     }
@@ -225,6 +228,23 @@ impl IntegerEncoding {
         }
     }
 
+    /// The non-null values at the provided rows as `(value, run_length)`
+    /// tuples, with each value converted to an `f64`.
+    pub fn value_runs_f64(&self, row_ids: &[u32]) -> Vec<(f64, u32)> {
+        match &self {
+            Self::I64(enc, _) => enc
+                .value_runs(row_ids)
+                .into_iter()
+                .map(|(v, rl)| (v as f64, rl))
+                .collect(),
+            Self::U64(enc, _) => enc
+                .value_runs(row_ids)
+                .into_iter()
+                .map(|(v, rl)| (v as f64, rl))
+                .collect(),
+        }
+    }
+
     /// The name of this encoding.
     pub fn name(&self) -> String {
         match self {
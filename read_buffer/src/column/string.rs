@@ -2,6 +2,7 @@ use std::collections::BTreeSet;
 
 use arrow::{self, array::Array};
 use either::Either;
+use regex::Regex;
 
 use super::cmp;
 use super::encoding::string::{dictionary, rle};
@@ -70,13 +71,18 @@ impl StringEncoding {
         }
     }
 
+    /// The name of this encoding.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RleDictionary(_) => rle::ENCODING_NAME,
+            Self::Dictionary(_) => dictionary::ENCODING_NAME,
+        }
+    }
+
     // Returns statistics about the physical layout of columns
     pub(crate) fn storage_stats(&self) -> Statistics {
         Statistics {
-            enc_type: match self {
-                Self::RleDictionary(_) => rle::ENCODING_NAME.into(),
-                Self::Dictionary(_) => dictionary::ENCODING_NAME.into(),
-            },
+            enc_type: self.name().into(),
             log_data_type: "string",
             values: self.num_rows(),
             nulls: self.null_count(),
@@ -140,6 +146,16 @@ impl StringEncoding {
         }
     }
 
+    /// Determines if the column contains `value` on at least one row. Only
+    /// the plain dictionary encoding scans rows, and only if `value` is in its
+    /// dictionary.
+    pub fn contains_value(&self, value: &str) -> bool {
+        match &self {
+            Self::RleDictionary(c) => c.contains_value(value),
+            Self::Dictionary(c) => c.contains_value(value),
+        }
+    }
+
     /// Returns the logical value found at the provided row id.
     pub fn value(&self, row_id: u32) -> Value<'_> {
         match &self {
@@ -309,6 +325,19 @@ impl StringEncoding {
         }
     }
 
+    /// Calls `f` with each distinct value found at the provided row ids,
+    /// stopping as soon as `f` returns `false`.
+    pub fn visit_distinct_values<'a>(
+        &'a self,
+        row_ids: impl Iterator<Item = u32>,
+        f: impl FnMut(Option<&'a str>) -> bool,
+    ) {
+        match &self {
+            Self::RleDictionary(c) => c.visit_distinct_values(row_ids, f),
+            Self::Dictionary(c) => c.visit_distinct_values(row_ids, f),
+        }
+    }
+
     /// Returns the row ids that satisfy the provided predicate.
     pub fn row_ids_filter(&self, op: &cmp::Operator, value: &str, dst: RowIDs) -> RowIDs {
         match &self {
@@ -317,6 +346,23 @@ impl StringEncoding {
         }
     }
 
+    /// Returns the row ids of all values equal to any of `values`.
+    pub fn row_ids_filter_in(&self, values: &[&str], dst: RowIDs) -> RowIDs {
+        match &self {
+            Self::RleDictionary(c) => c.row_ids_filter_in(values, dst),
+            Self::Dictionary(c) => c.row_ids_filter_in(values, dst),
+        }
+    }
+
+    /// Returns the row ids of all non-null values that match `regex`, or that
+    /// don't match it when `negated` is true.
+    pub fn row_ids_filter_regex(&self, regex: &Regex, negated: bool, dst: RowIDs) -> RowIDs {
+        match &self {
+            Self::RleDictionary(c) => c.row_ids_filter_regex(regex, negated, dst),
+            Self::Dictionary(c) => c.row_ids_filter_regex(regex, negated, dst),
+        }
+    }
+
     /// The lexicographic minimum non-null value at the rows specified, or the
     /// NULL value if the column only contains NULL values at the provided row
     /// ids.
@@ -10,9 +10,11 @@ mod value;
 
 // Identifiers that are exported as part of the public API.
 pub use self::schema::*;
-pub use chunk::{Chunk as RBChunk, ChunkMetrics, Error};
-pub use row_group::{BinaryExpr, Predicate};
-pub use table::ReadFilterResults;
+pub use chunk::{Chunk as RBChunk, ChunkMetrics, Error, ReadOnlyChunk};
+pub use column::RowIDs;
+pub use row_group::{BinaryExpr, ColumnComparison, Predicate};
+pub use table::{ColumnValues, ColumnValuesResult, ReadAggregateResults, ReadFilterResults};
+pub use value::{OwnedValue, Scalar};
 
 /// THIS MODULE SHOULD ONLY BE IMPORTED FOR BENCHMARKS.
 ///
@@ -10,11 +10,13 @@ use std::{
 use hashbrown::{hash_map, HashMap};
 use itertools::Itertools;
 use observability_deps::tracing::{debug, trace};
+use regex::Regex;
 use snafu::{ResultExt, Snafu};
 
+use crate::chunk::ColumnDecodeMetrics;
 use crate::column::{self, cmp::Operator, Column, RowIDs, RowIDsOption};
 use crate::schema;
-use crate::schema::{AggregateType, LogicalDataType, ResultSchema};
+use crate::schema::{AggregateType, LogicalDataType, ResultSchema, TimeWindow};
 use crate::value::{
     AggregateVec, EncodedValues, OwnedValue, Scalar, Value, Values, ValuesIterator,
 };
@@ -52,10 +54,14 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 /// definition it has the same schema as all the other row groups in the table.
 /// All the columns within the `RowGroup` must have the same number of logical
 /// rows.
+///
+/// Column data is shared between clones of a `RowGroup`, so that a row group
+/// shared with other readers can cheaply be copied before being modified.
+#[derive(Clone)]
 pub struct RowGroup {
     meta: MetaData,
 
-    columns: Vec<Column>,
+    columns: Vec<Arc<Column>>,
     all_columns_by_name: BTreeMap<String, usize>,
     time_column: usize,
 }
@@ -87,7 +93,7 @@ impl RowGroup {
                     );
 
                     all_columns_by_name.insert(name.clone(), all_columns.len());
-                    all_columns.push(c);
+                    all_columns.push(Arc::new(c));
                 }
                 ColumnType::Field(c) => {
                     assert_eq!(c.num_rows(), rows);
@@ -102,7 +108,7 @@ impl RowGroup {
                         c.cardinality(),
                     );
                     all_columns_by_name.insert(name.clone(), all_columns.len());
-                    all_columns.push(c);
+                    all_columns.push(Arc::new(c));
                 }
                 ColumnType::Time(c) => {
                     assert_eq!(c.num_rows(), rows);
@@ -119,7 +125,7 @@ impl RowGroup {
 
                     all_columns_by_name.insert(name.clone(), all_columns.len());
                     time_column = Some(all_columns.len());
-                    all_columns.push(c);
+                    all_columns.push(Arc::new(c));
                 }
             }
         }
@@ -196,7 +202,55 @@ impl RowGroup {
     // the lifetime of `self`, not the lifetime of the input.
     fn column_name_and_column(&self, name: ColumnName<'_>) -> (&str, &Column) {
         let (column_name, column_index) = self.all_columns_by_name.get_key_value(name).unwrap();
-        (column_name, &self.columns[*column_index])
+        (column_name, &*self.columns[*column_index])
+    }
+
+    /// Renames the column `old` to `new` without touching any column data.
+    ///
+    /// It is the caller's responsibility to ensure `old` exists in the row
+    /// group and `new` does not.
+    pub(crate) fn rename_column(&mut self, old: ColumnName<'_>, new: &str) {
+        let idx = self.all_columns_by_name.remove(old).unwrap();
+        self.all_columns_by_name.insert(new.to_owned(), idx);
+        self.meta.rename_column(old, new);
+    }
+
+    /// Adds a column named `name` holding a NULL value for every row of the
+    /// row group.
+    ///
+    /// It is the caller's responsibility to ensure `name` does not exist in
+    /// the row group, and that `column_type` is a tag or field column of a
+    /// logical data type other than `Binary`.
+    pub(crate) fn append_null_column(
+        &mut self,
+        name: &str,
+        column_type: schema::ColumnType,
+        data_type: LogicalDataType,
+    ) {
+        let rows = self.rows() as usize;
+        let column = match data_type {
+            LogicalDataType::Integer => Column::from(array::Int64Array::from(vec![None; rows])),
+            LogicalDataType::Unsigned => Column::from(array::UInt64Array::from(vec![None; rows])),
+            LogicalDataType::Float => Column::from(array::Float64Array::from(vec![None; rows])),
+            LogicalDataType::String => {
+                Column::from(array::StringArray::from(vec![None::<&str>; rows]))
+            }
+            LogicalDataType::Boolean => Column::from(array::BooleanArray::from(vec![None; rows])),
+            LogicalDataType::Binary => unimplemented!("binary columns are not supported"),
+        };
+
+        self.meta.add_column(
+            name,
+            column.size(),
+            column_type,
+            column.logical_datatype(),
+            column.column_range(),
+            column.storage_stats().nulls,
+            column.cardinality(),
+        );
+        self.all_columns_by_name
+            .insert(name.to_owned(), self.columns.len());
+        self.columns.push(Arc::new(column));
     }
 
     // Returns a reference to the timestamp column.
@@ -220,6 +274,15 @@ impl RowGroup {
             .all(|expr| self.meta.column_could_satisfy_binary_expr(expr))
     }
 
+    /// Determines if the named string column contains `value` on at least one
+    /// row of the row group. Returns `None` if the column is not a string
+    /// column.
+    ///
+    /// It is the caller's responsibility to ensure the column exists.
+    pub fn column_contains_string_value(&self, name: ColumnName<'_>, value: &str) -> Option<bool> {
+        self.column_by_name(name).contains_string_value(value)
+    }
+
     /// Determines if the row group contains one or more rows that satisfy all
     /// of the provided binary expressions, when conjunctively applied.
     ///
@@ -260,16 +323,31 @@ impl RowGroup {
         predicate: &Predicate,
         negated_predicates: &[Predicate],
     ) -> ReadFilterResult<'_> {
-        trace!(%predicate, ?columns, ?negated_predicates, row_group_total_rows=?self.rows(), "read_filter called");
+        self.read_filter_with_decode_metrics(columns, predicate, negated_predicates, None)
+    }
 
-        let select_columns = self.meta.schema_for_column_names(columns);
-        assert_eq!(select_columns.len(), columns.len());
+    /// As `read_filter`, but records the time taken to decode each selected
+    /// column into `decode_metrics`, if provided.
+    pub(crate) fn read_filter_with_decode_metrics(
+        &self,
+        columns: &[ColumnName<'_>],
+        predicate: &Predicate,
+        negated_predicates: &[Predicate],
+        decode_metrics: Option<&ColumnDecodeMetrics>,
+    ) -> ReadFilterResult<'_> {
+        trace!(%predicate, ?columns, ?negated_predicates, row_group_total_rows=?self.rows(), "read_filter called");
 
-        let schema = ResultSchema {
-            select_columns,
-            ..Default::default()
-        };
+        let row_ids = self.read_filter_row_ids(predicate, negated_predicates);
+        self.read_filter_rows(columns, row_ids, decode_metrics)
+    }
 
+    /// Determines the rows `read_filter` would return for the provided
+    /// predicate and delete predicates, without materialising any columns.
+    pub(crate) fn read_filter_row_ids(
+        &self,
+        predicate: &Predicate,
+        negated_predicates: &[Predicate],
+    ) -> RowIDsOption {
         // apply predicate to determine candidate rows.
         let now = std::time::Instant::now();
         let row_ids = self.row_ids_from_predicate(predicate);
@@ -323,8 +401,28 @@ impl RowGroup {
                 RowIDsOption::All(_) => self.rows() as usize,
         }, "read_filter candidate rows identified");
 
+        final_row_ids
+    }
+
+    /// Materialises the selected columns for the provided rows, which are
+    /// typically determined by `read_filter_row_ids`. Records the time taken
+    /// to decode each column into `decode_metrics`, if provided.
+    pub(crate) fn read_filter_rows(
+        &self,
+        columns: &[ColumnName<'_>],
+        row_ids: RowIDsOption,
+        decode_metrics: Option<&ColumnDecodeMetrics>,
+    ) -> ReadFilterResult<'_> {
+        let select_columns = self.meta.schema_for_column_names(columns);
+        assert_eq!(select_columns.len(), columns.len());
+
+        let schema = ResultSchema {
+            select_columns,
+            ..Default::default()
+        };
+
         let now = std::time::Instant::now();
-        let col_data = self.materialise_rows(&schema, final_row_ids);
+        let col_data = self.materialise_rows(&schema, row_ids, decode_metrics);
         trace!(elapsed=?now.elapsed(), "read_filter materialised rows");
 
         ReadFilterResult {
@@ -333,7 +431,12 @@ impl RowGroup {
         }
     }
 
-    fn materialise_rows(&self, schema: &ResultSchema, row_ids: RowIDsOption) -> Vec<Values<'_>> {
+    fn materialise_rows(
+        &self,
+        schema: &ResultSchema,
+        row_ids: RowIDsOption,
+        decode_metrics: Option<&ColumnDecodeMetrics>,
+    ) -> Vec<Values<'_>> {
         let mut col_data = Vec::with_capacity(schema.len());
         match row_ids {
             RowIDsOption::None(_) => col_data, // nothing to materialise
@@ -343,11 +446,15 @@ impl RowGroup {
                 let row_ids = row_ids.to_vec();
                 for (ct, _) in &schema.select_columns {
                     let (_, col) = self.column_name_and_column(ct.as_str());
+                    let now = std::time::Instant::now();
                     if let schema::ColumnType::Tag(_) = ct {
                         col_data.push(col.values_as_dictionary(row_ids.as_slice()));
                     } else {
                         col_data.push(col.values(row_ids.as_slice()));
                     }
+                    if let Some(metrics) = decode_metrics {
+                        metrics.record(col, now.elapsed());
+                    }
                 }
                 col_data
             }
@@ -355,17 +462,90 @@ impl RowGroup {
             RowIDsOption::All(_) => {
                 for (ct, _) in &schema.select_columns {
                     let (_, col) = self.column_name_and_column(ct.as_str());
+                    let now = std::time::Instant::now();
                     if let schema::ColumnType::Tag(_) = ct {
                         col_data.push(col.all_values_as_dictionary());
                     } else {
                         col_data.push(col.all_values());
                     }
+                    if let Some(metrics) = decode_metrics {
+                        metrics.record(col, now.elapsed());
+                    }
                 }
                 col_data
             }
         }
     }
 
+    /// Returns the values of the named column, in row order, for the rows
+    /// that satisfy the provided predicate. No other column is materialised.
+    ///
+    /// It is the caller's responsibility to ensure the column exists.
+    pub(crate) fn column_values_matching(
+        &self,
+        name: ColumnName<'_>,
+        predicate: &Predicate,
+    ) -> Vec<OwnedValue> {
+        let column = self.column_by_name(name);
+        let values = match self.row_ids_from_predicate(predicate) {
+            RowIDsOption::None(_) => return vec![],
+            RowIDsOption::Some(row_ids) => column.values(row_ids.to_vec().as_slice()),
+            RowIDsOption::All(_) => column.all_values(),
+        };
+
+        (0..values.len())
+            .map(|i| OwnedValue::from(values.value(i)))
+            .collect()
+    }
+
+    /// Returns the set of row ids within this row group that satisfy the
+    /// provided predicate. Unlike the internal `RowIDsOption` representation
+    /// the "all rows" case is materialised, so the returned set always
+    /// contains exactly the matching row ids.
+    pub(crate) fn row_ids_matching(&self, predicate: &Predicate) -> RowIDs {
+        match self.row_ids_from_predicate(predicate) {
+            RowIDsOption::None(mut row_ids) => {
+                row_ids.clear();
+                row_ids
+            }
+            RowIDsOption::Some(row_ids) => row_ids,
+            RowIDsOption::All(mut row_ids) => {
+                row_ids.clear();
+                row_ids.add_range(0, self.rows());
+                row_ids
+            }
+        }
+    }
+
+    /// As `read_filter`, but only materialises a pseudo-random sample of
+    /// roughly `fraction` of the rows satisfying the predicate and not
+    /// satisfying any of the delete predicates.
+    ///
+    /// Rows are sampled by their row id before any column values are
+    /// decoded, and whether a row is sampled depends only on `seed` and its
+    /// row id, so the same seed always produces the same sample.
+    pub(crate) fn read_filter_sample(
+        &self,
+        columns: &[ColumnName<'_>],
+        predicate: &Predicate,
+        negated_predicates: &[Predicate],
+        fraction: f64,
+        seed: u64,
+    ) -> ReadFilterResult<'_> {
+        let is_sampled = |&row_id: &u32| sample_row(seed, row_id, fraction);
+        let sampled = match self.read_filter_row_ids(predicate, negated_predicates) {
+            RowIDsOption::None(_) => vec![],
+            RowIDsOption::Some(row_ids) => row_ids.iter().filter(is_sampled).collect(),
+            RowIDsOption::All(_) => (0..self.rows()).filter(is_sampled).collect::<Vec<_>>(),
+        };
+        let row_ids = match sampled.is_empty() {
+            true => RowIDsOption::new_none(),
+            false => RowIDsOption::Some(RowIDs::Vector(sampled)),
+        };
+
+        self.read_filter_rows(columns, row_ids, None)
+    }
+
     // Determines the set of row ids that satisfy the provided predicate.
     fn row_ids_from_predicate(&self, predicate: &Predicate) -> RowIDsOption {
         trace!(%predicate, "row_ids_from_predicate called");
@@ -424,7 +604,14 @@ impl RowGroup {
             // on other columns. Each call to `row_ids_filter` returns the
             // buffer back enabling it to be re-used.
             let now = std::time::Instant::now();
-            let row_ids = col.row_ids_filter(&expr.op, &expr.literal_as_value(), dst);
+            let row_ids = match expr.literal() {
+                Literal::Regex(regex) => col.row_ids_filter_regex(&expr.op, regex.regex(), dst),
+                Literal::List(values) => {
+                    let values = values.iter().map(Literal::as_value).collect::<Vec<_>>();
+                    col.row_ids_filter_in(&values, dst)
+                }
+                _ => col.row_ids_filter(&expr.op, &expr.literal_as_value(), dst),
+            };
             trace!(elapsed=?now.elapsed(), rows=?match &row_ids{
                 RowIDsOption::None(_) => 0,
                 RowIDsOption::Some(row_ids) => row_ids.len(),
@@ -467,6 +654,11 @@ impl RowGroup {
             }
         }
 
+        if !predicate.column_comparisons().is_empty() {
+            return self
+                .row_ids_from_column_comparisons(predicate.column_comparisons(), result_row_ids);
+        }
+
         if result_row_ids.is_empty() {
             // All rows matched all expressions in the predicate.
             return RowIDsOption::All(result_row_ids);
@@ -474,6 +666,57 @@ impl RowGroup {
         RowIDsOption::Some(result_row_ids)
     }
 
+    // Applies column-vs-column comparisons to the rows matching all other
+    // expressions of a predicate, which are either `row_ids` or, if
+    // `row_ids` is empty, all rows.
+    //
+    // The comparisons are evaluated row by row because, unlike comparisons
+    // against a literal, they cannot be answered from a column's encoding.
+    fn row_ids_from_column_comparisons(
+        &self,
+        comparisons: &[ColumnComparison],
+        mut row_ids: RowIDs,
+    ) -> RowIDsOption {
+        // N.B columns should always exist because validation of predicates
+        // should happen at the `Table` level.
+        let columns = comparisons
+            .iter()
+            .map(|cmp| {
+                (
+                    self.column_by_name(cmp.left()),
+                    self.column_by_name(cmp.right()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let candidates = match row_ids.is_empty() {
+            true => (0..self.rows()).collect::<Vec<_>>(),
+            false => row_ids.iter().collect::<Vec<_>>(),
+        };
+
+        row_ids.clear();
+        for row_id in candidates {
+            let matches = comparisons
+                .iter()
+                .zip(&columns)
+                .all(|(cmp, (left, right))| {
+                    cmp.evaluate(&left.value(row_id), &right.value(row_id))
+                });
+            if matches {
+                row_ids.add(row_id);
+            }
+        }
+
+        match row_ids.len() {
+            0 => RowIDsOption::None(row_ids),
+            n if n == self.rows() as usize => {
+                row_ids.clear();
+                RowIDsOption::All(row_ids)
+            }
+            _ => RowIDsOption::Some(row_ids),
+        }
+    }
+
     // An optimised function for applying two comparison predicates to a time
     // column at once.
     fn row_ids_from_time_range(&self, time_range: &[BinaryExpr], dst: RowIDs) -> RowIDsOption {
@@ -532,6 +775,19 @@ impl RowGroup {
         predicate: &Predicate,
         group_columns: &[ColumnName<'_>],
         aggregates: &[(ColumnName<'_>, AggregateType)],
+    ) -> ReadAggregateResult<'_> {
+        self.read_aggregate_with_max_groups(predicate, group_columns, aggregates, None)
+    }
+
+    /// As `read_aggregate`, but grouping stops as soon as more than
+    /// `max_groups` distinct group keys have been found. The result then holds
+    /// `max_groups + 1` groups whose aggregates are incomplete.
+    pub fn read_aggregate_with_max_groups(
+        &self,
+        predicate: &Predicate,
+        group_columns: &[ColumnName<'_>],
+        aggregates: &[(ColumnName<'_>, AggregateType)],
+        max_groups: Option<usize>,
     ) -> ReadAggregateResult<'_> {
         let schema = ResultSchema {
             select_columns: vec![],
@@ -541,6 +797,7 @@ impl RowGroup {
 
         let mut result = ReadAggregateResult {
             schema,
+            max_groups,
             ..ReadAggregateResult::default()
         };
 
@@ -576,12 +833,73 @@ impl RowGroup {
             RowIDsOption::All(_) => None,
         };
 
-        let agg_cols_num = result.schema.aggregate_columns.len();
+        self.read_group_for_row_ids(&mut result, filter_row_ids.as_deref());
+        result
+    }
+
+    /// Returns aggregates segmented by the provided group columns and by
+    /// the time window each row's timestamp falls within, keyed by the start
+    /// of that window. Windows containing no rows satisfying the predicate
+    /// are omitted.
+    pub fn read_window_aggregate(
+        &self,
+        predicate: &Predicate,
+        window: &TimeWindow,
+        group_columns: &[ColumnName<'_>],
+        aggregates: &[(ColumnName<'_>, AggregateType)],
+    ) -> BTreeMap<i64, ReadAggregateResult<'_>> {
+        let schema = ResultSchema {
+            select_columns: vec![],
+            group_columns: self.meta.schema_for_column_names(group_columns),
+            aggregate_columns: self.meta.schema_for_aggregate_column_names(aggregates),
+        };
+
+        // bucket the ids of the matching rows by the window they fall within.
+        let row_ids = self.row_ids_matching(predicate).to_vec();
+        let timestamps = match self.time_column().values(&row_ids) {
+            Values::I64(timestamps) => timestamps,
+            _ => unreachable!("time column must contain non-null integers"),
+        };
+
+        let mut windows: BTreeMap<i64, Vec<u32>> = BTreeMap::new();
+        for (row_id, timestamp) in row_ids.into_iter().zip(timestamps) {
+            windows
+                .entry(window.start(timestamp))
+                .or_default()
+                .push(row_id);
+        }
+
+        windows
+            .into_iter()
+            .map(|(start, row_ids)| {
+                let mut result = ReadAggregateResult {
+                    schema: schema.clone(),
+                    ..ReadAggregateResult::default()
+                };
+
+                if group_columns.is_empty() {
+                    self.aggregate_columns_for_row_ids(&row_ids, &mut result);
+                } else {
+                    self.read_group_for_row_ids(&mut result, Some(&row_ids));
+                }
+                (start, result)
+            })
+            .collect()
+    }
+
+    // Groups and aggregates the rows identified by `filter_row_ids` into
+    // `dst`, where `None` identifies all rows in the row group.
+    fn read_group_for_row_ids<'a>(
+        &'a self,
+        dst: &mut ReadAggregateResult<'a>,
+        filter_row_ids: Option<&[u32]>,
+    ) {
+        let agg_cols_num = dst.schema.aggregate_columns.len();
 
         // materialise all *encoded* values for each column we are grouping on.
         // These will not be the logical (typically string) values, but will be
         // vectors of integers representing the physical values.
-        let groupby_encoded_ids: Vec<_> = result
+        let groupby_encoded_ids: Vec<_> = dst
             .schema
             .group_column_names_iter()
             .map(|name| {
@@ -591,7 +909,7 @@ impl RowGroup {
 
                 // Do we want some rows for the column (predicate filtered some
                 // rows) or all of them (predicates filtered no rows).
-                match &filter_row_ids {
+                match filter_row_ids {
                     Some(row_ids) => {
                         encoded_values_buf = col.encoded_values(row_ids, encoded_values_buf);
                     }
@@ -607,7 +925,7 @@ impl RowGroup {
 
         // Materialise values in aggregate columns.
         let mut aggregate_columns_data = Vec::with_capacity(agg_cols_num);
-        for (col_type, _, _) in &result.schema.aggregate_columns {
+        for (col_type, _, _) in &dst.schema.aggregate_columns {
             let col = self.column_by_name(col_type.as_str());
 
             // TODO(edd): this materialises a column per aggregate. If there are
@@ -615,7 +933,7 @@ impl RowGroup {
             // over-allocate
 
             // Do we want some rows for the column or all of them?
-            let column_values = match &filter_row_ids {
+            let column_values = match filter_row_ids {
                 Some(row_ids) => col.values(row_ids),
                 None => {
                     // None here means "no partial set of row ids", i.e., get
@@ -628,8 +946,7 @@ impl RowGroup {
         }
 
         // Perform the group by using a hashmap
-        self.read_group_with_hashing(&mut result, &groupby_encoded_ids, aggregate_columns_data);
-        result
+        self.read_group_with_hashing(dst, &groupby_encoded_ids, aggregate_columns_data);
     }
 
     // read_group_hash executes a read-group-aggregate operation on the
@@ -697,6 +1014,7 @@ impl RowGroup {
         // key_buf will be used as a temporary buffer for group keys represented
         // as a `Vec<u32>`.
         let mut key_buf = vec![0; dst.schema.group_columns.len()];
+        let mut sum_overflowed = false;
         let mut next_ordinal_id = 0; // assign a position for each group key in output columns.
         for row in 0..total_rows {
             // update the group key buffer with the group key for this row
@@ -712,11 +1030,11 @@ impl RowGroup {
                     // with the values present in the input columns at the
                     // current row.
                     for (agg_col_i, aggregate_result) in agg_cols_out.iter_mut().enumerate() {
-                        aggregate_result.update(
+                        sum_overflowed |= aggregate_result.update(
                             &aggregate_input_columns[agg_col_i],
                             row,
                             *ordinal_id,
-                        )
+                        );
                     }
                 }
                 // group key does not exist, so create it.
@@ -725,11 +1043,11 @@ impl RowGroup {
                     // with the values present in the input columns at the
                     // current row.
                     for (agg_col_i, aggregate_result) in agg_cols_out.iter_mut().enumerate() {
-                        aggregate_result.update(
+                        sum_overflowed |= aggregate_result.update(
                             &aggregate_input_columns[agg_col_i],
                             row,
                             next_ordinal_id,
-                        )
+                        );
                     }
 
                     // Add decoded group key values to the output group columns.
@@ -752,10 +1070,18 @@ impl RowGroup {
                     next_ordinal_id += 1;
                 }
             }
+
+            if dst.exceeds_max_groups(next_ordinal_id) {
+                break;
+            }
         }
 
+        for agg_col in &mut agg_cols_out {
+            agg_col.finalise();
+        }
         dst.group_key_cols = group_cols_out;
         dst.aggregate_cols = agg_cols_out;
+        dst.sum_overflowed = sum_overflowed;
     }
 
     // This function is similar to `read_group_hash_with_vec_key` in that it
@@ -804,6 +1130,7 @@ impl RowGroup {
             .map(|name| self.column_by_name(name))
             .collect::<Vec<_>>();
 
+        let mut sum_overflowed = false;
         let mut next_ordinal_id = 0; // assign a position for each group key in output columns.
         for row in 0..total_rows {
             // pack each column's encoded value for the row into a packed
@@ -821,11 +1148,11 @@ impl RowGroup {
                     // with the values present in the input columns at the
                     // current row.
                     for (agg_col_i, aggregate_result) in agg_cols_out.iter_mut().enumerate() {
-                        aggregate_result.update(
+                        sum_overflowed |= aggregate_result.update(
                             &aggregate_input_columns[agg_col_i],
                             row,
                             *ordinal_id,
-                        )
+                        );
                     }
                 }
                 hash_map::RawEntryMut::Vacant(entry) => {
@@ -833,11 +1160,11 @@ impl RowGroup {
                     // with the values present in the input columns at the
                     // current row.
                     for (agg_col_i, aggregate_result) in agg_cols_out.iter_mut().enumerate() {
-                        aggregate_result.update(
+                        sum_overflowed |= aggregate_result.update(
                             &aggregate_input_columns[agg_col_i],
                             row,
                             next_ordinal_id,
-                        )
+                        );
                     }
 
                     // Add decoded group key values to the output group columns.
@@ -860,10 +1187,18 @@ impl RowGroup {
                     next_ordinal_id += 1;
                 }
             }
+
+            if dst.exceeds_max_groups(next_ordinal_id) {
+                break;
+            }
         }
 
+        for agg_col in &mut agg_cols_out {
+            agg_col.finalise();
+        }
         dst.group_key_cols = group_cols_out;
         dst.aggregate_cols = agg_cols_out;
+        dst.sum_overflowed = sum_overflowed;
     }
 
     // Optimised `read_group` method when there are no predicates and all the
@@ -915,6 +1250,7 @@ impl RowGroup {
             .collect::<Vec<_>>();
 
         let mut output_rows = 0;
+        let mut sum_overflowed = false;
 
         // multi_cartesian_product will create the cartesian product of all
         // grouping-column values. This is likely going to be more group keys
@@ -987,28 +1323,38 @@ impl RowGroup {
 
             // Calculate an aggregate from each input aggregate column and
             // set it at the relevant offset in the output column.
+            let row_ids = group_key_row_ids.to_vec();
             for (agg_col_i, (agg_col, typ)) in input_aggregate_columns.iter().enumerate() {
                 match typ {
                     AggregateType::Count => {
-                        let agg = agg_col.count(&group_key_row_ids.to_vec()) as u64;
+                        let agg = agg_col.count(&row_ids) as u64;
                         agg_cols_out[agg_col_i].push(Value::Scalar(Scalar::U64(agg)))
                     }
                     AggregateType::First => {}
                     AggregateType::Last => {}
                     AggregateType::Min => {
-                        let agg = agg_col.min(&group_key_row_ids.to_vec());
+                        let agg = agg_col.min(&row_ids);
                         agg_cols_out[agg_col_i].push(agg);
                     }
                     AggregateType::Max => {
-                        let agg = agg_col.max(&group_key_row_ids.to_vec());
+                        let agg = agg_col.max(&row_ids);
                         agg_cols_out[agg_col_i].push(agg);
                     }
                     AggregateType::Sum => {
-                        let agg = agg_col.sum(&group_key_row_ids.to_vec());
+                        let (agg, overflowed) = agg_col.sum(&row_ids);
+                        sum_overflowed |= overflowed;
                         agg_cols_out[agg_col_i].push(Value::Scalar(agg));
                     }
+                    AggregateType::Percentile(_) | AggregateType::StdDev => {
+                        let runs = agg_col.value_runs(&row_ids);
+                        agg_cols_out[agg_col_i].push_runs(runs);
+                    }
                 }
             }
+
+            if dst.exceeds_max_groups(output_rows) {
+                break;
+            }
         }
 
         for col in &group_cols_out {
@@ -1020,6 +1366,7 @@ impl RowGroup {
 
         dst.group_key_cols = group_cols_out;
         dst.aggregate_cols = agg_cols_out;
+        dst.sum_overflowed = sum_overflowed;
     }
 
     // Optimised `read_group` method for cases where the columns being grouped
@@ -1057,6 +1404,17 @@ impl RowGroup {
             },
         };
 
+        self.aggregate_columns_for_row_ids(&row_ids, dst);
+    }
+
+    // Aggregates the rows identified by `row_ids` into a single row of
+    // aggregates in `dst`.
+    fn aggregate_columns_for_row_ids<'a>(
+        &'a self,
+        row_ids: &[u32],
+        dst: &mut ReadAggregateResult<'a>,
+    ) {
+        let mut sum_overflowed = false;
         dst.aggregate_cols = dst
             .schema
             .aggregate_columns
@@ -1069,24 +1427,34 @@ impl RowGroup {
                 // predicate filter.
                 match agg_type {
                     AggregateType::Count => {
-                        let value = Value::Scalar(Scalar::U64(col.count(&row_ids) as u64));
+                        let value = Value::Scalar(Scalar::U64(col.count(row_ids) as u64));
                         agg_vec.push(value);
                     }
                     AggregateType::First => unimplemented!("First not yet implemented"),
                     AggregateType::Last => unimplemented!("Last not yet implemented"),
-                    AggregateType::Min => agg_vec.push(col.min(&row_ids)),
-                    AggregateType::Max => agg_vec.push(col.max(&row_ids)),
-                    AggregateType::Sum => agg_vec.push(Value::Scalar(col.sum(&row_ids))),
+                    AggregateType::Min => agg_vec.push(col.min(row_ids)),
+                    AggregateType::Max => agg_vec.push(col.max(row_ids)),
+                    AggregateType::Sum => {
+                        let (sum, overflowed) = col.sum(row_ids);
+                        sum_overflowed |= overflowed;
+                        agg_vec.push(Value::Scalar(sum));
+                    }
+                    AggregateType::Percentile(_) | AggregateType::StdDev => {
+                        agg_vec.push_runs(col.value_runs(row_ids))
+                    }
                 }
                 agg_vec
             })
             .collect::<Vec<_>>();
+        dst.sum_overflowed = sum_overflowed;
     }
 
     /// Given the predicate (which may be empty), determine a set of rows
     /// contained in this row group that satisfy it. Any column that contains a
     /// non-null value at any of these row positions is then included in the
-    /// results, which are added to `dst`.
+    /// results, which are added to `dst`. If `include_null_only` is set then
+    /// every column is included as long as at least one row satisfies the
+    /// predicate, even if the column only has NULL values at those rows.
     ///
     /// As an optimisation, the contents of `dst` are checked before execution
     /// and any columns already existing in the set are not interrogated.
@@ -1098,6 +1466,7 @@ impl RowGroup {
         predicate: &Predicate,
         negated_predicates: &[Predicate],
         columns: Selection<'_>,
+        include_null_only: bool,
         dst: &mut BTreeSet<String>,
     ) {
         // Determine the set of columns in this row group that are not already
@@ -1110,10 +1479,10 @@ impl RowGroup {
                 // N.B there is bool::then() but it's currently unstable.
                 true => None,
                 false => match columns {
-                    Selection::All => Some((name, &self.columns[id])),
+                    Selection::All => Some((name, &*self.columns[id])),
                     Selection::Some(names) => {
                         if names.iter().any(|selection| name == selection) {
-                            Some((name, &self.columns[id]))
+                            Some((name, &*self.columns[id]))
                         } else {
                             None
                         }
@@ -1169,6 +1538,13 @@ impl RowGroup {
         // for each column where there is a non-null value.
         match final_row_ids {
             RowIDsOption::None(_) => {} // no valid rows
+            _ if include_null_only => {
+                dst.extend(
+                    candidate_columns
+                        .into_iter()
+                        .map(|(name, _)| name.to_owned()),
+                );
+            }
             RowIDsOption::Some(row_ids) => {
                 // TODO(edd): perf refactor these operations to use
                 // iterators of row IDs.
@@ -1202,14 +1578,31 @@ impl RowGroup {
         columns: &[ColumnName<'_>],
         mut dst: BTreeMap<String, BTreeSet<String>>,
     ) -> BTreeMap<String, BTreeSet<String>> {
+        self.column_values_limited(predicate, columns, None, &mut dst, &mut BTreeSet::new());
+        dst
+    }
+
+    /// As `column_values`, but stops accumulating the values of a column in
+    /// `dst` once it holds `max_values` values. The name of any column with
+    /// further distinct values is added to `truncated`, and columns already in
+    /// `truncated` are not processed.
+    pub fn column_values_limited(
+        &self,
+        predicate: &Predicate,
+        columns: &[ColumnName<'_>],
+        max_values: Option<usize>,
+        dst: &mut BTreeMap<String, BTreeSet<String>>,
+        truncated: &mut BTreeSet<String>,
+    ) {
         // Build up candidate columns
         let candidate_columns = self
             .all_columns_by_name
             .iter()
-            // Filter any columns that are not present in the `Selection`.
+            // Filter any columns that are not present in the `Selection`, or
+            // that have already reached their limit.
             .filter_map(|(name, &id)| {
-                if columns.iter().any(|selection| name == selection) {
-                    Some((name, &self.columns[id]))
+                if columns.iter().any(|selection| name == selection) && !truncated.contains(name) {
+                    Some((name, &*self.columns[id]))
                 } else {
                     None
                 }
@@ -1234,25 +1627,38 @@ impl RowGroup {
             // extract an iterator of those IDs. If all rows match then create
             // an iterator of all rows without materialising them.
             let row_itr: Box<dyn Iterator<Item = u32>> = match &row_ids {
-                RowIDsOption::None(_) => return dst,
+                RowIDsOption::None(_) => return,
                 RowIDsOption::Some(row_ids) => Box::new(row_ids.iter()),
                 RowIDsOption::All(_) => Box::new(0..self.rows()),
             };
 
+            // Check the limit as each distinct value is found so that no more
+            // of the column is read once it has been exceeded.
             let results = dst.entry(name.clone()).or_default();
-            for v in column.distinct_values(row_itr).into_iter().flatten() {
-                if !results.contains(v) {
+            column.visit_distinct_values(row_itr, |v| match v {
+                Some(v) if !results.contains(v) => {
+                    if matches!(max_values, Some(max) if results.len() >= max) {
+                        truncated.insert(name.clone());
+                        return false;
+                    }
                     results.insert(v.to_owned());
+                    true
                 }
-            }
+                _ => true,
+            });
         }
-
-        dst
     }
 
     pub(crate) fn column_storage_statistics(&self) -> Vec<column::Statistics> {
         self.columns.iter().map(|c| c.storage_stats()).collect()
     }
+
+    /// The storage statistics of the named column, if it exists.
+    pub(crate) fn column_storage_statistics_for(&self, name: &str) -> Option<column::Statistics> {
+        self.all_columns_by_name
+            .get(name)
+            .map(|&idx| self.columns[idx].storage_stats())
+    }
 }
 
 impl std::fmt::Display for &RowGroup {
@@ -1331,6 +1737,9 @@ impl From<RecordBatch> for RowGroup {
                         DataType::Boolean => Column::from(arrow::array::BooleanArray::from(
                             arrow_column.data().clone(),
                         )),
+                        // String fields share the tag encoding path, so
+                        // they are always dictionary encoded, and RLE
+                        // encoded when their cardinality is low enough.
                         DataType::Utf8 => Column::from(arrow::array::StringArray::from(
                             arrow_column.data().clone(),
                         )),
@@ -1378,12 +1787,34 @@ fn unpack_u128_group_key(group_key_packed: u128, n: usize, mut dst: Vec<u32>) ->
     dst
 }
 
+/// A conjunction of expressions comparing columns against literals, and
+/// comparisons of pairs of columns.
 #[derive(Clone, Default, Debug, PartialEq)]
-pub struct Predicate(Vec<BinaryExpr>);
+pub struct Predicate(Vec<BinaryExpr>, Vec<ColumnComparison>);
 
 impl Predicate {
     pub fn new(expr: Vec<BinaryExpr>) -> Self {
-        Self(expr)
+        Self(expr, vec![])
+    }
+
+    /// Adds the provided column-vs-column comparisons to the predicate, which
+    /// are applied conjunctively with its expressions.
+    pub fn with_column_comparisons(mut self, comparisons: Vec<ColumnComparison>) -> Self {
+        self.1.extend(comparisons);
+        self
+    }
+
+    /// Lowers a DataFusion expression into a `Predicate`.
+    ///
+    /// The expression must be a conjunction (`AND`) of comparisons between a
+    /// column and a literal, or between two columns. An error describing the
+    /// offending sub-expression is returned for any other shape, including
+    /// disjunctions (`OR`), which the read buffer cannot yet evaluate.
+    pub fn try_from_expr(expr: &DfExpr) -> Result<Self, String> {
+        let mut exprs = vec![];
+        let mut comparisons = vec![];
+        lower_conjunction(expr, &mut exprs, &mut comparisons)?;
+        Ok(Self::new(exprs).with_column_comparisons(comparisons))
     }
 
     /// Constructs a `Predicate` based on the provided collection of expressions
@@ -1400,12 +1831,12 @@ impl Predicate {
         ];
 
         time_exprs.extend_from_slice(exprs);
-        Self(time_exprs)
+        Self::new(time_exprs)
     }
 
-    /// A `Predicate` is empty if it has no expressions.
+    /// A `Predicate` is empty if it has no expressions or column comparisons.
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.0.is_empty() && self.1.is_empty()
     }
 
     pub fn iter(&self) -> std::slice::Iter<'_, BinaryExpr> {
@@ -1417,6 +1848,72 @@ impl Predicate {
         &self.0
     }
 
+    /// Returns all column-vs-column comparisons on the predicate.
+    pub fn column_comparisons(&self) -> &[ColumnComparison] {
+        &self.1
+    }
+
+    /// Merges multiple lower bounds (`>`, `>=`) or multiple upper bounds (`<`,
+    /// `<=`) on the same column into the single tightest bound, e.g.,
+    /// `time >= 100 AND time >= 50` becomes `time >= 100`.
+    ///
+    /// Bounds are only merged when their literals are of the same type. All
+    /// other expressions are left as they are.
+    pub fn normalize(&mut self) {
+        let mut exprs: Vec<BinaryExpr> = Vec::with_capacity(self.0.len());
+        for expr in self.0.drain(..) {
+            let (lower, upper) = (is_lower_bound(expr.op), is_upper_bound(expr.op));
+            if !lower && !upper {
+                exprs.push(expr);
+                continue;
+            }
+
+            let existing = exprs.iter_mut().find(|other| {
+                other.col == expr.col
+                    && is_lower_bound(other.op) == lower
+                    && is_upper_bound(other.op) == upper
+                    && cmp_literals(&other.value, &expr.value).is_some()
+            });
+
+            match existing {
+                Some(other) => {
+                    let tighter = match cmp_literals(&expr.value, &other.value) {
+                        Some(Ordering::Greater) => lower,
+                        Some(Ordering::Less) => upper,
+                        Some(Ordering::Equal) => matches!(expr.op, Operator::GT | Operator::LT),
+                        None => unreachable!("only comparable bounds are merged"),
+                    };
+                    if tighter {
+                        *other = expr;
+                    }
+                }
+                None => exprs.push(expr),
+            }
+        }
+        self.0 = exprs;
+    }
+
+    /// Returns true if the lower and upper bounds on some column describe an
+    /// empty range, e.g., `time >= 100 AND time < 50`, in which case no row can
+    /// satisfy the predicate.
+    pub fn is_contradictory(&self) -> bool {
+        self.0
+            .iter()
+            .filter(|lower| is_lower_bound(lower.op))
+            .any(|lower| {
+                self.0
+                    .iter()
+                    .filter(|upper| upper.col == lower.col && is_upper_bound(upper.op))
+                    .any(|upper| match cmp_literals(&lower.value, &upper.value) {
+                        Some(Ordering::Greater) => true,
+                        Some(Ordering::Equal) => {
+                            lower.op == Operator::GT || upper.op == Operator::LT
+                        }
+                        _ => false,
+                    })
+            })
+    }
+
     // Removes all expressions for specified column from the predicate and
     // returns them.
     //
@@ -1432,23 +1929,66 @@ impl Predicate {
         exprs
     }
 
-    // Returns true if the Predicate contains two time expressions.
+    // Returns true if the Predicate contains two time expressions, neither of
+    // which is an IN list.
     fn contains_time_range(&self) -> bool {
-        self.0
-            .iter()
-            .filter(|expr| expr.col == TIME_COLUMN_NAME)
-            .count()
-            == 2
+        let mut time_exprs = self.0.iter().filter(|expr| expr.col == TIME_COLUMN_NAME);
+        time_exprs.clone().count() == 2 && time_exprs.all(|expr| expr.op != Operator::In)
+    }
+}
+
+// Returns true if the row with `row_id` belongs to the sample of roughly
+// `fraction` of all rows identified by `seed`.
+fn sample_row(seed: u64, row_id: u32, fraction: f64) -> bool {
+    let z = mix_seed(seed, u64::from(row_id));
+
+    // the top 53 bits as a float in [0, 1)
+    ((z >> 11) as f64 / (1_u64 << 53) as f64) < fraction
+}
+
+/// Derives a uniformly distributed value from `seed` and `value`. Both are
+/// hashed with the SplitMix64 finaliser, so nearby seeds do not produce
+/// related values for nearby inputs.
+pub(crate) fn mix_seed(seed: u64, value: u64) -> u64 {
+    fn mix(mut z: u64) -> u64 {
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    mix(mix(seed.wrapping_add(0x9E37_79B9_7F4A_7C15)) ^ value)
+}
+
+fn is_lower_bound(op: Operator) -> bool {
+    matches!(op, Operator::GT | Operator::GTE)
+}
+
+fn is_upper_bound(op: Operator) -> bool {
+    matches!(op, Operator::LT | Operator::LTE)
+}
+
+// Compares two literals of the same type, returning `None` for literals of
+// different types.
+fn cmp_literals(a: &Literal, b: &Literal) -> Option<Ordering> {
+    match (a, b) {
+        (Literal::String(a), Literal::String(b)) => Some(a.cmp(b)),
+        (Literal::Integer(a), Literal::Integer(b)) => Some(a.cmp(b)),
+        (Literal::Unsigned(a), Literal::Unsigned(b)) => Some(a.cmp(b)),
+        (Literal::Float(a), Literal::Float(b)) => a.partial_cmp(b),
+        (Literal::Boolean(a), Literal::Boolean(b)) => Some(a.cmp(b)),
+        _ => None,
     }
 }
 
 impl Display for &Predicate {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (i, expr) in self.0.iter().enumerate() {
-            expr.fmt(f)?;
-            if i < self.0.len() - 1 {
+        let exprs = self.0.iter().map(|expr| expr as &dyn Display);
+        let comparisons = self.1.iter().map(|cmp| cmp as &dyn Display);
+        for (i, expr) in exprs.chain(comparisons).enumerate() {
+            if i > 0 {
                 write!(f, " AND ")?;
             }
+            expr.fmt(f)?;
         }
         Ok(())
     }
@@ -1456,7 +1996,7 @@ impl Display for &Predicate {
 
 impl From<Vec<BinaryExpr>> for Predicate {
     fn from(arr: Vec<BinaryExpr>) -> Self {
-        Self(arr)
+        Self::new(arr)
     }
 }
 
@@ -1478,6 +2018,48 @@ pub enum Literal {
     Unsigned(u64),
     Float(f64),
     Boolean(bool),
+    Regex(RegexLiteral),
+    List(Vec<Literal>),
+}
+
+impl Literal {
+    // The value of a single literal. Panics for a list of literals.
+    fn as_value(&self) -> Value<'_> {
+        match self {
+            Self::String(v) => Value::String(v),
+            Self::Integer(v) => Value::Scalar(Scalar::I64(*v)),
+            Self::Unsigned(v) => Value::Scalar(Scalar::U64(*v)),
+            Self::Float(v) => Value::Scalar(Scalar::F64(*v)),
+            Self::Boolean(v) => Value::Boolean(*v),
+            Self::Regex(v) => Value::String(v.regex().as_str()),
+            Self::List(_) => panic!("a list of literals has no single value"),
+        }
+    }
+}
+
+/// A compiled regular expression used as the literal of a regex match (`=~`)
+/// or not-match (`!~`) expression.
+///
+/// Two regex literals are equal if they were compiled from the same pattern.
+#[derive(Clone, Debug)]
+pub struct RegexLiteral(Regex);
+
+impl RegexLiteral {
+    pub fn regex(&self) -> &Regex {
+        &self.0
+    }
+}
+
+impl From<Regex> for RegexLiteral {
+    fn from(regex: Regex) -> Self {
+        Self(regex)
+    }
+}
+
+impl PartialEq for RegexLiteral {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
 }
 
 impl<'a> TryFrom<&DFScalarValue> for Literal {
@@ -1532,6 +2114,32 @@ impl BinaryExpr {
         }
     }
 
+    /// An expression matching the rows of a string column whose value matches
+    /// `regex`, e.g., `region =~ /^us-/`.
+    pub fn regex_match(column_name: impl Into<String>, regex: Regex) -> Self {
+        Self::new(
+            column_name,
+            Operator::RegexMatch,
+            Literal::Regex(regex.into()),
+        )
+    }
+
+    /// An expression matching the rows of a string column whose value does not
+    /// match `regex`, e.g., `region !~ /^us-/`. NULL values never match.
+    pub fn regex_not_match(column_name: impl Into<String>, regex: Regex) -> Self {
+        Self::new(
+            column_name,
+            Operator::RegexNotMatch,
+            Literal::Regex(regex.into()),
+        )
+    }
+
+    /// An expression matching the rows of a column whose value is equal to
+    /// any of `values`, e.g., `region IN ('west', 'east', 'north')`.
+    pub fn in_list(column_name: impl Into<String>, values: Vec<Literal>) -> Self {
+        Self::new(column_name, Operator::In, Literal::List(values))
+    }
+
     pub fn column(&self) -> ColumnName<'_> {
         self.col.as_str()
     }
@@ -1540,18 +2148,17 @@ impl BinaryExpr {
         self.op
     }
 
+    /// Returns true if the expression is a regex match or not-match.
+    pub fn is_regex(&self) -> bool {
+        matches!(self.op, Operator::RegexMatch | Operator::RegexNotMatch)
+    }
+
     pub fn literal(&self) -> &Literal {
         &self.value
     }
 
     fn literal_as_value(&self) -> Value<'_> {
-        match self.literal() {
-            Literal::String(v) => Value::String(v),
-            Literal::Integer(v) => Value::Scalar(Scalar::I64(*v)),
-            Literal::Unsigned(v) => Value::Scalar(Scalar::U64(*v)),
-            Literal::Float(v) => Value::Scalar(Scalar::F64(*v)),
-            Literal::Boolean(v) => Value::Boolean(*v),
-        }
+        self.literal().as_value()
     }
 }
 
@@ -1588,10 +2195,35 @@ macro_rules! binary_expr_from_impls {
                     )
                 }
             }
+
+            impl From<(&str, &str, Vec<$type>)> for BinaryExpr {
+                fn from(expr: (&str, &str, Vec<$type>)) -> Self {
+                    Self::new(
+                        expr.0,
+                        Operator::try_from(expr.1).unwrap(),
+                        Literal::List(expr.2.into_iter().map(Literal::$variant).collect()),
+                    )
+                }
+            }
         )*
     };
 }
 
+impl From<(&str, &str, Vec<&str>)> for BinaryExpr {
+    fn from(expr: (&str, &str, Vec<&str>)) -> Self {
+        Self::new(
+            expr.0,
+            Operator::try_from(expr.1).unwrap(),
+            Literal::List(
+                expr.2
+                    .into_iter()
+                    .map(|v| Literal::String(v.to_owned()))
+                    .collect(),
+            ),
+        )
+    }
+}
+
 binary_expr_from_impls! {
     (String, String),
     (i64, Integer),
@@ -1639,11 +2271,137 @@ impl TryFrom<&DfExpr> for BinaryExpr {
                     }
                 }
             }
+            DfExpr::InList {
+                expr,
+                list,
+                negated: false,
+            } => match &**expr {
+                DfExpr::Column(c) => {
+                    let values = list
+                        .iter()
+                        .map(|value| match value {
+                            DfExpr::Literal(scalar) => Literal::try_from(scalar),
+                            value => Err(format!("unsupported IN list value {:?}", value)),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(Self::in_list(&c.name, values))
+                }
+                expr => Err(format!("unsupported IN list expression {:?}", expr)),
+            },
             _ => return Err(format!("unsupported expression type {:?}", df_expr)),
         }
     }
 }
 
+// Lowers each operand of a tree of `AND` expressions into either a
+// column-vs-literal expression or a column-vs-column comparison.
+fn lower_conjunction(
+    expr: &DfExpr,
+    exprs: &mut Vec<BinaryExpr>,
+    comparisons: &mut Vec<ColumnComparison>,
+) -> Result<(), String> {
+    match expr {
+        DfExpr::BinaryExpr {
+            left,
+            op: DFOperator::And,
+            right,
+        } => {
+            lower_conjunction(left, exprs, comparisons)?;
+            lower_conjunction(right, exprs, comparisons)
+        }
+        DfExpr::BinaryExpr {
+            op: DFOperator::Or, ..
+        } => Err(format!("unsupported disjunction {:?}", expr)),
+        DfExpr::BinaryExpr { left, op, right } => match (&**left, &**right) {
+            (DfExpr::Column(left), DfExpr::Column(right)) => {
+                comparisons.push(ColumnComparison::new(
+                    &left.name,
+                    Operator::try_from(op)?,
+                    &right.name,
+                ));
+                Ok(())
+            }
+            _ => {
+                exprs.push(BinaryExpr::try_from(expr)?);
+                Ok(())
+            }
+        },
+        DfExpr::InList { .. } => {
+            exprs.push(BinaryExpr::try_from(expr)?);
+            Ok(())
+        }
+        _ => Err(format!("unsupported expression type {:?}", expr)),
+    }
+}
+
+/// An expression that compares the values of two columns on each row, e.g.,
+/// `src_region = dst_region`.
+///
+/// Rows where either column is NULL never satisfy the comparison.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnComparison {
+    left: String,
+    op: Operator,
+    right: String,
+}
+
+impl ColumnComparison {
+    pub fn new(left: impl Into<String>, op: Operator, right: impl Into<String>) -> Self {
+        Self {
+            left: left.into(),
+            op,
+            right: right.into(),
+        }
+    }
+
+    pub fn left(&self) -> ColumnName<'_> {
+        self.left.as_str()
+    }
+
+    pub fn op(&self) -> Operator {
+        self.op
+    }
+
+    pub fn right(&self) -> ColumnName<'_> {
+        self.right.as_str()
+    }
+
+    // Determines if a pair of values from the left and right columns
+    // satisfies the comparison.
+    fn evaluate(&self, left: &Value<'_>, right: &Value<'_>) -> bool {
+        if left.is_null() || right.is_null() {
+            return false;
+        }
+
+        match left.partial_cmp(right) {
+            Some(ordering) => match self.op {
+                Operator::Equal => ordering == Ordering::Equal,
+                Operator::NotEqual => ordering != Ordering::Equal,
+                Operator::GT => ordering == Ordering::Greater,
+                Operator::GTE => ordering != Ordering::Less,
+                Operator::LT => ordering == Ordering::Less,
+                Operator::LTE => ordering != Ordering::Greater,
+                Operator::RegexMatch | Operator::RegexNotMatch | Operator::In => {
+                    unreachable!("columns can't be compared using {}", self.op)
+                }
+            },
+            None => false,
+        }
+    }
+}
+
+impl Display for ColumnComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.left, self.op, self.right)
+    }
+}
+
+impl From<(&str, &str, &str)> for ColumnComparison {
+    fn from(expr: (&str, &str, &str)) -> Self {
+        Self::new(expr.0, Operator::try_from(expr.1).unwrap(), expr.2)
+    }
+}
+
 // A representation of a column name.
 pub type ColumnName<'a> = &'a str;
 
@@ -1674,6 +2432,25 @@ impl ColumnType {
     }
 }
 
+/// Renames the column `old` to `new` in a set of column meta data and the
+/// ordered column names describing it.
+///
+/// Panics if `old` does not exist.
+pub(crate) fn rename_column_meta(
+    columns: &mut BTreeMap<String, ColumnMeta>,
+    column_names: &mut [String],
+    old: &str,
+    new: &str,
+) {
+    let mut meta = columns.remove(old).unwrap();
+    meta.typ = meta.typ.with_name(new);
+    columns.insert(new.to_owned(), meta);
+
+    for name in column_names.iter_mut().filter(|name| *name == old) {
+        *name = new.to_owned();
+    }
+}
+
 #[derive(Debug, Clone)]
 /// Row Group metadata per Column
 pub struct ColumnMeta {
@@ -1716,7 +2493,7 @@ impl PartialEq for ColumnMeta {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 /// The metadata for a RowGroup
 pub struct MetaData {
     /// The total size in bytes of all column data in the `RowGroup`.
@@ -1778,6 +2555,15 @@ impl MetaData {
             None => return false, // column doesn't exist.
         };
 
+        // the column could contain the value if the column range covers any
+        // of the values in the list.
+        if let Literal::List(values) = expr.literal() {
+            return values.iter().any(|value| {
+                let value = &value.as_value();
+                column_min <= value && column_max >= value
+            });
+        }
+
         let (op, value) = (expr.op(), &expr.literal_as_value());
         match op {
             // If the column range covers the value then it could contain that
@@ -1803,9 +2589,20 @@ impl MetaData {
             // if the column min is at least as small as value then the column
             // could contain the value.
             Operator::LTE => column_min <= value,
+
+            // The column range says nothing about which values match a regex,
+            // but a column of only NULL values can't match either operator.
+            Operator::RegexMatch | Operator::RegexNotMatch => !column_max.is_null(),
+
+            Operator::In => unreachable!("IN lists are handled above"),
         }
     }
 
+    /// Renames the column `old` to `new`, keeping its position.
+    pub(crate) fn rename_column(&mut self, old: &str, new: &str) {
+        rename_column_meta(&mut self.columns, &mut self.column_names, old, new);
+    }
+
     pub fn add_column(
         &mut self,
         name: &str,
@@ -1854,16 +2651,9 @@ impl MetaData {
             .map(|(name, agg_type)| {
                 let schema = self.columns.get(*name).unwrap();
 
-                // TODO(edd): this check happens because an aggregate does
-                // not have to have the same physical type as the logical
-                // type of the column it is aggregating on. An example of
-                // this is Count. I'm going to fix this by associated data
-                // types with the aggregate itself.
-                let physical_data_type = if let AggregateType::Count = agg_type {
-                    LogicalDataType::Unsigned
-                } else {
-                    schema.logical_data_type
-                };
+                // an aggregate does not have to have the same physical type
+                // as the logical type of the column it is aggregating on.
+                let physical_data_type = agg_type.result_data_type(schema.logical_data_type);
 
                 (schema.typ.clone(), *agg_type, physical_data_type)
             })
@@ -2016,6 +2806,12 @@ pub struct ReadAggregateResult<'row_group> {
     pub(crate) aggregate_cols: Vec<AggregateVec>,
 
     pub(crate) group_keys_sorted: bool,
+
+    // Whether an integer SUM aggregate overflowed and was saturated.
+    pub(crate) sum_overflowed: bool,
+
+    // The number of distinct groups beyond which grouping stops.
+    pub(crate) max_groups: Option<usize>,
 }
 
 impl<'row_group> ReadAggregateResult<'row_group> {
@@ -2053,6 +2849,11 @@ impl<'row_group> ReadAggregateResult<'row_group> {
         self.group_key_cols[0].len()
     }
 
+    // Whether `groups` distinct groups are more than permitted.
+    fn exceeds_max_groups(&self, groups: usize) -> bool {
+        matches!(self.max_groups, Some(max_groups) if groups > max_groups)
+    }
+
     // Is this result for a grouped aggregate?
     pub fn is_grouped_aggregate(&self) -> bool {
         !self.group_key_cols.is_empty()
@@ -2068,6 +2869,19 @@ impl<'row_group> ReadAggregateResult<'row_group> {
         self.group_key_cols.is_empty() || self.group_keys_sorted
     }
 
+    /// Determines if any integer SUM aggregate in the results overflowed, in
+    /// which case it holds a value saturated at the bounds of its type.
+    pub fn sum_overflowed(&self) -> bool {
+        self.sum_overflowed
+    }
+
+    /// Determines if any float SUM aggregate in the results is non-finite.
+    pub fn sum_non_finite(&self) -> bool {
+        self.aggregate_cols
+            .iter()
+            .any(|col| col.has_non_finite_sum())
+    }
+
     /// Merges `other` and self, returning a new set of results.
     pub fn merge(
         mut self,
@@ -2081,6 +2895,12 @@ impl<'row_group> ReadAggregateResult<'row_group> {
             return self;
         }
 
+        // Global aggregates have a single row and no group keys, so there is
+        // nothing to sort or compare.
+        if self.schema.group_columns.is_empty() {
+            return self.merge_global(other);
+        }
+
         // `read_aggregate` uses a variety of ways to generate results. It is
         // not safe to assume any particular ordering, so we will sort self and
         // other and do a merge.
@@ -2093,6 +2913,8 @@ impl<'row_group> ReadAggregateResult<'row_group> {
         }
 
         let mut result = Self::new(self.schema.clone());
+        result.sum_overflowed = self.sum_overflowed || other.sum_overflowed;
+
         // Allocate output grouping columns
         result
             .group_key_cols
@@ -2121,8 +2943,19 @@ impl<'row_group> ReadAggregateResult<'row_group> {
                 // we can do type checking on a column basis (once) rather than
                 // for each row. This allows us to extract an aggregate vec
                 // and an iterator of the same type to extend the aggregate vec.
-                for (col_i, (_, _, data_type)) in result.schema.aggregate_columns.iter().enumerate()
+                for (col_i, (_, agg_type, data_type)) in
+                    result.schema.aggregate_columns.iter().enumerate()
                 {
+                    // percentiles and standard deviations keep the state
+                    // needed to merge them with later results.
+                    if let AggregateType::Percentile(_) | AggregateType::StdDev = agg_type {
+                        let mut arr = other.aggregate_cols.remove(0);
+                        for i in other_i..arr.len() {
+                            result.aggregate_cols[col_i].push_from(&mut arr, i);
+                        }
+                        continue;
+                    }
+
                     match data_type {
                         LogicalDataType::Integer => {
                             let arr = other.aggregate_cols.remove(0);
@@ -2165,8 +2998,19 @@ impl<'row_group> ReadAggregateResult<'row_group> {
                 }
 
                 // add the rest of self's aggregate columns
-                for (col_i, (_, _, data_type)) in result.schema.aggregate_columns.iter().enumerate()
+                for (col_i, (_, agg_type, data_type)) in
+                    result.schema.aggregate_columns.iter().enumerate()
                 {
+                    // percentiles and standard deviations keep the state
+                    // needed to merge them with later results.
+                    if let AggregateType::Percentile(_) | AggregateType::StdDev = agg_type {
+                        let mut arr = self.aggregate_cols.remove(0);
+                        for i in self_i..arr.len() {
+                            result.aggregate_cols[col_i].push_from(&mut arr, i);
+                        }
+                        continue;
+                    }
+
                     match data_type {
                         LogicalDataType::Integer => {
                             let arr = self.aggregate_cols.remove(0);
@@ -2227,7 +3071,7 @@ impl<'row_group> ReadAggregateResult<'row_group> {
                         col.push(self.group_key_cols[col_i][self_i]);
                     }
                     for (col_i, col) in result.aggregate_cols.iter_mut().enumerate() {
-                        col.push(self.aggregate_cols[col_i].value(self_i));
+                        col.push_from(&mut self.aggregate_cols[col_i], self_i);
                     }
                     self_i += 1;
                 }
@@ -2239,30 +3083,23 @@ impl<'row_group> ReadAggregateResult<'row_group> {
 
                     // merge all the aggregates for this group key.
                     for (col_i, col) in result.aggregate_cols.iter_mut().enumerate() {
+                        let (_, agg_type, _) = &self.schema.aggregate_columns[col_i];
+                        if let AggregateType::Percentile(_) | AggregateType::StdDev = agg_type {
+                            col.push_from(&mut self.aggregate_cols[col_i], self_i);
+                            col.merge_from(
+                                col.len() - 1,
+                                &mut other.aggregate_cols[col_i],
+                                other_i,
+                            );
+                            continue;
+                        }
+
                         let self_value = self.aggregate_cols[col_i].value(self_i);
                         let other_value = other.aggregate_cols[col_i].value(other_i);
-                        let (_, agg_type, _) = &self.schema.aggregate_columns[col_i];
-                        col.push(match agg_type {
-                            AggregateType::Count => self_value + other_value,
-                            AggregateType::Min => match self_value.partial_cmp(&other_value) {
-                                Some(ord) => match ord {
-                                    Ordering::Less => self_value,
-                                    Ordering::Equal => self_value,
-                                    Ordering::Greater => other_value,
-                                },
-                                None => self_value,
-                            },
-                            AggregateType::Max => match self_value.partial_cmp(&other_value) {
-                                Some(ord) => match ord {
-                                    Ordering::Less => other_value,
-                                    Ordering::Equal => other_value,
-                                    Ordering::Greater => self_value,
-                                },
-                                None => self_value,
-                            },
-                            AggregateType::Sum => self_value + other_value,
-                            _ => unimplemented!("first/last not implemented"),
-                        });
+                        let (value, overflowed) =
+                            merge_aggregate_values(agg_type, self_value, other_value);
+                        result.sum_overflowed |= overflowed;
+                        col.push(value);
                     }
                     self_i += 1;
                     other_i += 1;
@@ -2273,7 +3110,7 @@ impl<'row_group> ReadAggregateResult<'row_group> {
                         col.push(other.group_key_cols[col_i][other_i]);
                     }
                     for (col_i, col) in result.aggregate_cols.iter_mut().enumerate() {
-                        col.push(other.aggregate_cols[col_i].value(other_i));
+                        col.push_from(&mut other.aggregate_cols[col_i], other_i);
                     }
                     other_i += 1;
                 }
@@ -2282,6 +3119,41 @@ impl<'row_group> ReadAggregateResult<'row_group> {
         result
     }
 
+    // Merges the single row of aggregates in `other` into the single row of
+    // aggregates in self. Only valid when there are no group columns.
+    fn merge_global(mut self, mut other: Self) -> ReadAggregateResult<'row_group> {
+        let mut sum_overflowed = self.sum_overflowed || other.sum_overflowed;
+        let aggregate_cols = self
+            .schema
+            .aggregate_columns
+            .iter()
+            .enumerate()
+            .map(|(col_i, (_, agg_type, data_type))| {
+                let mut col = AggregateVec::from((agg_type, data_type));
+                if let AggregateType::Percentile(_) | AggregateType::StdDev = agg_type {
+                    col.push_from(&mut self.aggregate_cols[col_i], 0);
+                    col.merge_from(0, &mut other.aggregate_cols[col_i], 0);
+                    return col;
+                }
+
+                let (value, overflowed) = merge_aggregate_values(
+                    agg_type,
+                    self.aggregate_cols[col_i].value(0),
+                    other.aggregate_cols[col_i].value(0),
+                );
+                sum_overflowed |= overflowed;
+                col.push(value);
+                col
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            aggregate_cols,
+            sum_overflowed,
+            ..self
+        }
+    }
+
     // Executes a mutable sort of the results based on the lexicographic order
     // of each group key columns.
     //
@@ -2354,6 +3226,43 @@ impl<'row_group> ReadAggregateResult<'row_group> {
     }
 }
 
+// Combines two aggregate values of the same aggregate type, returning the
+// combined value and whether combining SUM aggregates overflowed.
+fn merge_aggregate_values<'a>(
+    agg_type: &AggregateType,
+    self_value: Value<'a>,
+    other_value: Value<'a>,
+) -> (Value<'a>, bool) {
+    let value = match agg_type {
+        AggregateType::Count => self_value + other_value,
+        AggregateType::Min => match self_value.partial_cmp(&other_value) {
+            Some(ord) => match ord {
+                Ordering::Less => self_value,
+                Ordering::Equal => self_value,
+                Ordering::Greater => other_value,
+            },
+            None => self_value,
+        },
+        AggregateType::Max => match self_value.partial_cmp(&other_value) {
+            Some(ord) => match ord {
+                Ordering::Less => other_value,
+                Ordering::Equal => other_value,
+                Ordering::Greater => self_value,
+            },
+            None => self_value,
+        },
+        AggregateType::Sum => match (self_value, other_value) {
+            (Value::Scalar(a), Value::Scalar(b)) => {
+                let (sum, overflowed) = a.checked_sum(b);
+                return (Value::Scalar(sum), overflowed);
+            }
+            _ => panic!("unsupported operation on Value"),
+        },
+        _ => unimplemented!("first/last not implemented"),
+    };
+    (value, false)
+}
+
 // The `GroupKey` struct is a wrapper over a specific row of data in grouping
 // columns.
 //
@@ -2391,11 +3300,22 @@ impl TryFrom<ReadAggregateResult<'_>> for RecordBatch {
         let mut columns: Vec<Arc<dyn arrow::array::Array>> =
             Vec::with_capacity(result.schema.len());
 
-        for (_, data_type) in &result.schema.group_columns {
-            match data_type {
-                LogicalDataType::String => {
+        for (col_type, data_type) in &result.schema.group_columns {
+            // move column out of result
+            match (col_type, data_type) {
+                // tag columns are dictionary encoded in the result schema
+                (schema::ColumnType::Tag(_), LogicalDataType::String) => {
+                    columns.push(Arc::new(
+                        result
+                            .group_key_cols
+                            .remove(0)
+                            .into_iter()
+                            .collect::<array::DictionaryArray<arrow::datatypes::Int32Type>>(),
+                    ));
+                }
+                (_, LogicalDataType::String) => {
                     columns.push(Arc::new(array::StringArray::from(
-                        result.group_key_cols.remove(0), // move column out of result
+                        result.group_key_cols.remove(0),
                     )));
                 }
                 _ => panic!("only String currently supported as group column"),
@@ -2548,6 +3468,85 @@ mod test {
         Predicate::new(vec![expr])
     }
 
+    #[test]
+    fn predicate_normalize() {
+        let mut predicate = Predicate::new(vec![
+            BinaryExpr::from(("time", ">=", 100_i64)),
+            BinaryExpr::from(("region", "=", "west")),
+            BinaryExpr::from(("time", ">=", 50_i64)),
+            BinaryExpr::from(("time", "<", 500_i64)),
+            BinaryExpr::from(("time", "<=", 500_i64)),
+            BinaryExpr::from(("time", ">", 100_i64)),
+        ]);
+        predicate.normalize();
+
+        // the tightest lower and upper bounds replace the first bound of
+        // each kind
+        assert_eq!(
+            predicate,
+            Predicate::new(vec![
+                BinaryExpr::from(("time", ">", 100_i64)),
+                BinaryExpr::from(("region", "=", "west")),
+                BinaryExpr::from(("time", "<", 500_i64)),
+            ])
+        );
+        assert!(!predicate.is_contradictory());
+
+        // bounds with literals of different types are not merged
+        let mut predicate = Predicate::new(vec![
+            BinaryExpr::from(("counter", ">", 1_i64)),
+            BinaryExpr::from(("counter", ">", 2.0)),
+        ]);
+        predicate.normalize();
+        assert_eq!(predicate.expressions().len(), 2);
+    }
+
+    #[test]
+    fn predicate_is_contradictory() {
+        let cases = vec![
+            (Predicate::with_time_range(&[], 100, 50), true),
+            (Predicate::with_time_range(&[], 100, 100), true),
+            (Predicate::with_time_range(&[], 100, 101), false),
+            (
+                Predicate::new(vec![
+                    BinaryExpr::from(("time", ">=", 100_i64)),
+                    BinaryExpr::from(("time", "<=", 100_i64)),
+                ]),
+                false,
+            ),
+            (
+                Predicate::new(vec![
+                    BinaryExpr::from(("region", ">", "west")),
+                    BinaryExpr::from(("region", "<", "east")),
+                ]),
+                true,
+            ),
+            (
+                // bounds on different columns never contradict
+                Predicate::new(vec![
+                    BinaryExpr::from(("time", ">", 100_i64)),
+                    BinaryExpr::from(("counter", "<", 10_i64)),
+                ]),
+                false,
+            ),
+        ];
+
+        for (predicate, exp) in cases {
+            assert_eq!(predicate.is_contradictory(), exp, "{:?}", predicate);
+        }
+
+        // a contradictory predicate is not satisfied by any row group
+        let columns = vec![("time".to_string(), ColumnType::create_time(&[50, 100, 200]))];
+        let row_group = RowGroup::new(3, columns);
+        let table = crate::table::Table::with_row_group("cpu", row_group);
+        assert!(table.satisfies_predicate(&Predicate::with_time_range(&[], 50, 300)));
+        assert!(!table.satisfies_predicate(&Predicate::new(vec![
+            BinaryExpr::from(("time", ">=", 100_i64)),
+            BinaryExpr::from(("time", ">=", 50_i64)),
+            BinaryExpr::from(("time", "<", 100_i64)),
+        ])));
+    }
+
     #[test]
     fn size() {
         let mut columns = vec![];
@@ -2879,6 +3878,71 @@ north,6
         assert!(results.is_empty());
     }
 
+    #[test]
+    fn read_filter_column_comparison() {
+        let mut columns = vec![];
+        let tc = ColumnType::Time(Column::from(&[1_i64, 2, 3, 4, 5, 6][..]));
+        columns.push(("time".to_string(), tc));
+
+        let src = ColumnType::Tag(Column::from(
+            &[
+                Some("west"),
+                Some("west"),
+                Some("east"),
+                None,
+                Some("south"),
+                Some("north"),
+            ][..],
+        ));
+        columns.push(("src_region".to_string(), src));
+
+        let dst = ColumnType::Tag(Column::from(
+            &[
+                Some("west"),
+                Some("east"),
+                Some("east"),
+                None,
+                Some("north"),
+                Some("north"),
+            ][..],
+        ));
+        columns.push(("dst_region".to_string(), dst));
+
+        let row_group = RowGroup::new(6, columns);
+
+        // rows where either region is NULL never match
+        let predicate = Predicate::default().with_column_comparisons(vec![ColumnComparison::from(
+            ("src_region", "=", "dst_region"),
+        )]);
+        let results = row_group.read_filter(&["src_region", "dst_region", "time"], &predicate, &[]);
+        assert_eq!(
+            format!("{:?}", &results),
+            "src_region,dst_region,time
+west,west,1
+east,east,3
+north,north,6
+"
+        );
+
+        // comparisons are applied conjunctively with other expressions
+        let predicate = Predicate::with_time_range(&[], 2, 6).with_column_comparisons(vec![
+            ColumnComparison::from(("src_region", "!=", "dst_region")),
+        ]);
+        let results = row_group.read_filter(&["time"], &predicate, &[]);
+        assert_eq!(
+            format!("{:?}", &results),
+            "time
+2
+5
+"
+        );
+        assert!(!row_group.satisfies_predicate(
+            &Predicate::with_time_range(&[], 3, 5).with_column_comparisons(vec![
+                ColumnComparison::from(("src_region", "!=", "dst_region")),
+            ])
+        ));
+    }
+
     #[test]
     fn read_filter_dictionaries() {
         let row_group = _read_filter_setup();
@@ -3070,6 +4134,40 @@ POST,west,2
 
         // test row group queries that only group on one column.
         read_aggregate_single_groupby_column(&row_group);
+
+        // test that each path stops grouping once the group limit is exceeded.
+        read_aggregate_max_groups(&row_group);
+    }
+
+    fn read_aggregate_max_groups(row_group: &RowGroup) {
+        let cases = vec![
+            // all rows, all RLE
+            (Predicate::default(), vec!["region"], 4),
+            // u128 key
+            (Predicate::with_time_range(&[], 0, 7), vec!["region"], 4),
+            // vector key
+            (
+                Predicate::with_time_range(&[], 0, 7),
+                vec!["region", "method", "env", "letters", "numbers"],
+                6,
+            ),
+        ];
+
+        let aggs = vec![("counter", AggregateType::Sum)];
+        for (predicate, group_cols, groups) in cases {
+            let results =
+                row_group.read_aggregate_with_max_groups(&predicate, &group_cols, &aggs, Some(1));
+            assert_eq!(results.cardinality(), 2);
+            assert_eq!(results.rows(), 2);
+
+            let results = row_group.read_aggregate_with_max_groups(
+                &predicate,
+                &group_cols,
+                &aggs,
+                Some(groups),
+            );
+            assert_eq!(results.cardinality(), groups);
+        }
     }
 
     // the read_group path where grouping is on fewer than five columns.
@@ -3441,6 +4539,8 @@ west,POST,304,101,203
                 AggregateVec::Count(vec![Some(3), Some(4), Some(3), Some(1), Some(9)]),
             ],
             group_keys_sorted: false,
+            sum_overflowed: false,
+            max_groups: None,
         };
 
         // Debug implementation
@@ -3519,6 +4619,8 @@ west,host-d,11,9
                 AggregateVec::Count(vec![Some(3), Some(4), Some(3), Some(1), Some(9)]),
             ],
             group_keys_sorted: false,
+            sum_overflowed: false,
+            max_groups: None,
         };
 
         result.sort();
@@ -3744,7 +4846,7 @@ west,host-c,pro,10,6
 
         // No predicate - just find a value in each column that matches.
         let mut dst = BTreeSet::new();
-        row_group.column_names(&Predicate::default(), &[], Selection::All, &mut dst);
+        row_group.column_names(&Predicate::default(), &[], Selection::All, false, &mut dst);
         assert_eq!(
             dst,
             vec!["region", "temp", "time", "track"]
@@ -3759,6 +4861,7 @@ west,host-c,pro,10,6
             &Predicate::new(vec![BinaryExpr::from(("region", "=", "east"))]),
             &[],
             Selection::All,
+            false,
             &mut dst,
         );
         assert!(dst.is_empty());
@@ -3770,6 +4873,7 @@ west,host-c,pro,10,6
             &Predicate::new(vec![BinaryExpr::from(("track", "=", "place"))]),
             &[],
             Selection::All,
+            false,
             &mut dst,
         );
         // query matches one row.
@@ -3797,7 +4901,7 @@ west,host-c,pro,10,6
         columns.push(("time".to_string(), tc));
         let row_group = RowGroup::new(1, columns);
 
-        row_group.column_names(&Predicate::default(), &[], Selection::All, &mut dst);
+        row_group.column_names(&Predicate::default(), &[], Selection::All, false, &mut dst);
         assert_eq!(
             dst,
             vec!["env", "temp", "time", "track"]
@@ -3812,6 +4916,7 @@ west,host-c,pro,10,6
             &Predicate::default(),
             &[],
             Selection::Some(&["env"]),
+            false,
             &mut dst,
         );
         assert_eq!(
@@ -3825,6 +4930,7 @@ west,host-c,pro,10,6
             &Predicate::default(),
             &[],
             Selection::Some(&["temp"]),
+            false,
             &mut dst,
         );
         assert_eq!(
@@ -3949,7 +5055,7 @@ west,host-c,pro,10,6
 
         for (i, (filter_pred, negated_preds, projection, exp)) in cases.into_iter().enumerate() {
             let mut dst = BTreeSet::new();
-            row_group.column_names(&filter_pred, &negated_preds, projection, &mut dst);
+            row_group.column_names(&filter_pred, &negated_preds, projection, false, &mut dst);
             assert_eq!(
                 dst,
                 exp.into_iter().map(|s| s.to_owned()).collect(),
@@ -4105,4 +5211,60 @@ west,host-c,pro,10,6
             assert!(BinaryExpr::try_from(&input).is_err());
         }
     }
+
+    #[test]
+    fn predicate_try_from_expr() {
+        let cases = vec![
+            (
+                // region = 'west'
+                col("region").eq(lit("west")),
+                Predicate::new(vec![BinaryExpr::from(("region", "=", "west"))]),
+            ),
+            (
+                // time >= 100 AND (region = 'west' AND 10 > count)
+                col("time").gt_eq(lit(100_i64)).and(
+                    col("region")
+                        .eq(lit("west"))
+                        .and(lit(10_i64).gt(col("count"))),
+                ),
+                Predicate::new(vec![
+                    BinaryExpr::from(("time", ">=", 100_i64)),
+                    BinaryExpr::from(("region", "=", "west")),
+                    BinaryExpr::from(("count", "<", 10_i64)),
+                ]),
+            ),
+            (
+                // src != dst AND count > 2
+                col("src")
+                    .not_eq(col("dst"))
+                    .and(col("count").gt(lit(2_i64))),
+                Predicate::new(vec![BinaryExpr::from(("count", ">", 2_i64))])
+                    .with_column_comparisons(vec![ColumnComparison::new(
+                        "src",
+                        Operator::NotEqual,
+                        "dst",
+                    )]),
+            ),
+        ];
+
+        for (input, exp) in cases {
+            assert_eq!(Predicate::try_from_expr(&input).unwrap(), exp);
+        }
+
+        // Error cases
+        let cases = vec![
+            // region = 'west' OR region = 'east'
+            col("region")
+                .eq(lit("west"))
+                .or(col("region").eq(lit("east"))),
+            // count > 2 AND 33 = 33
+            col("count").gt(lit(2_i64)).and(lit(33_i64).eq(lit(33_i64))),
+            // a
+            col("a"),
+        ];
+
+        for input in cases {
+            assert!(Predicate::try_from_expr(&input).is_err());
+        }
+    }
 }
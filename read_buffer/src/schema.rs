@@ -195,26 +195,139 @@ pub enum AggregateType {
     Min,
     Max,
     Sum,
+    /// The value at the provided quantile, which must be within `[0, 1]`,
+    /// using the nearest-rank method.
+    Percentile(f64),
+    /// The sample standard deviation.
+    StdDev,
     /* TODO - support:
      * Distinct - (edd): not sure this counts as an aggregations. Seems more like a special
-     * filter. CountDistinct
-     * Percentile */
+     * filter. CountDistinct */
+}
+
+impl AggregateType {
+    /// Determines if the aggregate can only be applied to numerical columns.
+    pub fn requires_numeric_column(&self) -> bool {
+        matches!(self, Self::Percentile(_) | Self::StdDev)
+    }
+
+    /// The logical data type of the aggregate's results when applied to a
+    /// column with the provided logical data type.
+    pub fn result_data_type(&self, column_data_type: LogicalDataType) -> LogicalDataType {
+        match self {
+            Self::Count => LogicalDataType::Unsigned,
+            Self::Percentile(_) | Self::StdDev => LogicalDataType::Float,
+            _ => column_data_type,
+        }
+    }
 }
 
 impl std::fmt::Display for AggregateType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::Count => "count",
-                Self::First => "first",
-                Self::Last => "last",
-                Self::Min => "min",
-                Self::Max => "max",
-                Self::Sum => "sum",
-            }
-        )
+        match self {
+            Self::Count => write!(f, "count"),
+            Self::First => write!(f, "first"),
+            Self::Last => write!(f, "last"),
+            Self::Min => write!(f, "min"),
+            Self::Max => write!(f, "max"),
+            Self::Sum => write!(f, "sum"),
+            // include the quantile so that multiple percentiles of the same
+            // column are distinguishable.
+            Self::Percentile(quantile) => write!(f, "percentile_{}", quantile),
+            Self::StdDev => write!(f, "stddev"),
+        }
+    }
+}
+
+/// Determines how `SUM` aggregates behave when their result can't be
+/// represented, i.e., an integer sum that overflows or a float sum that is not
+/// finite.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SumOverflowPolicy {
+    /// Integer sums saturate at the bounds of their type and float sums keep
+    /// their non-finite value. Either case is recorded on the results.
+    Saturate,
+
+    /// Reading the results fails.
+    Error,
+}
+
+impl Default for SumOverflowPolicy {
+    fn default() -> Self {
+        Self::Saturate
+    }
+}
+
+/// Determines how column names in predicates and projections are resolved
+/// against the columns of a chunk.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ColumnNameMatching {
+    /// Column names must match exactly.
+    Exact,
+
+    /// Column names are matched ignoring ASCII case. Resolving a name that
+    /// matches more than one column is an error.
+    CaseInsensitive,
+}
+
+impl Default for ColumnNameMatching {
+    fn default() -> Self {
+        Self::Exact
+    }
+}
+
+/// Determines the order of the columns in the results of a read when an
+/// explicit set of columns is selected. Selecting all columns always returns
+/// them in the table's ingestion order.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ColumnOrder {
+    /// Columns are returned in the order they were selected.
+    Selection,
+
+    /// Columns are returned in the order they were first ingested into the
+    /// table, i.e., the order of the columns of the first record batch.
+    Ingestion,
+}
+
+impl Default for ColumnOrder {
+    fn default() -> Self {
+        Self::Selection
+    }
+}
+
+/// A fixed-width time window used to bucket rows by their timestamp.
+///
+/// Windows are half-open, covering `[start, start + every_ns)`, and are
+/// aligned such that every window start is `offset_ns` past a multiple of
+/// `every_ns` (relative to the epoch).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TimeWindow {
+    /// The width of each window in nanoseconds. Must be positive.
+    pub every_ns: i64,
+
+    /// The offset of window starts from a multiple of `every_ns`.
+    pub offset_ns: i64,
+}
+
+impl TimeWindow {
+    pub fn new(every_ns: i64, offset_ns: i64) -> Self {
+        Self {
+            every_ns,
+            offset_ns,
+        }
+    }
+
+    /// Returns the start of the window `timestamp` falls within.
+    ///
+    /// The start of a window beginning before `i64::MIN` is reported as
+    /// `i64::MIN`.
+    pub fn start(&self, timestamp: i64) -> i64 {
+        // Unlike `timestamp - offset_ns`, the difference of the remainders of
+        // both cannot overflow.
+        let distance = (timestamp.rem_euclid(self.every_ns)
+            - self.offset_ns.rem_euclid(self.every_ns))
+        .rem_euclid(self.every_ns);
+        timestamp.saturating_sub(distance)
     }
 }
 
@@ -241,6 +354,18 @@ impl ColumnType {
         }
     }
 
+    /// Returns a column type with the same semantic meaning as this one but
+    /// with the provided name.
+    pub fn with_name(&self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        match self {
+            Self::Tag(_) => Self::Tag(name),
+            Self::Field(_) => Self::Field(name),
+            Self::Timestamp(_) => Self::Timestamp(name),
+            Self::Other(_) => Self::Other(name),
+        }
+    }
+
     pub fn as_influxdb_type(&self) -> Option<data_types::partition_metadata::InfluxDbType> {
         use data_types::partition_metadata::InfluxDbType;
         match self {
@@ -266,3 +391,40 @@ impl Display for ColumnType {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn time_window_start() {
+        let window = TimeWindow::new(100, 0);
+        assert_eq!(window.start(0), 0);
+        assert_eq!(window.start(99), 0);
+        assert_eq!(window.start(100), 100);
+        assert_eq!(window.start(-1), -100);
+
+        let window = TimeWindow::new(100, 30);
+        assert_eq!(window.start(29), -70);
+        assert_eq!(window.start(30), 30);
+        assert_eq!(window.start(-1), -70);
+    }
+
+    #[test]
+    fn time_window_start_extremes() {
+        // the window containing i64::MIN + 100 starts at a multiple of 100
+        let window = TimeWindow::new(100, 0);
+        assert_eq!(window.start(i64::MIN + 100), -9_223_372_036_854_775_800);
+
+        // the window containing i64::MIN starts before it
+        assert_eq!(window.start(i64::MIN), i64::MIN);
+        assert_eq!(TimeWindow::new(100, 50).start(i64::MIN), i64::MIN);
+        assert_eq!(TimeWindow::new(i64::MAX, 1).start(i64::MIN), i64::MIN);
+
+        assert_eq!(window.start(i64::MAX), 9_223_372_036_854_775_800);
+        assert_eq!(
+            TimeWindow::new(100, -30).start(i64::MAX),
+            9_223_372_036_854_775_770
+        );
+    }
+}
@@ -1,18 +1,23 @@
 use crate::{
-    column,
-    row_group::{self, ColumnName, Literal, Predicate, RowGroup},
-    schema::{AggregateType, ColumnType, LogicalDataType, ResultSchema},
+    chunk::ColumnDecodeMetrics,
+    column::{self, cmp::Operator, RowIDs, RowIDsOption},
+    row_group::{self, ColumnComparison, ColumnName, Literal, Predicate, RowGroup},
+    schema::{
+        AggregateType, ColumnNameMatching, ColumnType, LogicalDataType, ResultSchema,
+        SumOverflowPolicy, TimeWindow,
+    },
     value::{OwnedValue, Scalar, Value},
     BinaryExpr,
 };
-use arrow::record_batch::RecordBatch;
+use arrow::{array::BooleanArray, compute::filter_record_batch, record_batch::RecordBatch};
 use data_types::{chunk_metadata::ChunkColumnSummary, partition_metadata::TableSummary};
+use itertools::Itertools;
 use parking_lot::RwLock;
-use schema::selection::Selection;
-use snafu::{ensure, Snafu};
+use schema::{selection::Selection, InfluxColumnType, InfluxFieldType, Schema};
+use snafu::{ensure, OptionExt, ResultExt, Snafu};
 use std::{
     collections::{BTreeMap, BTreeSet},
-    convert::TryInto,
+    convert::{TryFrom, TryInto},
     fmt::Display,
     sync::Arc,
 };
@@ -27,6 +32,40 @@ pub enum Error {
 
     #[snafu(display("unsupported column operation on column \"{}\": {}", column_name, msg))]
     UnsupportedColumnOperation { msg: String, column_name: String },
+
+    #[snafu(display(
+        "column name \"{}\" is ambiguous, it matches columns {}",
+        column_name,
+        candidates.join(", ")
+    ))]
+    AmbiguousColumnName {
+        column_name: String,
+        candidates: Vec<String>,
+    },
+
+    #[snafu(display("integer SUM aggregate overflowed"))]
+    SumOverflow {},
+
+    #[snafu(display("float SUM aggregate is not finite"))]
+    SumNonFinite {},
+
+    #[snafu(display("aggregate produced more than the maximum of {} groups", max_groups))]
+    TooManyGroups { max_groups: usize },
+
+    #[snafu(display("time window width must be positive, got {}ns", every_ns))]
+    InvalidTimeWindow { every_ns: i64 },
+
+    #[snafu(display("sample fraction must be between 0 and 1, got {}", fraction))]
+    InvalidSampleFraction { fraction: f64 },
+
+    #[snafu(display("percentile quantile must be between 0 and 1, got {}", quantile))]
+    InvalidPercentile { quantile: f64 },
+
+    #[snafu(display("column \"{}\" is not a tag, field or timestamp column", column_name))]
+    UnsupportedColumnType { column_name: String },
+
+    #[snafu(display("unable to convert results to a record batch: {}", source))]
+    RecordBatchConversion { source: row_group::Error },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -48,7 +87,8 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 /// The total size of a table is tracked and can be increased or reduced by
 /// adding or removing row groups for that table.
 ///
-/// Tables must contain at least one row group with at least one row.
+/// Tables must contain at least one row group with at least one row, unless
+/// they were explicitly created empty via `new_empty_like`.
 pub struct Table {
     name: String,
 
@@ -90,6 +130,32 @@ impl Table {
         }
     }
 
+    /// Create a new table with no row groups that has the same name and schema
+    /// as this table.
+    pub fn new_empty_like(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            table_data: RwLock::new(RowGroupData {
+                meta: Arc::new(self.table_data.read().meta.without_data()),
+                data: vec![],
+            }),
+        }
+    }
+
+    /// Create a new table with no row groups but with the provided schema.
+    ///
+    /// Returns an error if the schema contains columns that are not tags,
+    /// fields or the timestamp column.
+    pub fn with_schema(name: impl Into<String>, schema: &Schema) -> Result<Self> {
+        Ok(Self {
+            name: name.into(),
+            table_data: RwLock::new(RowGroupData {
+                meta: Arc::new(MetaData::from_schema(schema)?),
+                data: vec![],
+            }),
+        })
+    }
+
     /// Add a new row group to this table.
     pub fn add_row_group(&mut self, rg: RowGroup) {
         let mut row_groups = self.table_data.write();
@@ -123,11 +189,142 @@ impl Table {
         Ok(())
     }
 
+    /// Keeps only the rows whose entry in `keep` is `true`, where `keep` has
+    /// an entry for every row of the table in row group order. Row groups
+    /// losing rows are rebuilt and dropped if left without rows; all others
+    /// are left untouched.
+    ///
+    /// Returns an error if no row group would be left.
+    pub fn retain_rows(&mut self, keep: &BooleanArray) -> Result<()> {
+        let mut table_data = self.table_data.write();
+        let column_names = table_data
+            .meta
+            .column_names
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            keep.len(),
+            table_data.meta.rows as usize,
+            "one entry per row"
+        );
+
+        let mut data = Vec::with_capacity(table_data.data.len());
+        let mut offset = 0;
+        for rg in &table_data.data {
+            let rows = offset..offset + rg.rows() as usize;
+            offset = rows.end;
+            if rows.clone().all(|row| keep.value(row)) {
+                data.push(Arc::clone(rg));
+                continue;
+            }
+            let mask = rows
+                .map(|row| Some(keep.value(row)))
+                .collect::<BooleanArray>();
+
+            let result = rg.read_filter(&column_names, &Predicate::default(), &[]);
+            let rb = RecordBatch::try_from(result).context(RecordBatchConversionSnafu)?;
+            let rb = filter_record_batch(&rb, &mask).expect("mask matches row group length");
+            if rb.num_rows() > 0 {
+                data.push(Arc::new(RowGroup::from(rb)));
+            }
+        }
+
+        // Tables must always have at least one row group.
+        ensure!(!data.is_empty(), EmptyTableSnafu);
+
+        table_data.meta = Arc::new(MetaData::from(data.as_slice()));
+        table_data.data = data;
+        Ok(())
+    }
+
+    /// Consumes the table, producing one table per row group. Each new table
+    /// has the same name as this one. Row group data is shared, not copied.
+    pub fn split_by_row_group(self) -> Vec<Self> {
+        let name = self.name;
+        self.table_data
+            .into_inner()
+            .data
+            .into_iter()
+            .map(|rg| Self {
+                name: name.clone(),
+                table_data: RwLock::new(RowGroupData {
+                    meta: Arc::new(MetaData::new(&rg)),
+                    data: vec![rg],
+                }),
+            })
+            .collect()
+    }
+
+    /// Returns a new table with the same name, meta data and row groups as
+    /// this one. Row group data is shared, not copied, and row groups added
+    /// to or removed from either table afterwards are not visible to the
+    /// other.
+    pub fn fork(&self) -> Self {
+        let table_data = self.table_data.read();
+        Self {
+            name: self.name.clone(),
+            table_data: RwLock::new(RowGroupData {
+                meta: Arc::clone(&table_data.meta),
+                data: table_data.data.clone(),
+            }),
+        }
+    }
+
     /// The name of the table (equivalent to measurement or table name).
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Rename the table. Row group data and meta-data are left untouched.
+    pub fn rename(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+    }
+
+    /// Rename the column `old` to `new` in every row group and in the table's
+    /// meta-data. Column data is left untouched.
+    ///
+    /// Row groups shared with other readers, e.g., a forked table or an
+    /// in-flight read, are copied before being renamed, so those readers
+    /// continue to see the old name. Copies share their column data.
+    ///
+    /// It is the caller's responsibility to ensure `old` exists in the table
+    /// and `new` does not.
+    pub fn rename_column(&mut self, old: &str, new: &str) {
+        let mut table_data = self.table_data.write();
+        for rg in &mut table_data.data {
+            Arc::make_mut(rg).rename_column(old, new);
+        }
+
+        // `meta` can't be modified whilst protected by an Arc so clone it if
+        // it is shared.
+        let meta = Arc::make_mut(&mut table_data.meta);
+        row_group::rename_column_meta(&mut meta.columns, &mut meta.column_names, old, new);
+    }
+
+    /// Adds a column named `name` holding only NULL values to every row group
+    /// and to the table's meta-data. Existing column data is left untouched.
+    ///
+    /// Row groups shared with other readers are copied before the column is
+    /// added, so those readers do not see it. Copies share their column data.
+    ///
+    /// It is the caller's responsibility to ensure the table has at least one
+    /// row group, `name` does not exist in the table, and that `column_type`
+    /// is a tag or field column of a logical data type other than `Binary`.
+    pub fn append_null_column(
+        &mut self,
+        name: &str,
+        column_type: ColumnType,
+        data_type: LogicalDataType,
+    ) {
+        let mut table_data = self.table_data.write();
+        for rg in &mut table_data.data {
+            Arc::make_mut(rg).append_null_column(name, column_type.clone(), data_type);
+        }
+        table_data.meta = Arc::new(MetaData::from(table_data.data.as_slice()));
+    }
+
     /// Determines if this table contains no row groups.
     pub fn is_empty(&self) -> bool {
         self.table_data.read().data.is_empty()
@@ -217,6 +414,7 @@ impl Table {
             (OwnedValue::Scalar(Scalar::I64(min)), OwnedValue::Scalar(Scalar::I64(max))) => {
                 (min, max)
             }
+            (OwnedValue::Null, OwnedValue::Null) => return None, // empty table
             (min, max) => {
                 panic!(
                     "invalid range type for timestamp column: ({:?}, {:?})",
@@ -239,7 +437,110 @@ impl Table {
     /// predicate cannot be applied.
     pub fn validate_predicate(&self, predicate: Predicate) -> Result<Predicate, Error> {
         let table_data = self.table_data.read();
-        Ok(table_data.meta.validate_exprs(predicate)?.into())
+        table_data.meta.validate_predicate(predicate)
+    }
+
+    /// As `validate_predicate`, but reports an error for every expression in
+    /// the predicate that cannot be applied, rather than only the first.
+    pub fn validate_predicate_all(&self, predicate: &Predicate) -> Result<(), Vec<Error>> {
+        let table_data = self.table_data.read();
+        let meta = &table_data.meta;
+
+        let mut errors = meta
+            .validate_exprs_all(predicate.iter())
+            .err()
+            .unwrap_or_default();
+        errors.extend(
+            predicate
+                .column_comparisons()
+                .iter()
+                .filter_map(|cmp| meta.validate_column_comparison(cmp).err()),
+        );
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Resolves each of the provided column names to the name of a column in
+    /// the table according to `matching`. Names that do not match any column
+    /// are returned unchanged.
+    pub fn resolve_column_names(
+        &self,
+        names: &[ColumnName<'_>],
+        matching: ColumnNameMatching,
+    ) -> Result<Vec<String>> {
+        let table_data = self.table_data.read();
+        names
+            .iter()
+            .map(|name| {
+                table_data
+                    .meta
+                    .resolve_column_name(name, matching)
+                    .map(ToOwned::to_owned)
+            })
+            .collect()
+    }
+
+    /// Returns `names` ordered by the position of each column in the table,
+    /// which is the order the columns were ingested in. Names that are not
+    /// columns in the table are placed last, in their provided order.
+    pub fn order_column_names(&self, names: &[ColumnName<'_>]) -> Vec<String> {
+        let table_data = self.table_data.read();
+        let positions = &table_data.meta.column_names;
+
+        let mut names = names
+            .iter()
+            .map(|&name| name.to_owned())
+            .collect::<Vec<_>>();
+        names.sort_by_key(|name| {
+            positions
+                .iter()
+                .position(|column| column == name)
+                .unwrap_or(usize::MAX)
+        });
+        names
+    }
+
+    /// Rewrites the column names in the predicate's expressions to the names
+    /// of the table's columns according to `matching`.
+    pub fn resolve_predicate(
+        &self,
+        predicate: Predicate,
+        matching: ColumnNameMatching,
+    ) -> Result<Predicate> {
+        if matching == ColumnNameMatching::Exact {
+            return Ok(predicate);
+        }
+
+        let table_data = self.table_data.read();
+        let exprs = predicate
+            .iter()
+            .map(|expr| {
+                let column_name = table_data
+                    .meta
+                    .resolve_column_name(expr.column(), matching)?;
+                Ok(BinaryExpr::new(
+                    column_name,
+                    expr.op(),
+                    expr.literal().clone(),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let comparisons = predicate
+            .column_comparisons()
+            .iter()
+            .map(|cmp| {
+                let left = table_data.meta.resolve_column_name(cmp.left(), matching)?;
+                let right = table_data.meta.resolve_column_name(cmp.right(), matching)?;
+                Ok(ColumnComparison::new(left, cmp.op(), right))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Predicate::new(exprs).with_column_comparisons(comparisons))
     }
 
     /// Determines if one of more row groups in the `Table` could possibly
@@ -247,8 +548,8 @@ impl Table {
     pub fn could_pass_predicate(&self, predicate: &Predicate) -> bool {
         let table_data = self.table_data.read();
 
-        let predicate = match table_data.meta.validate_exprs(predicate.clone()) {
-            Ok(exprs) => Predicate::new(exprs),
+        let predicate = match table_data.meta.validate_predicate(predicate.clone()) {
+            Ok(predicate) => predicate,
             Err(_) => return false,
         };
 
@@ -264,6 +565,11 @@ impl Table {
         predicate: &Predicate,
         row_groups: Vec<Arc<RowGroup>>,
     ) -> Vec<Arc<RowGroup>> {
+        // no row group can satisfy an empty range
+        if predicate.is_contradictory() {
+            return vec![];
+        }
+
         let mut filtered_row_groups = Vec::with_capacity(row_groups.len());
 
         'rowgroup: for rg in row_groups.iter() {
@@ -300,13 +606,13 @@ impl Table {
         };
 
         // Determine if predicate can be applied to table.
-        let predicate: Predicate = meta.validate_exprs(predicate.clone())?.into();
+        let predicate: Predicate = meta.validate_predicate(predicate.clone())?;
 
         // Determine if the negated predicates (deletes) can be applied to the
         // table.
         let mut n_predicates: Vec<Predicate> = vec![];
         for pred in negated_predicates {
-            n_predicates.push(meta.validate_exprs(pred.clone())?.into());
+            n_predicates.push(meta.validate_predicate(pred.clone())?);
         }
 
         let schema = ResultSchema {
@@ -325,9 +631,138 @@ impl Table {
             negated_predicates: n_predicates,
             schema,
             row_groups,
+            decode_metrics: None,
+            remaining_limit: None,
+            remaining_offset: 0,
+        })
+    }
+
+    /// Returns an iterator over the values of the named column for the rows
+    /// satisfying the predicate. Row groups are read one at a time as the
+    /// iterator advances, and only the named column is decoded.
+    ///
+    /// The column **must** exist within the schema.
+    pub fn column_iter(&self, column_name: &str, predicate: &Predicate) -> Result<ColumnValues> {
+        let (meta, row_groups) = {
+            let table_data = self.table_data.read();
+            (Arc::clone(&table_data.meta), table_data.data.clone())
+        };
+
+        let predicate = meta.validate_predicate(predicate.clone())?;
+        let row_groups = self.filter_row_groups(&predicate, row_groups);
+
+        Ok(ColumnValues {
+            column_name: column_name.to_string(),
+            predicate,
+            row_groups: row_groups.into_iter(),
+            values: vec![].into_iter(),
         })
     }
 
+    /// Returns a pseudo-random sample of roughly `fraction` of the rows
+    /// satisfying the predicate and none of the negated predicates (deletes),
+    /// materialised as a single `RecordBatch`.
+    ///
+    /// Rows are sampled before their values are decoded. The sample is
+    /// deterministic for a given `seed` and set of row groups.
+    pub fn read_sample(
+        &self,
+        columns: &Selection<'_>,
+        predicate: &Predicate,
+        negated_predicates: &[Predicate],
+        fraction: f64,
+        seed: u64,
+    ) -> Result<RecordBatch> {
+        ensure!(
+            (0.0..=1.0).contains(&fraction),
+            InvalidSampleFractionSnafu { fraction }
+        );
+
+        let (meta, row_groups) = {
+            let table_data = self.table_data.read();
+            (Arc::clone(&table_data.meta), table_data.data.clone())
+        };
+
+        // Determine if predicate can be applied to table.
+        let predicate: Predicate = meta.validate_predicate(predicate.clone())?;
+
+        // Determine if the negated predicates (deletes) can be applied to the
+        // table.
+        let mut n_predicates: Vec<Predicate> = vec![];
+        for pred in negated_predicates {
+            n_predicates.push(meta.validate_predicate(pred.clone())?);
+        }
+
+        let schema = ResultSchema {
+            select_columns: match columns {
+                Selection::All => meta.schema_for_all_columns(),
+                Selection::Some(column_names) => meta.schema_for_column_names(column_names),
+            },
+            ..ResultSchema::default()
+        };
+        let column_names = schema
+            .select_column_names_iter()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>();
+        let arrow_schema: arrow::datatypes::SchemaRef = ::schema::Schema::try_from(&schema)
+            .map_err(|source| row_group::Error::SchemaConversion { source })
+            .context(RecordBatchConversionSnafu)?
+            .into();
+
+        let mut batches = vec![];
+        if !predicate.is_contradictory() {
+            for (idx, rg) in row_groups.iter().enumerate() {
+                if !rg.could_satisfy_conjunctive_binary_expressions(predicate.iter()) {
+                    continue;
+                }
+
+                // sample each row group independently of the others
+                let rg_seed = row_group::mix_seed(seed, idx as u64);
+                let result = rg.read_filter_sample(
+                    &column_names,
+                    &predicate,
+                    &n_predicates,
+                    fraction,
+                    rg_seed,
+                );
+                if result.is_empty() {
+                    continue;
+                }
+                batches.push(RecordBatch::try_from(result).context(RecordBatchConversionSnafu)?);
+            }
+        }
+
+        RecordBatch::concat(&arrow_schema, &batches)
+            .map_err(|source| row_group::Error::ArrowConversion { source })
+            .context(RecordBatchConversionSnafu)
+    }
+
+    /// Returns the ids of the rows satisfying the provided predicate, with one
+    /// entry per row group in the table. Row ids are local to their row
+    /// group, and row groups that cannot satisfy the predicate are
+    /// represented by an empty set.
+    pub fn row_ids_matching(&self, predicate: &Predicate) -> Result<Vec<RowIDs>> {
+        let (meta, row_groups) = {
+            let table_data = self.table_data.read();
+            (Arc::clone(&table_data.meta), table_data.data.clone())
+        };
+
+        // Determine if predicate can be applied to table.
+        let predicate: Predicate = meta.validate_predicate(predicate.clone())?;
+
+        Ok(row_groups
+            .iter()
+            .map(|rg| {
+                if predicate.is_contradictory()
+                    || !rg.could_satisfy_conjunctive_binary_expressions(predicate.iter())
+                {
+                    return RowIDs::new_bitmap();
+                }
+                rg.row_ids_matching(&predicate)
+            })
+            .collect())
+    }
+
     /// Returns an iterable collection of data in group columns and aggregate
     /// columns, optionally filtered by the provided predicate. Results are
     /// merged across all row groups within the table.
@@ -346,13 +781,24 @@ impl Table {
         //
         // TODO(edd): add delete support if/when aggregates can be pushed down.
         //
+        for (_, agg_type) in aggregates {
+            if let AggregateType::Percentile(quantile) = agg_type {
+                ensure!(
+                    (0.0..=1.0).contains(quantile),
+                    InvalidPercentileSnafu {
+                        quantile: *quantile
+                    }
+                );
+            }
+        }
+
         let (meta, row_groups) = {
             let table_data = self.table_data.read();
             (Arc::clone(&table_data.meta), table_data.data.clone())
         };
 
         // Determine if predicate can be applied to table.
-        let predicate: Predicate = meta.validate_exprs(predicate)?.into();
+        let predicate: Predicate = meta.validate_predicate(predicate)?;
 
         // Filter out any column names that we do not have data for.
         let schema = ResultSchema {
@@ -375,6 +821,28 @@ impl Table {
             )
         }
 
+        // Check all aggregate columns are valid for their aggregate.
+        for (name, agg_type) in aggregates {
+            if let Some(column) = meta.columns.get(*name) {
+                ensure!(
+                    !agg_type.requires_numeric_column()
+                        || matches!(
+                            column.logical_data_type,
+                            LogicalDataType::Integer
+                                | LogicalDataType::Unsigned
+                                | LogicalDataType::Float
+                        ),
+                    UnsupportedColumnOperationSnafu {
+                        msg: format!(
+                            "{} aggregate requires a numerical column, got {:?}",
+                            agg_type, column.logical_data_type
+                        ),
+                        column_name: name.to_string(),
+                    },
+                )
+            }
+        }
+
         // Filtered set of row groups
         let row_groups = self.filter_row_groups(&predicate, row_groups);
 
@@ -389,34 +857,30 @@ impl Table {
 
     /// Returns aggregates segmented by grouping keys and windowed by time.
     ///
-    /// The set of data to be aggregated may be filtered by (currently only)
-    /// equality predicates, but can be ranged by time, which should be
-    /// represented as nanoseconds since the epoch. Results are included if they
-    /// satisfy the predicate and fall with the [min, max) time range domain.
-    ///
-    /// Group keys are determined according to the provided group column names
-    /// (`group_columns`). Currently only grouping by string (tag key) columns
-    /// is supported.
-    ///
-    /// Required aggregates are specified via a tuple comprising a column name
-    /// and the type of aggregation required. Multiple aggregations can be
-    /// applied to the same column.
-    ///
-    /// Results are grouped and windowed according to the `window` parameter,
-    /// which represents an interval in nanoseconds. For example, to window
-    /// results by one minute, window should be set to 600_000_000_000.
-    pub fn aggregate_window<'a>(
+    /// As `read_aggregate`, but rows are additionally grouped by the time
+    /// window their timestamp falls within. The results are materialised as a
+    /// single `RecordBatch` whose first column, `time`, holds the start of
+    /// each window, followed by the group key columns and the aggregate
+    /// columns. Rows are ordered by window start and then group key, and
+    /// windows without any rows satisfying the predicate are omitted.
+    pub fn read_window_aggregate(
         &self,
-        _time_range: (i64, i64),
-        _predicates: &[(&str, &str)],
-        _group_columns: Vec<ColumnName<'a>>,
-        _aggregates: Vec<(ColumnName<'a>, AggregateType)>,
-        _window: i64,
-    ) -> BTreeMap<Vec<String>, Vec<(ColumnName<'a>, ReadAggregateResults)>> {
-        // identify segments where time range and predicates match could match
-        // using segment meta data, and then execute against those segments and
-        // merge results.
-        todo!()
+        predicate: Predicate,
+        window: TimeWindow,
+        group_columns: &Selection<'_>,
+        aggregates: &[(ColumnName<'_>, AggregateType)],
+    ) -> Result<ReadAggregateResults> {
+        ensure!(
+            window.every_ns > 0,
+            InvalidTimeWindowSnafu {
+                every_ns: window.every_ns
+            }
+        );
+
+        // validates the predicate and group columns, and prunes row groups.
+        let mut results = self.read_aggregate(predicate, group_columns, aggregates)?;
+        results.time_window = Some(window);
+        Ok(results)
     }
 
     //
@@ -473,13 +937,16 @@ impl Table {
     ///
     /// Optionally a predicate may be provided. In such a case only column names
     /// will be returned belonging to columns whom have at least one non-null
-    /// value for any row satisfying the predicate. Finally, the caller can
-    /// specify a set of column names to limit execution to only those.
+    /// value for any row satisfying the predicate, unless `include_null_only`
+    /// is set, in which case columns with only NULL values on those rows are
+    /// also returned. Finally, the caller can specify a set of column names to
+    /// limit execution to only those.
     pub fn column_names(
         &self,
         predicate: &Predicate,
         negated_predicates: &[Predicate],
         columns: Selection<'_>,
+        include_null_only: bool,
         mut dst: BTreeSet<String>,
     ) -> Result<BTreeSet<String>> {
         let (meta, row_groups) = {
@@ -494,13 +961,13 @@ impl Table {
         }
 
         // Determine if predicate can be applied to table.
-        let predicate: Predicate = meta.validate_exprs(predicate.clone())?.into();
+        let predicate: Predicate = meta.validate_predicate(predicate.clone())?;
 
         // Determine if the negated predicates (deletes) can be applied to the
         // table.
         let mut n_predicates: Vec<Predicate> = vec![];
         for pred in negated_predicates {
-            n_predicates.push(meta.validate_exprs(pred.clone())?.into());
+            n_predicates.push(meta.validate_predicate(pred.clone())?);
         }
 
         // Filter set of row groups to process using predicate.
@@ -508,7 +975,13 @@ impl Table {
 
         // Execute against each row group
         for row_group in row_groups {
-            row_group.column_names(&predicate, negated_predicates, columns, &mut dst);
+            row_group.column_names(
+                &predicate,
+                negated_predicates,
+                columns,
+                include_null_only,
+                &mut dst,
+            );
         }
 
         Ok(dst)
@@ -519,20 +992,35 @@ impl Table {
     /// predicate. All values are deduplicated across row groups in the table.
     ///
     /// If the predicate is empty then all distinct values are returned.
+    ///
+    /// If `max_values` is set then at most that many values are returned for
+    /// each column, and columns with further distinct values are reported as
+    /// truncated. Row groups stop being read once every column present in the
+    /// table is truncated.
     pub fn column_values<'a>(
         &'a self,
         predicate: &Predicate,
         columns: &[ColumnName<'_>],
         mut dst: BTreeMap<String, BTreeSet<String>>,
-    ) -> Result<BTreeMap<String, BTreeSet<String>>> {
+        max_values: Option<usize>,
+    ) -> Result<ColumnValuesResult> {
         // TODO(edd): add delete support
         let (meta, row_groups) = {
             let table_data = self.table_data.read();
             (Arc::clone(&table_data.meta), table_data.data.clone())
         };
 
+        // Resolve the selection to the distinct columns present in the table,
+        // which is the set of columns `schema_for_column_names` describes.
+        let columns = columns
+            .iter()
+            .copied()
+            .unique()
+            .filter(|&name| meta.columns.contains_key(name))
+            .collect::<Vec<_>>();
+
         // Validate that only supported columns present in `columns`.
-        for (name, (ct, _)) in columns.iter().zip(meta.schema_for_column_names(columns)) {
+        for (name, (ct, _)) in columns.iter().zip(meta.schema_for_column_names(&columns)) {
             ensure!(
                 matches!(ct, ColumnType::Tag(_)),
                 UnsupportedColumnOperationSnafu {
@@ -543,17 +1031,87 @@ impl Table {
         }
 
         // Determine if predicate can be applied.
-        let predicate: Predicate = meta.validate_exprs(predicate.clone())?.into();
+        let predicate: Predicate = meta.validate_predicate(predicate.clone())?;
 
         // Filter set of row groups to process using predicate.
         let row_groups = self.filter_row_groups(&predicate, row_groups);
 
-        // Execute against each row group
+        // Execute against each row group, stopping once every selected column
+        // present in the table has been truncated.
+        let mut truncated = BTreeSet::new();
         for row_group in row_groups {
-            dst = row_group.column_values(&predicate, columns, dst)
+            if columns.iter().all(|&name| truncated.contains(name)) {
+                break;
+            }
+            row_group.column_values_limited(
+                &predicate,
+                &columns,
+                max_values,
+                &mut dst,
+                &mut truncated,
+            );
         }
 
-        Ok(dst)
+        Ok(ColumnValuesResult {
+            values: dst,
+            truncated,
+        })
+    }
+
+    /// Determines if `column` contains `value` on at least one row.
+    ///
+    /// String columns consult each row group's dictionary, which only requires
+    /// scanning rows for plain dictionary-encoded columns holding `value`. For
+    /// other columns `value` is parsed as the column's type and the rows are
+    /// scanned; a value that cannot be parsed is never contained.
+    pub fn contains_value(&self, column: ColumnName<'_>, value: &str) -> Result<bool> {
+        let (meta, row_groups) = {
+            let table_data = self.table_data.read();
+            (Arc::clone(&table_data.meta), table_data.data.clone())
+        };
+
+        let logical_data_type = match meta.columns.get(column) {
+            Some(column_meta) => column_meta.logical_data_type,
+            None => {
+                return UnsupportedColumnOperationSnafu {
+                    column_name: column.to_owned(),
+                    msg: "column does not exist",
+                }
+                .fail()
+            }
+        };
+
+        let literal = match logical_data_type {
+            LogicalDataType::String => {
+                return Ok(row_groups.iter().any(|row_group| {
+                    row_group
+                        .column_contains_string_value(column, value)
+                        .unwrap_or(false)
+                }))
+            }
+            LogicalDataType::Integer => value.parse().ok().map(Literal::Integer),
+            LogicalDataType::Unsigned => value.parse().ok().map(Literal::Unsigned),
+            LogicalDataType::Float => value.parse().ok().map(Literal::Float),
+            LogicalDataType::Boolean => value.parse().ok().map(Literal::Boolean),
+            LogicalDataType::Binary => {
+                return UnsupportedColumnOperationSnafu {
+                    column_name: column.to_owned(),
+                    msg: "contains_value is not supported on binary columns",
+                }
+                .fail()
+            }
+        };
+
+        Ok(match literal {
+            Some(literal) => {
+                let predicate =
+                    Predicate::new(vec![BinaryExpr::new(column, Operator::Equal, literal)]);
+                row_groups
+                    .iter()
+                    .any(|row_group| row_group.satisfies_predicate(&predicate))
+            }
+            None => false,
+        })
     }
 
     /// Determines if this table contains one or more rows that satisfy the
@@ -566,11 +1124,16 @@ impl Table {
         };
 
         // Determine if predicate can be applied.
-        let predicate: Predicate = match meta.validate_exprs(predicate.clone()) {
-            Ok(exprs) => exprs.into(),
+        let mut predicate = match meta.validate_predicate(predicate.clone()) {
+            Ok(predicate) => predicate,
             Err(_) => return false,
         };
 
+        predicate.normalize();
+        if predicate.is_contradictory() {
+            return false;
+        }
+
         // if the table doesn't have a column for one of the predicate's
         // expressions then the table cannot satisfy the predicate.
         if !predicate
@@ -596,6 +1159,17 @@ impl Table {
             .flatten()
             .collect()
     }
+
+    /// The storage statistics of the named column in each row group holding
+    /// it.
+    pub(crate) fn column_storage_statistics_for(&self, name: &str) -> Vec<column::Statistics> {
+        let table_data = self.table_data.read();
+        table_data
+            .data
+            .iter()
+            .filter_map(|rg| rg.column_storage_statistics_for(name))
+            .collect()
+    }
 }
 
 /// Table level MetaData
@@ -629,6 +1203,76 @@ impl MetaData {
         }
     }
 
+    /// Returns meta data for the provided schema that describes no rows.
+    fn from_schema(schema: &Schema) -> Result<Self> {
+        let mut columns = BTreeMap::new();
+        let mut column_names = Vec::with_capacity(schema.len());
+
+        for (influx_type, field) in schema.iter() {
+            let name = field.name().to_owned();
+            let (typ, logical_data_type) = match influx_type {
+                Some(InfluxColumnType::Tag) => {
+                    (ColumnType::Tag(name.clone()), LogicalDataType::String)
+                }
+                Some(InfluxColumnType::Field(field_type)) => (
+                    ColumnType::Field(name.clone()),
+                    match field_type {
+                        InfluxFieldType::Float => LogicalDataType::Float,
+                        InfluxFieldType::Integer => LogicalDataType::Integer,
+                        InfluxFieldType::UInteger => LogicalDataType::Unsigned,
+                        InfluxFieldType::String => LogicalDataType::String,
+                        InfluxFieldType::Boolean => LogicalDataType::Boolean,
+                    },
+                ),
+                Some(InfluxColumnType::Timestamp) => (
+                    ColumnType::Timestamp(name.clone()),
+                    LogicalDataType::Integer,
+                ),
+                None => return UnsupportedColumnTypeSnafu { column_name: name }.fail(),
+            };
+
+            let meta = row_group::ColumnMeta {
+                typ,
+                logical_data_type,
+                range: (OwnedValue::new_null(), OwnedValue::new_null()),
+                null_count: 0,
+                distinct_count: None,
+            };
+            columns.insert(name.clone(), meta);
+            column_names.push(name);
+        }
+
+        Ok(Self {
+            rgs_size: 0,
+            rows: 0,
+            columns,
+            column_names,
+        })
+    }
+
+    /// Returns a copy of the meta data that retains the schema but describes
+    /// no rows.
+    fn without_data(&self) -> Self {
+        Self {
+            rgs_size: 0,
+            rows: 0,
+            columns: self
+                .columns
+                .iter()
+                .map(|(name, meta)| {
+                    let meta = row_group::ColumnMeta {
+                        range: (OwnedValue::new_null(), OwnedValue::new_null()),
+                        null_count: 0,
+                        distinct_count: None,
+                        ..meta.clone()
+                    };
+                    (name.clone(), meta)
+                })
+                .collect(),
+            column_names: self.column_names.clone(),
+        }
+    }
+
     /// Returns the estimated size in bytes of the `MetaData` struct and all of
     /// the row group data associated with a `Table`.
     fn size(&self) -> usize {
@@ -648,8 +1292,11 @@ impl MetaData {
         let other_meta = rg.metadata();
 
         // first non-empty row group added to the table.
-        if this.columns.is_empty() {
-            this.rgs_size = rg.size();
+        if this.rows == 0 {
+            // an empty table may already have a schema, which must match.
+            assert!(this.columns.is_empty() || this.columns == other_meta.columns);
+
+            this.rgs_size += rg.size();
             this.rows = rg.rows() as u64;
             this.columns = other_meta.columns.clone();
             this.column_names = other_meta.column_names.clone();
@@ -684,13 +1331,15 @@ impl MetaData {
 
     /// Extract schema information for a set of columns. If a column name does
     /// not exist within the `Table` schema it is ignored and not present within
-    /// the resulting schema information.
+    /// the resulting schema information. Column names provided more than once
+    /// are only included once, at the position of their first occurrence.
     pub fn schema_for_column_names(
         &self,
         names: &[ColumnName<'_>],
     ) -> Vec<(ColumnType, LogicalDataType)> {
         names
             .iter()
+            .unique()
             .filter_map(|&name| {
                 self.columns
                     .get(name)
@@ -719,16 +1368,10 @@ impl MetaData {
             .iter()
             .filter_map(|(name, agg_type)| {
                 self.columns.get(*name).map(|schema| {
-                    // TODO(edd): this check happens because an aggregate does
-                    // not have to have the same physical type as the logical
-                    // type of the column it is aggregating on. An example of
-                    // this is Count. I'm going to fix this by associated data
-                    // types with the aggregate itself.
-                    let physical_data_type = if let AggregateType::Count = agg_type {
-                        LogicalDataType::Unsigned
-                    } else {
-                        schema.logical_data_type
-                    };
+                    // an aggregate does not have to have the same physical
+                    // type as the logical type of the column it is
+                    // aggregating on.
+                    let physical_data_type = agg_type.result_data_type(schema.logical_data_type);
 
                     (schema.typ.clone(), *agg_type, physical_data_type)
                 })
@@ -748,40 +1391,129 @@ impl MetaData {
         iter: impl IntoIterator<Item = BinaryExpr>,
     ) -> Result<Vec<BinaryExpr>, Error> {
         iter.into_iter().try_fold(vec![], |mut arr, expr| {
-            match self.columns.get(expr.column()) {
-                Some(col_meta) => match (col_meta.logical_data_type, expr.literal()) {
-                    (LogicalDataType::Integer, Literal::Integer(_))
-                    | (LogicalDataType::Unsigned, Literal::Unsigned(_))
-                    | (LogicalDataType::Float, Literal::Float(_))
-                    | (LogicalDataType::String, Literal::String(_))
-                    | (LogicalDataType::Binary, Literal::String(_))
-                    | (LogicalDataType::Boolean, Literal::Boolean(_)) => {
-                        arr.push(expr);
-                        Ok(arr)
-                    }
-                    _ => {
-                        return UnsupportedColumnOperationSnafu {
-                            column_name: expr.column().to_owned(),
-                            msg: format!(
-                                "cannot compare column type {} to expression literal {:?}",
-                                col_meta.logical_data_type,
-                                expr.literal(),
-                            ),
-                        }
-                        .fail()
-                    }
-                },
-                None => {
-                    return UnsupportedColumnOperationSnafu {
-                        column_name: expr.column().to_owned(),
-                        msg: "column does not exist",
-                    }
-                    .fail()
-                }
-            }
+            self.validate_expr(&expr)?;
+            arr.push(expr);
+            Ok(arr)
         })
     }
 
+    /// Determine, based on the table meta data, whether all of the predicate's
+    /// expressions and column comparisons can be applied, returning the
+    /// predicate if so.
+    pub fn validate_predicate(&self, predicate: Predicate) -> Result<Predicate, Error> {
+        for expr in predicate.iter() {
+            self.validate_expr(expr)?;
+        }
+        for cmp in predicate.column_comparisons() {
+            self.validate_column_comparison(cmp)?;
+        }
+        Ok(predicate)
+    }
+
+    /// As `validate_exprs`, but validates every expression rather than
+    /// stopping at the first one that cannot be applied, returning an error
+    /// for each expression that cannot be applied.
+    pub fn validate_exprs_all<'a>(
+        &self,
+        iter: impl IntoIterator<Item = &'a BinaryExpr>,
+    ) -> Result<(), Vec<Error>> {
+        let errors = iter
+            .into_iter()
+            .filter_map(|expr| self.validate_expr(expr).err())
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Determine whether the expression can be applied based on the column's
+    // logical data type.
+    fn validate_expr(&self, expr: &BinaryExpr) -> Result<(), Error> {
+        match self.columns.get(expr.column()) {
+            Some(col_meta) => {
+                let logical_data_type = col_meta.logical_data_type;
+                let supported = match (expr.op(), expr.literal()) {
+                    // regexes can only be matched against string columns
+                    (Operator::RegexMatch | Operator::RegexNotMatch, Literal::Regex(_)) => {
+                        matches!(logical_data_type, LogicalDataType::String)
+                    }
+                    // every value in an IN list must have the column's type
+                    (Operator::In, Literal::List(values)) => values
+                        .iter()
+                        .all(|value| literal_has_type(value, logical_data_type)),
+                    (Operator::RegexMatch | Operator::RegexNotMatch | Operator::In, _) => false,
+                    (_, literal) => literal_has_type(literal, logical_data_type),
+                };
+
+                ensure!(
+                    supported,
+                    UnsupportedColumnOperationSnafu {
+                        column_name: expr.column().to_owned(),
+                        msg: format!(
+                            "cannot compare column type {} to expression literal {:?} using {}",
+                            logical_data_type,
+                            expr.literal(),
+                            expr.op(),
+                        ),
+                    }
+                );
+                Ok(())
+            }
+            None => UnsupportedColumnOperationSnafu {
+                column_name: expr.column().to_owned(),
+                msg: "column does not exist",
+            }
+            .fail(),
+        }
+    }
+
+    // Determine whether the comparison can be applied, which requires both
+    // columns to exist and to have the same comparable logical data type.
+    fn validate_column_comparison(&self, cmp: &ColumnComparison) -> Result<(), Error> {
+        let column_type = |name: ColumnName<'_>| {
+            self.columns
+                .get(name)
+                .map(|col_meta| col_meta.logical_data_type)
+                .context(UnsupportedColumnOperationSnafu {
+                    column_name: name,
+                    msg: "column does not exist",
+                })
+        };
+        let (left, right) = (column_type(cmp.left())?, column_type(cmp.right())?);
+
+        if matches!(
+            cmp.op(),
+            Operator::RegexMatch | Operator::RegexNotMatch | Operator::In
+        ) {
+            return UnsupportedColumnOperationSnafu {
+                column_name: cmp.left().to_owned(),
+                msg: format!("cannot compare columns using {}", cmp.op()),
+            }
+            .fail();
+        }
+
+        match (left, right) {
+            (LogicalDataType::Integer, LogicalDataType::Integer)
+            | (LogicalDataType::Unsigned, LogicalDataType::Unsigned)
+            | (LogicalDataType::Float, LogicalDataType::Float)
+            | (LogicalDataType::String, LogicalDataType::String)
+            | (LogicalDataType::Boolean, LogicalDataType::Boolean) => Ok(()),
+            _ => UnsupportedColumnOperationSnafu {
+                column_name: cmp.left().to_owned(),
+                msg: format!(
+                    "cannot compare column type {} to column {} of type {}",
+                    left,
+                    cmp.right(),
+                    right,
+                ),
+            }
+            .fail(),
+        }
+    }
+
     pub fn to_summary(&self, table_name: impl Into<String>) -> TableSummary {
         use data_types::partition_metadata::{ColumnSummary, StatValues, Statistics};
         let columns = self
@@ -848,6 +1580,51 @@ impl MetaData {
     pub fn has_column(&self, name: &str) -> bool {
         self.columns.contains_key(name)
     }
+
+    /// Returns the name of the column that `name` refers to according to
+    /// `matching`. An exact match is always preferred; otherwise an error is
+    /// returned if `name` case-insensitively matches more than one column.
+    /// If no column matches then `name` is returned as is.
+    pub fn resolve_column_name<'a>(
+        &'a self,
+        name: &'a str,
+        matching: ColumnNameMatching,
+    ) -> Result<&'a str> {
+        if matching == ColumnNameMatching::Exact || self.columns.contains_key(name) {
+            return Ok(name);
+        }
+
+        let candidates = self
+            .column_names
+            .iter()
+            .filter(|column_name| column_name.eq_ignore_ascii_case(name))
+            .sorted()
+            .collect::<Vec<_>>();
+
+        match candidates.as_slice() {
+            [] => Ok(name),
+            [column_name] => Ok(column_name.as_str()),
+            _ => AmbiguousColumnNameSnafu {
+                column_name: name,
+                candidates: candidates.into_iter().cloned().collect::<Vec<_>>(),
+            }
+            .fail(),
+        }
+    }
+}
+
+// Returns true if a single literal can be compared to values of the logical
+// data type.
+fn literal_has_type(literal: &Literal, logical_data_type: LogicalDataType) -> bool {
+    matches!(
+        (logical_data_type, literal),
+        (LogicalDataType::Integer, Literal::Integer(_))
+            | (LogicalDataType::Unsigned, Literal::Unsigned(_))
+            | (LogicalDataType::Float, Literal::Float(_))
+            | (LogicalDataType::String, Literal::String(_))
+            | (LogicalDataType::Binary, Literal::String(_))
+            | (LogicalDataType::Boolean, Literal::Boolean(_))
+    )
 }
 
 // Create statistics for the specified data type with no values
@@ -887,6 +1664,12 @@ impl From<&[Arc<RowGroup>]> for MetaData {
 
 /// Results of a `read_filter` execution on the table. Execution is lazy -
 /// row groups are only queried when `ReadFilterResults` is iterated.
+///
+/// Each row group that has not yet been iterated is kept alive by these
+/// results, even if it is removed from the table in the meantime. A row group
+/// is released as soon as its `RecordBatch` has been produced, and no decoded
+/// data is retained between calls to `next`. Callers that stop iterating early
+/// should drop the results to release the remaining row groups.
 pub struct ReadFilterResults {
     // schema of all columns in the query results
     schema: ResultSchema,
@@ -897,6 +1680,26 @@ pub struct ReadFilterResults {
     predicate: Predicate,
 
     negated_predicates: Vec<Predicate>,
+
+    // records column decode times as row groups are read, if set.
+    decode_metrics: Option<ColumnDecodeMetrics>,
+
+    // the number of rows that may still be returned, if limited.
+    remaining_limit: Option<u64>,
+
+    // the number of matching rows still to be skipped.
+    remaining_offset: u64,
+}
+
+impl std::fmt::Debug for ReadFilterResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadFilterResults")
+            .field("schema", &self.schema)
+            .field("predicate", &self.predicate)
+            .field("negated_predicates", &self.negated_predicates)
+            .field("row_groups", &self.row_groups.len())
+            .finish()
+    }
 }
 
 impl ReadFilterResults {
@@ -914,6 +1717,23 @@ impl ReadFilterResults {
         &self.schema
     }
 
+    /// Records the time taken to decode each column into `decode_metrics` as
+    /// the results are iterated.
+    pub(crate) fn with_decode_metrics(mut self, decode_metrics: ColumnDecodeMetrics) -> Self {
+        self.decode_metrics = Some(decode_metrics);
+        self
+    }
+
+    /// Skips the first `offset` matching rows and stops returning results once
+    /// `limit` rows have been returned. Only the matching row ids of row
+    /// groups within the offset are counted, row groups beyond the limit are
+    /// never read, and only the rows within either boundary are materialised.
+    pub fn with_limit(mut self, limit: Option<u64>, offset: u64) -> Self {
+        self.remaining_limit = limit;
+        self.remaining_offset = offset;
+        self
+    }
+
     // useful for testing - materialise all results but don't convert them to
     // record batches. Skips any row groups that don't have any results
     fn row_group_results(&self) -> Vec<row_group::ReadFilterResult<'_>> {
@@ -941,27 +1761,129 @@ impl Iterator for ReadFilterResults {
     type Item = RecordBatch;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.is_empty() {
-            return None;
-        }
+        let select_columns = self
+            .schema
+            .select_column_names_iter()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>();
 
-        let row_group = self.row_groups.remove(0);
-        let result = row_group.read_filter(
-            &self
-                .schema()
-                .select_column_names_iter()
-                .map(|name| name.as_str())
-                .collect::<Vec<_>>(),
-            &self.predicate,
-            &self.negated_predicates,
-        );
+        loop {
+            if self.remaining_limit == Some(0) {
+                // release any row groups that will no longer be read.
+                self.row_groups.clear();
+            }
+
+            if self.row_groups.is_empty() {
+                return None;
+            }
+
+            let row_group = self.row_groups.remove(0);
+            let row_ids = row_group.read_filter_row_ids(&self.predicate, &self.negated_predicates);
+            let rows = match &row_ids {
+                RowIDsOption::None(_) => 0,
+                RowIDsOption::Some(row_ids) => row_ids.len() as u64,
+                RowIDsOption::All(_) => row_group.rows() as u64,
+            };
+
+            // skip row groups with no matching rows or whose matching rows
+            // all fall within the offset without materialising them.
+            if rows == 0 {
+                continue;
+            } else if self.remaining_offset >= rows {
+                self.remaining_offset -= rows;
+                continue;
+            }
 
-        if result.is_empty() {
-            return self.next(); // try next row group
+            let offset = std::mem::take(&mut self.remaining_offset);
+            let len = match &mut self.remaining_limit {
+                Some(limit) => {
+                    let len = (*limit).min(rows - offset);
+                    *limit -= len;
+                    len
+                }
+                None => rows - offset,
+            };
+            let row_ids = match len < rows {
+                true => slice_row_ids(row_ids, offset as usize, len as usize),
+                false => row_ids,
+            };
+
+            let result =
+                row_group.read_filter_rows(&select_columns, row_ids, self.decode_metrics.as_ref());
+            assert_eq!(result.schema(), &self.schema); // validate schema
+            let batch: RecordBatch = result.try_into().unwrap();
+            return Some(batch);
         }
+    }
+}
+
+// Restricts a set of row ids to `len` rows, starting with the `offset`-th
+// row id.
+fn slice_row_ids(row_ids: RowIDsOption, offset: usize, len: usize) -> RowIDsOption {
+    let row_ids = match row_ids {
+        RowIDsOption::None(_) => return RowIDsOption::new_none(),
+        RowIDsOption::Some(row_ids) => row_ids.iter().skip(offset).take(len).collect(),
+        RowIDsOption::All(_) => (offset as u32..(offset + len) as u32).collect(),
+    };
+    RowIDsOption::Some(RowIDs::Vector(row_ids))
+}
+
+/// The distinct values of a set of columns produced by `column_values`.
+#[derive(Debug, Default, PartialEq)]
+pub struct ColumnValuesResult {
+    /// The distinct non-null values of each column.
+    pub values: BTreeMap<String, BTreeSet<String>>,
 
-        assert_eq!(result.schema(), self.schema()); // validate schema
-        Some(result.try_into().unwrap())
+    /// The columns with more distinct values than the requested maximum.
+    /// Only the maximum number of values are returned for these columns.
+    pub truncated: BTreeSet<String>,
+}
+
+impl ColumnValuesResult {
+    /// Returns true if not all distinct values of `column` were returned.
+    pub fn is_truncated(&self, column: &str) -> bool {
+        self.truncated.contains(column)
+    }
+}
+
+/// The values of a single column produced by `column_iter`. Execution is
+/// lazy - each row group is only read once all values of the previous row
+/// group have been consumed.
+pub struct ColumnValues {
+    column_name: String,
+    predicate: Predicate,
+
+    // row groups that have not yet been read.
+    row_groups: std::vec::IntoIter<Arc<RowGroup>>,
+
+    // values of the most recently read row group not yet yielded.
+    values: std::vec::IntoIter<OwnedValue>,
+}
+
+impl std::fmt::Debug for ColumnValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ColumnValues")
+            .field("column_name", &self.column_name)
+            .field("predicate", &self.predicate)
+            .field("row_groups", &self.row_groups.len())
+            .finish()
+    }
+}
+
+impl Iterator for ColumnValues {
+    type Item = OwnedValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.values.next() {
+                return Some(value);
+            }
+
+            let row_group = self.row_groups.next()?;
+            self.values = row_group
+                .column_values_matching(&self.column_name, &self.predicate)
+                .into_iter();
+        }
     }
 }
 
@@ -999,9 +1921,38 @@ pub struct ReadAggregateResults {
     // aggregates to produce are determined by the `schema`.
     row_groups: Vec<Arc<RowGroup>>,
 
+    // how SUM aggregates that overflow or are non-finite are handled.
+    sum_overflow_policy: SumOverflowPolicy,
+
+    // whether any integer SUM aggregate overflowed.
+    sum_overflowed: bool,
+
+    // whether any float SUM aggregate was non-finite.
+    sum_non_finite: bool,
+
+    // whether groups are returned in ascending order of their group keys.
+    sort_groups: bool,
+
+    // the maximum number of distinct groups the results may contain.
+    max_groups: Option<usize>,
+
+    // the time windows rows are additionally grouped into, if any.
+    time_window: Option<TimeWindow>,
+
     drained: bool, // currently this iterator only yields once.
 }
 
+impl std::fmt::Debug for ReadAggregateResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadAggregateResults")
+            .field("schema", &self.schema)
+            .field("predicate", &self.predicate)
+            .field("row_groups", &self.row_groups.len())
+            .field("time_window", &self.time_window)
+            .finish()
+    }
+}
+
 impl ReadAggregateResults {
     /// Returns the schema associated with table result and therefore all of
     /// results from row groups.
@@ -1009,6 +1960,151 @@ impl ReadAggregateResults {
         &self.schema
     }
 
+    /// Sets how SUM aggregates that overflow or are non-finite are handled.
+    /// By default integer sums saturate and float sums keep non-finite values.
+    pub fn with_sum_overflow_policy(mut self, policy: SumOverflowPolicy) -> Self {
+        self.sum_overflow_policy = policy;
+        self
+    }
+
+    /// Returns groups sorted in ascending lexicographic order of their group
+    /// key columns. By default groups are returned in an unspecified order,
+    /// which avoids sorting results that span a single row group.
+    pub fn with_sorted_groups(mut self, sorted: bool) -> Self {
+        self.sort_groups = sorted;
+        self
+    }
+
+    /// Limits the number of distinct groups the results may contain. Once
+    /// more groups than `max_groups` have been discovered, within a row group
+    /// or when merging row groups, no further rows are aggregated and an error
+    /// is returned.
+    pub fn with_max_groups(mut self, max_groups: usize) -> Self {
+        self.max_groups = Some(max_groups);
+        self
+    }
+
+    /// Determines if an integer SUM aggregate overflowed in the results read
+    /// so far. Such a sum is saturated at the bounds of its type.
+    pub fn sum_overflowed(&self) -> bool {
+        self.sum_overflowed
+    }
+
+    /// Determines if a float SUM aggregate was non-finite in the results read
+    /// so far.
+    pub fn sum_non_finite(&self) -> bool {
+        self.sum_non_finite
+    }
+
+    /// Materialises the results as a single `RecordBatch` comprising the
+    /// group key columns followed by the aggregate columns, preceded by the
+    /// `time` column for results grouped into time windows.
+    ///
+    /// If no rows satisfy the predicate an empty `RecordBatch` with the
+    /// result schema is returned.
+    pub fn into_record_batch(mut self) -> Result<RecordBatch> {
+        if let Some(window) = self.time_window {
+            return self.window_record_batch(window);
+        }
+
+        let policy = self.sum_overflow_policy;
+        let max_groups = self.max_groups;
+        let schema = self.schema.clone();
+
+        let result = match self.next_merged_result() {
+            Some(result) if !result.is_empty() => result,
+            _ => {
+                let schema = ::schema::Schema::try_from(&schema)
+                    .map_err(|source| row_group::Error::SchemaConversion { source })
+                    .context(RecordBatchConversionSnafu)?;
+                return Ok(RecordBatch::new_empty(schema.into()));
+            }
+        };
+
+        if let Some(max_groups) = max_groups {
+            ensure!(
+                result.cardinality() <= max_groups,
+                TooManyGroupsSnafu { max_groups }
+            );
+        }
+
+        if policy == SumOverflowPolicy::Error {
+            ensure!(!result.sum_overflowed(), SumOverflowSnafu);
+            ensure!(!result.sum_non_finite(), SumNonFiniteSnafu);
+        }
+
+        RecordBatch::try_from(result).context(RecordBatchConversionSnafu)
+    }
+
+    // Materialises the results grouped by `window` as a single record batch
+    // with a leading `time` column holding the start of each window.
+    fn window_record_batch(&mut self, window: TimeWindow) -> Result<RecordBatch> {
+        self.drained = true;
+
+        let mut schema = self.schema.clone();
+        schema.select_columns = vec![(
+            ColumnType::Timestamp(row_group::TIME_COLUMN_NAME.to_string()),
+            LogicalDataType::Integer,
+        )];
+        let schema: arrow::datatypes::SchemaRef = ::schema::Schema::try_from(&schema)
+            .map_err(|source| row_group::Error::SchemaConversion { source })
+            .context(RecordBatchConversionSnafu)?
+            .into();
+
+        let group_column_names = self
+            .schema
+            .group_column_names_iter()
+            .map(|s| s.as_str())
+            .collect::<Vec<_>>();
+        let aggregates = self
+            .schema
+            .aggregate_columns
+            .iter()
+            .map(|(name, agg_type, _)| (name.as_str(), *agg_type))
+            .collect::<Vec<_>>();
+
+        // merge the results for each window across all row groups.
+        let mut windows: BTreeMap<i64, row_group::ReadAggregateResult<'_>> = BTreeMap::new();
+        for row_group in &self.row_groups {
+            let row_group_windows = row_group.read_window_aggregate(
+                &self.predicate,
+                &window,
+                &group_column_names,
+                &aggregates,
+            );
+
+            for (start, result) in row_group_windows {
+                let merged = match windows.remove(&start) {
+                    Some(merged) => merged.merge(result),
+                    None => result,
+                };
+                windows.insert(start, merged);
+            }
+        }
+
+        let batches = windows
+            .into_iter()
+            .map(|(start, mut result)| {
+                result.sort();
+                let batch = RecordBatch::try_from(result).context(RecordBatchConversionSnafu)?;
+
+                let mut columns: Vec<arrow::array::ArrayRef> =
+                    vec![Arc::new(arrow::array::TimestampNanosecondArray::from_vec(
+                        vec![start; batch.num_rows()],
+                        None,
+                    ))];
+                columns.extend(batch.columns().iter().cloned());
+                RecordBatch::try_new(Arc::clone(&schema), columns)
+                    .map_err(|source| row_group::Error::ArrowConversion { source })
+                    .context(RecordBatchConversionSnafu)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        RecordBatch::concat(&schema, &batches)
+            .map_err(|source| row_group::Error::ArrowConversion { source })
+            .context(RecordBatchConversionSnafu)
+    }
+
     // Logic to get next result merged across all row groups for the table is
     // pulled out so we can decouple this from materialising record batches,
     // which means we're not forced to use record batches in tests.
@@ -1017,26 +2113,36 @@ impl ReadAggregateResults {
             return None;
         }
 
-        let mut merged_results = self.row_groups.get(0).unwrap().read_aggregate(
-            &self.predicate,
-            &self
-                .schema
-                .group_column_names_iter()
-                .map(|s| s.as_str())
-                .collect::<Vec<_>>(),
-            &self
-                .schema
-                .aggregate_columns
-                .iter()
-                .map(|(name, agg_type, _)| (name.as_str(), *agg_type))
-                .collect::<Vec<_>>(),
-        );
+        let mut merged_results = self
+            .row_groups
+            .get(0)
+            .unwrap()
+            .read_aggregate_with_max_groups(
+                &self.predicate,
+                &self
+                    .schema
+                    .group_column_names_iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>(),
+                &self
+                    .schema
+                    .aggregate_columns
+                    .iter()
+                    .map(|(name, agg_type, _)| (name.as_str(), *agg_type))
+                    .collect::<Vec<_>>(),
+                self.max_groups,
+            );
         assert_eq!(merged_results.schema(), self.schema()); // validate schema
 
         // Execute against remaining row groups, merging each into the merged
         // set.
         for row_group in self.row_groups.iter().skip(1) {
-            let result = row_group.read_aggregate(
+            // stop aggregating once the group limit has been exceeded
+            if matches!(self.max_groups, Some(max) if merged_results.cardinality() > max) {
+                break;
+            }
+
+            let result = row_group.read_aggregate_with_max_groups(
                 &self.predicate,
                 &self
                     .schema
@@ -1049,6 +2155,7 @@ impl ReadAggregateResults {
                     .iter()
                     .map(|(name, agg_type, _)| (name.as_str(), *agg_type))
                     .collect::<Vec<_>>(),
+                self.max_groups,
             );
 
             if result.is_empty() {
@@ -1060,6 +2167,12 @@ impl ReadAggregateResults {
             merged_results = merged_results.merge(result);
         }
 
+        if self.sort_groups {
+            merged_results.sort();
+        }
+
+        self.sum_overflowed |= merged_results.sum_overflowed();
+        self.sum_non_finite |= merged_results.sum_non_finite();
         self.drained = true;
         Some(merged_results)
     }
@@ -1075,12 +2188,44 @@ impl ReadAggregateResults {
 /// Given that, it's expected that this iterator will only iterate once, but
 /// perhaps in the future we will break the work up and send intermediate
 /// results back.
+///
+/// An error is returned if a SUM aggregate overflows or is non-finite and the
+/// `SumOverflowPolicy::Error` policy has been set, or if the results contain
+/// more groups than permitted by `with_max_groups`.
+///
+/// Results grouped into time windows, see `Table::read_window_aggregate`, are
+/// yielded as a single batch with a leading `time` column.
 impl Iterator for ReadAggregateResults {
-    type Item = RecordBatch;
+    type Item = Result<RecordBatch>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next_merged_result()
-            .map(|merged_result| merged_result.try_into().unwrap())
+        if let Some(window) = self.time_window {
+            if self.row_groups.is_empty() || self.drained {
+                return None;
+            }
+            return Some(self.window_record_batch(window));
+        }
+
+        let policy = self.sum_overflow_policy;
+        let max_groups = self.max_groups;
+        let merged_result = self.next_merged_result()?;
+
+        if let Some(max_groups) = max_groups {
+            if merged_result.cardinality() > max_groups {
+                return Some(TooManyGroupsSnafu { max_groups }.fail());
+            }
+        }
+
+        if policy == SumOverflowPolicy::Error {
+            if merged_result.sum_overflowed() {
+                return Some(SumOverflowSnafu.fail());
+            }
+            if merged_result.sum_non_finite() {
+                return Some(SumNonFiniteSnafu.fail());
+            }
+        }
+
+        Some(Ok(merged_result.try_into().unwrap()))
     }
 }
 
@@ -1107,7 +2252,10 @@ impl std::fmt::Display for DisplayReadAggregateResults<'_> {
 
 #[cfg(test)]
 mod test {
-    use arrow::array::BooleanArray;
+    use arrow::{
+        array::{BooleanArray, DictionaryArray, Int64Array},
+        datatypes::Int32Type,
+    };
     use data_types::partition_metadata::{StatValues, Statistics};
 
     use super::*;
@@ -1230,6 +2378,47 @@ mod test {
         }
     }
 
+    #[test]
+    fn meta_validate_column_comparisons() {
+        let time = ColumnType::Time(Column::from(&[1_i64][..]));
+        let src = ColumnType::Tag(Column::from(&["west"][..]));
+        let dst = ColumnType::Tag(Column::from(&["east"][..]));
+        let col_a = ColumnType::Field(Column::from(&[1_i64][..]));
+        let col_b = ColumnType::Field(Column::from(&[1_u64][..]));
+
+        let columns = vec![
+            ("time".to_string(), time),
+            ("src_region".to_string(), src),
+            ("dst_region".to_string(), dst),
+            ("i64_col".to_string(), col_a),
+            ("u64_col".to_string(), col_b),
+        ];
+        let table = Table::with_row_group("cpu", RowGroup::new(1, columns));
+
+        let valid = vec![
+            ColumnComparison::from(("src_region", "=", "dst_region")),
+            ColumnComparison::from(("time", ">", "i64_col")),
+        ];
+        for cmp in valid {
+            let predicate = Predicate::default().with_column_comparisons(vec![cmp]);
+            assert!(table.validate_predicate(predicate).is_ok());
+        }
+
+        let invalid = vec![
+            ColumnComparison::from(("src_region", "=", "i64_col")),
+            ColumnComparison::from(("i64_col", "<", "u64_col")),
+            ColumnComparison::from(("src_region", "=", "region")),
+        ];
+        for cmp in invalid {
+            let predicate = Predicate::default().with_column_comparisons(vec![cmp]);
+            assert!(table.validate_predicate(predicate.clone()).is_err());
+            assert_eq!(
+                table.validate_predicate_all(&predicate).unwrap_err().len(),
+                1
+            );
+        }
+    }
+
     #[test]
     fn meta_data_update_with_null() {
         let columns = vec![
@@ -1310,6 +2499,32 @@ mod test {
             .expect_err("drop_row_group should have returned an error");
     }
 
+    #[test]
+    fn read_filter_results_drop() {
+        let time =
+            |values: &[i64]| vec![("time".to_string(), ColumnType::Time(Column::from(values)))];
+        let mut table = Table::with_row_group("cpu", RowGroup::new(2, time(&[1, 2])));
+        table.add_row_group(RowGroup::new(2, time(&[3, 4])));
+        table.add_row_group(RowGroup::new(2, time(&[5, 6])));
+
+        let row_groups = table.table_data.read().data.clone();
+        let ref_counts = || row_groups.iter().map(Arc::strong_count).collect::<Vec<_>>();
+        assert_eq!(ref_counts(), vec![2, 2, 2]); // table and `row_groups`
+
+        let mut results = table
+            .read_filter(&Selection::All, &Predicate::default(), &[])
+            .unwrap();
+        assert_eq!(ref_counts(), vec![3, 3, 3]);
+
+        // a row group is released once it has been read
+        results.next().unwrap();
+        assert_eq!(ref_counts(), vec![2, 3, 3]);
+
+        // dropping early releases the remaining row groups
+        drop(results);
+        assert_eq!(ref_counts(), vec![2, 2, 2]);
+    }
+
     #[test]
     fn column_sizes() {
         let tc = ColumnType::Time(Column::from(&[10_i64, 20, 30][..]));
@@ -1554,6 +2769,112 @@ mod test {
         );
     }
 
+    #[test]
+    fn read_aggregate_sum_overflow() {
+        let columns = vec![
+            ("time".to_string(), ColumnType::create_time(&[1, 2])),
+            (
+                "region".to_string(),
+                ColumnType::create_tag(&["west", "west"]),
+            ),
+            (
+                "counter".to_string(),
+                ColumnType::Field(Column::from(&[i64::MAX - 10, 20][..])),
+            ),
+            (
+                "temp".to_string(),
+                ColumnType::Field(Column::from(&[f64::MAX, f64::MAX / 2.0][..])),
+            ),
+        ];
+        let rg = RowGroup::new(2, columns);
+        let mut table = Table::with_row_group("cpu", rg);
+
+        let columns = vec![
+            ("time".to_string(), ColumnType::create_time(&[3])),
+            ("region".to_string(), ColumnType::create_tag(&["west"])),
+            (
+                "counter".to_string(),
+                ColumnType::Field(Column::from(&[10_i64][..])),
+            ),
+            (
+                "temp".to_string(),
+                ColumnType::Field(Column::from(&[1.0_f64][..])),
+            ),
+        ];
+        let rg = RowGroup::new(1, columns);
+        table.add_row_group(rg);
+
+        // Overflow is detected with and without grouping, and with and without
+        // a predicate, which exercise different aggregation paths.
+        let cases = vec![
+            (
+                Predicate::default(),
+                &[][..],
+                "counter_sum\n9223372036854775807\n",
+            ),
+            (
+                Predicate::default(),
+                &["region"][..],
+                "region,counter_sum\nwest,9223372036854775807\n",
+            ),
+            (
+                Predicate::new(vec![BinaryExpr::from(("time", ">", 0_i64))]),
+                &["region"][..],
+                "region,counter_sum\nwest,9223372036854775807\n",
+            ),
+        ];
+
+        for (predicate, group_columns, exp) in cases {
+            // by default the sum saturates and the overflow is recorded.
+            let mut results = table
+                .read_aggregate(
+                    predicate.clone(),
+                    &Selection::Some(group_columns),
+                    &[("counter", AggregateType::Sum)],
+                )
+                .unwrap();
+            assert!(!results.sum_overflowed());
+            assert_eq!(
+                DisplayReadAggregateResults(vec![results.next_merged_result().unwrap()])
+                    .to_string(),
+                exp,
+            );
+            assert!(results.sum_overflowed());
+
+            let mut results = table
+                .read_aggregate(
+                    predicate,
+                    &Selection::Some(group_columns),
+                    &[("counter", AggregateType::Sum)],
+                )
+                .unwrap()
+                .with_sum_overflow_policy(SumOverflowPolicy::Error);
+            assert!(matches!(results.next(), Some(Err(Error::SumOverflow {}))));
+        }
+
+        // non-finite float sums are recorded, and optionally reported.
+        let mut results = table
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&[]),
+                &[("temp", AggregateType::Sum)],
+            )
+            .unwrap();
+        assert!(results.next().unwrap().is_ok());
+        assert!(results.sum_non_finite());
+        assert!(!results.sum_overflowed());
+
+        let mut results = table
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&[]),
+                &[("temp", AggregateType::Sum)],
+            )
+            .unwrap()
+            .with_sum_overflow_policy(SumOverflowPolicy::Error);
+        assert!(matches!(results.next(), Some(Err(Error::SumNonFinite {}))));
+    }
+
     #[test]
     fn read_aggregate_no_groups() {
         // Build first row group.
@@ -1642,6 +2963,213 @@ mod test {
         ),);
     }
 
+    #[test]
+    fn read_aggregate_sorted_groups() {
+        let columns = vec![
+            (
+                "time".to_string(),
+                ColumnType::create_time(&[1, 2, 3, 4, 5, 6]),
+            ),
+            (
+                "region".to_string(),
+                ColumnType::create_tag(&["west", "east", "west", "north", "east", "west"]),
+            ),
+            (
+                "host".to_string(),
+                ColumnType::create_tag(&["b", "a", "a", "c", "a", "b"]),
+            ),
+            (
+                "counter".to_string(),
+                ColumnType::Field(Column::from(&[1_i64, 2, 3, 4, 5, 6][..])),
+            ),
+        ];
+        let rg = RowGroup::new(6, columns);
+        let mut table = Table::with_row_group("cpu", rg);
+
+        let columns = vec![
+            ("time".to_string(), ColumnType::create_time(&[7, 8])),
+            (
+                "region".to_string(),
+                ColumnType::create_tag(&["south", "east"]),
+            ),
+            ("host".to_string(), ColumnType::create_tag(&["a", "b"])),
+            (
+                "counter".to_string(),
+                ColumnType::Field(Column::from(&[7_i64, 8][..])),
+            ),
+        ];
+        let rg = RowGroup::new(2, columns);
+        table.add_row_group(rg);
+
+        let mut results = table
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&["region", "host"]),
+                &[("counter", AggregateType::Sum)],
+            )
+            .unwrap()
+            .with_sorted_groups(true);
+
+        assert_eq!(
+            DisplayReadAggregateResults(vec![results.next_merged_result().unwrap()]).to_string(),
+            "region,host,counter_sum
+east,a,7
+east,b,8
+north,c,4
+south,a,7
+west,a,3
+west,b,7
+",
+        );
+        assert!(matches!(results.next_merged_result(), None));
+
+        // sorting also applies to results from a single row group.
+        table.drop_row_group(1).unwrap();
+        let mut results = table
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&["region", "host"]),
+                &[("counter", AggregateType::Sum)],
+            )
+            .unwrap()
+            .with_sorted_groups(true);
+
+        assert_eq!(
+            DisplayReadAggregateResults(vec![results.next_merged_result().unwrap()]).to_string(),
+            "region,host,counter_sum
+east,a,7
+north,c,4
+west,a,3
+west,b,7
+",
+        );
+    }
+
+    #[test]
+    fn read_aggregate_into_record_batch() {
+        let columns = vec![
+            ("time".to_string(), ColumnType::create_time(&[1, 2, 3, 4])),
+            (
+                "region".to_string(),
+                ColumnType::create_tag(&["west", "east", "west", "east"]),
+            ),
+            (
+                "counter".to_string(),
+                ColumnType::Field(Column::from(&[1_i64, 2, 3, 4][..])),
+            ),
+        ];
+        let rg = RowGroup::new(4, columns);
+        let mut table = Table::with_row_group("cpu", rg);
+
+        let columns = vec![
+            ("time".to_string(), ColumnType::create_time(&[5])),
+            ("region".to_string(), ColumnType::create_tag(&["north"])),
+            (
+                "counter".to_string(),
+                ColumnType::Field(Column::from(&[5_i64][..])),
+            ),
+        ];
+        let rg = RowGroup::new(1, columns);
+        table.add_row_group(rg);
+
+        let results = table
+            .read_aggregate(
+                Predicate::default(),
+                &Selection::Some(&["region"]),
+                &[("counter", AggregateType::Sum)],
+            )
+            .unwrap()
+            .with_sorted_groups(true);
+        let schema = Schema::try_from(results.schema()).unwrap();
+        let rb = results.into_record_batch().unwrap();
+
+        let expected = RecordBatch::try_new(
+            schema.into(),
+            vec![
+                Arc::new(
+                    vec![Some("east"), Some("north"), Some("west")]
+                        .into_iter()
+                        .collect::<DictionaryArray<Int32Type>>(),
+                ),
+                Arc::new(Int64Array::from(vec![6, 5, 4])),
+            ],
+        )
+        .unwrap();
+        assert_eq!(rb, expected);
+
+        // no matching rows produces an empty batch with the same schema
+        let rb = table
+            .read_aggregate(
+                Predicate::new(vec![BinaryExpr::from(("time", ">", 100_i64))]),
+                &Selection::Some(&["region"]),
+                &[("counter", AggregateType::Sum)],
+            )
+            .unwrap()
+            .into_record_batch()
+            .unwrap();
+        assert_eq!(rb.num_rows(), 0);
+        assert_eq!(rb.schema(), expected.schema());
+    }
+
+    #[test]
+    fn read_aggregate_max_groups() {
+        let columns = vec![
+            ("time".to_string(), ColumnType::create_time(&[1, 2, 3])),
+            (
+                "region".to_string(),
+                ColumnType::create_tag(&["west", "east", "north"]),
+            ),
+            (
+                "counter".to_string(),
+                ColumnType::Field(Column::from(&[1_i64, 2, 3][..])),
+            ),
+        ];
+        let rg = RowGroup::new(3, columns);
+        let mut table = Table::with_row_group("cpu", rg);
+
+        let columns = vec![
+            ("time".to_string(), ColumnType::create_time(&[4, 5])),
+            (
+                "region".to_string(),
+                ColumnType::create_tag(&["south", "west"]),
+            ),
+            (
+                "counter".to_string(),
+                ColumnType::Field(Column::from(&[4_i64, 5][..])),
+            ),
+        ];
+        let rg = RowGroup::new(2, columns);
+        table.add_row_group(rg);
+
+        let read_aggregate = |max_groups| {
+            table
+                .read_aggregate(
+                    Predicate::default(),
+                    &Selection::Some(&["region"]),
+                    &[("counter", AggregateType::Sum)],
+                )
+                .unwrap()
+                .with_max_groups(max_groups)
+        };
+
+        // the limit is exceeded by the first row group
+        let mut results = read_aggregate(2);
+        assert!(matches!(
+            results.next(),
+            Some(Err(Error::TooManyGroups { max_groups: 2 }))
+        ));
+        assert!(results.next().is_none());
+
+        // the limit is only exceeded once row groups are merged
+        assert!(matches!(
+            read_aggregate(3).into_record_batch(),
+            Err(Error::TooManyGroups { max_groups: 3 })
+        ));
+
+        let rb = read_aggregate(4).into_record_batch().unwrap();
+        assert_eq!(rb.num_rows(), 4);
+    }
+
     #[test]
     fn read_aggregate_result_display() {
         let result_a = ReadAggregateResult {
@@ -1734,7 +3262,7 @@ west,host-b,100
 
         let mut dst: BTreeSet<String> = BTreeSet::new();
         dst = table
-            .column_names(&Predicate::default(), &[], Selection::All, dst)
+            .column_names(&Predicate::default(), &[], Selection::All, false, dst)
             .unwrap();
 
         assert_eq!(
@@ -1744,7 +3272,7 @@ west,host-b,100
 
         // re-run and get the same answer
         dst = table
-            .column_names(&Predicate::default(), &[], Selection::All, dst)
+            .column_names(&Predicate::default(), &[], Selection::All, false, dst)
             .unwrap();
         assert_eq!(
             dst.iter().cloned().collect::<Vec<_>>(),
@@ -1758,6 +3286,7 @@ west,host-b,100
                 &Predicate::new(vec![BinaryExpr::from(("time", ">=", 300_i64))]),
                 &[],
                 Selection::All,
+                false,
                 dst,
             )
             .unwrap();
@@ -1772,6 +3301,7 @@ west,host-b,100
                 &Predicate::new(vec![BinaryExpr::from(("time", ">=", 300_i64))]),
                 &[],
                 Selection::All,
+                false,
                 BTreeSet::new(),
             )
             .unwrap();
@@ -1786,6 +3316,7 @@ west,host-b,100
                 &Predicate::new(vec![BinaryExpr::from(("time", ">=", "not a number"))]),
                 &[],
                 Selection::All,
+                false,
                 dst,
             )
             .is_err());
@@ -2,6 +2,7 @@ use std::cmp::Ordering;
 use std::{convert::TryFrom, fmt::Formatter};
 use std::{mem::size_of, sync::Arc};
 
+use crate::column::encoding::scalar::SumAdd;
 use crate::{AggregateType, LogicalDataType};
 
 #[derive(Clone, PartialEq, Debug)]
@@ -46,6 +47,117 @@ pub enum AggregateVec {
     LastString((Vec<Option<String>>, Vec<Option<i64>>)),
     LastBytes((Vec<Option<Vec<u8>>>, Vec<Option<i64>>)),
     LastBool((Vec<Option<bool>>, Vec<Option<i64>>)),
+
+    // Percentiles retain the `(value, run_length)` tuples they were computed
+    // from, sorted by value, and standard deviations a running state, so that
+    // they can be merged across row groups before being finalised.
+    Percentile((f64, Vec<Option<ValueRuns>>)),
+    StdDev(Vec<Option<StdDevState>>),
+}
+
+/// Runs of `(value, run_length)` tuples.
+pub type ValueRuns = Vec<(f64, u32)>;
+
+// Appends `run_length` occurrences of `value` to `runs`, extending the last run
+// if it holds the same value.
+fn push_run(runs: &mut ValueRuns, value: f64, run_length: u32) {
+    if let Some((v, rl)) = runs.last_mut() {
+        if v.to_bits() == value.to_bits() {
+            *rl += run_length;
+            return;
+        }
+    }
+    runs.push((value, run_length));
+}
+
+// Sorts `runs` by value, combining runs of the same value.
+fn sort_runs(runs: &mut ValueRuns) {
+    runs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+    runs.dedup_by(|(v, rl), (prev_v, prev_rl)| {
+        let same = v.to_bits() == prev_v.to_bits();
+        if same {
+            *prev_rl += *rl;
+        }
+        same
+    });
+}
+
+// Merges the runs in `other` into `runs`, both of which must be sorted by
+// value.
+fn merge_sorted_runs(runs: &mut ValueRuns, other: ValueRuns) {
+    let mut merged = Vec::with_capacity(runs.len() + other.len());
+    let mut other = other.into_iter().peekable();
+    for (v, rl) in runs.drain(..) {
+        while let Some((other_v, other_rl)) = other.next_if(|(other_v, _)| *other_v < v) {
+            push_run(&mut merged, other_v, other_rl);
+        }
+        push_run(&mut merged, v, rl);
+    }
+    for (v, rl) in other {
+        push_run(&mut merged, v, rl);
+    }
+    *runs = merged;
+}
+
+// The value at `quantile` within `runs`, which must be sorted by value, using
+// the nearest-rank method, or `None` if there are no values.
+fn percentile(quantile: f64, runs: &[(f64, u32)]) -> Option<f64> {
+    let total: u64 = runs.iter().map(|(_, rl)| *rl as u64).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let rank = ((quantile * total as f64).ceil() as u64).max(1);
+    let mut seen = 0;
+    for (v, rl) in runs {
+        seen += *rl as u64;
+        if seen >= rank {
+            return Some(*v);
+        }
+    }
+    None
+}
+
+/// The running state of a standard deviation aggregate, which can be updated
+/// with runs of values and combined with other states.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct StdDevState {
+    count: u64,
+    mean: f64,
+    // sum of squared differences from the mean
+    m2: f64,
+}
+
+impl StdDevState {
+    /// Adds `run_length` occurrences of `value` to the state.
+    pub fn update(&mut self, value: f64, run_length: u32) {
+        self.merge(&Self {
+            count: run_length as u64,
+            mean: value,
+            m2: 0.0,
+        });
+    }
+
+    /// Combines `other` into the state, as if all of its values had been
+    /// added.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 +=
+            other.m2 + delta * delta * (self.count as f64 * other.count as f64) / count as f64;
+        self.count = count;
+    }
+
+    /// The sample standard deviation, which is undefined for fewer than two
+    /// values.
+    pub fn std_dev(&self) -> Option<f64> {
+        (self.count > 1).then(|| (self.m2 / (self.count - 1) as f64).sqrt())
+    }
 }
 
 impl AggregateVec {
@@ -79,6 +191,20 @@ impl AggregateVec {
             Self::LastString((arr, _)) => arr.len(),
             Self::LastBytes((arr, _)) => arr.len(),
             Self::LastBool((arr, _)) => arr.len(),
+            Self::Percentile((_, arr)) => arr.len(),
+            Self::StdDev(arr) => arr.len(),
+        }
+    }
+
+    // The finalised value of a percentile or standard deviation aggregate
+    // specified by `offset`.
+    fn finalised_f64(&self, offset: usize) -> Option<f64> {
+        match self {
+            Self::Percentile((quantile, arr)) => arr[offset]
+                .as_ref()
+                .and_then(|runs| percentile(*quantile, runs)),
+            Self::StdDev(arr) => arr[offset].and_then(|state| state.std_dev()),
+            _ => panic!("{} is not finalised", self),
         }
     }
 
@@ -101,6 +227,7 @@ impl AggregateVec {
             Self::MaxString(arr) => Value::from(arr[offset].as_deref()),
             Self::MaxBytes(arr) => Value::from(arr[offset].as_deref()),
             Self::MaxBool(arr) => Value::from(arr[offset]),
+            Self::Percentile(_) | Self::StdDev(_) => Value::from(self.finalised_f64(offset)),
             _ => unimplemented!("first/last not yet implemented"),
         }
     }
@@ -108,10 +235,13 @@ impl AggregateVec {
     /// Updates with a new value located in the provided input column help in
     /// `Values`.
     ///
+    /// Returns `true` if updating an integer SUM aggregate overflowed, in which
+    /// case the aggregate saturates at the bounds of its type.
+    ///
     /// Panics if the type of `Value` does not satisfy the aggregate type.
-    pub fn update(&mut self, values: &Values<'_>, row_id: usize, offset: usize) {
+    pub fn update(&mut self, values: &Values<'_>, row_id: usize, offset: usize) -> bool {
         if values.is_null(row_id) {
-            return;
+            return false;
         }
 
         match self {
@@ -128,7 +258,7 @@ impl AggregateVec {
                 }
 
                 match &mut arr[offset] {
-                    Some(v) => *v += values.value_i64(row_id),
+                    Some(v) => return v.sum_add(values.value_i64(row_id)),
                     None => arr[offset] = Some(values.value_i64(row_id)),
                 }
             }
@@ -138,7 +268,7 @@ impl AggregateVec {
                 }
 
                 match &mut arr[offset] {
-                    Some(v) => *v += values.value_u64(row_id),
+                    Some(v) => return v.sum_add(values.value_u64(row_id)),
                     None => arr[offset] = Some(values.value_u64(row_id)),
                 }
             }
@@ -292,9 +422,30 @@ impl AggregateVec {
                     None => arr[offset] = Some(values.value_bool(row_id)),
                 }
             }
+            Self::Percentile((_, arr)) => {
+                if offset >= arr.len() {
+                    arr.resize(offset + 1, None);
+                }
+
+                push_run(
+                    arr[offset].get_or_insert_with(Vec::new),
+                    values.value_as_f64(row_id),
+                    1,
+                );
+            }
+            Self::StdDev(arr) => {
+                if offset >= arr.len() {
+                    arr.resize(offset + 1, None);
+                }
+
+                arr[offset]
+                    .get_or_insert_with(StdDevState::default)
+                    .update(values.value_as_f64(row_id), 1);
+            }
             // TODO - implement first/last
             _ => unimplemented!("aggregate update not implemented"),
         }
+        false
     }
 
     /// Appends the provided value to the end of the aggregate vector.
@@ -500,6 +651,85 @@ impl AggregateVec {
                     arr.push(Some(value.bool()));
                 }
             }
+            Self::Percentile(_) | Self::StdDev(_) => {
+                panic!("cannot push a finalised value onto {}", self)
+            }
+        }
+    }
+
+    /// Appends an aggregate of the provided `(value, run_length)` tuples to
+    /// the end of a percentile or standard deviation aggregate vector.
+    ///
+    /// Panics for any other aggregate.
+    pub fn push_runs(&mut self, mut runs: ValueRuns) {
+        match self {
+            Self::Percentile((_, arr)) => {
+                sort_runs(&mut runs);
+                arr.push((!runs.is_empty()).then(|| runs));
+            }
+            Self::StdDev(arr) => {
+                if runs.is_empty() {
+                    arr.push(None);
+                } else {
+                    let mut state = StdDevState::default();
+                    for (v, rl) in runs {
+                        state.update(v, rl);
+                    }
+                    arr.push(Some(state));
+                }
+            }
+            _ => panic!("cannot push runs onto {}", self),
+        }
+    }
+
+    /// Sorts the runs retained by a percentile aggregate so that they can be
+    /// merged and finalised. Must be called once the aggregate has been
+    /// `update`d for the last time; a no-op for any other aggregate.
+    pub fn finalise(&mut self) {
+        if let Self::Percentile((_, arr)) = self {
+            for runs in arr.iter_mut().flatten() {
+                sort_runs(runs);
+            }
+        }
+    }
+
+    /// Appends the aggregate in `other` specified by `other_offset` to the
+    /// end of the aggregate vector, moving any state needed to merge it out
+    /// of `other`.
+    ///
+    /// Panics if `other` is not the same kind of aggregate vector.
+    pub fn push_from(&mut self, other: &mut Self, other_offset: usize) {
+        match (self, other) {
+            (Self::Percentile((_, arr)), Self::Percentile((_, other_arr))) => {
+                arr.push(other_arr[other_offset].take());
+            }
+            (Self::StdDev(arr), Self::StdDev(other_arr)) => {
+                arr.push(other_arr[other_offset]);
+            }
+            (this, other) => this.push(other.value(other_offset)),
+        }
+    }
+
+    /// Merges the percentile or standard deviation aggregate in `other`
+    /// specified by `other_offset` into the aggregate at `offset`, moving any
+    /// state out of `other`.
+    ///
+    /// Panics for any other aggregate.
+    pub fn merge_from(&mut self, offset: usize, other: &mut Self, other_offset: usize) {
+        match (self, other) {
+            (Self::Percentile((_, arr)), Self::Percentile((_, other_arr))) => {
+                if let Some(other_runs) = other_arr[other_offset].take() {
+                    merge_sorted_runs(arr[offset].get_or_insert_with(Vec::new), other_runs);
+                }
+            }
+            (Self::StdDev(arr), Self::StdDev(other_arr)) => {
+                if let Some(other_state) = &other_arr[other_offset] {
+                    arr[offset]
+                        .get_or_insert_with(StdDevState::default)
+                        .merge(other_state);
+                }
+            }
+            (this, _) => panic!("cannot merge state into {}", this),
         }
     }
 
@@ -619,6 +849,10 @@ impl AggregateVec {
                 Some(v) => write!(f, "{}", v)?,
                 None => write!(f, "NULL")?,
             },
+            Self::Percentile(_) | Self::StdDev(_) => match self.finalised_f64(offset) {
+                Some(v) => write!(f, "{}", v)?,
+                None => write!(f, "NULL")?,
+            },
         }
         Ok(())
     }
@@ -650,6 +884,9 @@ impl AggregateVec {
             Self::SumF64(arr) => arr,
             Self::MinF64(arr) => arr,
             Self::MaxF64(arr) => arr,
+            Self::Percentile(_) | Self::StdDev(_) => {
+                (0..self.len()).map(|i| self.finalised_f64(i)).collect()
+            }
             _ => panic!("cannot convert {} to Vec<Option<f64>>", self),
         }
     }
@@ -681,6 +918,15 @@ impl AggregateVec {
         }
     }
 
+    /// Determines if this is a float SUM aggregate holding a non-finite
+    /// (infinite or NaN) value.
+    pub fn has_non_finite_sum(&self) -> bool {
+        match self {
+            Self::SumF64(arr) => arr.iter().flatten().any(|v| !v.is_finite()),
+            _ => false,
+        }
+    }
+
     /// Extends the `AggregateVec` with the provided `Option<i64>` iterator.
     pub fn extend_with_i64(&mut self, itr: impl Iterator<Item = Option<i64>>) {
         match self {
@@ -869,6 +1115,12 @@ impl AggregateVec {
                 *arr = p.apply_slice(arr.as_slice());
                 *time = p.apply_slice(time.as_slice());
             }
+            Self::Percentile((_, arr)) => {
+                *arr = p.apply_slice(arr.as_slice());
+            }
+            Self::StdDev(arr) => {
+                *arr = p.apply_slice(arr.as_slice());
+            }
         }
     }
 }
@@ -904,6 +1156,8 @@ impl std::fmt::Display for AggregateVec {
             Self::LastString(_) => write!(f, "Last<String>"),
             Self::LastBytes(_) => write!(f, "Last<Vec<u8>>"),
             Self::LastBool(_) => write!(f, "Last<bool>"),
+            Self::Percentile((quantile, _)) => write!(f, "Percentile({})", quantile),
+            Self::StdDev(_) => write!(f, "StdDev"),
         }
     }
 }
@@ -940,6 +1194,8 @@ impl From<(&AggregateType, &LogicalDataType)> for AggregateVec {
             (AggregateType::Sum, LogicalDataType::Unsigned) => Self::SumU64(vec![]),
             (AggregateType::Sum, LogicalDataType::Float) => Self::SumF64(vec![]),
             (AggregateType::Sum, _) => unreachable!("unsupported SUM aggregates"),
+            (AggregateType::Percentile(quantile), _) => Self::Percentile((*quantile, vec![])),
+            (AggregateType::StdDev, _) => Self::StdDev(vec![]),
         }
     }
 }
@@ -1016,6 +1272,22 @@ impl Scalar {
         matches!(self, Self::Null)
     }
 
+    /// Adds `other` to `self` as part of a SUM aggregate, returning the sum and
+    /// whether it overflowed. Integer sums saturate on overflow.
+    pub fn checked_sum(self, other: Self) -> (Self, bool) {
+        match (self, other) {
+            (Self::I64(mut a), Self::I64(b)) => {
+                let overflowed = a.sum_add(b);
+                (Self::I64(a), overflowed)
+            }
+            (Self::U64(mut a), Self::U64(b)) => {
+                let overflowed = a.sum_add(b);
+                (Self::U64(a), overflowed)
+            }
+            (a, b) => (a + b, false),
+        }
+    }
+
     // Implementations of all the accessors for the variants of `Scalar`.
     typed_scalar_converters! {
         (as_i64, try_as_i64, i64),
@@ -1267,6 +1539,18 @@ impl std::fmt::Display for &OwnedValue {
     }
 }
 
+impl From<Value<'_>> for OwnedValue {
+    fn from(value: Value<'_>) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::String(s) => Self::String(s.to_owned()),
+            Value::ByteArray(b) => Self::ByteArray(b.to_vec()),
+            Value::Boolean(b) => Self::Boolean(b),
+            Value::Scalar(s) => Self::Scalar(s),
+        }
+    }
+}
+
 impl PartialEq<Value<'_>> for OwnedValue {
     fn eq(&self, other: &Value<'_>) -> bool {
         match (&self, other) {
@@ -1590,6 +1874,19 @@ impl<'a> Values<'a> {
         }
     }
 
+    // Returns a numerical value as an f64. Panics if not possible.
+    fn value_as_f64(&self, i: usize) -> f64 {
+        match &self {
+            Values::I64(c) => c[i] as f64,
+            Values::I64N(c) => c[i].unwrap() as f64,
+            Values::U64(c) => c[i] as f64,
+            Values::U64N(c) => c[i].unwrap() as f64,
+            Values::F64(c) => c[i],
+            Values::F64N(c) => c[i].unwrap(),
+            _ => panic!("value cannot be returned as f64"),
+        }
+    }
+
     // Returns a value as a &str. Panics if not possible.
     fn value_str(&self, i: usize) -> &'a str {
         match &self {
@@ -1969,6 +2266,33 @@ mod test {
         );
     }
 
+    #[test]
+    fn percentile_merge() {
+        let mut agg = AggregateVec::Percentile((0.5, vec![]));
+        agg.push_runs(vec![(3.0, 1), (1.0, 2), (3.0, 1)]);
+        assert_eq!(agg.value(0), Value::from(Some(1.0)));
+
+        let mut other = AggregateVec::Percentile((0.5, vec![]));
+        other.push_runs(vec![(4.0, 3), (2.0, 1)]);
+        agg.merge_from(0, &mut other, 0);
+
+        // 1, 1, 2, 3, 3, 4, 4, 4
+        let mut merged = AggregateVec::Percentile((0.5, vec![]));
+        merged.push_from(&mut agg, 0);
+        assert_eq!(merged.value(0), Value::from(Some(3.0)));
+        assert_eq!(
+            merged,
+            AggregateVec::Percentile((
+                0.5,
+                vec![Some(vec![(1.0, 2), (2.0, 1), (3.0, 2), (4.0, 3)])]
+            ))
+        );
+
+        // merged state is moved rather than copied
+        assert_eq!(other, AggregateVec::Percentile((0.5, vec![None])));
+        assert_eq!(agg, AggregateVec::Percentile((0.5, vec![None])));
+    }
+
     #[test]
     fn size() {
         let v1 = OwnedValue::new_null();
@@ -0,0 +1,202 @@
+//! Planning of memory-bounded chunk compactions.
+
+use data_types::chunk_metadata::{ChunkId, ChunkStorage, ChunkSummary};
+use std::collections::BTreeMap;
+
+/// Groups `chunks` into batches that can each be compacted into a single
+/// chunk without the memory used by the compaction exceeding `budget_bytes`.
+///
+/// `chunks` are expected to be all the chunks of their partitions. Only
+/// chunks of the same table and partition are compacted together, and a
+/// batch only holds a contiguous run of chunks by
+/// [`ChunkOrder`](data_types::chunk_metadata::ChunkOrder), as required by the
+/// catalog and by deduplication: a chunk that cannot be compacted splits the
+/// run. Chunks can be compacted if they are in the read buffer and have no
+/// outstanding lifecycle action.
+///
+/// While compacting, the input chunks and the output chunk, assumed to be no
+/// larger than the inputs combined, are held in memory together. Each batch
+/// therefore keeps twice its combined size within the budget. Batches of a
+/// single chunk are never planned as compacting them would not reduce the
+/// number of chunks.
+///
+/// Batches are ordered by table name, partition key and chunk order, and the
+/// chunks of a batch by their chunk order.
+pub fn plan_compaction(chunks: &[ChunkSummary], budget_bytes: usize) -> Vec<Vec<ChunkId>> {
+    let mut partitions: BTreeMap<_, Vec<&ChunkSummary>> = BTreeMap::new();
+    for chunk in chunks {
+        partitions
+            .entry((&chunk.table_name, &chunk.partition_key))
+            .or_default()
+            .push(chunk);
+    }
+
+    let mut plan = vec![];
+    for (_, mut chunks) in partitions {
+        chunks.sort_by_key(|chunk| (chunk.order, chunk.id));
+
+        let mut batch: Vec<&ChunkSummary> = vec![];
+        let mut batch_bytes = 0;
+        for chunk in chunks {
+            if !is_compactable(chunk) {
+                push_batch(&mut plan, std::mem::take(&mut batch));
+                batch_bytes = 0;
+                continue;
+            }
+
+            if compaction_bytes(batch_bytes + chunk.memory_bytes) > budget_bytes {
+                push_batch(&mut plan, std::mem::take(&mut batch));
+                batch_bytes = 0;
+
+                // too large to be compacted with any other chunk
+                if compaction_bytes(chunk.memory_bytes) > budget_bytes {
+                    continue;
+                }
+            }
+            batch_bytes += chunk.memory_bytes;
+            batch.push(chunk);
+        }
+        push_batch(&mut plan, batch);
+    }
+    plan
+}
+
+/// Returns true if `chunk` can be compacted.
+fn is_compactable(chunk: &ChunkSummary) -> bool {
+    chunk.lifecycle_action.is_none()
+        && matches!(
+            chunk.storage,
+            ChunkStorage::ReadBuffer | ChunkStorage::ReadBufferAndObjectStore
+        )
+}
+
+/// The memory used while compacting input chunks of `input_bytes`: the
+/// inputs plus an output chunk of at most the same size.
+fn compaction_bytes(input_bytes: usize) -> usize {
+    input_bytes.saturating_mul(2)
+}
+
+/// Adds `batch` to `plan` if compacting it would reduce the number of chunks.
+fn push_batch(plan: &mut Vec<Vec<ChunkId>>, batch: Vec<&ChunkSummary>) {
+    if batch.len() < 2 {
+        return;
+    }
+    plan.push(batch.into_iter().map(|chunk| chunk.id).collect());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_types::chunk_metadata::{ChunkLifecycleAction, ChunkOrder};
+    use std::sync::Arc;
+    use time::Time;
+
+    fn chunk(partition_key: &str, id: u128, memory_bytes: usize) -> ChunkSummary {
+        ChunkSummary {
+            partition_key: Arc::from(partition_key),
+            table_name: Arc::from("cpu"),
+            order: ChunkOrder::new(id as u32).unwrap(),
+            id: ChunkId::new_test(id),
+            storage: ChunkStorage::ReadBuffer,
+            lifecycle_action: None,
+            memory_bytes,
+            object_store_bytes: 0,
+            row_count: 1,
+            time_of_last_access: None,
+            time_of_first_write: Time::from_timestamp_nanos(0),
+            time_of_last_write: Time::from_timestamp_nanos(0),
+        }
+    }
+
+    #[test]
+    fn test_plan_compaction() {
+        let mut busy = chunk("p1", 6, 10);
+        busy.lifecycle_action = Some(ChunkLifecycleAction::Persisting);
+        let mut open = chunk("p2", 13, 10);
+        open.storage = ChunkStorage::OpenMutableBuffer;
+        let mut persisted = chunk("p2", 12, 0);
+        persisted.storage = ChunkStorage::ObjectStoreOnly;
+
+        let chunks = vec![
+            chunk("p1", 1, 100),
+            chunk("p1", 2, 150),
+            chunk("p1", 3, 200),
+            chunk("p1", 4, 100),
+            chunk("p1", 5, 50),
+            busy,
+            chunk("p1", 7, 50),
+            chunk("p1", 8, 600),
+            chunk("p1", 9, 50),
+            chunk("p1", 10, 50),
+            chunk("p2", 11, 100),
+            persisted,
+            chunk("p2", 14, 100),
+            open,
+            chunk("p3", 15, 100),
+        ];
+        let budget = 1_000;
+
+        let plan = plan_compaction(&chunks, budget);
+
+        let ids =
+            |ids: &[u128]| -> Vec<_> { ids.iter().map(|id| ChunkId::new_test(*id)).collect() };
+        assert_eq!(
+            plan,
+            vec![
+                // the first chunks of p1 fill the budget, leaving chunks 4 and
+                // 5 before the busy chunk
+                ids(&[1, 2, 3]),
+                ids(&[4, 5]),
+                // chunk 7 is left on its own as chunk 8 is too large to be
+                // compacted with any other chunk
+                ids(&[9, 10]),
+                // chunks 11 and 14 of p2 are separated by chunks that can't
+                // be compacted, and p3 only has a single chunk
+            ]
+        );
+
+        for batch in &plan {
+            let bytes: usize = chunks
+                .iter()
+                .filter(|chunk| batch.contains(&chunk.id))
+                .map(|chunk| chunk.memory_bytes)
+                .sum();
+            assert!(bytes * 2 <= budget, "batch {:?} exceeds budget", batch);
+        }
+    }
+
+    #[test]
+    fn test_plan_compaction_contiguous() {
+        // chunks are batched by order, not by size, and a chunk that can't be
+        // compacted splits a batch
+        let mut busy = chunk("p1", 3, 10);
+        busy.lifecycle_action = Some(ChunkLifecycleAction::Compacting);
+        let chunks = vec![
+            chunk("p1", 4, 10),
+            chunk("p1", 1, 10),
+            busy,
+            chunk("p1", 2, 400),
+            chunk("p1", 5, 10),
+        ];
+
+        let plan = plan_compaction(&chunks, 1_000);
+        assert_eq!(
+            plan,
+            vec![
+                vec![ChunkId::new_test(1), ChunkId::new_test(2)],
+                vec![ChunkId::new_test(4), ChunkId::new_test(5)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_compaction_empty() {
+        assert!(plan_compaction(&[], 1_000).is_empty());
+        // compacting the two chunks needs room for the output chunk
+        assert!(plan_compaction(&[chunk("p1", 1, 10), chunk("p1", 2, 10)], 39).is_empty());
+        assert_eq!(
+            plan_compaction(&[chunk("p1", 1, 10), chunk("p1", 2, 10)], 40).len(),
+            1
+        );
+    }
+}
@@ -100,6 +100,7 @@ use uuid::Uuid;
 
 pub use application::ApplicationState;
 mod application;
+pub mod compaction;
 pub mod database;
 pub mod rules;
 use rules::{PersistedDatabaseRules, ProvidedDatabaseRules};